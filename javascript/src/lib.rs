@@ -4,13 +4,18 @@ use wasm_bindgen::prelude::*;
 
 #[cfg(feature = "verify")]
 use elliptic_curve::sec1::FromEncodedPoint;
+#[cfg(feature = "sign")]
 use elliptic_curve::sec1::ToEncodedPoint;
+#[cfg(feature = "verify")]
+use plume_rustcrypto::AffinePoint;
+#[cfg(feature = "sign")]
 use signature::RandomizedSigner;
 #[cfg(feature = "verify")]
 use std::convert::TryInto;
 use zeroize::Zeroize;
 
 #[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
 /// @typedef {Object} PlumeSignature - Wrapper around [`plume_rustcrypto::PlumeSignature`](https://docs.rs/plume_rustcrypto/latest/plume_rustcrypto/struct.PlumeSignature.html).
 /// [`plume_rustcrypto::AffinePoint`](https://docs.rs/plume_rustcrypto/latest/plume_rustcrypto/struct.AffinePoint.html) is represented as a `Uint8Array` containing SEC1 encoded point.
 /// [`plume_rustcrypto::NonZeroScalar`](https://docs.rs/plume_rustcrypto/latest/plume_rustcrypto/type.NonZeroScalar.html) is represented as a `Uint8Array` containing SEC1 DER secret key.
@@ -98,6 +103,32 @@ impl PlumeSignature {
     }
 }
 
+#[cfg(feature = "verify")]
+#[wasm_bindgen(skip_jsdoc)]
+/// Verifies the signature with `plume_rustcrypto`, leaving the object usable.
+/// @throws an error if the data in the object doesn't let it to properly run verification, as with `PlumeSignature.verify`.
+/// @param {PlumeSignature} signature
+/// @returns {boolean}
+pub fn verify(signature: &PlumeSignature) -> Result<bool, JsError> {
+    Ok(plume_rustcrypto::PlumeSignature::verify(
+        &signature.clone().try_into()?,
+    ))
+}
+
+#[cfg(feature = "sign")]
+#[wasm_bindgen(skip_jsdoc)]
+/// Generates a secret key with the randomness of the platform.
+/// @throws a "crypto error" in case of a problem with encoding the key
+/// @returns {Uint8Array} the secret key in SEC1 DER format, as `sign` takes it.
+pub fn keygen() -> Result<Vec<u8>, JsError> {
+    Ok(
+        plume_rustcrypto::SecretKey::random(&mut signature::rand_core::OsRng)
+            .to_sec1_der()?
+            .to_vec(),
+    )
+}
+
+#[cfg(feature = "sign")]
 #[wasm_bindgen(skip_jsdoc)]
 /// @throws a "crypto error" in case of a problem with the secret key
 /// @param {boolean} v1 - is the flag to choose between V1 and V2 output.
@@ -114,6 +145,34 @@ pub fn sign(v1: bool, sk: &mut [u8], msg: &[u8]) -> Result<PlumeSignature, JsErr
         .into())
 }
 
+#[cfg(feature = "sign")]
+#[wasm_bindgen(js_name = signHex, skip_jsdoc)]
+/// Same as `sign`, with hex strings for the inputs; a `0x` prefix is allowed.
+/// @throws an error if an input isn't hex, or a "crypto error" in case of a problem with the secret key
+/// @param {boolean} v1 - is the flag to choose between V1 and V2 output.
+/// @param {string} sk - the 32 bytes of the secret key, big-endian.
+/// @param {string} msg
+/// @returns {PlumeSignature}
+pub fn sign_hex(v1: bool, sk: &str, msg: &str) -> Result<PlumeSignature, JsError> {
+    let decode = |name: &str, value: &str| {
+        hex::decode(value.strip_prefix("0x").unwrap_or(value))
+            .map_err(|er| JsError::new(&format!("while proccessing {name} : {er}")))
+    };
+    let mut sk_bytes = decode("`sk`", sk)?;
+    let sk_z = if sk_bytes.len() == 32 {
+        plume_rustcrypto::SecretKey::from_slice(&sk_bytes).map_err(JsError::from)
+    } else {
+        Err(JsError::new("`sk` must be 32 bytes"))
+    };
+    sk_bytes.zeroize();
+    let signer = plume_rustcrypto::randomizedsigner::PlumeSigner::new(&sk_z?, v1);
+
+    Ok(signer
+        .sign_with_rng(&mut signature::rand_core::OsRng, &decode("`msg`", msg)?)
+        .into())
+}
+
+#[cfg(feature = "sign")]
 impl From<plume_rustcrypto::PlumeSignature> for PlumeSignature {
     fn from(value: plume_rustcrypto::PlumeSignature) -> Self {
         PlumeSignature {
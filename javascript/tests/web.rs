@@ -0,0 +1,119 @@
+//! Checks the Wasm functions against `plume_rustcrypto` on the vectors of its tests; run with `wasm-pack test --node --features verify`.
+#![cfg(all(feature = "sign", feature = "verify"))]
+
+use elliptic_curve::sec1::ToEncodedPoint;
+use plume_sig::{keygen, sign, sign_hex, verify, PlumeSignature};
+use signature::rand_core::{CryptoRng, RngCore};
+use signature::RandomizedSigner;
+use std::convert::TryInto;
+use wasm_bindgen_test::*;
+
+const MESSAGE: &[u8] = b"An example app message string";
+const R: &str = "93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808";
+const SK: &str = "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464";
+const PK: &str = "030cec028ee08d09e02672a68310814354f9eabfff0de6dacc1cd3a774496076ae";
+const NULLIFIER: &str = "0357bc3ed28172ef8adde4b9e0c2cce745fcc5a66473a45c1e626f1d0c67e55830";
+const V1_C: &str = "c6a7fc2c926ddbaf20731a479fb6566f2daa5514baae5223fe3b32edbce83254";
+const V1_S: &str = "e69f027d84cb6fe5f761e333d12e975fb190d163e8ea132d7de0bd6079ba28ca";
+const V2_C: &str = "3dbfb717705010d4f44a70720c95e74b475bd3a783ab0b9e8a6b3b363434eb96";
+const V2_S: &str = "528e8fbb6452f82200797b1a73b2947a92524bd611085a920f1177cb8098136b";
+
+struct Mock {}
+impl RngCore for Mock {
+    fn next_u32(&mut self) -> u32 {
+        unimplemented!()
+    }
+    fn next_u64(&mut self) -> u64 {
+        unimplemented!()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.copy_from_slice(&hex::decode(R).unwrap());
+    }
+
+    fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), signature::rand_core::Error> {
+        unimplemented!()
+    }
+}
+impl CryptoRng for Mock {}
+
+fn secret_key() -> plume_rustcrypto::SecretKey {
+    plume_rustcrypto::SecretKey::from_slice(&hex::decode(SK).unwrap()).unwrap()
+}
+
+fn scalar_hex(sec1_der: &[u8]) -> String {
+    hex::encode(
+        plume_rustcrypto::SecretKey::from_sec1_der(sec1_der)
+            .unwrap()
+            .to_bytes(),
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_native_vectors() {
+    for (v1, c, s) in [(true, V1_C, V1_S), (false, V2_C, V2_S)] {
+        let native = plume_rustcrypto::randomizedsigner::PlumeSigner::new(&secret_key(), v1)
+            .sign_with_rng(&mut Mock {}, MESSAGE);
+        let wasm = PlumeSignature::from(native);
+        assert_eq!(hex::encode(&wasm.pk), PK);
+        assert_eq!(hex::encode(&wasm.nullifier), NULLIFIER);
+        assert_eq!(scalar_hex(&wasm.c), c);
+        assert_eq!(scalar_hex(&wasm.s), s);
+        assert_eq!(wasm.v1specific.is_some(), v1);
+        assert!(verify(&wasm).unwrap());
+
+        // and back to the native signature
+        let native: plume_rustcrypto::PlumeSignature = wasm.try_into().unwrap();
+        assert!(native.verify());
+        assert_eq!(hex::encode(native.c.to_bytes()), c);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_sign_matches_native() {
+    let mut sk_der = secret_key().to_sec1_der().unwrap().to_vec();
+    for v1 in [true, false] {
+        let by_bytes = sign(v1, &mut sk_der.clone(), MESSAGE).unwrap();
+        let by_hex = sign_hex(v1, SK, &hex::encode(MESSAGE)).unwrap();
+        let by_prefixed_hex = sign_hex(
+            v1,
+            &format!("0x{SK}"),
+            &format!("0x{}", hex::encode(MESSAGE)),
+        )
+        .unwrap();
+        for signature in [by_bytes, by_hex, by_prefixed_hex] {
+            assert_eq!(signature.message, MESSAGE);
+            assert_eq!(hex::encode(&signature.pk), PK);
+            assert_eq!(hex::encode(&signature.nullifier), NULLIFIER);
+            assert_eq!(signature.v1specific.is_some(), v1);
+            assert!(verify(&signature).unwrap());
+            let native: plume_rustcrypto::PlumeSignature = signature.try_into().unwrap();
+            assert!(native.verify());
+        }
+    }
+
+    // `sign` wipes the key it's given
+    sign(false, &mut sk_der, MESSAGE).unwrap();
+    assert!(sk_der.iter().all(|byte| *byte == 0));
+
+    assert!(sign_hex(false, &SK[2..], "").is_err());
+    assert!(sign_hex(false, SK, "0").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_keygen() {
+    let mut sk = keygen().unwrap();
+    let pk = plume_rustcrypto::SecretKey::from_sec1_der(&sk)
+        .unwrap()
+        .public_key();
+    let signature = sign(true, &mut sk, MESSAGE).unwrap();
+    assert_eq!(signature.pk, pk.to_encoded_point(true).as_bytes());
+    assert!(verify(&signature).unwrap());
+
+    let mut tampered = signature.clone();
+    tampered.message = b"another message".to_vec();
+    assert!(!verify(&tampered).unwrap());
+    let mut tampered = signature;
+    std::mem::swap(&mut tampered.c, &mut tampered.s);
+    assert!(!verify(&tampered).unwrap());
+}
@@ -0,0 +1,306 @@
+//! `plume`, a command line for producing and checking PLUME keys, signatures and nullifiers without writing Rust.
+//!
+//! # Commands
+//! - `keygen` draws a key pair and prints the secret key and the public key, a line each.
+//! - `sign --sk <hex> <message> [--r <hex>]` prints the signature in the canonical encoding of `plume_arkworks::formats`. `--r` signs
+//!   with the given nonce instead of a random one, for reproducing vectors; never reuse one for real keys.
+//! - `verify --pk <hex> <message> --signature <hex or JSON>` prints `valid`, or fails with the check which didn't pass.
+//! - `nullifier --sk <hex> <message>` prints the nullifier `hash_to_curve(message ‖ pk)^sk`.
+//! - `offline-request <message> [--scope <text> | --scope-hex <hex>] [--fragment <bytes>]` prints the parts of a
+//!   `plume_arkworks::offline::SigningRequest` for an air-gapped signer, a line each, of at most `--fragment` bytes (128 by default).
+//! - `offline-sign --sk <hex> --part <part>...` signs the request joined from its parts, on the air-gapped machine, and prints the parts
+//!   of the response; the scope of the request is printed to stderr for the operator to check.
+//! - `offline-verify --part <part>...`, given the parts of a request and of its response in any order, prints `valid` and the public key
+//!   which signed, or fails.
+//!
+//! The message is `--message <text>`, its UTF-8 bytes, or `--message-hex <hex>`. Every command takes `--curve <name>`, one of the
+//! names of `plume_core::consts::CURVES` (`secp256k1` by default), and `--json` to print JSON instead; a signature in JSON has the
+//! ERC-7524 field names of `plume_arkworks::serialization`. `--version <v1|v2|v1.1|v2.1>` picks the version of `sign` and `verify`, `v2`
+//! by default; `v1.1` and `v2.1` are the framed versions. Keys and scalars are big-endian hex, points SEC1 compressed hex.
+
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::AffineCurve;
+use ark_ff::PrimeField;
+use plume_arkworks::hash_to_curve::hash_to_curve;
+use plume_arkworks::offline::{
+    self, SigningRequest, SigningResponse, REQUEST_UR_TYPE, RESPONSE_UR_TYPE,
+};
+use plume_arkworks::{
+    babyjubjub, bn254, constant_time, encoding, p256, pasta, HashToCurveSuite, Parameters,
+    PlumeSignature, PlumeVersion, SWModelParameters,
+};
+use secp256k1::curves::Secp256k1Parameters;
+use serde_json::json;
+use std::{env, process};
+use zeroize::Zeroizing;
+
+const USAGE: &str = "usage: plume keygen
+       plume sign --sk <hex> (--message <text> | --message-hex <hex>) [--r <hex>]
+       plume verify --pk <hex> (--message <text> | --message-hex <hex>) --signature <hex or JSON>
+       plume nullifier --sk <hex> (--message <text> | --message-hex <hex>)
+       plume offline-request (--message <text> | --message-hex <hex>) [--scope <text> | --scope-hex <hex>] [--fragment <bytes>]
+       plume offline-sign --sk <hex> --part <part>...
+       plume offline-verify --part <part>...
+options: --curve <secp256k1|p256|pallas|vesta|bn254|grumpkin|babyjubjub> --version <v1|v2|v1.1|v2.1> --json";
+
+/// The command and its flags.
+struct Options {
+    command: String,
+    curve: String,
+    version: PlumeVersion,
+    json: bool,
+    secret_key: Option<Zeroizing<String>>,
+    public_key: Option<String>,
+    message: Option<Vec<u8>>,
+    signature: Option<String>,
+    r: Option<Zeroizing<String>>,
+    scope: Vec<u8>,
+    parts: Vec<String>,
+    fragment_len: usize,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = parse(&args).and_then(|options| match options.curve.as_str() {
+        "secp256k1" => run::<Secp256k1Parameters>(&options),
+        "p256" => run::<p256::P256Parameters>(&options),
+        "pallas" => run::<pasta::PallasParameters>(&options),
+        "vesta" => run::<pasta::VestaParameters>(&options),
+        "bn254" => run::<bn254::Bn254Parameters>(&options),
+        "grumpkin" => run::<bn254::GrumpkinParameters>(&options),
+        "babyjubjub" => run::<babyjubjub::BabyJubJubParameters>(&options),
+        curve => Err(format!("unknown curve `{curve}`\n{USAGE}")),
+    });
+    match result {
+        Ok(output) => println!("{output}"),
+        Err(msg) => {
+            eprintln!("{msg}");
+            process::exit(1);
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Result<Options, String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    let mut options = Options {
+        command: command.clone(),
+        curve: "secp256k1".to_owned(),
+        version: PlumeVersion::V2,
+        json: false,
+        secret_key: None,
+        public_key: None,
+        message: None,
+        signature: None,
+        r: None,
+        scope: Vec::new(),
+        parts: Vec::new(),
+        fragment_len: 128,
+    };
+    let mut args = rest.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or(USAGE);
+        match arg.as_str() {
+            "--curve" => options.curve = value()?,
+            "--version" => options.version = parse_version(&value()?)?,
+            "--json" => options.json = true,
+            "--sk" => options.secret_key = Some(Zeroizing::new(value()?)),
+            "--pk" => options.public_key = Some(value()?),
+            "--message" => options.message = Some(value()?.into_bytes()),
+            "--message-hex" => options.message = Some(from_hex(&value()?)?),
+            "--signature" => options.signature = Some(value()?),
+            "--r" => options.r = Some(Zeroizing::new(value()?)),
+            "--scope" => options.scope = value()?.into_bytes(),
+            "--scope-hex" => options.scope = from_hex(&value()?)?,
+            "--part" => options.parts.push(value()?),
+            "--fragment" => {
+                options.fragment_len = value()?
+                    .parse()
+                    .map_err(|_| format!("invalid --fragment\n{USAGE}"))?
+            }
+            _ => return Err(format!("unexpected argument `{arg}`\n{USAGE}")),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_version(version: &str) -> Result<PlumeVersion, String> {
+    match version.to_ascii_lowercase().as_str() {
+        "1" | "v1" => Ok(PlumeVersion::V1),
+        "2" | "v2" => Ok(PlumeVersion::V2),
+        "1.1" | "v1.1" => Ok(PlumeVersion::V1_1),
+        "2.1" | "v2.1" => Ok(PlumeVersion::V2_1),
+        _ => Err(format!("unknown version `{version}`\n{USAGE}")),
+    }
+}
+
+fn run<P: HashToCurveSuite>(options: &Options) -> Result<String, String>
+where
+    P::BaseField: PrimeField,
+{
+    let pp = Parameters {
+        g_point: GroupAffine::<P>::prime_subgroup_generator(),
+    };
+    match options.command.as_str() {
+        "keygen" => {
+            let (pk, sk) = PlumeSignature::keygen_zeroizing(&pp, &mut rand::thread_rng());
+            let (sk, pk) = (
+                Zeroizing::new(encoding::field_to_hex(&*sk)),
+                point_to_hex(&pk)?,
+            );
+            Ok(if options.json {
+                json!({ "curve": options.curve, "secretKey": sk.as_str(), "publicKey": pk })
+                    .to_string()
+            } else {
+                format!("{}\n{pk}", *sk)
+            })
+        }
+        "sign" => {
+            let sk = secret_key::<P>(options)?;
+            let pk = constant_time::mul(&pp.g_point, &sk);
+            let message = message(options)?;
+            let signature = match &options.r {
+                Some(r) => {
+                    let r = Zeroizing::new(field_from_hex::<P::ScalarField>(r, "--r")?);
+                    PlumeSignature::sign_with_r(&pp, (&pk, &sk), message, *r, options.version)
+                }
+                None => PlumeSignature::sign(
+                    &pp,
+                    &mut rand::thread_rng(),
+                    (&pk, &sk),
+                    message,
+                    options.version,
+                ),
+            }
+            .map_err(|e| e.to_string())?;
+            if options.json {
+                serde_json::to_string(&signature).map_err(|e| e.to_string())
+            } else {
+                signature.to_hex().map_err(|e| e.to_string())
+            }
+        }
+        "verify" => {
+            let pk = encoding::point_from_hex::<P>(options.public_key.as_deref().ok_or(USAGE)?)
+                .map_err(|e| format!("invalid --pk: {e:?}"))?;
+            let signature = options.signature.as_deref().ok_or(USAGE)?;
+            let signature: PlumeSignature<P> = if signature.trim_start().starts_with('{') {
+                serde_json::from_str(signature).map_err(|e| format!("invalid --signature: {e}"))?
+            } else {
+                PlumeSignature::from_hex(signature)
+                    .map_err(|e| format!("invalid --signature: {e}"))?
+            };
+            signature
+                .verify(&pp, &pk, message(options)?, options.version)
+                .map_err(|e| format!("invalid signature: {e}"))?;
+            Ok(if options.json {
+                json!({ "valid": true }).to_string()
+            } else {
+                "valid".to_owned()
+            })
+        }
+        "nullifier" => {
+            let sk = secret_key::<P>(options)?;
+            let pk = constant_time::mul(&pp.g_point, &sk);
+            let hashed_to_curve =
+                hash_to_curve::<P>(message(options)?, &pk).map_err(|e| e.to_string())?;
+            let nullifier = point_to_hex(&constant_time::mul(&hashed_to_curve, &sk))?;
+            Ok(if options.json {
+                json!({ "publicKey": point_to_hex(&pk)?, "nullifier": nullifier }).to_string()
+            } else {
+                nullifier
+            })
+        }
+        "offline-request" => {
+            let request =
+                SigningRequest::new(&pp, message(options)?, &options.scope, options.version)
+                    .map_err(|e| e.to_string())?;
+            Ok(print_parts(
+                options,
+                offline::to_parts(REQUEST_UR_TYPE, &request.to_cbor(), options.fragment_len),
+            ))
+        }
+        "offline-sign" => {
+            let sk = secret_key::<P>(options)?;
+            let pk = constant_time::mul(&pp.g_point, &sk);
+            let request = request(options)?;
+            eprintln!("scope: {}", String::from_utf8_lossy(&request.scope));
+            let response = request
+                .sign(&pp, &mut rand::thread_rng(), (&pk, &sk))
+                .map_err(|e| e.to_string())?;
+            let cbor = response.to_cbor().map_err(|e| e.to_string())?;
+            Ok(print_parts(
+                options,
+                offline::to_parts(RESPONSE_UR_TYPE, &cbor, options.fragment_len),
+            ))
+        }
+        "offline-verify" => {
+            let request = request(options)?;
+            let response = SigningResponse::<P>::from_cbor(&join_parts(options, RESPONSE_UR_TYPE)?)
+                .map_err(|e| format!("invalid response: {e}"))?;
+            if !response
+                .verify(&pp, &request)
+                .map_err(|e| format!("invalid response: {e}"))?
+            {
+                return Err(
+                    "the response doesn't answer the request with a valid signature".to_owned(),
+                );
+            }
+            let pk = point_to_hex(&response.pk)?;
+            Ok(if options.json {
+                json!({ "valid": true, "publicKey": pk }).to_string()
+            } else {
+                format!("valid\n{pk}")
+            })
+        }
+        command => Err(format!("unknown command `{command}`\n{USAGE}")),
+    }
+}
+
+/// The request joined from the `--part`s of its type.
+fn request(options: &Options) -> Result<SigningRequest, String> {
+    SigningRequest::from_cbor(&join_parts(options, REQUEST_UR_TYPE)?)
+        .map_err(|e| format!("invalid request: {e}"))
+}
+
+/// Joins the `--part`s of `ur_type`, ignoring those of other types.
+fn join_parts(options: &Options, ur_type: &str) -> Result<Vec<u8>, String> {
+    let prefix = format!("UR:{ur_type}/");
+    let parts: Vec<&str> = options
+        .parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| part.starts_with(&prefix))
+        .collect();
+    offline::from_parts(ur_type, &parts).map_err(|e| format!("invalid --part of {ur_type}: {e}"))
+}
+
+fn print_parts(options: &Options, parts: Vec<String>) -> String {
+    if options.json {
+        json!({ "parts": parts }).to_string()
+    } else {
+        parts.join("\n")
+    }
+}
+
+fn secret_key<P: HashToCurveSuite>(options: &Options) -> Result<Zeroizing<P::ScalarField>, String> {
+    let sk = options.secret_key.as_deref().ok_or(USAGE)?;
+    Ok(Zeroizing::new(field_from_hex(sk, "--sk")?))
+}
+
+fn message(options: &Options) -> Result<&[u8], String> {
+    options.message.as_deref().ok_or_else(|| USAGE.to_owned())
+}
+
+fn field_from_hex<F: PrimeField>(hex: &str, flag: &str) -> Result<F, String> {
+    encoding::field_from_hex(hex).map_err(|e| format!("invalid {flag}: {e:?}"))
+}
+
+fn point_to_hex<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<String, String>
+where
+    P::BaseField: PrimeField,
+{
+    encoding::point_to_hex(point).map_err(|e| format!("{e:?}"))
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    encoding::from_hex(hex).map_err(|e| format!("invalid hex: {e:?}"))
+}
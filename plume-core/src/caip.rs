@@ -0,0 +1,224 @@
+//! Scopes bound to chains and accounts by their CAIP-2 and CAIP-10 identifiers.
+//!
+//! A multi-chain application gets one nullifier per chain by signing a scope which includes the chain. For the nullifiers to agree
+//! across wallets and backends the identifier has to be the same bytes everywhere, so the parsing here is strict: anything outside the
+//! CAIP grammar is rejected rather than cleaned up, with the one exception of `eip155` addresses, which are lowercased since wallets
+//! differ on the EIP-55 checksum casing.
+
+use crate::transcript::framed;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+const CHAIN_SCOPE_TAG: &[u8] = b"PLUME/caip2-scope";
+const ACCOUNT_SCOPE_TAG: &[u8] = b"PLUME/caip10-scope";
+
+/// A CAIP-2 chain id, `namespace:reference`, e.g. `eip155:1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChainId {
+    namespace: String,
+    reference: String,
+}
+
+impl ChainId {
+    /// Parses `namespace:reference`: the namespace is 3 to 8 of `[-a-z0-9]`, the reference 1 to 32 of `[-_a-zA-Z0-9]`. An `eip155`
+    /// reference is a decimal number without leading zeros.
+    pub fn parse(chain_id: &str) -> Result<Self, CaipError> {
+        let (namespace, reference) = chain_id.split_once(':').ok_or(CaipError::Separator)?;
+        if !(3..=8).contains(&namespace.len())
+            || !namespace
+                .bytes()
+                .all(|b| b == b'-' || b.is_ascii_lowercase() || b.is_ascii_digit())
+        {
+            return Err(CaipError::Namespace);
+        }
+        if !(1..=32).contains(&reference.len())
+            || !reference
+                .bytes()
+                .all(|b| b == b'-' || b == b'_' || b.is_ascii_alphanumeric())
+        {
+            return Err(CaipError::Reference);
+        }
+        if namespace == "eip155"
+            && (!reference.bytes().all(|b| b.is_ascii_digit())
+                || (reference.len() > 1 && reference.starts_with('0')))
+        {
+            return Err(CaipError::Reference);
+        }
+        Ok(Self {
+            namespace: namespace.to_string(),
+            reference: reference.to_string(),
+        })
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+}
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.reference)
+    }
+}
+
+/// A CAIP-10 account id, `chain_id:address`, e.g. `eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountId {
+    chain: ChainId,
+    address: String,
+}
+
+impl AccountId {
+    /// Parses `namespace:reference:address`: the chain id as [`ChainId::parse`], and the address 1 to 128 of `[-.%a-zA-Z0-9]`. An `eip155`
+    /// address is `0x` and 40 hex digits, which are lowercased.
+    pub fn parse(account_id: &str) -> Result<Self, CaipError> {
+        let (chain, address) = account_id.rsplit_once(':').ok_or(CaipError::Separator)?;
+        let chain = ChainId::parse(chain)?;
+        if !(1..=128).contains(&address.len())
+            || !address
+                .bytes()
+                .all(|b| b == b'-' || b == b'.' || b == b'%' || b.is_ascii_alphanumeric())
+        {
+            return Err(CaipError::Address);
+        }
+        let address = if chain.namespace == "eip155" {
+            match address.strip_prefix("0x") {
+                Some(hex) if hex.len() == 40 && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                    address.to_ascii_lowercase()
+                }
+                _ => return Err(CaipError::Address),
+            }
+        } else {
+            address.to_string()
+        };
+        Ok(Self { chain, address })
+    }
+
+    pub fn chain(&self) -> &ChainId {
+        &self.chain
+    }
+
+    /// The address, lowercased for `eip155`.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.chain, self.address)
+    }
+}
+
+/// The scope `scope` on `chain`. The fields are the tag `PLUME/caip2-scope`, the chain id as written by [`ChainId`]'s `Display`, and
+/// `scope`.
+pub fn chain_scope(chain: &ChainId, scope: &[u8]) -> Vec<u8> {
+    framed(&[CHAIN_SCOPE_TAG, chain.to_string().as_bytes(), scope])
+}
+
+/// The scope `scope` of `account`. The fields are the tag `PLUME/caip10-scope`, the account id as written by [`AccountId`]'s `Display`,
+/// and `scope`.
+pub fn account_scope(account: &AccountId, scope: &[u8]) -> Vec<u8> {
+    framed(&[ACCOUNT_SCOPE_TAG, account.to_string().as_bytes(), scope])
+}
+
+/// The part of a CAIP identifier which doesn't follow the grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaipError {
+    /// A `:` separating the parts is missing.
+    Separator,
+    /// The namespace of the chain id.
+    Namespace,
+    /// The reference of the chain id.
+    Reference,
+    /// The address of the account id.
+    Address,
+}
+
+impl fmt::Display for CaipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Separator => "the identifier is missing a `:` separator",
+            Self::Namespace => "the chain namespace isn't 3 to 8 of `[-a-z0-9]`",
+            Self::Reference => "the chain reference isn't valid for the namespace",
+            Self::Address => "the account address isn't valid for the namespace",
+        })
+    }
+}
+
+impl core::error::Error for CaipError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caip_scopes() {
+        let mainnet = ChainId::parse("eip155:1").unwrap();
+        assert_eq!((mainnet.namespace(), mainnet.reference()), ("eip155", "1"));
+        assert_eq!(mainnet.to_string(), "eip155:1");
+        assert_eq!(
+            ChainId::parse("bip122:000000000019d6689c085ae165831e93")
+                .unwrap()
+                .reference(),
+            "000000000019d6689c085ae165831e93"
+        );
+        for invalid in [
+            "eip155",
+            "EIP155:1",
+            "ei:1",
+            "eip155:",
+            "eip155:01",
+            "eip155:0x1",
+            " eip155:1",
+            "eip155:1 ",
+            "cosmos:cosmoshub-4:extra",
+        ] {
+            assert!(ChainId::parse(invalid).is_err(), "{invalid}");
+        }
+
+        // the checksummed and the lowercase address are the same account
+        let checksummed =
+            AccountId::parse("eip155:1:0xAb16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb").unwrap();
+        let lowercase =
+            AccountId::parse("eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb").unwrap();
+        assert_eq!(checksummed, lowercase);
+        assert_eq!(checksummed.chain(), &mainnet);
+        assert_eq!(
+            account_scope(&checksummed, b"vote"),
+            account_scope(&lowercase, b"vote")
+        );
+        // other namespaces are case sensitive
+        let solana =
+            "solana:4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZ:7S3P4HxJpyyigGzodYwHtCxZyUQe9JiBMHyRWXArAaKv";
+        assert_eq!(AccountId::parse(solana).unwrap().to_string(), solana);
+        for invalid in [
+            "eip155:1:ab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb",
+            "eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcd",
+            "eip155:1:0xzz16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb",
+            "eip155:1:",
+            "eip155:1:a/b",
+            "0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb",
+        ] {
+            assert!(AccountId::parse(invalid).is_err(), "{invalid}");
+        }
+
+        assert_eq!(
+            chain_scope(&mainnet, b"vote"),
+            framed(&[&b"PLUME/caip2-scope"[..], &b"eip155:1"[..], &b"vote"[..]])
+        );
+        assert_ne!(
+            chain_scope(&mainnet, b"vote"),
+            chain_scope(&ChainId::parse("eip155:10").unwrap(), b"vote")
+        );
+        assert_ne!(
+            chain_scope(&mainnet, b"vote"),
+            account_scope(&lowercase, b"vote")
+        );
+    }
+}
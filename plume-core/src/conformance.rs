@@ -0,0 +1,803 @@
+//! A conformance suite for implementations of PLUME, with a machine-readable report.
+//!
+//! A backend implements [`PlumeScheme`] over the encodings of the protocol: scalars as big-endian bytes of the width of the group order,
+//! points as SEC1 compressed. [`run_all`] runs every suite against it on its curve and returns a [`Report`] of each case, which
+//! [`Report::to_json`] writes out for a CI job or an audit to keep. The expected values are the vectors of the backends of this repository:
+//!
+//! - [`Suite::Vectors`] derives the key, the hash to the curve and the signatures of [`VECTORS`], and verifies the signatures of the
+//!   vectors;
+//! - [`Suite::Adversarial`] checks that verification rejects the vector signatures altered in each way an attacker could alter them:
+//!   another message, key or version, tampered scalars, non-canonical encodings, and points swapped, malformed or off the curve;
+//! - [`Suite::RoundTrip`] checks that the backend verifies its own signatures of the framed versions, which have no vectors, and only
+//!   under the version they were made with.
+
+use crate::consts::{CurveConstants, POINT_TAG_EVEN, POINT_TAG_ODD};
+use crate::PlumeVersion;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// A signature in the encodings of the protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedSignature {
+    /// The nullifier `h^sk`, compressed.
+    pub nullifier: Vec<u8>,
+    /// `g^r`, compressed.
+    pub r_point: Vec<u8>,
+    /// `z = h^r`, compressed.
+    pub hashed_to_curve_r: Vec<u8>,
+    /// The response `s`, big-endian.
+    pub s: Vec<u8>,
+    /// The challenge `c`, big-endian.
+    pub c: Vec<u8>,
+}
+
+/// An implementation of PLUME on one curve, as the conformance suite sees it.
+///
+/// Every input is in the encodings of the protocol, and may be malformed in the adversarial cases; a method which can't take its input
+/// returns `None` (or `false` for [`Self::verify`]) rather than panicking.
+pub trait PlumeScheme {
+    /// The name of the backend in the report.
+    fn name(&self) -> String;
+
+    /// The curve it implements, which picks the vectors.
+    fn curve(&self) -> &'static CurveConstants;
+
+    /// The public key of `secret_key`.
+    fn public_key(&self, secret_key: &[u8]) -> Option<Vec<u8>>;
+
+    /// `hash_to_curve(message ‖ public_key)` under the DST of the curve.
+    fn hash_to_curve(&self, message: &[u8], public_key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Signs `message` with the nonce `r`.
+    fn sign_with_r(
+        &self,
+        secret_key: &[u8],
+        message: &[u8],
+        r: &[u8],
+        version: PlumeVersion,
+    ) -> Option<EncodedSignature>;
+
+    /// Whether `signature` is a valid signature of `message` by `public_key`.
+    fn verify(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &EncodedSignature,
+        version: PlumeVersion,
+    ) -> bool;
+}
+
+/// The signature of a vector under one version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VectorChallenge {
+    pub version: PlumeVersion,
+    /// The challenge `c`, hex.
+    pub c: &'static str,
+    /// The response `s`, hex.
+    pub s: &'static str,
+}
+
+/// A test vector: a key and a nonce, the message they sign, and the values of the protocol, all hex.
+///
+/// The nullifier, `g^r` and `z` don't depend on the version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vector {
+    /// The [name](CurveConstants::name) of the curve.
+    pub curve: &'static str,
+    pub secret_key: &'static str,
+    pub r: &'static str,
+    pub message: &'static [u8],
+    pub public_key: &'static str,
+    pub hashed_to_curve: &'static str,
+    pub nullifier: &'static str,
+    pub r_point: &'static str,
+    pub hashed_to_curve_r: &'static str,
+    pub challenges: [VectorChallenge; 2],
+}
+
+const MESSAGE: &[u8] = b"An example app message string";
+
+const fn challenges(
+    v1: (&'static str, &'static str),
+    v2: (&'static str, &'static str),
+) -> [VectorChallenge; 2] {
+    [
+        VectorChallenge {
+            version: PlumeVersion::V1,
+            c: v1.0,
+            s: v1.1,
+        },
+        VectorChallenge {
+            version: PlumeVersion::V2,
+            c: v2.0,
+            s: v2.1,
+        },
+    ]
+}
+
+/// The vectors of every curve of [`CURVES`](crate::consts::CURVES), for the same key and nonce reduced modulo the group order.
+pub const VECTORS: &[Vector] = &[
+    Vector {
+        curve: "secp256k1",
+        secret_key: "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464",
+        r: "93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808",
+        message: MESSAGE,
+        public_key: "030cec028ee08d09e02672a68310814354f9eabfff0de6dacc1cd3a774496076ae",
+        hashed_to_curve: "02bcac2d0e12679f23c218889395abcdc01f2affbc49c54d1136a2190db0800b65",
+        nullifier: "0357bc3ed28172ef8adde4b9e0c2cce745fcc5a66473a45c1e626f1d0c67e55830",
+        r_point: "039d8ca4350e7e2ad27abc6d2a281365818076662962a28429590e2dc736fe9804",
+        hashed_to_curve_r: "036d017c6f63c59fa7a5b1e9a654e27d2869579f4d152131db270558fccd27b97c",
+        challenges: challenges(
+            (
+                "c6a7fc2c926ddbaf20731a479fb6566f2daa5514baae5223fe3b32edbce83254",
+                "e69f027d84cb6fe5f761e333d12e975fb190d163e8ea132d7de0bd6079ba28ca",
+            ),
+            (
+                "3dbfb717705010d4f44a70720c95e74b475bd3a783ab0b9e8a6b3b363434eb96",
+                "528e8fbb6452f82200797b1a73b2947a92524bd611085a920f1177cb8098136b",
+            ),
+        ),
+    },
+    Vector {
+        curve: "p256",
+        secret_key: "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464",
+        r: "93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808",
+        message: MESSAGE,
+        public_key: "031ccbe91c075fc7f4f033bfa248db8fccd3565de94bbfb12f3c59ff46c271bf83",
+        hashed_to_curve: "035526c1f70487be768b313122b51abd5717c72e3d0b2cc54d8cb8ab6fa362c63b",
+        nullifier: "02b4830c4d6b4088ecd4ba44cca7c15fcd94b65ad8522348cb437476db0a5e64c1",
+        r_point: "02c5b190a3bb50a4da9f826d6348a09a8e5995f464fdcbfbf572b69905b4a05f7d",
+        hashed_to_curve_r: "025662bed3f19eb243ef2e4cb57ed779e446747f588cd09c30c06eea2ab0f5fbfe",
+        challenges: challenges(
+            (
+                "74f435d0d3acd6cb2f2e3af0cb0974aeb16c9063e319f5be3ced9963222858b8",
+                "3633626eaf06648315ad0925b751c4b916d23c505cb254a2f797dda3a11acb91",
+            ),
+            (
+                "513233958e2ccb9a6bcc65d5b61aef278116683077f1081cd07339e03b40cd43",
+                "01be1c370b8282359032b42e8c1bba44904178f65261745859f5732f724aa784",
+            ),
+        ),
+    },
+    Vector {
+        curve: "pallas",
+        secret_key: "119b423d715f8b581f4fa8ee59f4771a38fe2f1701b995cf3e0dba4cda72b463",
+        r: "13b9323b629f251b8f3fc2dd11f4672c10b7b62afa1fed13d20ad2c9da789806",
+        message: MESSAGE,
+        public_key: "03147ec6210003954d3993148740e5094ad72ea775f77cd6ffafe91ce2cb75beec",
+        hashed_to_curve: "022aff3d461fbe91de9174e631fe2d6728a6439063eda3c8c296e81c528164220b",
+        nullifier: "0300a90dfdd30bf9e0292ee122f0527023b79966b381203e7422e9524abc1ec789",
+        r_point: "02226016a916f09e07ae9fe52c4878ed8820434accef96698a0f50f7694c1f701c",
+        hashed_to_curve_r: "03080a863bb36cdee898315a54399968dde11f590f6949d77a283610ccdff18714",
+        challenges: challenges(
+            (
+                "0c0398efe14f230517e67ceeb23e69fe1fe62c484e6f78d37d3b3b09f4bc46de",
+                "0c0043ec99f3af4ece9f3e5f059e85422c29650e0babc2251177ea811d144c7a",
+            ),
+            (
+                "371e04034d393a514858180649faae3b476a0949da0a23037907c51de49cf2d7",
+                "1118597af5238959e8a7b0b0478c4d35de8988a2f9887b6ee3bbe1e683ff7ddc",
+            ),
+        ),
+    },
+    Vector {
+        curve: "vesta",
+        secret_key: "119b423d715f8b581f4fa8ee59f4771a38fe2f170201459131277480da72b463",
+        r: "13b9323b629f251b8f3fc2dd11f4672c10b7b62afaaf4c97b83e4731da789806",
+        message: MESSAGE,
+        public_key: "0207ed77bac8652f255cc38c7d376e9eaaeeb69189cf4468babea44911a0fb24cd",
+        hashed_to_curve: "0222b1d6ede9095d6143a12718e1b6558f28fc136c5acafee93162257d449a8f1e",
+        nullifier: "021e8170fc1d14f9cc3b7652b3bdf6a489dee126d2f6b322c14cd3cb7b268d0942",
+        r_point: "023de2a51a0b5504c85057e4c71b9179cebce3035bab2e1eab9ac718300998171a",
+        hashed_to_curve_r: "033e924cef7ecc5ec6b3a03185a4ff06b9f14e7f69e51c2e2179e413d0f9178c2e",
+        challenges: challenges(
+            (
+                "0b82753a0a894bd53075e5cec7f5e8da0848d8538cc7f6eda16066292593d7f8",
+                "12f83967d4d91c9f6eb71a8f2c44db9ba0e9145db4a3ca1c04fc990909aa4769",
+            ),
+            (
+                "0f81451342a3a20d0fdeca660a354db1b88216fe1bea4ce23aeb1e6b1862ec97",
+                "2ff72c9d4dce5fdd304a785549c624baa150ca8c996228663cb4013c8b7866b2",
+            ),
+        ),
+    },
+    Vector {
+        curve: "bn254",
+        secret_key: "2136f3ca902deb2e66ff6337d8731ebd3310dfca9194ce1b8672afd9ea72b463",
+        r: "028c46e2bf0a449e664ef1b98d705e14dca92f49a01ced1b1ef2c8500a789805",
+        message: MESSAGE,
+        public_key: "03022a9ee73909e577e2670cec620777d600aecfc1c288cee36d49b71019390997",
+        hashed_to_curve: "03175fa41dd33ee3f4418f3cbaffb09d1c3b134a217d25cef75be2724c7d5379d7",
+        nullifier: "020d59a9db8b72c0b97766a79deb3228ec1196d7b2a6f3c3b1c0994b81e16ce738",
+        r_point: "02153ab740868aad291e684fece6275232b0ddb70274d8ae01398f593aef6a4fb8",
+        hashed_to_curve_r: "02225877edd3d5db9b3215c66f9f725f0ebfff2106b2c9ed1cc666eb1bd7a2fb97",
+        challenges: challenges(
+            (
+                "136cfea6b9b6bbd2798e9af30e28557d6309a73a5542be11b6566683702edb7e",
+                "1aad5b3131de8eb46bf9198d8e7da84470bd10a96c8e5978cb66745b778c67ad",
+            ),
+            (
+                "224f277da38402fdf770a4d64cb323197f2d4a53835d364956df3bf064b5257d",
+                "05c87fef898f981f4a3047bb87ada293a36d0f67c86d1bf0883d0ac66b61f106",
+            ),
+        ),
+    },
+    Vector {
+        curve: "grumpkin",
+        secret_key: "2136f3ca902deb2e66ff6337d8731ebcc3c35d81a2dc741f8e34195701f5b71d",
+        r: "028c46e2bf0a449e664ef1b98d705e138ec0a86ed3f3df27363704c75101a033",
+        message: MESSAGE,
+        public_key: "020cd11bf6397443d375e2c792a839d02df7a554d2c269b022a27a4ce6c4d22d1d",
+        hashed_to_curve: "02021ef9001e885ac8110295abf42414f24cad03c86676582df802b9c1390315c2",
+        nullifier: "021f33b6af2dfc1996ee256b753d4a39cb84140607e1df103fe59af2e790c06dfd",
+        r_point: "0323c5f7ff734d8a75805e04a7ddabf0b6605daebfd1cfa78136258ab8546ee809",
+        hashed_to_curve_r: "020a51c22cfb452b3210f9efd61d184f41a56905d20c04c401b4dcdb388ea3b66a",
+        challenges: challenges(
+            (
+                "0990e1d64bd08c4ee493ff5a2b89271804f62ceb90f4f2cfdc27a8f4d02d0c1b",
+                "081b49fd75fcd4fb0a5e25e4f61a23896fd16b3ec942c46a8d3f9b1b7266cb3a",
+            ),
+            (
+                "296c3dedaef80ae9e09050142b10c39ebe85e4bbe363362d994b6b02e951c6a9",
+                "287347bdf45b7baa3332a5ecf8547d6e99c744e2ca3b2c9192712245d1fa5972",
+            ),
+        ),
+    },
+    Vector {
+        curve: "babyjubjub",
+        secret_key: "02f842c2c36ee71453cd37a5c7824782a912b5b824a228258982ef3ef3c3ba27",
+        r: "028c46e2bf0a449e664ef1b98d705e14475e9eddb232edd537da6c667f5cf170",
+        message: MESSAGE,
+        public_key: "03180409708343c2b6c9cacc1a1b73379a4f1d060faba28bfdae38e0a23ac489b9",
+        hashed_to_curve: "0208095668ad269b1f9a7f9c669aa2275d3edfd1ae18d2bd2279203e8e1654abf2",
+        nullifier: "030d956b8deae2305cdca176b35b3972a7ce17764ed1489f33a56b54ee3fd59b1b",
+        r_point: "0227a54bd5b6225ccaacecf7ef1c211c84000f45e4ce81acbd9a4513ed61b19402",
+        hashed_to_curve_r: "0225f9333c77f33b3ef7288105d218a3443abed52789cf6bfd552c8d1f7e89570b",
+        challenges: challenges(
+            (
+                "012c96ebcf726e82b3a1f9459fd102acc4966bbabea2b244eee194be5b57f20b",
+                "035f7aa422b4602adebeaf95ec19289aa1f9dca32ae4bdfb510038ac5192f63e",
+            ),
+            (
+                "03a39820ae6724b0de797941d067c371927157d077d9bb97332317f16696c95b",
+                "0044fe9f4509c774dbcebb0b5c6b8c656cd59fcc8268188136174d32f5d89114",
+            ),
+        ),
+    },
+];
+
+/// The suite a case belongs to; see [the module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suite {
+    Vectors,
+    Adversarial,
+    RoundTrip,
+}
+
+impl Suite {
+    /// The name of the suite in the report.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Vectors => "vectors",
+            Self::Adversarial => "adversarial",
+            Self::RoundTrip => "round-trip",
+        }
+    }
+}
+
+/// The outcome of one case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub suite: Suite,
+    /// The name of the case, unique in its suite, e.g. `sign/V1`.
+    pub name: String,
+    pub passed: bool,
+    /// What went wrong, for a failed case.
+    pub detail: Option<String>,
+}
+
+/// The outcomes of every case run against a backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The [name](PlumeScheme::name) of the backend.
+    pub backend: String,
+    /// The [name](CurveConstants::name) of its curve.
+    pub curve: &'static str,
+    pub cases: Vec<Case>,
+}
+
+impl Report {
+    /// Whether every case passed.
+    pub fn passed(&self) -> bool {
+        self.cases.iter().all(|case| case.passed)
+    }
+
+    /// The failed cases.
+    pub fn failures(&self) -> impl Iterator<Item = &Case> {
+        self.cases.iter().filter(|case| !case.passed)
+    }
+
+    /// The report as JSON: the backend, the curve, whether it passed, and the cases with their suite, name, outcome and detail.
+    ///
+    /// ```json
+    /// {"backend":"…","curve":"secp256k1","passed":true,"cases":[{"suite":"vectors","name":"public_key","passed":true,"detail":null}]}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"backend\":{},\"curve\":{},\"passed\":{},\"cases\":[",
+            json_string(&self.backend),
+            json_string(self.curve),
+            self.passed()
+        );
+        for (i, case) in self.cases.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let detail = case
+                .detail
+                .as_deref()
+                .map_or_else(|| String::from("null"), json_string);
+            json.push_str(&format!(
+                "{{\"suite\":{},\"name\":{},\"passed\":{},\"detail\":{}}}",
+                json_string(case.suite.name()),
+                json_string(&case.name),
+                case.passed,
+                detail
+            ));
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Runs every suite against `backend` on its curve.
+///
+/// A curve without [vectors](VECTORS) gets a single failed case, since there's nothing to check it against.
+pub fn run_all<B: PlumeScheme + ?Sized>(backend: &B) -> Report {
+    let curve = backend.curve();
+    let mut report = Report {
+        backend: backend.name(),
+        curve: curve.name,
+        cases: Vec::new(),
+    };
+    let vectors: Vec<&Vector> = VECTORS
+        .iter()
+        .filter(|vector| vector.curve == curve.name)
+        .collect();
+    if vectors.is_empty() {
+        report.fail(
+            Suite::Vectors,
+            "vectors",
+            String::from("no vectors for the curve"),
+        );
+    }
+    for vector in vectors {
+        let Some(vector) = Decoded::new(vector) else {
+            report.fail(Suite::Vectors, "vectors", String::from("malformed vector"));
+            continue;
+        };
+        vectors_suite(backend, &vector, &mut report);
+        adversarial_suite(backend, curve, &vector, &mut report);
+        round_trip_suite(backend, &vector, &mut report);
+    }
+    report
+}
+
+impl Report {
+    fn check(&mut self, suite: Suite, name: &str, passed: bool, detail: impl FnOnce() -> String) {
+        let detail = if passed { None } else { Some(detail()) };
+        self.cases.push(Case {
+            suite,
+            name: String::from(name),
+            passed,
+            detail,
+        });
+    }
+
+    fn fail(&mut self, suite: Suite, name: &str, detail: String) {
+        self.check(suite, name, false, || detail);
+    }
+
+    /// Checks that `got` is `expected`.
+    fn expect(&mut self, suite: Suite, name: &str, got: Option<&[u8]>, expected: &[u8]) {
+        self.check(suite, name, got == Some(expected), || match got {
+            Some(got) => format!("expected {}, got {}", hex(expected), hex(got)),
+            None => format!("expected {}, got nothing", hex(expected)),
+        });
+    }
+}
+
+/// A vector with its values decoded.
+struct Decoded {
+    secret_key: Vec<u8>,
+    r: Vec<u8>,
+    message: &'static [u8],
+    public_key: Vec<u8>,
+    hashed_to_curve: Vec<u8>,
+    signatures: Vec<(PlumeVersion, EncodedSignature)>,
+}
+
+impl Decoded {
+    fn new(vector: &Vector) -> Option<Self> {
+        let signatures = vector
+            .challenges
+            .iter()
+            .map(|challenge| {
+                Some((
+                    challenge.version,
+                    EncodedSignature {
+                        nullifier: unhex(vector.nullifier)?,
+                        r_point: unhex(vector.r_point)?,
+                        hashed_to_curve_r: unhex(vector.hashed_to_curve_r)?,
+                        s: unhex(challenge.s)?,
+                        c: unhex(challenge.c)?,
+                    },
+                ))
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            secret_key: unhex(vector.secret_key)?,
+            r: unhex(vector.r)?,
+            message: vector.message,
+            public_key: unhex(vector.public_key)?,
+            hashed_to_curve: unhex(vector.hashed_to_curve)?,
+            signatures,
+        })
+    }
+}
+
+fn vectors_suite<B: PlumeScheme + ?Sized>(backend: &B, vector: &Decoded, report: &mut Report) {
+    report.expect(
+        Suite::Vectors,
+        "public_key",
+        backend.public_key(&vector.secret_key).as_deref(),
+        &vector.public_key,
+    );
+    report.expect(
+        Suite::Vectors,
+        "hash_to_curve",
+        backend
+            .hash_to_curve(vector.message, &vector.public_key)
+            .as_deref(),
+        &vector.hashed_to_curve,
+    );
+    for (version, expected) in &vector.signatures {
+        let signature =
+            backend.sign_with_r(&vector.secret_key, vector.message, &vector.r, *version);
+        let name = format!("sign/{version:?}");
+        report.check(
+            Suite::Vectors,
+            &name,
+            signature.as_ref() == Some(expected),
+            || format!("expected {expected:?}, got {signature:?}"),
+        );
+        report.check(
+            Suite::Vectors,
+            &format!("verify/{version:?}"),
+            backend.verify(&vector.public_key, vector.message, expected, *version),
+            || String::from("rejected the signature of the vector"),
+        );
+    }
+}
+
+/// The name of a case, then the key, the message, the signature, and the version it's verified with.
+type AdversarialCase<'a> = (&'a str, &'a [u8], &'a [u8], EncodedSignature, PlumeVersion);
+
+fn adversarial_suite<B: PlumeScheme + ?Sized>(
+    backend: &B,
+    curve: &CurveConstants,
+    vector: &Decoded,
+    report: &mut Report,
+) {
+    let order = unhex(curve.order).unwrap_or_default();
+    let field_modulus = unhex(curve.field_modulus).unwrap_or_default();
+    // An `x` of the width of the field which isn't canonical
+    let x_not_canonical = [&[POINT_TAG_EVEN][..], &field_modulus].concat();
+    for (version, signature) in &vector.signatures {
+        let other_version = match version {
+            PlumeVersion::V1 => PlumeVersion::V2,
+            _ => PlumeVersion::V1,
+        };
+        let flip_last = |bytes: &[u8]| {
+            let mut bytes = bytes.to_vec();
+            if let Some(last) = bytes.last_mut() {
+                *last ^= 1;
+            }
+            bytes
+        };
+        let other_tag = |point: &[u8]| {
+            let mut point = point.to_vec();
+            if let Some(tag) = point.first_mut() {
+                *tag = if *tag == POINT_TAG_EVEN {
+                    POINT_TAG_ODD
+                } else {
+                    POINT_TAG_EVEN
+                };
+            }
+            point
+        };
+        let with = |edit: &dyn Fn(&mut EncodedSignature)| {
+            let mut signature = signature.clone();
+            edit(&mut signature);
+            signature
+        };
+
+        let (pk, message) = (&vector.public_key[..], vector.message);
+        let (v, v_other, v_framed) = (*version, other_version, framed(*version));
+        let sig = || signature.clone();
+        let cases: [AdversarialCase; 22] = [
+            ("another_message", pk, b"another message", sig(), v),
+            ("another_public_key", &signature.r_point, message, sig(), v),
+            ("negated_public_key", &other_tag(pk), message, sig(), v),
+            ("another_version", pk, message, sig(), v_other),
+            ("framed_version", pk, message, sig(), v_framed),
+            (
+                "tampered_s",
+                pk,
+                message,
+                with(&|sig| sig.s = flip_last(&sig.s)),
+                v,
+            ),
+            (
+                "tampered_c",
+                pk,
+                message,
+                with(&|sig| sig.c = flip_last(&sig.c)),
+                v,
+            ),
+            (
+                "s_is_order",
+                pk,
+                message,
+                with(&|sig| sig.s.clone_from(&order)),
+                v,
+            ),
+            (
+                "c_is_order",
+                pk,
+                message,
+                with(&|sig| sig.c.clone_from(&order)),
+                v,
+            ),
+            (
+                "s_truncated",
+                pk,
+                message,
+                with(&|sig| sig.s.truncate(sig.s.len().saturating_sub(1))),
+                v,
+            ),
+            ("s_zero", pk, message, with(&|sig| sig.s.fill(0)), v),
+            (
+                "another_nullifier",
+                pk,
+                message,
+                with(&|sig| sig.nullifier.clone_from(&vector.hashed_to_curve)),
+                v,
+            ),
+            (
+                "negated_nullifier",
+                pk,
+                message,
+                with(&|sig| sig.nullifier = other_tag(&sig.nullifier)),
+                v,
+            ),
+            (
+                "negated_r_point",
+                pk,
+                message,
+                with(&|sig| sig.r_point = other_tag(&sig.r_point)),
+                v,
+            ),
+            (
+                "negated_hashed_to_curve_r",
+                pk,
+                message,
+                with(&|sig| sig.hashed_to_curve_r = other_tag(&sig.hashed_to_curve_r)),
+                v,
+            ),
+            (
+                "nullifier_invalid_tag",
+                pk,
+                message,
+                with(&|sig| sig.nullifier.iter_mut().take(1).for_each(|tag| *tag = 0x04)),
+                v,
+            ),
+            (
+                "nullifier_truncated",
+                pk,
+                message,
+                with(&|sig| {
+                    sig.nullifier
+                        .truncate(sig.nullifier.len().saturating_sub(1))
+                }),
+                v,
+            ),
+            (
+                "nullifier_identity",
+                pk,
+                message,
+                with(&|sig| sig.nullifier = vec![0x00]),
+                v,
+            ),
+            (
+                "nullifier_x_not_canonical",
+                pk,
+                message,
+                with(&|sig| sig.nullifier.clone_from(&x_not_canonical)),
+                v,
+            ),
+            (
+                "r_point_identity",
+                pk,
+                message,
+                with(&|sig| sig.r_point = vec![0x00]),
+                v,
+            ),
+            ("public_key_identity", &[0x00], message, sig(), v),
+            (
+                "public_key_x_not_canonical",
+                &x_not_canonical,
+                message,
+                sig(),
+                v,
+            ),
+        ];
+        for (name, public_key, message, signature, version) in cases {
+            report.check(
+                Suite::Adversarial,
+                &format!("{name}/{version:?}"),
+                !backend.verify(public_key, message, &signature, version),
+                || String::from("accepted"),
+            );
+        }
+    }
+}
+
+fn round_trip_suite<B: PlumeScheme + ?Sized>(backend: &B, vector: &Decoded, report: &mut Report) {
+    for version in [PlumeVersion::V1_1, PlumeVersion::V2_1] {
+        let signature = backend.sign_with_r(&vector.secret_key, vector.message, &vector.r, version);
+        let verifies = |under: PlumeVersion| {
+            signature.as_ref().is_some_and(|signature| {
+                backend.verify(&vector.public_key, vector.message, signature, under)
+            })
+        };
+        report.check(
+            Suite::RoundTrip,
+            &format!("sign_verify/{version:?}"),
+            verifies(version),
+            || String::from("rejected its own signature"),
+        );
+        let unframed = if version.is_v1() {
+            PlumeVersion::V1
+        } else {
+            PlumeVersion::V2
+        };
+        report.check(
+            Suite::RoundTrip,
+            &format!("unframed/{version:?}"),
+            signature.is_some() && !verifies(unframed),
+            || format!("accepted its own signature under {unframed:?}"),
+        );
+    }
+}
+
+/// The framed version of `version`, or the same one if it's framed.
+fn framed(version: PlumeVersion) -> PlumeVersion {
+    match version {
+        PlumeVersion::V1 | PlumeVersion::V1_1 => PlumeVersion::V1_1,
+        PlumeVersion::V2 | PlumeVersion::V2_1 => PlumeVersion::V2_1,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [_, _] => u8::from_str_radix(core::str::from_utf8(pair).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `value` as a JSON string, quoted and escaped.
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::{CURVES, SECP256K1};
+
+    /// A backend which can't do anything.
+    struct Nothing;
+
+    impl PlumeScheme for Nothing {
+        fn name(&self) -> String {
+            String::from("nothing \"at all\"")
+        }
+
+        fn curve(&self) -> &'static CurveConstants {
+            &SECP256K1
+        }
+
+        fn public_key(&self, _: &[u8]) -> Option<Vec<u8>> {
+            None
+        }
+
+        fn hash_to_curve(&self, _: &[u8], _: &[u8]) -> Option<Vec<u8>> {
+            None
+        }
+
+        fn sign_with_r(
+            &self,
+            _: &[u8],
+            _: &[u8],
+            _: &[u8],
+            _: PlumeVersion,
+        ) -> Option<EncodedSignature> {
+            None
+        }
+
+        fn verify(&self, _: &[u8], _: &[u8], _: &EncodedSignature, _: PlumeVersion) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_conformance_report() {
+        for curve in CURVES {
+            let vectors: Vec<_> = VECTORS
+                .iter()
+                .filter(|vector| vector.curve == curve.name)
+                .collect();
+            assert_eq!(vectors.len(), 1, "{}", curve.name);
+            assert!(Decoded::new(vectors[0]).is_some(), "{}", curve.name);
+        }
+
+        let report = run_all(&Nothing);
+        assert!(!report.passed());
+        // rejecting everything passes the adversarial cases only
+        assert!(report
+            .failures()
+            .all(|case| case.suite != Suite::Adversarial && case.detail.is_some()));
+        assert!(report
+            .cases
+            .iter()
+            .any(|case| case.suite == Suite::Adversarial));
+        let json = report.to_json();
+        assert!(json.starts_with(
+            r#"{"backend":"nothing \"at all\"","curve":"secp256k1","passed":false,"cases":[{"suite":"vectors","name":"public_key","passed":false,"detail":"expected 030cec"#
+        ));
+        assert!(json.contains(
+            r#"{"suite":"adversarial","name":"tampered_s/V1","passed":true,"detail":null}"#
+        ));
+        assert!(json.ends_with("}]}"));
+        assert_eq!(json_string("a\\b\n\u{1}"), r#""a\\b\n\u0001""#);
+    }
+}
@@ -0,0 +1,137 @@
+//! The constants of the protocol which the other stacks of the repository need as well.
+//!
+//! A Solidity verifier or the TypeScript around the circuits can't import these, so `cargo xtask codegen` writes them out as a Solidity
+//! library and a TypeScript module instead; edit them here and regenerate rather than copying numbers by hand. The backends take their
+//! suites and tags from here too, and their tests check the curve constants against the curve arithmetic.
+
+/// The constants of a curve the signatures are defined on.
+///
+/// The numbers are the lowercase big-endian hex of their bytes, of the width of their encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveConstants {
+    /// The name of the curve, which the generated constants are prefixed with.
+    pub name: &'static str,
+    /// The RFC 9380 identifier of the hash-to-curve suite.
+    pub suite: &'static str,
+    /// The domain separation tag the signatures hash to the curve with.
+    pub dst: &'static [u8],
+    /// The prime of the base field.
+    pub field_modulus: &'static str,
+    /// The order of the group, the modulus of the scalars.
+    pub order: &'static str,
+    /// The `x` of the standard generator.
+    pub generator_x: &'static str,
+    /// The `y` of the standard generator.
+    pub generator_y: &'static str,
+}
+
+impl CurveConstants {
+    /// The number of bytes of an encoded scalar.
+    pub const fn scalar_len(&self) -> usize {
+        self.order.len() / 2
+    }
+
+    /// The number of bytes of an encoded base field element.
+    pub const fn field_len(&self) -> usize {
+        self.field_modulus.len() / 2
+    }
+
+    /// The number of bytes of a compressed point: the tag and `x`.
+    pub const fn compressed_point_len(&self) -> usize {
+        1 + self.field_len()
+    }
+}
+
+/// `secp256k1`, the curve of Ethereum keys.
+pub const SECP256K1: CurveConstants = CurveConstants {
+    name: "secp256k1",
+    suite: "secp256k1_XMD:SHA-256_SSWU_RO_",
+    dst: b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_",
+    field_modulus: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    order: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    generator_x: "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    generator_y: "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+};
+
+/// NIST P-256 (`secp256r1`), the curve of WebAuthn passkeys.
+pub const P256: CurveConstants = CurveConstants {
+    name: "p256",
+    suite: "P256_XMD:SHA-256_SSWU_RO_",
+    dst: b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_",
+    field_modulus: "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    order: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+    generator_x: "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    generator_y: "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+};
+
+/// Pallas, the curve over the base field of the Pasta cycle.
+pub const PALLAS: CurveConstants = CurveConstants {
+    name: "pallas",
+    suite: "pallas_XMD:SHA-256_SVDW_RO_",
+    dst: b"QUUX-V01-CS02-with-pallas_XMD:SHA-256_SVDW_RO_",
+    field_modulus: "40000000000000000000000000000000224698fc094cf91b992d30ed00000001",
+    order: "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001",
+    generator_x: "40000000000000000000000000000000224698fc094cf91b992d30ed00000000",
+    generator_y: "0000000000000000000000000000000000000000000000000000000000000002",
+};
+
+/// Vesta, the curve over the scalar field of Pallas.
+pub const VESTA: CurveConstants = CurveConstants {
+    name: "vesta",
+    suite: "vesta_XMD:SHA-256_SVDW_RO_",
+    dst: b"QUUX-V01-CS02-with-vesta_XMD:SHA-256_SVDW_RO_",
+    field_modulus: "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001",
+    order: "40000000000000000000000000000000224698fc094cf91b992d30ed00000001",
+    generator_x: "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000000",
+    generator_y: "0000000000000000000000000000000000000000000000000000000000000002",
+};
+
+/// The `G1` of BN254 (`alt_bn128`), the pairing curve of the Ethereum precompiles.
+pub const BN254: CurveConstants = CurveConstants {
+    name: "bn254",
+    suite: "BN254G1_XMD:SHA-256_SVDW_RO_",
+    dst: b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_",
+    field_modulus: "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+    order: "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    generator_x: "0000000000000000000000000000000000000000000000000000000000000001",
+    generator_y: "0000000000000000000000000000000000000000000000000000000000000002",
+};
+
+/// Grumpkin, the curve over the scalar field of BN254.
+pub const GRUMPKIN: CurveConstants = CurveConstants {
+    name: "grumpkin",
+    suite: "grumpkin_XMD:SHA-256_SVDW_RO_",
+    dst: b"QUUX-V01-CS02-with-grumpkin_XMD:SHA-256_SVDW_RO_",
+    field_modulus: "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    order: "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+    generator_x: "0000000000000000000000000000000000000000000000000000000000000001",
+    generator_y: "0000000000000002cf135e7506a45d632d270d45f1181294833fc48d823f272c",
+};
+
+/// Baby Jubjub in its short Weierstrass form, of the subgroup of prime order; the generator is `Base8` of circomlib.
+pub const BABYJUBJUB: CurveConstants = CurveConstants {
+    name: "babyjubjub",
+    suite: "babyjubjub_XMD:SHA-256_SSWU_RO_",
+    dst: b"QUUX-V01-CS02-with-babyjubjub_XMD:SHA-256_SSWU_RO_",
+    field_modulus: "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    order: "060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1",
+    generator_x: "1fde0a3cac7cb46b36c79f4c0a7a732e38c2c7ee9ac41f44392a07b748a0869f",
+    generator_y: "203a710160811d5c07ebaeb8fe1d9ce201c66b970d66f18d0d2b264c195309aa",
+};
+
+/// Every curve with constants, in the order they're generated in.
+pub const CURVES: &[CurveConstants] =
+    &[SECP256K1, P256, PALLAS, VESTA, BN254, GRUMPKIN, BABYJUBJUB];
+
+/// What the tag of a curve is followed by to hash a 32-byte digest to the curve, so that the digest and the same bytes as a message have
+/// distinct nullifiers.
+pub const DIGEST_DST_SUFFIX: &[u8] = b"DIGEST_";
+
+/// The tag of a SEC1 compressed point with an even `y`.
+pub const POINT_TAG_EVEN: u8 = 0x02;
+
+/// The tag of a SEC1 compressed point with an odd `y`.
+pub const POINT_TAG_ODD: u8 = 0x03;
+
+/// The number of bytes of the length prefix of a [framed](crate::transcript::framed) element, a big-endian `u64`.
+pub const FRAME_PREFIX_LEN: usize = 8;
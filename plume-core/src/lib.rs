@@ -0,0 +1,83 @@
+//! The protocol logic of PLUME shared by the curve backends.
+//!
+//! The versions, the layout of the challenge transcript and the constants of the curves live here, free of dependencies and of any curve
+//! arithmetic, so a review of the protocol can focus on this crate. The backends (`plume_arkworks`, `plume_rustcrypto`) encode the points
+//! and adapt these to their libraries.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod caip;
+pub mod conformance;
+pub mod consts;
+pub mod transcript;
+
+/// An `enum` representing the variant of the PLUME protocol.
+///
+/// It's `non_exhaustive`, as versions are added over time; a `match` outside this crate needs a `_` arm, and [`Self::code`] identifies a
+/// version without one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlumeVersion {
+    V1,
+    V2,
+    /// V1 with each element of the challenge preimage prefixed with its length as a big-endian `u64`.
+    V1_1,
+    /// V2 with each element of the challenge preimage prefixed with its length as a big-endian `u64`.
+    V2_1,
+}
+
+impl PlumeVersion {
+    /// Whether the elements of the challenge preimage are length prefixed. `V1` and `V2` keep the plain concatenation, so their
+    /// signatures stay byte compatible.
+    pub fn is_framed(self) -> bool {
+        matches!(self, Self::V1_1 | Self::V2_1)
+    }
+
+    /// Whether the challenge covers the generator, the key and the hash to the curve as well, as in V1.
+    pub fn is_v1(self) -> bool {
+        matches!(self, Self::V1 | Self::V1_1)
+    }
+
+    /// The number of the version in encodings and in the seeds of deterministic nonces: `1` to `4` for `V1`, `V2`, `V1_1` and `V2_1`.
+    /// A version keeps its number, and a new one gets the next.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+            Self::V1_1 => 3,
+            Self::V2_1 => 4,
+        }
+    }
+
+    /// The version of [`Self::code`].
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::V1),
+            2 => Some(Self::V2),
+            3 => Some(Self::V1_1),
+            4 => Some(Self::V2_1),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_codes() {
+        for version in [
+            PlumeVersion::V1,
+            PlumeVersion::V2,
+            PlumeVersion::V1_1,
+            PlumeVersion::V2_1,
+        ] {
+            assert_eq!(PlumeVersion::from_code(version.code()), Some(version));
+        }
+        assert_eq!(PlumeVersion::from_code(0), None);
+        assert_eq!(PlumeVersion::from_code(5), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! The byte layouts hashed by the protocol.
+//!
+//! Elements are either concatenated as they are, or framed: each prefixed with its length as a big-endian `u64`, so that no two lists of
+//! elements give the same bytes.
+
+use crate::consts::FRAME_PREFIX_LEN;
+use crate::PlumeVersion;
+use alloc::vec::Vec;
+
+/// Concatenates the fields, prefixing each with its length.
+pub fn framed(fields: &[&[u8]]) -> Vec<u8> {
+    join(fields, true)
+}
+
+/// Splits bytes [framed](framed) into their fields; `None` if a length runs past the end.
+pub fn unframe(mut bytes: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let (len, rest) = bytes.split_at_checked(FRAME_PREFIX_LEN)?;
+        let len = usize::try_from(u64::from_be_bytes(len.try_into().ok()?)).ok()?;
+        let (field, rest) = rest.split_at_checked(len)?;
+        fields.push(field);
+        bytes = rest;
+    }
+    Some(fields)
+}
+
+/// Joins `elements`, prefixing each with its length if `framed`.
+///
+/// Writes into a single buffer of the final size.
+pub fn join<E: AsRef<[u8]>>(elements: &[E], framed: bool) -> Vec<u8> {
+    let prefix_len = if framed { FRAME_PREFIX_LEN } else { 0 };
+    let mut joined = Vec::with_capacity(
+        elements
+            .iter()
+            .map(|element| prefix_len + element.as_ref().len())
+            .sum(),
+    );
+    for element in elements {
+        let element = element.as_ref();
+        if framed {
+            joined.extend_from_slice(&(element.len() as u64).to_be_bytes());
+        }
+        joined.extend_from_slice(element);
+    }
+    joined
+}
+
+/// The preimage of the challenge `c` of `version` from the encoded points.
+///
+/// V1 hashes `g, pk, h, nul, g^r, z`; V2 only `nul, g^r, z`, so `g`, `pk` and `h` aren't used by it.
+pub fn challenge_preimage<E: AsRef<[u8]>>(
+    version: PlumeVersion,
+    [g, pk, h, nul, g_r, z]: [E; 6],
+) -> Vec<u8> {
+    if version.is_v1() {
+        join(&[g, pk, h, nul, g_r, z], version.is_framed())
+    } else {
+        join(&[nul, g_r, z], version.is_framed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framing() {
+        assert_eq!(join(&[&b"ab"[..], &b"c"[..]], false), b"abc");
+        assert_eq!(
+            framed(&[&b"ab"[..], &b"c"[..]]),
+            [
+                &[0, 0, 0, 0, 0, 0, 0, 2][..],
+                b"ab",
+                &[0, 0, 0, 0, 0, 0, 0, 1],
+                b"c"
+            ]
+            .concat()
+        );
+        assert_ne!(
+            framed(&[&b"ab"[..], &b"c"[..]]),
+            framed(&[&b"a"[..], &b"bc"[..]])
+        );
+        assert_eq!(
+            unframe(&framed(&[&b"ab"[..], &b""[..], &b"c"[..]])),
+            Some(alloc::vec![&b"ab"[..], &b""[..], &b"c"[..]])
+        );
+        assert_eq!(unframe(&framed(&[&b"ab"[..]])[..9]), None);
+    }
+
+    #[test]
+    fn test_challenge_preimage() {
+        let points = [b"g", b"p", b"h", b"n", b"r", b"z"];
+        assert_eq!(challenge_preimage(PlumeVersion::V1, points), b"gphnrz");
+        assert_eq!(challenge_preimage(PlumeVersion::V2, points), b"nrz");
+        assert_eq!(
+            challenge_preimage(PlumeVersion::V2_1, points),
+            framed(&[&b"n"[..], &b"r"[..], &b"z"[..]])
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! The costs of key generation, hashing to the curve, signing and verifying on `secp256k1`, by message size.
+//!
+//! `cargo bench --bench plume` runs them; the randomness is seeded, so two runs sign and verify the same keys, nonces and messages. The
+//! numbers compare with the `plume` benchmark of `plume_rustcrypto`, which measures the same operations on `k256`.
+
+use ark_ec::AffineCurve;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use plume_arkworks::batch::{self, BatchItem};
+use plume_arkworks::hash_to_curve::hash_to_curve;
+use plume_arkworks::{Parameters, PlumeSignature, PlumeVersion};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use secp256k1::curves::{Affine, Secp256k1Parameters as P};
+
+const SEED: u64 = 0x504c_554d_45;
+
+/// The lengths of the messages, from a hash to a large payload.
+const MESSAGE_SIZES: [usize; 3] = [32, 256, 4096];
+
+/// The number of signatures of a batch.
+const BATCH_SIZES: [usize; 2] = [16, 128];
+
+fn parameters() -> Parameters<P> {
+    Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    }
+}
+
+fn message(rng: &mut StdRng, size: usize) -> Vec<u8> {
+    let mut message = vec![0; size];
+    rng.fill_bytes(&mut message);
+    message
+}
+
+fn bench_keygen(c: &mut Criterion) {
+    let pp = parameters();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    c.bench_function("keygen", |b| {
+        b.iter(|| PlumeSignature::keygen(&pp, &mut rng))
+    });
+}
+
+fn bench_hash_to_curve(c: &mut Criterion) {
+    let pp = parameters();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let (pk, _) = PlumeSignature::keygen(&pp, &mut rng);
+    let mut group = c.benchmark_group("hash_to_curve");
+    for size in MESSAGE_SIZES {
+        let message = message(&mut rng, size);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &message, |b, message| {
+            b.iter(|| hash_to_curve::<P>(message, &pk).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_sign(c: &mut Criterion) {
+    let pp = parameters();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    for (name, version) in [("sign_v1", PlumeVersion::V1), ("sign_v2", PlumeVersion::V2)] {
+        let mut group = c.benchmark_group(name);
+        for size in MESSAGE_SIZES {
+            let message = message(&mut rng, size);
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(size), &message, |b, message| {
+                b.iter(|| {
+                    PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap()
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let pp = parameters();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    for (name, version) in [
+        ("verify_v1", PlumeVersion::V1),
+        ("verify_v2", PlumeVersion::V2),
+    ] {
+        let mut group = c.benchmark_group(name);
+        for size in MESSAGE_SIZES {
+            let message = message(&mut rng, size);
+            let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), &message, version).unwrap();
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(size), &message, |b, message| {
+                b.iter(|| sig.verify(&pp, &pk, message, version).unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+/// Verifying a batch one by one with [`batch::verify_batch`], and all at once with [`PlumeSignature::verify_batch_combined`], over messages of
+/// the smallest size, so the group operations dominate.
+fn bench_verify_batch(c: &mut Criterion) {
+    let pp = parameters();
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let version = PlumeVersion::V2;
+    let mut group = c.benchmark_group("verify_batch");
+    for size in BATCH_SIZES {
+        let signed: Vec<_> = (0..size)
+            .map(|_| {
+                let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+                let message = message(&mut rng, MESSAGE_SIZES[0]);
+                let sig =
+                    PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), &message, version).unwrap();
+                (message, pk, sig)
+            })
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("each", size), &signed, |b, signed| {
+            b.iter(|| {
+                batch::verify_batch(
+                    &pp,
+                    signed.iter().map(|(message, pk, signature)| BatchItem {
+                        pk,
+                        message,
+                        signature,
+                        version,
+                    }),
+                )
+            })
+        });
+        let items: Vec<_> = signed
+            .iter()
+            .map(|(message, pk, sig)| (message.as_slice(), pk, sig))
+            .collect();
+        group.bench_with_input(BenchmarkId::new("combined", size), &items, |b, items| {
+            b.iter(|| {
+                assert!(
+                    PlumeSignature::verify_batch_combined(&pp, &mut rng, items, version).is_ok()
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_keygen,
+    bench_hash_to_curve,
+    bench_sign,
+    bench_verify,
+    bench_verify_batch
+);
+criterion_main!(benches);
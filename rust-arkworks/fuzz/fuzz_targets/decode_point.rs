@@ -0,0 +1,29 @@
+//! Arbitrary bytes as a point and as a public key: decoding never panics, and what decodes is canonical and in the prime order subgroup.
+
+#![no_main]
+
+use ark_ff::PrimeField;
+use libfuzzer_sys::fuzz_target;
+use plume_arkworks::babyjubjub::BabyJubJubParameters;
+use plume_arkworks::{affine_to_bytes, encoding, formats, validate, SWModelParameters};
+use secp256k1::curves::Secp256k1Parameters;
+
+fn check<P: SWModelParameters>(data: &[u8])
+where
+    P::BaseField: PrimeField,
+{
+    if let Ok(point) = encoding::point_from_bytes::<P>(data) {
+        assert!(validate::public_key(&point).is_ok());
+        assert_eq!(encoding::point_to_bytes(&point).unwrap(), data);
+        assert_eq!(affine_to_bytes(&point), data);
+    }
+    if let Ok(pk) = formats::public_key_from_bytes::<P>(data) {
+        assert!(validate::public_key(&pk).is_ok());
+        assert_eq!(formats::public_key_to_bytes(&pk).unwrap(), data);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<Secp256k1Parameters>(data);
+    check::<BabyJubJubParameters>(data);
+});
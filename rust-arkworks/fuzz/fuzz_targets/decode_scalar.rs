@@ -0,0 +1,20 @@
+//! Arbitrary bytes as a scalar: decoding never panics, and only the canonical encoding of a scalar less than the order decodes.
+
+#![no_main]
+
+use ark_ec::ModelParameters;
+use libfuzzer_sys::fuzz_target;
+use plume_arkworks::babyjubjub::BabyJubJubParameters;
+use plume_arkworks::encoding;
+use secp256k1::curves::Secp256k1Parameters;
+
+fn check<P: ModelParameters>(data: &[u8]) {
+    if let Ok(scalar) = encoding::field_from_bytes::<P::ScalarField>(data) {
+        assert_eq!(encoding::field_to_bytes(&scalar), data);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<Secp256k1Parameters>(data);
+    check::<BabyJubJubParameters>(data);
+});
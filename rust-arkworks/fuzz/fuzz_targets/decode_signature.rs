@@ -0,0 +1,41 @@
+//! Arbitrary bytes as a signature, in every format and in JSON: decoding never panics, and what decodes is canonical and validated.
+
+#![no_main]
+
+use ark_ff::PrimeField;
+use libfuzzer_sys::fuzz_target;
+use plume_arkworks::babyjubjub::BabyJubJubParameters;
+use plume_arkworks::formats::{self, Format};
+use plume_arkworks::{validate, HashToCurveSuite, PlumeSignature};
+use secp256k1::curves::Secp256k1Parameters;
+
+fn check<P: HashToCurveSuite>(data: &[u8])
+where
+    P::BaseField: PrimeField,
+{
+    if let Ok(sig) = PlumeSignature::<P>::from_bytes(data) {
+        assert!(validate::signature(&sig).is_ok());
+        assert_eq!(sig.to_bytes().unwrap(), data);
+    }
+    if let Ok(sig) = formats::decode::<P>(data) {
+        assert!(validate::signature(&sig).is_ok());
+        let latest = Format::Binary {
+            version: formats::BINARY_VERSION,
+        };
+        let migrated = formats::migrate::<P>(data, latest).unwrap();
+        assert!(PlumeSignature::<P>::from_bytes(&migrated).is_ok());
+    }
+    if let Ok(hex) = core::str::from_utf8(data) {
+        if let Ok(sig) = PlumeSignature::<P>::from_hex(hex) {
+            assert!(validate::signature(&sig).is_ok());
+        }
+    }
+    if let Ok(sig) = serde_json::from_slice::<PlumeSignature<P>>(data) {
+        assert!(validate::signature(&sig).is_ok());
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<Secp256k1Parameters>(data);
+    check::<BabyJubJubParameters>(data);
+});
@@ -0,0 +1,57 @@
+//! An arbitrary key, message and signature through verification: it never panics, and never accepts what it's given, since forging a
+//! signature is out of reach of a fuzzer. A valid signature with bytes changed by the fuzzer is rejected as well.
+
+#![no_main]
+
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::PrimeField;
+use libfuzzer_sys::fuzz_target;
+use plume_arkworks::{encoding, HashToCurveSuite, Parameters, PlumeSignature, PlumeVersion};
+use secp256k1::curves::Secp256k1Parameters;
+
+const VERSIONS: [PlumeVersion; 4] = [
+    PlumeVersion::V1,
+    PlumeVersion::V2,
+    PlumeVersion::V1_1,
+    PlumeVersion::V2_1,
+];
+
+fn check<P: HashToCurveSuite>(version: u8, pk: &[u8], message: &[u8], sig: &[u8], tamper: &[u8])
+where
+    P::BaseField: PrimeField,
+{
+    let pp = Parameters {
+        g_point: GroupAffine::<P>::prime_subgroup_generator(),
+    };
+    let version = VERSIONS[usize::from(version) % VERSIONS.len()];
+    if let (Ok(pk), Ok(sig)) = (
+        encoding::point_from_bytes::<P>(pk),
+        PlumeSignature::<P>::from_bytes(sig),
+    ) {
+        assert!(sig.verify(&pp, &pk, message, version).is_err());
+    }
+
+    // a signature by a fixed key, with the bytes `tamper` names xored
+    let sk = P::ScalarField::from(7u64);
+    let pk = pp.g_point.mul(sk).into_affine();
+    let Ok(sig) = PlumeSignature::sign_with_r(&pp, (&pk, &sk), message, 11u64.into(), version)
+    else {
+        return;
+    };
+    let mut bytes = sig.to_bytes().unwrap();
+    for pair in tamper.chunks_exact(2) {
+        let index = usize::from(pair[0]) % bytes.len();
+        bytes[index] ^= pair[1];
+    }
+    if bytes != sig.to_bytes().unwrap() {
+        if let Ok(tampered) = PlumeSignature::<P>::from_bytes(&bytes) {
+            assert!(tampered.verify(&pp, &pk, message, version).is_err());
+        }
+    }
+}
+
+fuzz_target!(|input: (u8, &[u8], &[u8], &[u8], &[u8])| {
+    let (version, pk, message, sig, tamper) = input;
+    check::<Secp256k1Parameters>(version, pk, message, sig, tamper);
+});
@@ -0,0 +1,132 @@
+//! Ethereum accounts as PLUME keypairs, with the `eth-recovery` feature: the key of a wallet's exported private key, with its address.
+//!
+//! A claim such as "this nullifier belongs to the address X" needs the address of the key that signed; [`EthereumAccount`] carries it next
+//! to the keypair, as [`recovery::address`] computes it, the last 20 bytes of the Keccak-256 of the uncompressed key. The addresses are
+//! written and read in the mixed-case checksum of EIP-55.
+
+use crate::keys::SecretKeyBytes;
+use crate::recovery::{self, Address};
+use crate::{encoding, secret_mul, EncodingError, PublicKey, SecretKeyMaterial};
+use alloc::string::String;
+use ark_ec::AffineCurve;
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+use tiny_keccak::{Hasher, Keccak};
+use zeroize::Zeroizing;
+
+/// A keypair on `secp256k1` with its Ethereum address; the secret key is wiped on drop.
+#[derive(Clone)]
+pub struct EthereumAccount {
+    public_key: PublicKey<Secp256k1Parameters>,
+    secret_key: Zeroizing<SecretKeyMaterial<Secp256k1Parameters>>,
+    address: Address,
+}
+
+impl core::fmt::Debug for EthereumAccount {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EthereumAccount")
+            .field("address", &checksum_address(&self.address))
+            .finish_non_exhaustive()
+    }
+}
+
+impl EthereumAccount {
+    /// The account of the private key `hex`, 32 bytes as wallets export it: with or without `0x`, in either case.
+    pub fn from_private_key_hex(hex: &str) -> Result<Self, EncodingError> {
+        let digits = Zeroizing::new(hex.strip_prefix("0x").unwrap_or(hex).to_ascii_lowercase());
+        let bytes = Zeroizing::new(encoding::hex_digits(&digits)?);
+        let bytes: Zeroizing<[u8; 32]> =
+            Zeroizing::new(bytes.as_slice().try_into().map_err(|_| {
+                EncodingError::InvalidLength {
+                    expected: 32,
+                    actual: bytes.len(),
+                }
+            })?);
+        Self::from_secret_key(&SecretKeyMaterial::<Secp256k1Parameters>::from_bytes(
+            &bytes,
+        )?)
+    }
+
+    /// The account of `secret_key`; fails with [`EncodingError::ZeroScalar`] for zero.
+    pub fn from_secret_key(
+        secret_key: &SecretKeyMaterial<Secp256k1Parameters>,
+    ) -> Result<Self, EncodingError> {
+        let public_key = secret_mul(&Affine::prime_subgroup_generator(), secret_key);
+        let address = recovery::address(&public_key).map_err(|_| EncodingError::ZeroScalar)?;
+        Ok(Self {
+            public_key,
+            secret_key: Zeroizing::new(*secret_key),
+            address,
+        })
+    }
+
+    /// The keypair, as [`PlumeSignature::sign`](crate::PlumeSignature::sign) takes it.
+    pub fn keypair(
+        &self,
+    ) -> (
+        &PublicKey<Secp256k1Parameters>,
+        &SecretKeyMaterial<Secp256k1Parameters>,
+    ) {
+        (&self.public_key, &self.secret_key)
+    }
+
+    /// The public key.
+    pub fn public_key(&self) -> &PublicKey<Secp256k1Parameters> {
+        &self.public_key
+    }
+
+    /// The address of the public key.
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+}
+
+/// The address of `public_key` as [`recovery::address`], with its checksummed hex.
+pub fn public_key_address(
+    public_key: &PublicKey<Secp256k1Parameters>,
+) -> Result<(Address, String), EncodingError> {
+    let address = recovery::address(public_key)?;
+    Ok((address, checksum_address(&address)))
+}
+
+/// The `0x`-prefixed hex of `address` with the checksum of EIP-55: a letter is uppercase when its nibble of the Keccak-256 of the lowercase
+/// hex is 8 or more.
+pub fn checksum_address(address: &Address) -> String {
+    let hex = encoding::to_hex(address);
+    let mut digest = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(hex.as_bytes());
+    keccak.finalize(&mut digest);
+
+    let mut checksummed = String::with_capacity(2 + hex.len());
+    checksummed.push_str("0x");
+    for (i, c) in hex.chars().enumerate() {
+        let nibble = (digest[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        checksummed.push(if nibble >= 8 {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        });
+    }
+    checksummed
+}
+
+/// The address of `hex`, with or without `0x`. An all-lowercase or all-uppercase address is taken as it is; a mixed-case one has to match
+/// its checksum, failing with [`EncodingError::InvalidChecksum`].
+pub fn parse_address(hex: &str) -> Result<Address, EncodingError> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+    let bytes = encoding::hex_digits(&digits.to_ascii_lowercase())?;
+    let address: Address =
+        bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| EncodingError::InvalidLength {
+                expected: 20,
+                actual: bytes.len(),
+            })?;
+    let mixed_case = digits.bytes().any(|b| b.is_ascii_lowercase())
+        && digits.bytes().any(|b| b.is_ascii_uppercase());
+    if mixed_case && checksum_address(&address)[2..] != *digits {
+        return Err(EncodingError::InvalidChecksum);
+    }
+    Ok(address)
+}
@@ -0,0 +1,108 @@
+//! Checks of signatures meant to be unlinkable across scopes.
+//!
+//! Nullifiers for different scopes are unlinkable only as long as the messages really differ per scope. Scope messages built by hand get
+//! this wrong in a few recurring ways: the same scope bytes used twice, the same message signed under two scopes, messages which don't
+//! contain their scope, and plain concatenations where one scope is a prefix of another. [`audit`] looks for these in a set of signatures
+//! and reports each occurrence as a [`Finding`].
+
+use crate::{affine_to_bytes, Message, PlumeSignature};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use ark_ec::models::SWModelParameters;
+
+/// A signature along with the scope it's claimed to be for.
+pub struct AuditEntry<'a, P: SWModelParameters> {
+    /// The scope the signature is meant for.
+    pub scope: &'a [u8],
+    /// The message signed.
+    pub message: Message<'a>,
+    /// The signature.
+    pub signature: &'a PlumeSignature<P>,
+}
+
+/// A linkability mistake, referring to the entries by their index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    /// The entries have the same scope bytes.
+    DuplicateScope { indices: Vec<usize> },
+    /// The entries sign the same message, so a signer gets the same nullifier under each of the scopes.
+    ReusedMessage { indices: Vec<usize> },
+    /// The entries have the same nullifier, so they're linked.
+    SharedNullifier { indices: Vec<usize> },
+    /// The message doesn't contain its scope, so the scope doesn't bind the nullifier.
+    ScopeNotInMessage { index: usize },
+    /// The scope of `prefix` starts the scope of `of`; messages concatenated from them without framing can collide.
+    ScopePrefix { prefix: usize, of: usize },
+}
+
+/// The findings of an [`audit`], in the order of the checks and then of the entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkageReport {
+    pub findings: Vec<Finding>,
+}
+
+impl LinkageReport {
+    /// Whether nothing was found.
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Checks `entries`, allegedly of distinct scopes, for linkability mistakes.
+pub fn audit<'a, P: SWModelParameters + 'a>(
+    entries: impl IntoIterator<Item = AuditEntry<'a, P>>,
+) -> LinkageReport {
+    let entries = entries.into_iter().collect::<Vec<_>>();
+    let mut findings = Vec::new();
+
+    findings.extend(
+        groups(entries.iter().map(|entry| entry.scope.to_vec()))
+            .map(|indices| Finding::DuplicateScope { indices }),
+    );
+    findings.extend(
+        groups(entries.iter().map(|entry| entry.message.to_vec()))
+            .map(|indices| Finding::ReusedMessage { indices }),
+    );
+    findings.extend(
+        groups(
+            entries
+                .iter()
+                .map(|entry| affine_to_bytes(&entry.signature.nullifier)),
+        )
+        .map(|indices| Finding::SharedNullifier { indices }),
+    );
+    findings.extend(
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !contains(entry.message, entry.scope))
+            .map(|(index, _)| Finding::ScopeNotInMessage { index }),
+    );
+    for (prefix, entry) in entries.iter().enumerate() {
+        for (of, other) in entries.iter().enumerate() {
+            if entry.scope.len() < other.scope.len() && other.scope.starts_with(entry.scope) {
+                findings.push(Finding::ScopePrefix { prefix, of });
+            }
+        }
+    }
+
+    LinkageReport { findings }
+}
+
+/// The indices of the keys occurring more than once, grouped by key in the order of their first occurrence.
+fn groups(keys: impl Iterator<Item = Vec<u8>>) -> impl Iterator<Item = Vec<usize>> {
+    let mut by_key = BTreeMap::<Vec<u8>, Vec<usize>>::new();
+    for (index, key) in keys.enumerate() {
+        by_key.entry(key).or_default().push(index);
+    }
+    let mut groups = by_key
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .collect::<Vec<_>>();
+    groups.sort();
+    groups.into_iter()
+}
+
+fn contains(message: &[u8], scope: &[u8]) -> bool {
+    scope.is_empty() || message.windows(scope.len()).any(|window| window == scope)
+}
@@ -0,0 +1,141 @@
+//! Baby Jubjub, the twisted Edwards curve over the BN254 scalar field which circom projects keep identities on.
+//!
+//! The signatures are over short Weierstrass curves, so Baby Jubjub is taken in its equivalent Weierstrass form: the twisted Edwards
+//! curve `168700 ⋅ x^2 + y^2 = 1 + 168696 ⋅ x^2 ⋅ y^2` of circomlib is birationally equivalent to the Montgomery curve
+//! `y^2 = x^3 + 168698 ⋅ x^2 + x`, which is the Weierstrass curve of [`BabyJubJubParameters`] after `x ↦ x + 168698 / 3`. The group is
+//! the same, and [`to_edwards`] and [`from_edwards`] convert the points, so keys and nullifiers can go to and from circuits in the
+//! coordinates of circomlib. The generator is circomlib's `Base8`, of the prime order subgroup; the cofactor is `8`.
+//!
+//! The Weierstrass form has `A ⋅ B ≠ 0`, so it hashes by the simplified SWU map of RFC 9380, [`hash_to_curve_sswu`], with `Z = 5` as
+//! `find_z_sswu` of the RFC picks, under the suite `babyjubjub_XMD:SHA-256_SSWU_RO_`, and the output is multiplied by the cofactor. The
+//! fields are those of `ark-ed-on-bn254`.
+
+use crate::error::{EncodingError, HashToCurveError};
+use crate::hash_to_curve::{hash_to_curve_sswu, HashToCurveSuite};
+use crate::validate;
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{ModelParameters, SWModelParameters};
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::{Field, One, Zero};
+use plume_core::consts;
+
+pub use ark_ed_on_bn254::{Fq, Fr};
+
+/// A point of Baby Jubjub in affine Weierstrass coordinates.
+pub type Affine = GroupAffine<BabyJubJubParameters>;
+/// A point of Baby Jubjub in Jacobian Weierstrass coordinates.
+pub type Projective = GroupProjective<BabyJubJubParameters>;
+
+/// The `a` of the twisted Edwards form.
+const EDWARDS_A: u64 = 168700;
+/// The `d` of the twisted Edwards form.
+const EDWARDS_D: u64 = 168696;
+/// The `A` of the Montgomery form, `2 ⋅ (a + d) / (a - d)`; its `B` is `4 / (a - d) = 1`.
+const MONTGOMERY_A: u64 = 168698;
+
+/// The parameters of the Weierstrass form of Baby Jubjub, `y^2 = x^3 + A ⋅ x + B` over [`Fq`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BabyJubJubParameters;
+
+impl ModelParameters for BabyJubJubParameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for BabyJubJubParameters {
+    /// `(3 - 168698^2) / 3`
+    const COEFF_A: Fq = Fq::new(BigInteger([
+        0x0c24bbcad9e014b8,
+        0xc8bb93c2a5ca1505,
+        0xed958fa60152f67a,
+        0x2b3b14c7dffbfa45,
+    ]));
+
+    /// `(2 ⋅ 168698^3 - 9 ⋅ 168698) / 27`
+    const COEFF_B: Fq = Fq::new(BigInteger([
+        0xa15cc6212f8b8aae,
+        0x706695618f1a43da,
+        0x38c069142fad86d3,
+        0x14e5e52de2ac7efc,
+    ]));
+
+    const COFACTOR: &'static [u64] = &[8];
+
+    /// `1 / 8`
+    const COFACTOR_INV: Fr = Fr::new(BigInteger([
+        0xfac308b2e25a3d4b,
+        0xa7c55b66e25b59cb,
+        0xeccdd46def0f28c5,
+        0x01c14ef83340fbe5,
+    ]));
+
+    /// `Base8` of circomlib, `(x, y) = (0x0bb77a6a…bb957051, 0x25797203…872d7d8b)` in the twisted Edwards form.
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) = (
+        Fq::new(BigInteger([
+            0x933aea2e6368a895,
+            0x487ac8d5fbba2eae,
+            0xa658cd77ae948ea4,
+            0x247c2ce8a0769c4d,
+        ])),
+        Fq::new(BigInteger([
+            0x4401b01a45396255,
+            0xc87cce343ae6d04b,
+            0x6bfc0568672d07a0,
+            0x1b08246433be8597,
+        ])),
+    );
+}
+
+impl HashToCurveSuite for BabyJubJubParameters {
+    const SUITE: &'static str = consts::BABYJUBJUB.suite;
+    const DST: &'static [u8] = consts::BABYJUBJUB.dst;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        // `Z = 5`
+        hash_to_curve_sswu::<Self>(msgs, dst, Fq::from(5u64))
+    }
+}
+
+/// The shift of `x` from the Montgomery form to the Weierstrass form, `A / 3`.
+fn x_shift() -> Fq {
+    Fq::from(MONTGOMERY_A) / Fq::from(3u64)
+}
+
+/// The twisted Edwards coordinates `(x, y)` of `point`, as circomlib has them; `(0, 1)` for the point at infinity.
+pub fn to_edwards(point: &Affine) -> (Fq, Fq) {
+    if point.is_zero() {
+        return (Fq::zero(), Fq::one());
+    }
+    let u = point.x - x_shift();
+    let v = point.y;
+    // `(0, 0)` of the Montgomery form, of order 2
+    let Some(v_inv) = v.inverse() else {
+        return (Fq::zero(), -Fq::one());
+    };
+    // `u = -1` has no point: `d` isn't a square, so the Edwards form has no exceptional points
+    let y = (u - Fq::one()) * (u + Fq::one()).inverse().unwrap_or_else(Fq::zero);
+    (u * v_inv, y)
+}
+
+/// The point of the twisted Edwards coordinates `(x, y)`, checking it's on the curve, not the identity, and in the prime order subgroup
+/// as a key or a nullifier has to be.
+pub fn from_edwards(x: Fq, y: Fq) -> Result<Affine, EncodingError> {
+    let (x2, y2) = (x.square(), y.square());
+    if Fq::from(EDWARDS_A) * x2 + y2 != Fq::one() + Fq::from(EDWARDS_D) * x2 * y2 {
+        return Err(EncodingError::NotOnCurve);
+    }
+    let Some(one_minus_y_inv) = (Fq::one() - y).inverse() else {
+        // `y = 1` is only the identity, `(0, 1)`
+        return Err(EncodingError::Identity);
+    };
+    let point = match x.inverse() {
+        Some(x_inv) => {
+            let u = (Fq::one() + y) * one_minus_y_inv;
+            Affine::new(u + x_shift(), u * x_inv, false)
+        }
+        // `(0, -1)`, of order 2
+        None => Affine::new(x_shift(), Fq::zero(), false),
+    };
+    validate::point(&point)?;
+    Ok(point)
+}
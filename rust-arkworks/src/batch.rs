@@ -0,0 +1,175 @@
+//! Signing and verifying many messages, with a result per item.
+//!
+//! A batch is not all-or-nothing: each item gets its own result in the input order, so a single malformed item in a large batch is located
+//! by its index without going through the batch again one by one.
+//!
+//! [`PlumeSignature::verify_batch_combined`] is all-or-nothing instead, for when a batch is expected to be valid: it checks all the
+//! signatures with a single multi-scalar multiplication.
+
+use crate::challenge::CurveDefault;
+use crate::{
+    compute_c_v1, compute_c_v2, compute_h, validate, HashToCurveError, HashToCurveSuite, Message,
+    Parameters, PlumeSignature, PlumeVerifyError, PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ec::models::SWModelParameters;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ff::{PrimeField, Zero};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use core::fmt;
+use sha2::Sha256;
+
+/// A signature to verify, with the key and the message it's checked against.
+pub struct BatchItem<'a, P: SWModelParameters> {
+    /// The key of the signer.
+    pub pk: &'a PublicKey<P>,
+    /// The message signed.
+    pub message: Message<'a>,
+    /// The signature to verify.
+    pub signature: &'a PlumeSignature<P>,
+    /// The version the signature is made with.
+    pub version: PlumeVersion,
+}
+
+/// Signs each of `messages`; the results are in the order of `messages`.
+pub fn sign_batch<'a, P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    rng: &mut impl Rng,
+    keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+    messages: impl IntoIterator<Item = Message<'a>>,
+    version: PlumeVersion,
+) -> Vec<Result<PlumeSignature<P>, HashToCurveError>> {
+    messages
+        .into_iter()
+        .map(|message| PlumeSignature::sign(pp, rng, keypair, message, version))
+        .collect()
+}
+
+/// Verifies each of `items`; the results are in the order of `items`.
+///
+/// An item's result is `Ok(())` for a valid signature and otherwise the error of [`PlumeSignature::verify_non_zk`], which says why it
+/// didn't verify.
+pub fn verify_batch<'a, P: HashToCurveSuite + 'a>(
+    pp: &Parameters<P>,
+    items: impl IntoIterator<Item = BatchItem<'a, P>>,
+) -> Vec<Result<(), PlumeVerifyError>> {
+    items
+        .into_iter()
+        .map(|item| {
+            item.signature
+                .verify_non_zk(pp, item.pk, item.message, item.version)
+        })
+        .collect()
+}
+
+/// The indices of the items of a [`verify_batch`] result which didn't verify.
+pub fn failed_indices(results: &[Result<(), PlumeVerifyError>]) -> Vec<usize> {
+    results
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| result.is_err())
+        .map(|(index, _)| index)
+        .collect()
+}
+
+impl<P: HashToCurveSuite> PlumeSignature<P> {
+    /// Verifies all of `items`, signatures of a message by a key, made with `version`; `Ok(())` only if every one is valid.
+    ///
+    /// The two equations `g^s = g^r ⋅ pk^c` and `h^s = z ⋅ nul^c` of each signature are combined with random weights drawn from `rng`
+    /// into one multi-scalar multiplication, after checking the points and the challenges. The weights are chosen after the signatures, so an
+    /// invalid signature makes the combination cancel out only with probability `1 / n` for the order `n`. An empty batch is valid.
+    pub fn verify_batch_combined(
+        pp: &Parameters<P>,
+        rng: &mut (impl Rng + CryptoRng),
+        items: &[(Message, &PublicKey<P>, &Self)],
+        version: PlumeVersion,
+    ) -> Result<(), BatchVerifyError> {
+        let mut hashed_to_curve = Vec::with_capacity(items.len());
+        for (index, (message, pk, sig)) in items.iter().enumerate() {
+            let item = |error| BatchVerifyError::Item { index, error };
+            validate::public_key(pk).map_err(|e| item(PlumeVerifyError::InvalidPublicKey(e)))?;
+            validate::signature(sig).map_err(|e| item(PlumeVerifyError::InvalidSignature(e)))?;
+            let h =
+                compute_h::<P>(pk, message).map_err(|e| item(PlumeVerifyError::HashToCurve(e)))?;
+            let c = match version {
+                PlumeVersion::V1 | PlumeVersion::V1_1 => compute_c_v1::<P, Sha256, _>(
+                    &pp.g_point,
+                    pk,
+                    &h,
+                    &sig.nullifier,
+                    &sig.r_point,
+                    &sig.hashed_to_curve_r,
+                    version.is_framed(),
+                    &[],
+                    &CurveDefault,
+                ),
+                _ => compute_c_v2::<P, Sha256, _>(
+                    &sig.nullifier,
+                    &sig.r_point,
+                    &sig.hashed_to_curve_r,
+                    version.is_framed(),
+                    &[],
+                    &CurveDefault,
+                ),
+            };
+            if c != sig.c {
+                return Err(item(PlumeVerifyError::ChallengeMismatch));
+            }
+            hashed_to_curve.push(h);
+        }
+
+        // Σ a_i ⋅ (s_i ⋅ g - c_i ⋅ pk_i - g^r_i) + b_i ⋅ (s_i ⋅ h_i - c_i ⋅ nul_i - z_i) = 0
+        let mut bases = Vec::with_capacity(1 + 5 * items.len());
+        let mut scalars = Vec::with_capacity(1 + 5 * items.len());
+        let mut g_scalar = P::ScalarField::zero();
+        for ((_, pk, sig), h) in items.iter().zip(&hashed_to_curve) {
+            let (a, b) = (P::ScalarField::rand(rng), P::ScalarField::rand(rng));
+            g_scalar += a * sig.s;
+            bases.extend([**pk, sig.r_point, *h, sig.nullifier, sig.hashed_to_curve_r]);
+            scalars.extend([-(a * sig.c), -a, b * sig.s, -(b * sig.c), -b]);
+        }
+        bases.push(pp.g_point);
+        scalars.push(g_scalar);
+
+        let scalars = scalars
+            .iter()
+            .map(PrimeField::into_repr)
+            .collect::<Vec<_>>();
+        if VariableBaseMSM::multi_scalar_mul(&bases, &scalars).is_zero() {
+            Ok(())
+        } else {
+            Err(BatchVerifyError::Combination)
+        }
+    }
+}
+
+/// Why [`PlumeSignature::verify_batch_combined`] rejected a batch.
+#[derive(Debug, Clone)]
+pub enum BatchVerifyError {
+    /// The item at `index` failed a check of its own: a point, hashing to the curve, or the challenge.
+    Item {
+        index: usize,
+        error: PlumeVerifyError,
+    },
+    /// The combined equations don't hold, so some signature is invalid; [`verify_batch`] says which.
+    Combination,
+}
+
+impl fmt::Display for BatchVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Item { index, error } => write!(f, "item {index} of the batch: {error}"),
+            Self::Combination => write!(f, "the combined equations of the batch don't hold"),
+        }
+    }
+}
+
+impl core::error::Error for BatchVerifyError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Item { error, .. } => Some(error),
+            Self::Combination => None,
+        }
+    }
+}
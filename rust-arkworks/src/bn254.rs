@@ -0,0 +1,101 @@
+//! BN254 and Grumpkin, the cycle of the Noir and Aztec circuits.
+//!
+//! Each curve's base field is the other's scalar field, so a circuit over the BN254 scalar field, as Noir's are, verifies the nullifier
+//! relation on Grumpkin natively. BN254 is the `G1` of `ark-bn254`; Grumpkin, `y^2 = x^3 - 17` with the generator `(1, sqrt(-16))` as
+//! in Noir, is defined here over its fields, with the constants in Montgomery form as `ark-ff` expects. Both are of prime order and of
+//! `A = 0`, so they hash by the Shallue-van de Woestijne map of RFC 9380, [`hash_to_curve_svdw`], with `Z = 1` as its `find_z_svdw`
+//! picks: BN254 under the `BN254G1_XMD:SHA-256_SVDW_RO_` suite of the drafts of the RFC, Grumpkin under `grumpkin_XMD:SHA-256_SVDW_RO_`.
+//!
+//! The challenge hash is SHA-256 as on every curve; circuits which would rather hash in the field take another [`PlumeHasher`] with
+//! [`PlumeSignature::sign_with_hasher`](crate::PlumeSignature::sign_with_hasher).
+//!
+//! [`PlumeHasher`]: crate::hasher::PlumeHasher
+
+use crate::error::HashToCurveError;
+use crate::hash_to_curve::{hash_to_curve_svdw, HashToCurveSuite};
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{ModelParameters, SWModelParameters};
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::One;
+use plume_core::consts;
+
+pub use ark_bn254::g1::Parameters as Bn254Parameters;
+pub use ark_bn254::{Fq, Fr, G1Affine, G1Projective};
+
+/// A point of Grumpkin in affine coordinates.
+pub type GrumpkinAffine = GroupAffine<GrumpkinParameters>;
+/// A point of Grumpkin in Jacobian coordinates.
+pub type GrumpkinProjective = GroupProjective<GrumpkinParameters>;
+
+impl HashToCurveSuite for Bn254Parameters {
+    const SUITE: &'static str = consts::BN254.suite;
+    const DST: &'static [u8] = consts::BN254.dst;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        // `Z = 1`
+        hash_to_curve_svdw::<Self>(msgs, dst, Fq::one())
+    }
+}
+
+/// The parameters of Grumpkin, `y^2 = x^3 - 17` over [`Fr`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrumpkinParameters;
+
+impl ModelParameters for GrumpkinParameters {
+    type BaseField = Fr;
+    type ScalarField = Fq;
+}
+
+impl SWModelParameters for GrumpkinParameters {
+    /// `0`
+    const COEFF_A: Fr = Fr::new(BigInteger([
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+    ]));
+
+    /// `-17`
+    const COEFF_B: Fr = Fr::new(BigInteger([
+        0xdd7056026000005a,
+        0x223fa97acb319311,
+        0xcc388229877910c0,
+        0x034394632b724eaa,
+    ]));
+
+    const COFACTOR: &'static [u64] = &[1];
+
+    /// `1`
+    const COFACTOR_INV: Fq = Fq::new(BigInteger([
+        0xd35d438dc58f0d9d,
+        0x0a78eb28f5c70b3d,
+        0x666ea36f7879462c,
+        0x0e0a77c19a07df2f,
+    ]));
+
+    /// `(1, 0x2cf135e7…823f272c)`
+    const AFFINE_GENERATOR_COEFFS: (Fr, Fr) = (
+        Fr::new(BigInteger([
+            0xac96341c4ffffffb,
+            0x36fc76959f60cd29,
+            0x666ea36f7879462e,
+            0x0e0a77c19a07df2f,
+        ])),
+        Fr::new(BigInteger([
+            0x11b2dff1448c41d8,
+            0x23d3446f21c77dc3,
+            0xaa7b8cf435dfafbb,
+            0x14b34cf69dc25d68,
+        ])),
+    );
+}
+
+impl HashToCurveSuite for GrumpkinParameters {
+    const SUITE: &'static str = consts::GRUMPKIN.suite;
+    const DST: &'static [u8] = consts::GRUMPKIN.dst;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        // `Z = 1`
+        hash_to_curve_svdw::<Self>(msgs, dst, Fr::one())
+    }
+}
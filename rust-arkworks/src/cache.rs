@@ -0,0 +1,207 @@
+//! Parameters and precomputed tables shared across the signatures of a process, for services verifying many signatures.
+//!
+//! [`StandardParameters::parameters`] is the [`Parameters`] of the standard generator of a curve, built once per process in a [`OnceLock`]
+//! and shared by every thread, rather than rebuilt by each. [`StandardParameters::generator_table`] is a [`PointTable`] of the generator
+//! shared the same way.
+//!
+//! The tables of the keys are per thread, so verifying doesn't contend on a lock: [`warm`] builds the table of a key into the cache of the
+//! calling thread, [`warm_generator`] links the shared table of the generator into it, and [`evict`] and [`clear`] drop them. While both
+//! the generator of the parameters and the key are warm, [`PlumeSignature::verify`](crate::PlumeSignature::verify) and the other
+//! verifications compute `g^s ⋅ pk^{-c}` from the tables; otherwise they compute it as they would without the cache. A table is about
+//! `64 ⋅ 15` points, some 60 KiB on a 256-bit curve, so evict the keys which aren't verified against any more.
+//!
+//! The module needs the `std` feature.
+
+use crate::error::EncodingError;
+use crate::{encoding, validate, Parameters};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{AffineCurve, ProjectiveCurve, SWModelParameters};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use core::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// The number of bits of the scalar each window of a [`PointTable`] takes.
+const WINDOW_BITS: usize = 4;
+
+/// The multiples of a point by each digit of a window, but zero.
+const WINDOW_MULTIPLES: usize = (1 << WINDOW_BITS) - 1;
+
+/// The multiples of a point for multiplying it by any scalar with additions only, in windows of 4 bits.
+///
+/// A multiplication is one mixed addition per window, about 64 on a 256-bit curve, where the generic one doubles for every bit. It isn't
+/// constant time; verification multiplies public values only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointTable<P: SWModelParameters> {
+    /// `windows[i][j]` is the point multiplied by `(j + 1) ⋅ 16^i`.
+    windows: Vec<Vec<GroupAffine<P>>>,
+}
+
+impl<P: SWModelParameters> PointTable<P> {
+    /// The table of `point`.
+    pub fn new(point: &GroupAffine<P>) -> Self {
+        let bits = P::ScalarField::size_in_bits();
+        let mut base = point.into_projective();
+        let windows = (0..(bits + WINDOW_BITS - 1) / WINDOW_BITS)
+            .map(|_| {
+                let mut multiples = Vec::with_capacity(WINDOW_MULTIPLES);
+                let mut multiple = base;
+                for _ in 0..WINDOW_MULTIPLES {
+                    multiples.push(multiple);
+                    multiple += &base;
+                }
+                base = multiple;
+                GroupProjective::batch_normalization(&mut multiples);
+                multiples.iter().map(|p| p.into_affine()).collect()
+            })
+            .collect();
+        Self { windows }
+    }
+
+    /// The point multiplied by `scalar`.
+    pub fn mul(&self, scalar: &P::ScalarField) -> GroupProjective<P> {
+        let bits = scalar.into_repr().to_bits_le();
+        let mut sum = GroupProjective::zero();
+        for (multiples, window) in self.windows.iter().zip(bits.chunks(WINDOW_BITS)) {
+            let digit = window
+                .iter()
+                .rev()
+                .fold(0, |digit, &bit| (digit << 1) | usize::from(bit));
+            if let Some(multiple) = digit.checked_sub(1).and_then(|j| multiples.get(j)) {
+                sum.add_assign_mixed(multiple);
+            }
+        }
+        sum
+    }
+}
+
+/// The curves with standard parameters shared by the process.
+pub trait StandardParameters: SWModelParameters {
+    /// The parameters of the standard generator, built on the first call.
+    fn parameters() -> &'static Parameters<Self>;
+
+    /// The table of the standard generator, built on the first call.
+    fn generator_table() -> &'static PointTable<Self>;
+}
+
+macro_rules! standard_parameters {
+    ($($curve:ty),* $(,)?) => {
+        $(
+            impl StandardParameters for $curve {
+                fn parameters() -> &'static Parameters<Self> {
+                    static PARAMETERS: OnceLock<Parameters<$curve>> = OnceLock::new();
+                    PARAMETERS.get_or_init(|| Parameters {
+                        g_point: GroupAffine::prime_subgroup_generator(),
+                    })
+                }
+
+                fn generator_table() -> &'static PointTable<Self> {
+                    static TABLE: OnceLock<PointTable<$curve>> = OnceLock::new();
+                    TABLE.get_or_init(|| PointTable::new(&Self::parameters().g_point))
+                }
+            }
+        )*
+    };
+}
+
+standard_parameters!(
+    secp256k1::curves::Secp256k1Parameters,
+    crate::p256::P256Parameters,
+    crate::pasta::PallasParameters,
+    crate::pasta::VestaParameters,
+    crate::bn254::Bn254Parameters,
+    crate::bn254::GrumpkinParameters,
+    crate::babyjubjub::BabyJubJubParameters,
+);
+
+/// A table in the cache of a thread: built for it, or the table of a generator shared by the process.
+enum Entry {
+    Owned(Box<dyn Any>),
+    Shared(&'static dyn Any),
+}
+
+impl Entry {
+    fn table<P: SWModelParameters>(&self) -> Option<&PointTable<P>> {
+        match self {
+            Entry::Owned(table) => table.downcast_ref(),
+            Entry::Shared(table) => table.downcast_ref(),
+        }
+    }
+}
+
+/// The curve and the compressed encoding of a point.
+type Key = (TypeId, Vec<u8>);
+
+std::thread_local! {
+    static TABLES: RefCell<HashMap<Key, Entry>> = RefCell::new(HashMap::new());
+}
+
+fn key<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<Key, EncodingError> {
+    Ok((TypeId::of::<P>(), encoding::point_to_bytes(point)?))
+}
+
+/// Builds the table of `point`, usually a public key, into the cache of this thread, unless it's there already.
+///
+/// It fails on a point which isn't a valid key; see [`validate::point`].
+pub fn warm<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<(), EncodingError> {
+    validate::point(point)?;
+    let key = key(point)?;
+    TABLES.with(|tables| {
+        tables
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with(|| Entry::Owned(Box::new(PointTable::new(point))));
+    });
+    Ok(())
+}
+
+/// Links the shared table of the standard generator of `P` into the cache of this thread.
+pub fn warm_generator<P: StandardParameters>() {
+    let g_point = &P::parameters().g_point;
+    if let Ok(key) = key(g_point) {
+        TABLES.with(|tables| {
+            tables
+                .borrow_mut()
+                .insert(key, Entry::Shared(P::generator_table()));
+        });
+    }
+}
+
+/// Whether the table of `point` is in the cache of this thread.
+pub fn is_warm<P: SWModelParameters>(point: &GroupAffine<P>) -> bool {
+    key(point).is_ok_and(|key| TABLES.with(|tables| tables.borrow().contains_key(&key)))
+}
+
+/// Drops the table of `point` from the cache of this thread; `false` if it wasn't there.
+pub fn evict<P: SWModelParameters>(point: &GroupAffine<P>) -> bool {
+    key(point).is_ok_and(|key| TABLES.with(|tables| tables.borrow_mut().remove(&key).is_some()))
+}
+
+/// Drops every table from the cache of this thread.
+pub fn clear() {
+    TABLES.with(|tables| tables.borrow_mut().clear());
+}
+
+/// The number of tables in the cache of this thread.
+pub fn len() -> usize {
+    TABLES.with(|tables| tables.borrow().len())
+}
+
+/// Computes `a^x ⋅ b^y` from the tables of this thread, if both points are warm.
+pub(crate) fn double_mul<P: SWModelParameters>(
+    a: &GroupAffine<P>,
+    x: P::ScalarField,
+    b: &GroupAffine<P>,
+    y: P::ScalarField,
+) -> Option<GroupAffine<P>> {
+    let (a_key, b_key) = (key(a).ok()?, key(b).ok()?);
+    TABLES.with(|tables| {
+        let tables = tables.borrow();
+        let a_table = tables.get(&a_key)?.table::<P>()?;
+        let b_table = tables.get(&b_key)?.table::<P>()?;
+        Some((a_table.mul(&x) + b_table.mul(&y)).into_affine())
+    })
+}
@@ -0,0 +1,137 @@
+//! Derivation of the challenge scalar `c` from the challenge preimage.
+//!
+//! The preimage is hashed with a [`PlumeHasher`], SHA-256 by default, which gives a uniform 256-bit number; how it's mapped into the scalar
+//! field decides the bias of `c`. The digests below are given for SHA-256.
+//!
+//! - [`ChallengeDerivation::ModOrder`] reduces the digest modulo the group order `n`. Residues below `2^256 mod n` are hit once more often
+//!   than the rest, so the statistical distance from uniform is about `(2^256 mod n) / 2^256`. That's under `2^-127` for `secp256k1`, but for
+//!   a field of 255 bits or less (most SNARK-friendly curves) a large share of the values is twice as likely.
+//! - [`ChallengeDerivation::TruncateAndRetry`] keeps the leading `log2(n)` bits of the digest, rehashing the digest until the value is less
+//!   than `n`. It's unbiased; as `n` is above `2^(log2(n) - 1)` it expects less than two hashes.
+//! - [`ChallengeDerivation::WideReduction`] reduces 64 bytes (`SHA-256(preimage || 0x00) || SHA-256(preimage || 0x01)`) modulo `n`;
+//!   the distance from uniform is below `n / 2^512`.
+//!
+//! [`ChallengeDerivation::statistical_distance`] gives the exact distance of each for a field. The signatures use [`CurveDefault`], the
+//! derivation of [`ChallengeDerivation::for_field`]; another one, or a strategy of its own implementing [`ChallengeReduction`], is passed to
+//! [`PlumeSignature::sign_with_reduction`] and [`PlumeSignature::verify_non_zk_with_reduction`], and the verifier has to use the same.
+//!
+//! [`PlumeSignature::sign_with_reduction`]: crate::PlumeSignature::sign_with_reduction
+//! [`PlumeSignature::verify_non_zk_with_reduction`]: crate::PlumeSignature::verify_non_zk_with_reduction
+
+use crate::hasher::PlumeHasher;
+use alloc::{vec, vec::Vec};
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+
+/// A strategy mapping the challenge preimage to a scalar.
+pub trait ChallengeReduction {
+    /// Maps the challenge preimage to a scalar of `F`, hashing with `H`.
+    fn reduce<F: PrimeField, H: PlumeHasher>(&self, preimage: &[u8]) -> F;
+}
+
+/// The derivation of [`ChallengeDerivation::for_field`] for the scalar field of the curve; what the signatures use unless told otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CurveDefault;
+
+impl ChallengeReduction for CurveDefault {
+    fn reduce<F: PrimeField, H: PlumeHasher>(&self, preimage: &[u8]) -> F {
+        ChallengeDerivation::for_field::<F>().derive::<F, H>(preimage)
+    }
+}
+
+impl ChallengeReduction for ChallengeDerivation {
+    fn reduce<F: PrimeField, H: PlumeHasher>(&self, preimage: &[u8]) -> F {
+        self.derive::<F, H>(preimage)
+    }
+}
+
+/// A policy mapping the challenge preimage to a scalar. See [the module docs](self) for the bias of each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeDerivation {
+    /// `from_be_bytes_mod_order` of the SHA-256 digest; the original PLUME derivation.
+    ModOrder,
+    /// The digest truncated to the bit length of the order, rehashed until it's a canonical scalar.
+    TruncateAndRetry,
+    /// A 64-byte expansion of the preimage reduced modulo the order.
+    WideReduction,
+}
+
+impl ChallengeDerivation {
+    /// The derivation used for the curve with the scalar field `F`.
+    ///
+    /// It's [`Self::ModOrder`] when its bias is below `2^-64`, which preserves the signatures over `secp256k1`; otherwise
+    /// [`Self::WideReduction`].
+    pub fn for_field<F: PrimeField>() -> Self {
+        let modulus = <F::Params as FpParameters>::MODULUS;
+        let bits = F::size_in_bits();
+        // the bias of the reduction is below `2^-64` if the order is at least `2^256 - 2^192`, or when the digest is never reduced
+        if bits > 256 || (bits == 256 && (192..256).all(|i| BigInteger::get_bit(&modulus, i))) {
+            Self::ModOrder
+        } else {
+            Self::WideReduction
+        }
+    }
+
+    /// The statistical distance from uniform of the scalars of `F` derived from uniform digests.
+    ///
+    /// Reducing `k` uniform bits modulo `n` hits the residues below `2^k mod n` once more often than the others, which is
+    /// `(2^k mod n) ⋅ (n - 2^k mod n) / (n ⋅ 2^k)` away from uniform; it's `1 - 2^k / n` when `2^k < n`, as the scalars from `2^k` up are
+    /// never hit. Truncating and retrying is uniform. The value is computed in `f64`, so a distance below `2^-1022` is zero.
+    pub fn statistical_distance<F: PrimeField>(&self) -> f64 {
+        let k = match self {
+            Self::ModOrder => 256,
+            Self::TruncateAndRetry => return 0.0,
+            Self::WideReduction => 512,
+        };
+        let mut power = vec![0u8; k / 8 + 1];
+        power[0] = 1;
+        let residue = to_f64(&F::from_be_bytes_mod_order(&power).into_repr());
+        let modulus = to_f64(&<F::Params as FpParameters>::MODULUS);
+        let two_to_k = (0..k).fold(1.0, |acc, _| acc * 2.0);
+        residue / two_to_k * (1.0 - residue / modulus)
+    }
+
+    /// Maps the challenge preimage to a scalar, hashing with `H`.
+    pub fn derive<F: PrimeField, H: PlumeHasher>(&self, preimage: &[u8]) -> F {
+        match self {
+            Self::ModOrder => F::from_be_bytes_mod_order(&H::hash(preimage)),
+            Self::TruncateAndRetry => {
+                let bits = F::size_in_bits();
+                let mut digest = H::hash(preimage);
+                // failing every attempt has the probability below `2^-256`, so the bound is never reached in practice
+                for _ in 0..=u8::MAX {
+                    let truncated = digest
+                        .iter()
+                        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+                        .take(bits)
+                        .collect::<Vec<_>>();
+                    if let Some(scalar) =
+                        F::from_repr(<F::BigInt as BigInteger>::from_bits_be(&truncated))
+                    {
+                        return scalar;
+                    }
+                    digest = H::hash(&digest);
+                }
+                F::from_be_bytes_mod_order(&digest)
+            }
+            Self::WideReduction => {
+                let wide = [0u8, 1]
+                    .iter()
+                    .flat_map(|i| {
+                        let mut hasher = H::default();
+                        hasher.absorb(preimage);
+                        hasher.absorb(&[*i]);
+                        hasher.finish()
+                    })
+                    .collect::<Vec<_>>();
+                F::from_be_bytes_mod_order(&wide)
+            }
+        }
+    }
+}
+
+/// The nearest `f64` of `n`.
+fn to_f64<B: BigInteger>(n: &B) -> f64 {
+    n.as_ref().iter().rev().fold(0.0, |acc, limb| {
+        acc * 18_446_744_073_709_551_616.0 + *limb as f64
+    })
+}
@@ -0,0 +1,105 @@
+//! Signing a message that is disclosed later.
+//!
+//! The signer commits to the message with a salted hash and signs the commitment in place of the message; the opening, i.e. the message
+//! with its salt, is revealed later. The salt hides the message until then, and the hash binds the signature to this very message.
+//!
+//! Note that the nullifier is computed over the commitment, so it's determined by the key, the message, *and the salt*: for a nullifier
+//! standing for the message alone, sign the message directly.
+
+use crate::error::validity;
+use crate::scope::framed;
+use crate::{
+    encoding, EncodingError, HashToCurveError, HashToCurveSuite, Parameters, PlumeSignature,
+    PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_std::rand::Rng;
+use sha2::{Digest, Sha256};
+
+const COMMITMENT_TAG: &[u8] = b"PLUME/message-commitment";
+
+/// The byte length of the salt of an opening.
+pub const SALT_LEN: usize = 32;
+
+/// A binding and hiding commitment to a message: `sha256(tag || salt || message)`, the fields framed as in [`crate::scope`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageCommitment(pub [u8; 32]);
+
+impl MessageCommitment {
+    /// The hex of the 32 bytes.
+    pub fn to_hex(&self) -> String {
+        encoding::to_hex(&self.0)
+    }
+
+    /// Decodes the hex of exactly 32 bytes; see [`encoding::from_hex`].
+    pub fn from_hex(hex: &str) -> Result<Self, EncodingError> {
+        let bytes = encoding::from_hex(hex)?;
+        Ok(Self(bytes.as_slice().try_into().map_err(|_| {
+            EncodingError::InvalidLength {
+                expected: 32,
+                actual: bytes.len(),
+            }
+        })?))
+    }
+}
+
+/// The message of a [`MessageCommitment`] together with its salt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    /// The message committed to.
+    pub message: Vec<u8>,
+    /// The randomness hiding the message.
+    pub salt: [u8; SALT_LEN],
+}
+
+impl Opening {
+    /// Salts `message` with fresh randomness.
+    pub fn new(message: &[u8], rng: &mut impl Rng) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill(&mut salt);
+        Self {
+            message: message.to_vec(),
+            salt,
+        }
+    }
+
+    /// The commitment this opening opens.
+    pub fn commitment(&self) -> MessageCommitment {
+        let digest = Sha256::digest(framed(&[COMMITMENT_TAG, &self.salt, &self.message]));
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&digest);
+        MessageCommitment(bytes)
+    }
+}
+
+impl<P: HashToCurveSuite> PlumeSignature<P> {
+    /// Signs the commitment of `opening`, returning the signature with the commitment to publish alongside it.
+    pub fn sign_commitment(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        opening: &Opening,
+        version: PlumeVersion,
+    ) -> Result<(Self, MessageCommitment), HashToCurveError> {
+        let commitment = opening.commitment();
+        let signature = Self::sign(pp, rng, keypair, &commitment.0, version)?;
+        Ok((signature, commitment))
+    }
+
+    /// Verifies a signature on `commitment` and that `opening` opens it.
+    ///
+    /// Returns `false` when the opening doesn't match the commitment, even if the signature itself is valid.
+    pub fn verify_opening(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        commitment: &MessageCommitment,
+        opening: &Opening,
+        version: PlumeVersion,
+    ) -> Result<bool, HashToCurveError> {
+        if opening.commitment() != *commitment {
+            return Ok(false);
+        }
+        validity(self.verify_non_zk(pp, pk, &commitment.0, version))
+    }
+}
@@ -0,0 +1,114 @@
+//! The arkworks backend as a [`PlumeScheme`], for the conformance suite of `plume_core`.
+//!
+//! [`ArkworksBackend`] takes and returns the encodings of [`mod@encoding`]. `conformance::run_all(&ArkworksBackend::<P>::new()?)` checks
+//! the backend on the curve `P`, and the same suite runs against any other implementation which implements the trait.
+
+use crate::hash_to_curve::hash_to_curve;
+use crate::{encoding, secret_mul, EncodingError, HashToCurveSuite, Parameters, PlumeSignature};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::AffineCurve;
+use ark_ff::PrimeField;
+use core::marker::PhantomData;
+pub use plume_core::conformance::{run_all, EncodedSignature, PlumeScheme, Report};
+use plume_core::consts::{CurveConstants, CURVES};
+use plume_core::PlumeVersion;
+
+/// The arkworks backend on the curve `P`, with the standard generator.
+#[derive(Debug, Clone, Copy)]
+pub struct ArkworksBackend<P: HashToCurveSuite> {
+    curve: &'static CurveConstants,
+    _curve: PhantomData<P>,
+}
+
+impl<P: HashToCurveSuite> ArkworksBackend<P> {
+    /// The backend on `P`; `None` for a curve without [constants](plume_core::consts), which has no vectors to check against.
+    pub fn new() -> Option<Self> {
+        let curve = CURVES.iter().find(|curve| curve.suite == P::SUITE)?;
+        Some(Self {
+            curve,
+            _curve: PhantomData,
+        })
+    }
+
+    fn parameters() -> Parameters<P> {
+        Parameters {
+            g_point: GroupAffine::prime_subgroup_generator(),
+        }
+    }
+}
+
+impl<P: HashToCurveSuite> PlumeScheme for ArkworksBackend<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn name(&self) -> String {
+        format!("plume_arkworks {}", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn curve(&self) -> &'static CurveConstants {
+        self.curve
+    }
+
+    fn public_key(&self, secret_key: &[u8]) -> Option<Vec<u8>> {
+        let secret_key = encoding::field_from_bytes::<P::ScalarField>(secret_key).ok()?;
+        encoding::point_to_bytes(&secret_mul(&Self::parameters().g_point, &secret_key)).ok()
+    }
+
+    fn hash_to_curve(&self, message: &[u8], public_key: &[u8]) -> Option<Vec<u8>> {
+        let public_key = encoding::point_from_bytes::<P>(public_key).ok()?;
+        let hashed_to_curve = hash_to_curve::<P>(message, &public_key).ok()?;
+        encoding::point_to_bytes(&hashed_to_curve).ok()
+    }
+
+    fn sign_with_r(
+        &self,
+        secret_key: &[u8],
+        message: &[u8],
+        r: &[u8],
+        version: PlumeVersion,
+    ) -> Option<EncodedSignature> {
+        let pp = Self::parameters();
+        let secret_key = encoding::field_from_bytes::<P::ScalarField>(secret_key).ok()?;
+        let r = encoding::field_from_bytes::<P::ScalarField>(r).ok()?;
+        let public_key = secret_mul(&pp.g_point, &secret_key);
+        let signature =
+            PlumeSignature::sign_with_r(&pp, (&public_key, &secret_key), message, r, version)
+                .ok()?;
+        Some(EncodedSignature {
+            nullifier: encoding::point_to_bytes(&signature.nullifier).ok()?,
+            r_point: encoding::point_to_bytes(&signature.r_point).ok()?,
+            hashed_to_curve_r: encoding::point_to_bytes(&signature.hashed_to_curve_r).ok()?,
+            s: encoding::field_to_bytes(&signature.s),
+            c: encoding::field_to_bytes(&signature.c),
+        })
+    }
+
+    fn verify(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &EncodedSignature,
+        version: PlumeVersion,
+    ) -> bool {
+        let decoded = || -> Result<_, EncodingError> {
+            Ok((
+                encoding::point_from_bytes::<P>(public_key)?,
+                PlumeSignature {
+                    nullifier: encoding::point_from_bytes(&signature.nullifier)?,
+                    r_point: encoding::point_from_bytes(&signature.r_point)?,
+                    hashed_to_curve_r: encoding::point_from_bytes(&signature.hashed_to_curve_r)?,
+                    s: encoding::field_from_bytes(&signature.s)?,
+                    c: encoding::field_from_bytes(&signature.c)?,
+                },
+            ))
+        };
+        decoded().is_ok_and(|(public_key, signature)| {
+            signature
+                .verify_non_zk(&Self::parameters(), &public_key, message, version)
+                .is_ok()
+        })
+    }
+}
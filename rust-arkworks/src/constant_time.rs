@@ -0,0 +1,174 @@
+//! The constant-time multiplications of the signing path, behind the `constant-time` feature (on by default).
+//!
+//! # Threat model
+//!
+//! The attacker measures the time of key generation and of signing, or shares a cache with the signer, and sees the messages, the keys,
+//! and the signatures; the secret key and the nonce `r` are what it's after. With the feature, every multiplication by `sk` or `r`, and
+//! the response `s = r + sk ⋅ c`, runs the same instructions and touches the same memory whatever the scalar is:
+//! - on `secp256k1` they're computed by `k256`, whose point and scalar arithmetic is constant time;
+//! - on the other curves [`mul`] recodes the scalar into odd signed digits of 4 bits, so it adds for every window, with no zero digit to
+//!   skip, and reads every entry of its table of `P, 3 ⋅ P, …, 15 ⋅ P`, selecting the digit's with [`subtle`]; the conditionals are
+//!   arithmetic rather than branches.
+//!
+//! What's left on the curves other than `secp256k1`: the field arithmetic of arkworks reduces with data dependent conditional
+//! subtractions, and the inversion to affine coordinates isn't constant time, so the coordinates of the intermediate points can leak
+//! through timing at a finer grain than the scalar bits. Whether the scalar is zero also shows, but so does the output. Verification
+//! only handles public values and stays variable time, as do the same signer and migration proofs. A signer exposed to fine-grained
+//! local timing should sign on `secp256k1`.
+//!
+//! A scalar multiplication this way costs more than the variable time one; without the feature the signing path takes the arkworks
+//! multiplication.
+
+use crate::encoding;
+use crate::hash_to_curve::k256_affine_to_arkworks_secp256k1_affine;
+use crate::optimizations::{downcast, to_k256_point, to_k256_scalar};
+use alloc::vec::Vec;
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{AffineCurve, ModelParameters, ProjectiveCurve, SWModelParameters};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use secp256k1::curves::Secp256k1Parameters;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroizing;
+
+/// The number of bits of the scalar each digit takes.
+const WINDOW_BITS: usize = 4;
+
+/// The number of odd multiples in the table, `P, 3 ⋅ P, …, 15 ⋅ P`.
+const TABLE_LEN: usize = 1 << (WINDOW_BITS - 1);
+
+type Secp256k1Scalar = <Secp256k1Parameters as ModelParameters>::ScalarField;
+
+/// Computes `point^scalar` in constant time with respect to `scalar`; see [the threat model](self).
+pub fn mul<P: SWModelParameters>(
+    point: &GroupAffine<P>,
+    scalar: &P::ScalarField,
+) -> GroupAffine<P> {
+    if let (Some(point), Some(scalar)) = (
+        downcast::<_, GroupAffine<Secp256k1Parameters>>(point),
+        downcast::<_, Secp256k1Scalar>(scalar),
+    ) {
+        if let Some(result) = k256_mul(point, scalar)
+            .as_ref()
+            .and_then(downcast::<_, GroupAffine<P>>)
+        {
+            return *result;
+        }
+    }
+    generic_mul(point, scalar)
+}
+
+/// Computes the response `r + sk ⋅ c` in constant time with respect to `r` and `sk` on `secp256k1`; by the field arithmetic otherwise.
+pub fn response<P: SWModelParameters>(
+    r: &P::ScalarField,
+    sk: &P::ScalarField,
+    c: &P::ScalarField,
+) -> P::ScalarField {
+    if let (Some(r), Some(sk), Some(c)) = (
+        downcast::<_, Secp256k1Scalar>(r),
+        downcast::<_, Secp256k1Scalar>(sk),
+        downcast::<_, Secp256k1Scalar>(c),
+    ) {
+        if let Some(s) = k256_response(r, sk, c)
+            .as_ref()
+            .and_then(downcast::<_, P::ScalarField>)
+        {
+            return *s;
+        }
+    }
+    *r + *sk * c
+}
+
+fn k256_mul(
+    point: &GroupAffine<Secp256k1Parameters>,
+    scalar: &Secp256k1Scalar,
+) -> Option<GroupAffine<Secp256k1Parameters>> {
+    let scalar = Zeroizing::new(to_k256_scalar(scalar)?);
+    let product = (to_k256_point(point)? * &*scalar).to_affine();
+    if product == k256::AffinePoint::IDENTITY {
+        return Some(GroupAffine::zero());
+    }
+    k256_affine_to_arkworks_secp256k1_affine(product).ok()
+}
+
+fn k256_response(
+    r: &Secp256k1Scalar,
+    sk: &Secp256k1Scalar,
+    c: &Secp256k1Scalar,
+) -> Option<Secp256k1Scalar> {
+    let (r, sk) = (
+        Zeroizing::new(to_k256_scalar(r)?),
+        Zeroizing::new(to_k256_scalar(sk)?),
+    );
+    let s = *r + *sk * to_k256_scalar(c)?;
+    encoding::field_from_bytes(&s.to_bytes()).ok()
+}
+
+/// `a` if `choice` is 0, `b` if it's 1, by arithmetic rather than a branch.
+fn select<F: Field>(a: F, b: F, choice: Choice) -> F {
+    a + (b - a) * F::from(u64::from(choice.unwrap_u8()))
+}
+
+/// Reads the entry `index` of `table`, reading every entry.
+fn lookup<P: SWModelParameters>(table: &[GroupAffine<P>; TABLE_LEN], index: u8) -> GroupAffine<P> {
+    table
+        .iter()
+        .zip(0u8..)
+        .fold(table[0], |selected, (entry, j)| {
+            let choice = j.ct_eq(&index);
+            GroupAffine::new(
+                select(selected.x, entry.x, choice),
+                select(selected.y, entry.y, choice),
+                false,
+            )
+        })
+}
+
+/// The fixed-window multiplication by the regular recoding of Joye and Tunstall: an odd `k` is `Σ d_i ⋅ 16^i` with every `d_i` odd,
+/// `-15 ≤ d_i ≤ 15`, and the digits read straight from the bits of `k`.
+fn generic_mul<P: SWModelParameters>(
+    point: &GroupAffine<P>,
+    scalar: &P::ScalarField,
+) -> GroupAffine<P> {
+    if point.is_zero() || scalar.is_zero() {
+        return GroupAffine::zero();
+    }
+    // The recoding takes an odd scalar: for an even `k`, `n - k` is odd and `(n - k) ⋅ P = -(k ⋅ P)`
+    let even = !Choice::from(u8::from(Zeroizing::new(scalar.into_repr()).is_odd()));
+    let odd = Zeroizing::new(select(*scalar, -*scalar, even));
+    let bits = Zeroizing::new(odd.into_repr().to_bits_le());
+    let bit = |i: usize| bits.get(i).map_or(0, |&bit| u8::from(bit));
+    // The bits `1 + 4 ⋅ i, …, 4 + 4 ⋅ i` after a 1; the digit is this less 16, or this for the last one
+    let window = |i: usize| {
+        (1..=WINDOW_BITS).fold(1u8, |window, j| window | (bit(WINDOW_BITS * i + j) << j))
+    };
+
+    let double = point.into_projective().double();
+    let mut multiples = Vec::with_capacity(TABLE_LEN);
+    let mut multiple = point.into_projective();
+    for _ in 0..TABLE_LEN {
+        multiples.push(multiple);
+        multiple += &double;
+    }
+    GroupProjective::batch_normalization(&mut multiples);
+    let table: [GroupAffine<P>; TABLE_LEN] = core::array::from_fn(|j| multiples[j].into_affine());
+
+    let digits = (P::ScalarField::size_in_bits() + WINDOW_BITS - 1) / WINDOW_BITS;
+    // The last digit is the rest of the scalar, odd and below 16
+    let mut sum = lookup(&table, window(digits - 1) >> 1).into_projective();
+    for i in (0..digits - 1).rev() {
+        for _ in 0..WINDOW_BITS {
+            sum.double_in_place();
+        }
+        let w = Zeroizing::new(window(i));
+        // `|d_i| = w - 16` of index `(w mod 16) >> 1` for a positive digit, `16 - w` of index `7 - (w >> 1)` otherwise
+        let negative = (*w >> WINDOW_BITS) ^ 1;
+        let index = ((*w & 0x0f) >> 1) ^ (negative.wrapping_neg() & 0x07);
+        let mut entry = lookup(&table, index);
+        entry.y = select(entry.y, -entry.y, Choice::from(negative));
+        sum.add_assign_mixed(&entry);
+    }
+
+    let mut result = sum.into_affine();
+    result.y = select(result.y, -result.y, even);
+    result
+}
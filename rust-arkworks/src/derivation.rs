@@ -0,0 +1,346 @@
+//! Deriving `secp256k1` nullifier keys from a wallet's master key with BIP-32, so they can be regenerated from its backup.
+//!
+//! An [`ExtendedSecretKey`] is imported from a seed or an `xprv`, and derived along a [`DerivationPath`]. The paths of
+//! [`DerivationPath::plume`] are under the hardened purpose [`PURPOSE`], `m/7524'/account'` after the number of ERC-7524: a key of such a
+//! path is unrelated to the wallet's Ethereum keys, so a nullifier doesn't reveal which account it belongs to, and hardened steps keep the
+//! extended public keys of the wallet from deriving it. Just the private derivation of BIP-32 is provided; `vectors/derivation.json` holds
+//! vectors shared with the other implementations.
+
+use crate::encoding;
+use crate::{secret_mul, PublicKey, SecretKeyMaterial};
+use alloc::string::String;
+use alloc::vec::Vec;
+use ark_ec::AffineCurve;
+use ark_ff::Zero;
+use core::fmt;
+use core::str::FromStr;
+use hmac::digest::{generic_array::GenericArray, KeyInit};
+use hmac::{Hmac, Mac};
+use ripemd::Ripemd160;
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+use secp256k1::fields::Fr;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+
+/// The hardened purpose of the nullifier keys, the number of ERC-7524.
+pub const PURPOSE: u32 = 7524;
+
+/// The bit of a hardened child number.
+pub const HARDENED: u32 = 1 << 31;
+
+/// The version bytes of a mainnet `xprv`.
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+
+/// The version bytes of a testnet `tprv`.
+const TPRV_VERSION: [u8; 4] = [0x04, 0x35, 0x83, 0x94];
+
+/// The length of a serialized extended key, before the checksum.
+const SERIALIZED_LEN: usize = 78;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// HMAC-SHA-512 of the concatenation of `data`, with a `key` no longer than the block.
+fn hmac(key: &[u8], data: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    // HMAC pads a key shorter than the block with zeros, so this is the HMAC with the bytes of `key`
+    let mut block = Zeroizing::new([0u8; 128]);
+    block[..key.len()].copy_from_slice(key);
+    let mut mac = <HmacSha512 as KeyInit>::new(GenericArray::from_slice(&*block));
+    for data in data {
+        mac.update(data);
+    }
+    let mut output = Zeroizing::new([0u8; 64]);
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    output
+}
+
+/// Why a key couldn't be derived or imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DerivationError {
+    /// The seed isn't between 16 and 64 bytes long, the bounds of BIP-32.
+    InvalidSeedLength(usize),
+    /// The path isn't `m` followed by `/`-separated child numbers below `2^31`, each optionally marked hardened with `'` or `h`.
+    InvalidPath,
+    /// The string isn't the Base58Check encoding of an extended secret key.
+    InvalidXprv,
+    /// The derivation hit a key out of range, which BIP-32 gives a probability below `2^-127`; the next child number is to be used instead.
+    InvalidKey,
+    /// The key is at depth 255, and has no children.
+    DepthExceeded,
+}
+
+impl fmt::Display for DerivationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSeedLength(len) => {
+                write!(f, "the seed is {len} bytes, not between 16 and 64")
+            }
+            Self::InvalidPath => write!(f, "the derivation path is malformed"),
+            Self::InvalidXprv => write!(f, "the extended secret key is malformed"),
+            Self::InvalidKey => write!(f, "the derived key is out of range"),
+            Self::DepthExceeded => write!(f, "the key is at the maximum depth"),
+        }
+    }
+}
+
+impl core::error::Error for DerivationError {}
+
+/// The child numbers of a derivation from the master key, hardened ones with [`HARDENED`] set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The path of a nullifier key of `account`: `m/7524'/account'`. Fails with [`DerivationError::InvalidPath`] on an `account` of
+    /// `2^31` or more, which has no hardened child number.
+    pub fn plume(account: u32) -> Result<Self, DerivationError> {
+        if account >= HARDENED {
+            return Err(DerivationError::InvalidPath);
+        }
+        Ok(Self(alloc::vec![PURPOSE | HARDENED, account | HARDENED]))
+    }
+
+    /// The child numbers, from the master key's child.
+    pub fn child_numbers(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl From<Vec<u32>> for DerivationPath {
+    fn from(child_numbers: Vec<u32>) -> Self {
+        Self(child_numbers)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = DerivationError;
+
+    /// Parses `m/7524'/0'`, the notation of BIP-32.
+    fn from_str(path: &str) -> Result<Self, DerivationError> {
+        let mut steps = path.split('/');
+        if steps.next() != Some("m") {
+            return Err(DerivationError::InvalidPath);
+        }
+        steps
+            .map(|step| {
+                let (number, hardened) = match step.strip_suffix(['\'', 'h']) {
+                    Some(number) => (number, HARDENED),
+                    None => (step, 0),
+                };
+                // `u32::from_str` takes a leading `+`, which isn't part of the notation
+                if !number.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(DerivationError::InvalidPath);
+                }
+                match number.parse::<u32>() {
+                    Ok(number) if number < HARDENED => Ok(number | hardened),
+                    _ => Err(DerivationError::InvalidPath),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for &number in &self.0 {
+            if number & HARDENED != 0 {
+                write!(f, "/{}'", number & !HARDENED)?;
+            } else {
+                write!(f, "/{number}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A secret key of BIP-32, with the chain code its children are derived with; both are wiped on drop.
+#[derive(Clone)]
+pub struct ExtendedSecretKey {
+    secret_key: Zeroizing<SecretKeyMaterial<Secp256k1Parameters>>,
+    chain_code: Zeroizing<[u8; 32]>,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+}
+
+impl fmt::Debug for ExtendedSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedSecretKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_number", &self.child_number)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The key and the chain code of the HMAC output `i`, the key being `IL + parent`.
+fn split(i: &[u8; 64], parent: Fr) -> Result<(Fr, Zeroizing<[u8; 32]>), DerivationError> {
+    let il = Zeroizing::new(
+        encoding::field_from_bytes::<Fr>(&i[..32]).map_err(|_| DerivationError::InvalidKey)?,
+    );
+    let secret_key = *il + parent;
+    if secret_key.is_zero() {
+        return Err(DerivationError::InvalidKey);
+    }
+    let mut chain_code = Zeroizing::new([0; 32]);
+    chain_code.copy_from_slice(&i[32..]);
+    Ok((secret_key, chain_code))
+}
+
+impl ExtendedSecretKey {
+    /// The master key of `seed`, of 16 to 64 bytes, e.g. the seed of a BIP-39 mnemonic.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, DerivationError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(DerivationError::InvalidSeedLength(seed.len()));
+        }
+        let i = hmac(b"Bitcoin seed", &[seed]);
+        let (secret_key, chain_code) = split(&i, Fr::zero())?;
+        Ok(Self {
+            secret_key: Zeroizing::new(secret_key),
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+        })
+    }
+
+    /// Decodes an `xprv`, or a testnet `tprv`.
+    pub fn from_xprv(xprv: &str) -> Result<Self, DerivationError> {
+        let bytes = Zeroizing::new(
+            bs58::decode(xprv)
+                .with_check(None)
+                .into_vec()
+                .map_err(|_| DerivationError::InvalidXprv)?,
+        );
+        if bytes.len() != SERIALIZED_LEN
+            || (bytes[..4] != XPRV_VERSION && bytes[..4] != TPRV_VERSION)
+            || bytes[45] != 0x00
+        {
+            return Err(DerivationError::InvalidXprv);
+        }
+        let secret_key = encoding::field_from_bytes::<Fr>(&bytes[46..])
+            .map_err(|_| DerivationError::InvalidXprv)?;
+        if secret_key.is_zero() {
+            return Err(DerivationError::InvalidXprv);
+        }
+        let mut parent_fingerprint = [0; 4];
+        parent_fingerprint.copy_from_slice(&bytes[5..9]);
+        let mut child_number = [0; 4];
+        child_number.copy_from_slice(&bytes[9..13]);
+        let mut chain_code = Zeroizing::new([0; 32]);
+        chain_code.copy_from_slice(&bytes[13..45]);
+        let depth = bytes[4];
+        // a master key has no parent
+        if depth == 0 && (parent_fingerprint != [0; 4] || child_number != [0; 4]) {
+            return Err(DerivationError::InvalidXprv);
+        }
+        Ok(Self {
+            secret_key: Zeroizing::new(secret_key),
+            chain_code,
+            depth,
+            parent_fingerprint,
+            child_number: u32::from_be_bytes(child_number),
+        })
+    }
+
+    /// The mainnet `xprv` of the key.
+    pub fn to_xprv(&self) -> Zeroizing<String> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(SERIALIZED_LEN));
+        bytes.extend_from_slice(&XPRV_VERSION);
+        bytes.push(self.depth);
+        bytes.extend_from_slice(&self.parent_fingerprint);
+        bytes.extend_from_slice(&self.child_number.to_be_bytes());
+        bytes.extend_from_slice(&*self.chain_code);
+        bytes.push(0x00);
+        bytes.extend_from_slice(&Zeroizing::new(encoding::field_to_bytes(&*self.secret_key)));
+        Zeroizing::new(bs58::encode(&*bytes).with_check().into_string())
+    }
+
+    /// The child of `child_number`, hardened when it has [`HARDENED`] set.
+    pub fn derive_child(&self, child_number: u32) -> Result<Self, DerivationError> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(DerivationError::DepthExceeded)?;
+        let public_key = self.public_key_bytes()?;
+        let i = if child_number & HARDENED != 0 {
+            let secret_key = Zeroizing::new(encoding::field_to_bytes(&*self.secret_key));
+            hmac(
+                &*self.chain_code,
+                &[
+                    &[0x00][..],
+                    &secret_key[..],
+                    &child_number.to_be_bytes()[..],
+                ],
+            )
+        } else {
+            hmac(
+                &*self.chain_code,
+                &[&public_key[..], &child_number.to_be_bytes()[..]],
+            )
+        };
+        let (secret_key, chain_code) = split(&i, *self.secret_key)?;
+        let mut parent_fingerprint = [0; 4];
+        parent_fingerprint.copy_from_slice(&Ripemd160::digest(Sha256::digest(&public_key))[..4]);
+        Ok(Self {
+            secret_key: Zeroizing::new(secret_key),
+            chain_code,
+            depth,
+            parent_fingerprint,
+            child_number,
+        })
+    }
+
+    /// The descendant of the key along `path`, which is taken to start at this key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, DerivationError> {
+        path.child_numbers()
+            .iter()
+            .try_fold(self.clone(), |key, &child_number| {
+                key.derive_child(child_number)
+            })
+    }
+
+    /// The secret key, to sign with.
+    pub fn secret_key(&self) -> &SecretKeyMaterial<Secp256k1Parameters> {
+        &self.secret_key
+    }
+
+    /// The public key of [`Self::secret_key`].
+    pub fn public_key(&self) -> PublicKey<Secp256k1Parameters> {
+        secret_mul(&Affine::prime_subgroup_generator(), &*self.secret_key)
+    }
+
+    /// The chain code the children are derived with.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// The number of derivations from the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The child number the key was derived with; zero for a master key.
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    fn public_key_bytes(&self) -> Result<Vec<u8>, DerivationError> {
+        encoding::point_to_bytes(&self.public_key()).map_err(|_| DerivationError::InvalidKey)
+    }
+}
+
+/// The nullifier key of `account` under the master key of `seed`, at [`DerivationPath::plume`]; the secret key is wiped on drop.
+pub fn derive_plume_key(
+    seed: &[u8],
+    account: u32,
+) -> Result<
+    (
+        PublicKey<Secp256k1Parameters>,
+        Zeroizing<SecretKeyMaterial<Secp256k1Parameters>>,
+    ),
+    DerivationError,
+> {
+    let key = ExtendedSecretKey::from_seed(seed)?.derive_path(&DerivationPath::plume(account)?)?;
+    Ok((key.public_key(), Zeroizing::new(*key.secret_key())))
+}
@@ -0,0 +1,118 @@
+//! Signing with the nonce derived from the key and the message, as in RFC 6979.
+//!
+//! The nonce `r` is drawn from the HMAC-DRBG of RFC 6979, section 3.2, with HMAC-SHA-256: seeded with the secret key and, in place of the
+//! message hash, the SHA-256 of the framed tag `PLUME/deterministic-nonce`, the version, and the message. Candidates are the leftmost bits
+//! of the output, of the group order's length, and the first one in `[1, n)` is taken. Signing then needs no randomness, and the same key,
+//! message and version always give the same signature, which makes test vectors reproducible across implementations.
+//!
+//! The nonce stays secret and distinct for distinct messages as long as the key is, and the version is part of the seed, so a message
+//! signed with two versions doesn't reuse `r`: with the same `r` and two challenges, the two signatures would reveal the key.
+
+use crate::challenge::CurveDefault;
+use crate::hasher::{ChallengeHash, SpecHasher};
+use crate::offline::version_code;
+use crate::scope::framed;
+use crate::{
+    encoding, Domain, HashToCurveError, HashToCurveSuite, Message, Parameters, PlumeSignature,
+    PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ff::{PrimeField, Zero};
+use hmac::digest::{generic_array::GenericArray, KeyInit};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const NONCE_TAG: &[u8] = b"PLUME/deterministic-nonce";
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8; 32], data: &[&[u8]]) -> [u8; 32] {
+    // HMAC pads a key shorter than the block with zeros, so this is the HMAC with the 32 bytes of `key`
+    let mut block = Zeroizing::new([0u8; 64]);
+    block[..32].copy_from_slice(key);
+    let mut mac = <HmacSha256 as KeyInit>::new(GenericArray::from_slice(&*block));
+    for data in data {
+        mac.update(data);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// The nonce of RFC 6979 for `sk`, `message` and `version`; see [the module docs](self).
+///
+/// It's only for [`PlumeSignature::sign_deterministic`]: the seed doesn't bind the hasher, the reduction or the context of the challenge,
+/// so signing with it under any other would reuse `r` for another `c`.
+pub(crate) fn nonce<F: PrimeField>(sk: &F, message: Message, version: PlumeVersion) -> F {
+    let x = Zeroizing::new(encoding::field_to_bytes(sk));
+    let h1 = Sha256::digest(framed(&[
+        NONCE_TAG,
+        &version_code(version, ChallengeHash::Sha256).to_be_bytes(),
+        message,
+    ]));
+    let qlen = F::size_in_bits();
+    // bits2octets: the leftmost `qlen` bits of the hash, reduced modulo the order
+    let h1 = encoding::field_to_bytes(&F::from_be_bytes_mod_order(&bits2int(h1.to_vec(), qlen)));
+
+    let mut k = Zeroizing::new([0u8; 32]);
+    let mut v = Zeroizing::new([1u8; 32]);
+    *k = hmac(&k, &[&v[..], &[0x00], &x[..], &h1[..]]);
+    *v = hmac(&k, &[&v[..]]);
+    *k = hmac(&k, &[&v[..], &[0x01], &x[..], &h1[..]]);
+    *v = hmac(&k, &[&v[..]]);
+
+    let rlen = (qlen + 7) / 8;
+    loop {
+        let mut t = Zeroizing::new(Vec::with_capacity(rlen + 32));
+        while t.len() < rlen {
+            *v = hmac(&k, &[&v[..]]);
+            t.extend_from_slice(&v[..]);
+        }
+        t.truncate(rlen);
+        let t = Zeroizing::new(bits2int(core::mem::take(&mut *t), qlen));
+        if let Ok(candidate) = encoding::field_from_bytes::<F>(&t) {
+            if !candidate.is_zero() {
+                return candidate;
+            }
+        }
+
+        *k = hmac(&k, &[&v[..], &[0x00]]);
+        *v = hmac(&k, &[&v[..]]);
+    }
+}
+
+/// bits2int of RFC 6979: the leftmost `qlen` bits of `bytes`, as a big-endian number of the same length.
+fn bits2int(mut bytes: Vec<u8>, qlen: usize) -> Vec<u8> {
+    let shift = (8 * bytes.len()).saturating_sub(qlen);
+    let (whole, bits) = (shift / 8, shift % 8);
+    bytes.rotate_right(whole);
+    bytes[..whole].fill(0);
+    if bits > 0 {
+        for i in (0..bytes.len()).rev() {
+            let carry = if i > 0 { bytes[i - 1] << (8 - bits) } else { 0 };
+            bytes[i] = (bytes[i] >> bits) | carry;
+        }
+    }
+    bytes
+}
+
+impl<P: HashToCurveSuite> PlumeSignature<P> {
+    /// Signs `message` with the nonce of [the module docs](self) instead of one from an RNG; the signature is valid for
+    /// [`Self::verify_non_zk`] as any other.
+    pub fn sign_deterministic(
+        pp: &Parameters<P>,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        let r_scalar = Zeroizing::new(nonce(keypair.1, message, version));
+        Self::sign_with_context::<SpecHasher, _>(
+            pp,
+            keypair,
+            message,
+            &*r_scalar,
+            version,
+            Domain::plain::<P>(),
+            &CurveDefault,
+        )
+    }
+}
@@ -0,0 +1,76 @@
+//! Signing 32-byte digests from an external hash pipeline.
+//!
+//! A system which already has a digest of its message, such as a transaction hash, signs the digest with [`PlumeSignature::sign_digest`]:
+//! the 32 bytes are used directly as the message hashed to the curve, `h = hash[digest, pk]`, rather than hashed once more. That makes the
+//! signed value explicit, where signing the digest as a message leaves each integrator to decide whether to hash it again.
+//!
+//! Digests are hashed to the curve under a tag of their own, the [DST](HashToCurveSuite::DST) of the suite followed by
+//! [`DIGEST_DST_SUFFIX`], so a digest signature never verifies as the signature on the same 32 bytes as a message, and the two have
+//! distinct nullifiers. The challenge is that of the plain protocol.
+
+use crate::challenge::CurveDefault;
+use crate::{
+    Domain, HashToCurveError, HashToCurveSuite, Parameters, PlumeSignature, PlumeVerifyError,
+    PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_std::{rand::Rng, UniformRand};
+pub use plume_core::consts::DIGEST_DST_SUFFIX;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// The byte length of a digest.
+pub const DIGEST_LEN: usize = 32;
+
+/// The tag digests are hashed to the curve of `P` with.
+pub fn digest_dst<P: HashToCurveSuite>() -> Vec<u8> {
+    [P::DST, DIGEST_DST_SUFFIX].concat()
+}
+
+impl<P: HashToCurveSuite> PlumeSignature<P> {
+    /// Signs `digest`, hashing it to the curve as it is under [`digest_dst`]; see [the module docs](self).
+    pub fn sign_digest(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        digest: &[u8; DIGEST_LEN],
+        version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        let r_scalar = Zeroizing::new(P::ScalarField::rand(rng));
+        Self::sign_with_context::<Sha256, _>(
+            pp,
+            keypair,
+            digest,
+            &*r_scalar,
+            version,
+            Domain {
+                dst: &digest_dst::<P>(),
+                context: &[],
+            },
+            &CurveDefault,
+        )
+    }
+
+    /// Verifies a signature made with [`Self::sign_digest`] on `digest` by `pk`.
+    ///
+    /// An invalid signature is an `Err`, including the signature on the bytes of `digest` as a message; see [`Self::verify_non_zk`].
+    pub fn verify_digest(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        digest: &[u8; DIGEST_LEN],
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.verify_with_context::<Sha256, _>(
+            pp,
+            pk,
+            digest,
+            version,
+            Domain {
+                dst: &digest_dst::<P>(),
+                context: &[],
+            },
+            &CurveDefault,
+        )
+    }
+}
@@ -0,0 +1,304 @@
+//! Stable byte encodings of points and scalars.
+//!
+//! Unlike the `CanonicalSerialize` derives (see the `legacy-ark-serialize` feature) these don't depend on the `ark-serialize` version.
+//! A point is SEC1 compressed: a `0x02` (even `y`) or `0x03` (odd `y`) tag followed by the big-endian `x`. A field element, scalars included, is
+//! big-endian bytes of the field's width. Decoding is strict: wrong lengths, unknown tags, non-canonical values, and points off the curve or
+//! outside the prime order subgroup are rejected.
+//!
+//! [`Parameters`] are written after a header describing the curve and the suites they're for, which is checked on load, so that
+//! parameters from another build or fork fail to decode instead of giving signatures nobody can verify.
+//!
+//! # Hex
+//! Every value with a fixed width encoding has a hex form as well: the lowercase hex of its bytes, as [`to_hex`] writes it. Decoding with
+//! [`from_hex`] tolerates a `0x` prefix but nothing else: uppercase digits, an odd number of digits, and a wrong width are errors, so a
+//! dropped leading zero is caught at the boundary rather than decoded as another value. The `*_to_hex`/`*_from_hex` functions below, and
+//! the `to_hex`/`from_hex` methods of the types of the crate, are the hex forms of the byte encodings.
+
+use crate::error::EncodingError;
+use crate::hash_to_curve::HashToCurveSuite;
+use crate::{validate, Parameters};
+use alloc::{string::String, vec, vec::Vec};
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{BigInteger, FpParameters, FromBytes, PrimeField, Zero};
+use plume_core::{consts, transcript};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const TAG_EVEN: u8 = consts::POINT_TAG_EVEN;
+const TAG_ODD: u8 = consts::POINT_TAG_ODD;
+
+const PARAMETERS_MAGIC: &[u8] = b"PLUME/parameters";
+const CURVE_ID_TAG: &[u8] = b"PLUME/curve-id";
+/// The hash of the challenge.
+const HASH_SUITE: &[u8] = b"SHA-256";
+
+/// The version of the [`ParametersHeader`] layout.
+pub const PARAMETERS_HEADER_VERSION: u8 = 1;
+
+/// The lowercase hex of `bytes`, without a prefix.
+pub fn to_hex(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+/// Decodes lowercase hex, with or without a `0x` prefix.
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, EncodingError> {
+    hex_digits(hex.strip_prefix("0x").unwrap_or(hex))
+}
+
+/// Decodes lowercase hex digits alone.
+pub(crate) fn hex_digits(digits: &str) -> Result<Vec<u8>, EncodingError> {
+    if digits.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(EncodingError::InvalidHex);
+    }
+    hex::decode(digits).map_err(|_| EncodingError::InvalidHex)
+}
+
+/// The number of bytes an element of the prime field `F` is encoded to.
+pub fn field_byte_len<F: PrimeField>() -> usize {
+    (F::size_in_bits() + 7) / 8
+}
+
+/// The number of bytes a point of the curve `P` is encoded to.
+pub fn point_byte_len<P: SWModelParameters>() -> usize
+where
+    P::BaseField: PrimeField,
+{
+    1 + field_byte_len::<P::BaseField>()
+}
+
+/// Encodes a prime field element as fixed width big-endian bytes.
+pub fn field_to_bytes<F: PrimeField>(element: &F) -> Vec<u8> {
+    let repr = BigInteger::to_bytes_be(&element.into_repr());
+    // the representation is padded to whole limbs
+    repr[repr.len() - field_byte_len::<F>()..].to_vec()
+}
+
+/// Decodes a prime field element from fixed width big-endian bytes, rejecting values not less than the modulus.
+pub fn field_from_bytes<F: PrimeField>(bytes: &[u8]) -> Result<F, EncodingError> {
+    let expected = field_byte_len::<F>();
+    if bytes.len() != expected {
+        return Err(EncodingError::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        });
+    }
+
+    // `FromBytes` of the representation reads little-endian limbs; a secret key goes through here, so the copy is wiped
+    let mut repr_bytes = Zeroizing::new(vec![0u8; F::zero().into_repr().as_ref().len() * 8]);
+    repr_bytes
+        .iter_mut()
+        .zip(bytes.iter().rev())
+        .for_each(|(repr_byte, byte)| *repr_byte = *byte);
+    let repr = <F::BigInt as FromBytes>::read(repr_bytes.as_slice())
+        .map_err(|_| EncodingError::NonCanonicalField)?;
+
+    F::from_repr(repr).ok_or(EncodingError::NonCanonicalField)
+}
+
+/// Encodes a point in the SEC1 compressed form.
+///
+/// Fails for the point at infinity.
+pub fn point_to_bytes<P: SWModelParameters>(
+    point: &GroupAffine<P>,
+) -> Result<Vec<u8>, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    if point.is_zero() {
+        return Err(EncodingError::Identity);
+    }
+
+    let tag = if BigInteger::is_odd(&point.y.into_repr()) {
+        TAG_ODD
+    } else {
+        TAG_EVEN
+    };
+    Ok([vec![tag], field_to_bytes(&point.x)].concat())
+}
+
+/// Decodes a point from the SEC1 compressed form, checking it's in the prime order subgroup.
+pub fn point_from_bytes<P: SWModelParameters>(bytes: &[u8]) -> Result<GroupAffine<P>, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    let expected = point_byte_len::<P>();
+    let (tag, x_bytes) = match bytes.split_first() {
+        Some(split) if bytes.len() == expected => split,
+        _ => {
+            return Err(EncodingError::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            })
+        }
+    };
+    let y_odd = match *tag {
+        TAG_EVEN => false,
+        TAG_ODD => true,
+        tag => return Err(EncodingError::InvalidTag(tag)),
+    };
+
+    let x = field_from_bytes::<P::BaseField>(x_bytes)?;
+    let point = GroupAffine::<P>::get_point_from_x(x, false).ok_or(EncodingError::NotOnCurve)?;
+    let point = if BigInteger::is_odd(&point.y.into_repr()) == y_odd {
+        point
+    } else {
+        -point
+    };
+
+    validate::subgroup(&point)?;
+    Ok(point)
+}
+
+/// The hex of [`field_to_bytes`].
+pub fn field_to_hex<F: PrimeField>(element: &F) -> String {
+    to_hex(&field_to_bytes(element))
+}
+
+/// Decodes the hex of [`field_to_bytes`]; see [`from_hex`].
+pub fn field_from_hex<F: PrimeField>(hex: &str) -> Result<F, EncodingError> {
+    field_from_bytes(&from_hex(hex)?)
+}
+
+/// The hex of [`point_to_bytes`].
+pub fn point_to_hex<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<String, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    Ok(to_hex(&point_to_bytes(point)?))
+}
+
+/// Decodes the hex of [`point_to_bytes`]; see [`from_hex`].
+pub fn point_from_hex<P: SWModelParameters>(hex: &str) -> Result<GroupAffine<P>, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    point_from_bytes(&from_hex(hex)?)
+}
+
+/// What the parameters are for, written before them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParametersHeader {
+    /// The version of the layout.
+    pub version: u8,
+    /// The SHA-256 of the curve's base field modulus, coefficients, order and cofactor.
+    pub curve_id: [u8; 32],
+    /// The hash of the challenge.
+    pub hash_suite: Vec<u8>,
+    /// The hash to curve suite, as its domain separation tag.
+    pub htc_suite: Vec<u8>,
+}
+
+impl ParametersHeader {
+    /// The header of this build for the curve `P`.
+    pub fn for_curve<P: HashToCurveSuite>() -> Self
+    where
+        P::BaseField: PrimeField,
+    {
+        Self {
+            version: PARAMETERS_HEADER_VERSION,
+            curve_id: curve_id::<P>(),
+            hash_suite: HASH_SUITE.to_vec(),
+            htc_suite: P::DST.to_vec(),
+        }
+    }
+}
+
+/// Identifies the curve `P` by its parameters rather than by a name, so that curves can't be confused even across forks.
+pub fn curve_id<P: SWModelParameters>() -> [u8; 32]
+where
+    P::BaseField: PrimeField,
+{
+    let cofactor = P::COFACTOR
+        .iter()
+        .rev()
+        .flat_map(|limb| limb.to_be_bytes())
+        .collect::<Vec<_>>();
+    Sha256::digest(transcript::framed(&[
+        CURVE_ID_TAG,
+        <P::BaseField as PrimeField>::Params::MODULUS
+            .to_bytes_be()
+            .as_slice(),
+        field_to_bytes(&P::COEFF_A).as_slice(),
+        field_to_bytes(&P::COEFF_B).as_slice(),
+        <P::ScalarField as PrimeField>::Params::MODULUS
+            .to_bytes_be()
+            .as_slice(),
+        cofactor.as_slice(),
+    ]))
+    .into()
+}
+
+/// Encodes `pp` after the [`ParametersHeader`] of this build.
+pub fn parameters_to_bytes<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+) -> Result<Vec<u8>, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    let header = ParametersHeader::for_curve::<P>();
+    let generator = point_to_bytes(&pp.g_point)?;
+    Ok([
+        PARAMETERS_MAGIC,
+        transcript::framed(&[
+            &[header.version][..],
+            &header.curve_id[..],
+            header.hash_suite.as_slice(),
+            header.htc_suite.as_slice(),
+            generator.as_slice(),
+        ])
+        .as_slice(),
+    ]
+    .concat())
+}
+
+/// Decodes parameters, checking their header matches this build for the curve `P`.
+pub fn parameters_from_bytes<P: HashToCurveSuite>(
+    bytes: &[u8],
+) -> Result<Parameters<P>, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    let fields = bytes
+        .strip_prefix(PARAMETERS_MAGIC)
+        .and_then(transcript::unframe)
+        .ok_or(EncodingError::InvalidHeader)?;
+    let (version, curve_id, hash_suite, htc_suite, generator) = match fields[..] {
+        [version, curve_id, hash_suite, htc_suite, generator] => {
+            (version, curve_id, hash_suite, htc_suite, generator)
+        }
+        _ => return Err(EncodingError::InvalidHeader),
+    };
+    if version != [PARAMETERS_HEADER_VERSION] {
+        return Err(EncodingError::InvalidHeader);
+    }
+
+    let expected = ParametersHeader::for_curve::<P>();
+    for (field, actual, expected) in [
+        ("curve_id", curve_id, &expected.curve_id[..]),
+        ("hash_suite", hash_suite, &expected.hash_suite[..]),
+        ("htc_suite", htc_suite, &expected.htc_suite[..]),
+    ] {
+        if actual != expected {
+            return Err(EncodingError::ParameterMismatch { field });
+        }
+    }
+
+    Ok(Parameters {
+        g_point: point_from_bytes(generator)?,
+    })
+}
+
+impl<P: HashToCurveSuite> Parameters<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// The hex of [`parameters_to_bytes`].
+    pub fn to_hex(&self) -> Result<String, EncodingError> {
+        Ok(to_hex(&parameters_to_bytes(self)?))
+    }
+
+    /// Decodes the hex of [`parameters_to_bytes`]; see [`from_hex`].
+    pub fn from_hex(hex: &str) -> Result<Self, EncodingError> {
+        parameters_from_bytes(&from_hex(hex)?)
+    }
+}
@@ -0,0 +1,215 @@
+//! Signatures valid for a period of time, with a key id.
+//!
+//! An [`Envelope`] holds the metadata an application would otherwise frame into its messages: the time the signature is valid from, the
+//! time it expires, and the id of the key. It's hashed into the challenge after the points, so changing it invalidates the signature, but
+//! it isn't part of the message: the nullifier `hash[m, pk]^sk` is the same with any envelope, and a nullifier proof which expires still
+//! stands for the same thing as one which doesn't.
+//!
+//! The envelope is encoded framed as in [`crate::scope`]: the tag `PLUME/envelope`, `not_before` and `not_after` as big-endian `u64` or
+//! empty when absent, and the key id. As the encoding is never empty, an enveloped signature is never a valid plain one, nor the other
+//! way around. The verifier reads the time from a [`Clock`], in seconds since the Unix epoch.
+
+use crate::challenge::CurveDefault;
+use crate::error::validity;
+use crate::scope::framed;
+use crate::{
+    Domain, HashToCurveError, HashToCurveSuite, Message, Parameters, PlumeSignature, PlumeVersion,
+    PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ec::models::SWModelParameters;
+use ark_std::{rand::Rng, UniformRand};
+use core::fmt;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+const ENVELOPE_TAG: &[u8] = b"PLUME/envelope";
+
+/// The source of the current time of a verifier.
+pub trait Clock {
+    /// The seconds since the Unix epoch.
+    fn now(&self) -> u64;
+}
+
+/// The clock of the system.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+    }
+}
+
+/// A clock stopped at the given time, for tests and for checking at a past time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The metadata signed along with a message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Envelope {
+    /// The first second the signature is valid at.
+    pub not_before: Option<u64>,
+    /// The last second the signature is valid at.
+    pub not_after: Option<u64>,
+    /// The id of the key, in the format of the application; empty for none.
+    pub key_id: Vec<u8>,
+}
+
+/// The reason an enveloped signature isn't accepted.
+#[derive(Debug, Clone)]
+pub enum EnvelopeError {
+    /// The time is before `not_before`.
+    NotYetValid,
+    /// The time is after `not_after`.
+    Expired,
+    /// A field of the encoded envelope is malformed.
+    Malformed,
+    /// The signature is invalid for the message, the key and the envelope.
+    InvalidSignature,
+    /// The message and the key couldn't be hashed to the curve.
+    HashToCurve(HashToCurveError),
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotYetValid => write!(f, "the signature isn't valid yet"),
+            Self::Expired => write!(f, "the signature has expired"),
+            Self::Malformed => write!(f, "the envelope is malformed"),
+            Self::InvalidSignature => write!(f, "the signature is invalid"),
+            Self::HashToCurve(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl core::error::Error for EnvelopeError {}
+
+impl From<HashToCurveError> for EnvelopeError {
+    fn from(e: HashToCurveError) -> Self {
+        Self::HashToCurve(e)
+    }
+}
+
+impl Envelope {
+    /// The encoding hashed into the challenge; see [the module docs](self).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let time = |time: Option<u64>| time.map(u64::to_be_bytes);
+        let (not_before, not_after) = (time(self.not_before), time(self.not_after));
+        framed(&[
+            ENVELOPE_TAG,
+            not_before.as_ref().map_or(&[][..], |time| &time[..]),
+            not_after.as_ref().map_or(&[][..], |time| &time[..]),
+            &self.key_id,
+        ])
+    }
+
+    /// Decodes [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        let time = |field: &[u8]| match field.len() {
+            0 => Ok(None),
+            8 => Ok(Some(u64::from_be_bytes(
+                field.try_into().map_err(|_| EnvelopeError::Malformed)?,
+            ))),
+            _ => Err(EnvelopeError::Malformed),
+        };
+        match plume_core::transcript::unframe(bytes).as_deref() {
+            Some([ENVELOPE_TAG, not_before, not_after, key_id]) => Ok(Self {
+                not_before: time(not_before)?,
+                not_after: time(not_after)?,
+                key_id: key_id.to_vec(),
+            }),
+            _ => Err(EnvelopeError::Malformed),
+        }
+    }
+
+    /// Checks the time of `clock` is within the validity period; both bounds are inclusive.
+    pub fn check_time(&self, clock: &impl Clock) -> Result<(), EnvelopeError> {
+        let now = clock.now();
+        if self.not_before.is_some_and(|not_before| now < not_before) {
+            return Err(EnvelopeError::NotYetValid);
+        }
+        if self.not_after.is_some_and(|not_after| now > not_after) {
+            return Err(EnvelopeError::Expired);
+        }
+        Ok(())
+    }
+}
+
+/// A signature with the envelope it's made with.
+#[derive(Clone)]
+pub struct EnvelopedSignature<P: SWModelParameters> {
+    /// The signature, which challenge covers the envelope.
+    pub signature: PlumeSignature<P>,
+    /// The metadata signed.
+    pub envelope: Envelope,
+}
+
+impl<P: HashToCurveSuite> EnvelopedSignature<P> {
+    /// Signs `message` with `envelope`; the nullifier is the same as that of [`PlumeSignature::sign`].
+    pub fn sign(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        envelope: Envelope,
+        version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        let r_scalar = Zeroizing::new(P::ScalarField::rand(rng));
+        let signature = PlumeSignature::sign_with_context::<Sha256, _>(
+            pp,
+            keypair,
+            message,
+            &*r_scalar,
+            version,
+            Domain {
+                dst: P::DST,
+                context: &envelope.to_bytes(),
+            },
+            &CurveDefault,
+        )?;
+        Ok(Self {
+            signature,
+            envelope,
+        })
+    }
+
+    /// Verifies the signature on `message` by `pk`, made with `version`, and that the time of `clock` is within the period of the envelope.
+    ///
+    /// The time is checked first, so a signature out of its period is rejected without the curve arithmetic.
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+        clock: &impl Clock,
+    ) -> Result<(), EnvelopeError> {
+        self.envelope.check_time(clock)?;
+        if validity(self.signature.verify_with_context::<Sha256, _>(
+            pp,
+            pk,
+            message,
+            version,
+            Domain {
+                dst: P::DST,
+                context: &self.envelope.to_bytes(),
+            },
+            &CurveDefault,
+        ))? {
+            Ok(())
+        } else {
+            Err(EnvelopeError::InvalidSignature)
+        }
+    }
+}
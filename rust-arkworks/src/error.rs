@@ -13,19 +13,146 @@
 // PointNotOnCurve,
 // }
 
+use alloc::string::String;
+
 // Let's outline what errors will be in `~0.4.0`
-/// It's an interim `enum` between legacy definition of the errors and prospective which will be relying on [`ark_ec::hashing::HashToCurveError`]. 
+/// It's an interim `enum` between legacy definition of the errors and prospective which will be relying on [`ark_ec::hashing::HashToCurveError`].
 #[derive(Debug, Clone)]
 pub enum HashToCurveError {
     /// Mimics the `ark_ec::hashing::HashToCurveError` enum
     UnsupportedCurveError(String),
     /// Mimics the `ark_ec::hashing::HashToCurveError` enum
     MapToCurveError(String),
-    /* let's add two more items to absorb everything
-    in `crate::hash_to_curve` which is
-    subject to deprecation */
+    /// The map of the suite `suite` failed on the input; `reason` is the error of the implementation.
+    UnsupportedSuite { suite: String, reason: String },
+    /// The domain separation tag can't be hashed with, for `reason`; e.g. it's empty.
+    InvalidDst { reason: &'static str },
+    /// The `len` bytes asked of [`expand_message_xmd`](crate::hash_to_curve::expand_message_xmd) are over the `max` it can output.
+    InvalidInput { len: usize, max: usize },
+    /// A coordinate of the point produced by the map isn't a valid base field element, or is missing as the point is the identity.
+    InvalidFieldElement { coordinate: &'static str },
+    /// No point was found within the limit of `iterations` attempts of try-and-increment.
+    IterationLimitExceeded { iterations: usize },
     /// Absorbs any legacy error in [`mod@crate::hash_to_curve`]. They will be deprecated with upgrade to `~0.4.0`.
+    #[deprecated(note = "no longer returned; match the typed variants instead")]
     Legacy,
     /// A special case for a reference function. It will be moved to <./examples> with the upgrade to `~0.4.0`.
+    #[deprecated(
+        note = "no longer returned; `_try_and_increment` fails with `IterationLimitExceeded`"
+    )]
     ReferenceTryAndIncrement,
 }
+
+/// An error decoding a value from the byte encoding of [`mod@crate::encoding`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// The input length doesn't match the fixed width of the value.
+    InvalidLength { expected: usize, actual: usize },
+    /// The leading byte of a point is neither `0x02` nor `0x03` (nor `0x04` for [`PublicKeySec1`](crate::keys::PublicKeySec1)).
+    InvalidTag(u8),
+    /// The bytes represent a number which isn't less than the field modulus.
+    NonCanonicalField,
+    /// There's no point on the curve with the given `x`.
+    NotOnCurve,
+    /// The point isn't in the prime order subgroup.
+    NotInSubgroup,
+    /// The point at infinity doesn't have a fixed width encoding.
+    Identity,
+    /// The scalar is zero, which isn't a secret key.
+    ZeroScalar,
+    /// The parameters, or a signature record of [`SignatureReader`](crate::reader::SignatureReader), don't start with a well-formed header
+    /// of a known version.
+    InvalidHeader,
+    /// The parameters were made for another curve or suite; `field` is the header field which differs from this build.
+    ParameterMismatch { field: &'static str },
+    /// The string isn't an even number of lowercase hex digits, after an optional `0x`.
+    InvalidHex,
+    /// The mixed-case hex of an Ethereum address doesn't match its EIP-55 checksum.
+    InvalidChecksum,
+}
+
+/// Why a signature didn't verify: which check of the verification failed, or that it couldn't run.
+#[derive(Debug, Clone)]
+pub enum PlumeVerifyError {
+    /// The public key is the identity, or isn't a point of the prime order subgroup.
+    InvalidPublicKey(EncodingError),
+    /// A point of the signature is the identity, or isn't a point of the prime order subgroup.
+    InvalidSignature(EncodingError),
+    /// `g^s ⋅ pk^{-c}` isn't the `g^r` of the signature.
+    RPointMismatch,
+    /// `h^s ⋅ nul^{-c}` isn't the `z` of the signature.
+    HashedPointMismatch,
+    /// The challenge of the points isn't the `c` of the signature.
+    ChallengeMismatch,
+    /// The message and the key couldn't be hashed to the curve.
+    HashToCurve(HashToCurveError),
+}
+
+impl From<HashToCurveError> for PlumeVerifyError {
+    fn from(e: HashToCurveError) -> Self {
+        Self::HashToCurve(e)
+    }
+}
+
+/// The result of a verification as it was before [`PlumeVerifyError`]: `Ok(false)` for an invalid signature, an `Err` when the message
+/// and the key couldn't be hashed to the curve.
+pub(crate) fn validity(result: Result<(), PlumeVerifyError>) -> Result<bool, HashToCurveError> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(PlumeVerifyError::HashToCurve(e)) => Err(e),
+        Err(_) => Ok(false),
+    }
+}
+
+impl core::fmt::Display for HashToCurveError {
+    #[allow(deprecated)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedCurveError(msg) => write!(f, "unsupported curve: {msg}"),
+            Self::MapToCurveError(msg) => write!(f, "map to curve failed: {msg}"),
+            Self::UnsupportedSuite { suite, reason } => {
+                write!(f, "hash-to-curve suite `{suite}` failed: {reason}")
+            }
+            Self::InvalidDst { reason } => write!(f, "invalid domain separation tag: {reason}"),
+            Self::InvalidInput { len, max } => {
+                write!(
+                    f,
+                    "{len} bytes is over the limit of {max} of `expand_message_xmd`"
+                )
+            }
+            Self::InvalidFieldElement { coordinate } => write!(
+                f,
+                "the mapped point has no valid `{coordinate}` coordinate in the base field"
+            ),
+            Self::IterationLimitExceeded { iterations } => {
+                write!(f, "no point found within {iterations} iterations")
+            }
+            Self::Legacy => write!(f, "legacy hash-to-curve error"),
+            Self::ReferenceTryAndIncrement => write!(f, "try-and-increment failed"),
+        }
+    }
+}
+
+impl core::error::Error for HashToCurveError {}
+
+impl core::fmt::Display for PlumeVerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPublicKey(e) => write!(f, "the public key is invalid: {e:?}"),
+            Self::InvalidSignature(e) => write!(f, "a point of the signature is invalid: {e:?}"),
+            Self::RPointMismatch => write!(f, "`g^s ⋅ pk^-c` doesn't match `g^r`"),
+            Self::HashedPointMismatch => write!(f, "`h^s ⋅ nul^-c` doesn't match `h^r`"),
+            Self::ChallengeMismatch => write!(f, "the challenge doesn't match the points"),
+            Self::HashToCurve(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl core::error::Error for PlumeVerifyError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::HashToCurve(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! The wire formats of [`PlumeSignature`], with detection and migration between them.
+//!
+//! A long-lived store can hold signatures written in different formats over time. Each format is listed in [`Format`]; [`detect_format`]
+//! tells which one some bytes are in, and [`migrate`] rewrites them in another format without losing any field.
+//!
+//! - [`Format::LegacyArk`] is the output of the `CanonicalSerialize` derive (the `legacy-ark-serialize` feature) with `ark-serialize`
+//!   `0.3`: the fields in the declaration order, compressed, without a header. It's read and written here without the feature.
+//! - [`Format::Binary`] starts with the header `PLM`, the format tag `0x01` and the format version. Version `1` is then `nul`, `g^r`, `z`
+//!   in the encoding of [`mod@crate::encoding`], followed by `c` and `s`.
+//!
+//! The JSON of the RustCrypto crate is its own; it carries the message and the key as well, which these formats don't.
+//!
+//! # The canonical encoding
+//!
+//! [`PlumeSignature::to_bytes`] writes the latest [`Format::Binary`], and [`public_key_to_bytes`] a public key under a header of its own;
+//! these are the interchange formats with the other implementations of the repository, and don't change with the arkworks version. Points
+//! are SEC1 compressed and scalars are big-endian, each of the fixed width of its field, so the length only depends on the curve:
+//!
+//! | bytes  | signature, version `1`           | public key, version `1` |
+//! |--------|----------------------------------|-------------------------|
+//! | `0..3` | `PLM`                            | `PLM`                   |
+//! | `3`    | `0x01`                           | `0x02`                  |
+//! | `4`    | the version, `0x01`              | the version, `0x01`     |
+//! | then   | `nul`, `g^r`, `z`, then `c`, `s` | `pk`                    |
+//!
+//! On a 256-bit curve such as `secp256k1` that's `5 + 3 ⋅ 33 + 2 ⋅ 32 = 168` bytes for a signature, [`signature_byte_len`], and
+//! `5 + 33 = 38` for a key, [`public_key_byte_len`]. Reading checks the header, the length, that the scalars are less than the order, and
+//! that the points are in the prime order subgroup. Secret keys have no such format; they aren't meant to be interchanged.
+
+use crate::encoding::{self, field_byte_len, point_byte_len};
+use crate::error::EncodingError;
+use crate::{PlumeSignature, PublicKey};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use core::fmt;
+
+const MAGIC: &[u8] = b"PLM";
+const TAG_BINARY: u8 = 0x01;
+const TAG_PUBLIC_KEY: u8 = 0x02;
+/// The magic, the tag and the version.
+const HEADER_LEN: usize = MAGIC.len() + 2;
+
+/// The latest version of [`Format::Binary`].
+pub const BINARY_VERSION: u8 = 1;
+
+/// The latest version of the format of [`public_key_to_bytes`].
+pub const PUBLIC_KEY_VERSION: u8 = 1;
+
+/// A wire format of [`PlumeSignature`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The headerless `ark-serialize` `0.3` output.
+    LegacyArk,
+    /// The stable binary format of the given version.
+    Binary { version: u8 },
+}
+
+/// An error reading or writing a format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    /// The bytes aren't in any known format.
+    Unknown,
+    /// The format isn't supported; e.g. a binary version newer than this crate.
+    Unsupported(Format),
+    /// The public key is in a version of its format newer than this crate.
+    UnsupportedPublicKey,
+    /// A field of the stable binary format is invalid.
+    Encoding(EncodingError),
+    /// The `ark-serialize` data is invalid.
+    LegacyArk(String),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "the bytes aren't in a known signature format"),
+            Self::Unsupported(format) => write!(f, "the format {format:?} isn't supported"),
+            Self::UnsupportedPublicKey => write!(f, "the public key format isn't supported"),
+            Self::Encoding(e) => write!(f, "invalid field: {e:?}"),
+            Self::LegacyArk(e) => write!(f, "invalid legacy ark-serialize data: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for FormatError {}
+
+impl From<EncodingError> for FormatError {
+    fn from(e: EncodingError) -> Self {
+        Self::Encoding(e)
+    }
+}
+
+/// Tells the format of `bytes`, by the header and the length.
+pub fn detect_format<P: SWModelParameters>(bytes: &[u8]) -> Option<Format>
+where
+    P::BaseField: PrimeField,
+{
+    if let Some(rest) = bytes.strip_prefix(MAGIC) {
+        if let [TAG_BINARY, version, body @ ..] = rest {
+            if *version != BINARY_VERSION || body.len() == binary_body_len::<P>() {
+                return Some(Format::Binary { version: *version });
+            }
+        }
+    }
+    if bytes.len() == legacy_ark_len::<P>() {
+        return Some(Format::LegacyArk);
+    }
+    None
+}
+
+/// Writes `signature` in `format`.
+pub fn encode<P: SWModelParameters>(
+    signature: &PlumeSignature<P>,
+    format: Format,
+) -> Result<Vec<u8>, FormatError>
+where
+    P::BaseField: PrimeField,
+{
+    match format {
+        Format::LegacyArk => {
+            let mut bytes = Vec::with_capacity(legacy_ark_len::<P>());
+            legacy_ark_write(signature, &mut bytes)
+                .map_err(|e| FormatError::LegacyArk(e.to_string()))?;
+            Ok(bytes)
+        }
+        Format::Binary {
+            version: BINARY_VERSION,
+        } => {
+            let mut bytes = Vec::with_capacity(signature_byte_len::<P>());
+            bytes.extend_from_slice(MAGIC);
+            bytes.extend_from_slice(&[TAG_BINARY, BINARY_VERSION]);
+            for point in [
+                &signature.nullifier,
+                &signature.r_point,
+                &signature.hashed_to_curve_r,
+            ] {
+                bytes.extend(encoding::point_to_bytes(point)?);
+            }
+            bytes.extend(encoding::field_to_bytes(&signature.c));
+            bytes.extend(encoding::field_to_bytes(&signature.s));
+            Ok(bytes)
+        }
+        format => Err(FormatError::Unsupported(format)),
+    }
+}
+
+/// Reads a signature in whichever format `bytes` are in.
+pub fn decode<P: SWModelParameters>(bytes: &[u8]) -> Result<PlumeSignature<P>, FormatError>
+where
+    P::BaseField: PrimeField,
+{
+    match detect_format::<P>(bytes).ok_or(FormatError::Unknown)? {
+        Format::LegacyArk => {
+            legacy_ark_read(bytes).map_err(|e| FormatError::LegacyArk(e.to_string()))
+        }
+        Format::Binary {
+            version: BINARY_VERSION,
+        } => Ok(decode_binary(bytes)?),
+        format => Err(FormatError::Unsupported(format)),
+    }
+}
+
+/// Reads a signature in the latest [`Format::Binary`], without trying the other formats; for the records of
+/// [`SignatureReader`](crate::reader::SignatureReader).
+pub(crate) fn decode_binary<P: SWModelParameters>(
+    bytes: &[u8],
+) -> Result<PlumeSignature<P>, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    let Some([TAG_BINARY, BINARY_VERSION, body @ ..]) = bytes.strip_prefix(MAGIC) else {
+        return Err(EncodingError::InvalidHeader);
+    };
+    if body.len() != binary_body_len::<P>() {
+        return Err(EncodingError::InvalidLength {
+            expected: signature_byte_len::<P>(),
+            actual: bytes.len(),
+        });
+    }
+
+    let (point_len, scalar_len) = (point_byte_len::<P>(), field_byte_len::<P::ScalarField>());
+    let (points, scalars) = body.split_at(3 * point_len);
+    let point =
+        |i: usize| encoding::point_from_bytes::<P>(&points[i * point_len..(i + 1) * point_len]);
+    Ok(PlumeSignature {
+        nullifier: point(0)?,
+        r_point: point(1)?,
+        hashed_to_curve_r: point(2)?,
+        c: encoding::field_from_bytes(&scalars[..scalar_len])?,
+        s: encoding::field_from_bytes(&scalars[scalar_len..])?,
+    })
+}
+
+/// Rewrites the signature in `bytes` in the format `to`.
+pub fn migrate<P: SWModelParameters>(bytes: &[u8], to: Format) -> Result<Vec<u8>, FormatError>
+where
+    P::BaseField: PrimeField,
+{
+    encode(&decode::<P>(bytes)?, to)
+}
+
+/// The number of bytes of a signature on the curve `P` in the latest [`Format::Binary`], [`PlumeSignature::to_bytes`].
+pub fn signature_byte_len<P: SWModelParameters>() -> usize
+where
+    P::BaseField: PrimeField,
+{
+    HEADER_LEN + binary_body_len::<P>()
+}
+
+/// The number of bytes of a public key on the curve `P` written by [`public_key_to_bytes`].
+pub fn public_key_byte_len<P: SWModelParameters>() -> usize
+where
+    P::BaseField: PrimeField,
+{
+    HEADER_LEN + point_byte_len::<P>()
+}
+
+/// Writes `pk` in the canonical encoding of [the module docs](self); it fails for the point at infinity.
+pub fn public_key_to_bytes<P: SWModelParameters>(pk: &PublicKey<P>) -> Result<Vec<u8>, FormatError>
+where
+    P::BaseField: PrimeField,
+{
+    Ok([
+        MAGIC,
+        &[TAG_PUBLIC_KEY, PUBLIC_KEY_VERSION],
+        &encoding::point_to_bytes(pk)?,
+    ]
+    .concat())
+}
+
+/// Reads a public key written by [`public_key_to_bytes`], checking it's in the prime order subgroup.
+pub fn public_key_from_bytes<P: SWModelParameters>(
+    bytes: &[u8],
+) -> Result<PublicKey<P>, FormatError>
+where
+    P::BaseField: PrimeField,
+{
+    match bytes.strip_prefix(MAGIC) {
+        Some([TAG_PUBLIC_KEY, PUBLIC_KEY_VERSION, point @ ..]) => {
+            encoding::point_from_bytes(point).map_err(|e| match e {
+                // the length of the whole encoding rather than of the point
+                EncodingError::InvalidLength { .. } => {
+                    FormatError::Encoding(EncodingError::InvalidLength {
+                        expected: public_key_byte_len::<P>(),
+                        actual: bytes.len(),
+                    })
+                }
+                e => FormatError::Encoding(e),
+            })
+        }
+        Some([TAG_PUBLIC_KEY, ..]) => Err(FormatError::UnsupportedPublicKey),
+        _ => Err(FormatError::Unknown),
+    }
+}
+
+/// Writes the fields in the declaration order, as the derive does.
+fn legacy_ark_write<P: SWModelParameters>(
+    signature: &PlumeSignature<P>,
+    bytes: &mut Vec<u8>,
+) -> Result<(), SerializationError> {
+    signature.hashed_to_curve_r.serialize(&mut *bytes)?;
+    signature.r_point.serialize(&mut *bytes)?;
+    signature.s.serialize(&mut *bytes)?;
+    signature.c.serialize(&mut *bytes)?;
+    signature.nullifier.serialize(&mut *bytes)
+}
+
+fn legacy_ark_read<P: SWModelParameters>(
+    mut bytes: &[u8],
+) -> Result<PlumeSignature<P>, SerializationError> {
+    Ok(PlumeSignature {
+        hashed_to_curve_r: CanonicalDeserialize::deserialize(&mut bytes)?,
+        r_point: CanonicalDeserialize::deserialize(&mut bytes)?,
+        s: CanonicalDeserialize::deserialize(&mut bytes)?,
+        c: CanonicalDeserialize::deserialize(&mut bytes)?,
+        nullifier: CanonicalDeserialize::deserialize(&mut bytes)?,
+    })
+}
+
+fn binary_body_len<P: SWModelParameters>() -> usize
+where
+    P::BaseField: PrimeField,
+{
+    3 * point_byte_len::<P>() + 2 * field_byte_len::<P::ScalarField>()
+}
+
+fn legacy_ark_len<P: SWModelParameters>() -> usize {
+    3 * GroupAffine::<P>::zero().serialized_size() + 2 * P::ScalarField::zero().serialized_size()
+}
+
+impl<P: SWModelParameters> PlumeSignature<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// The canonical encoding of the signature, the latest [`Format::Binary`] of [`signature_byte_len`] bytes; see
+    /// [the module docs](self).
+    ///
+    /// It fails if a point is the point at infinity, which no valid signature has.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        encode(
+            self,
+            Format::Binary {
+                version: BINARY_VERSION,
+            },
+        )
+    }
+
+    /// Decodes the bytes of [`Self::to_bytes`]. The other formats are rejected, so a signature has a single canonical encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormatError> {
+        if let Some([TAG_BINARY, BINARY_VERSION, ..]) = bytes.strip_prefix(MAGIC) {
+            let expected = signature_byte_len::<P>();
+            if bytes.len() != expected {
+                return Err(FormatError::Encoding(EncodingError::InvalidLength {
+                    expected,
+                    actual: bytes.len(),
+                }));
+            }
+        }
+        match detect_format::<P>(bytes) {
+            Some(Format::Binary {
+                version: BINARY_VERSION,
+            }) => decode(bytes),
+            Some(format) => Err(FormatError::Unsupported(format)),
+            None => Err(FormatError::Unknown),
+        }
+    }
+
+    /// The hex of [`Self::to_bytes`].
+    pub fn to_hex(&self) -> Result<String, FormatError> {
+        Ok(encoding::to_hex(&self.to_bytes()?))
+    }
+
+    /// Decodes the hex of [`Self::to_hex`]; see [`encoding::from_hex`] and [`Self::from_bytes`].
+    pub fn from_hex(hex: &str) -> Result<Self, FormatError> {
+        Self::from_bytes(&encoding::from_hex(hex)?)
+    }
+}
@@ -1,35 +1,315 @@
+//! Hashing to the curve by the RFC 9380 suite of its [`HashToCurveSuite`].
+//!
+//! The map to the curve is the one step of the scheme which isn't generic arithmetic over the curve parameters, so a curve is usable
+//! with the signatures once its parameters implement [`HashToCurveSuite`]. `secp256k1` does, with the `secp256k1_XMD:SHA-256_SSWU_RO_`
+//! suite: the messages are expanded by [`expand_message_xmd`] with SHA-256, mapped with the simplified SWU map through the 3-isogeny,
+//! and the two points are added. [`hash_to_curve_sswu`] is the same construction without an isogeny, for the curves of nonzero `A` and
+//! `B` such as P-256, and [`hash_to_curve_svdw`] with the Shallue-van de Woestijne map, which takes any curve, such as the Pasta curves
+//! of `A = 0`. The domain separation tag is up to the caller; [`HashToCurveSuite::DST`] is the one the signatures use.
+
 use crate::error::HashToCurveError;
+use alloc::string::ToString;
+use alloc::{vec, vec::Vec};
 use ark_ec::short_weierstrass_jacobian::GroupAffine;
-use ark_ec::{AffineCurve, ProjectiveCurve};
-use ark_ff::FromBytes;
+use ark_ec::{AffineCurve, ProjectiveCurve, SWModelParameters};
+use ark_ff::{BigInteger, Field, FromBytes, One, PrimeField, SquareRootField, Zero};
 use elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
 use elliptic_curve::sec1::ToEncodedPoint;
 // TODO why not ark libs for these? oO
 use k256::{sha2::Sha256, AffinePoint, ProjectivePoint, Secp256k1};
-use secp256k1::Sec1EncodePoint;
+use plume_core::consts;
+use secp256k1::curves::Secp256k1Parameters;
+use sha2::Digest;
 use tiny_keccak::{Hasher, Shake, Xof};
 
-pub fn hash_to_curve<Fp: ark_ff::PrimeField, P: ark_ec::SWModelParameters>(
+/// The RFC 9380 identifier of the suite of `secp256k1`.
+pub const SUITE: &str = consts::SECP256K1.suite;
+
+/// The domain separation tag the signatures on `secp256k1` hash with.
+pub const DST: &[u8] = consts::SECP256K1.dst;
+
+/// The prefix RFC 9380 hashes a tag longer than 255 bytes with.
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// The most bytes [`expand_message_xmd`] outputs: 255 blocks of SHA-256.
+pub const EXPAND_MESSAGE_MAX_LEN: usize = 255 * 32;
+
+/// The parameters of a curve with a hash-to-curve suite of RFC 9380, which `h = hash[m, pk]` is computed with.
+pub trait HashToCurveSuite: SWModelParameters {
+    /// The RFC 9380 identifier of the suite.
+    const SUITE: &'static str;
+    /// The domain separation tag the signatures hash with.
+    const DST: &'static [u8];
+
+    /// The point the concatenation of `msgs` maps to under the tag `dst`, before [clearing the cofactor](clear_cofactor).
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError>;
+}
+
+impl HashToCurveSuite for Secp256k1Parameters {
+    const SUITE: &'static str = SUITE;
+    const DST: &'static [u8] = DST;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        check_dst(dst)?;
+        let pt: ProjectivePoint = Secp256k1::hash_from_bytes::<ExpandMsgXmd<Sha256>>(msgs, dst)
+            .map_err(|e| HashToCurveError::UnsupportedSuite {
+                suite: SUITE.to_string(),
+                reason: e.to_string(),
+            })?;
+
+        k256_affine_to_arkworks_secp256k1_affine::<Self>(pt.to_affine())
+    }
+}
+
+/// Hashes `msg || pk` to the curve under the [`DST`](HashToCurveSuite::DST) of its suite.
+pub fn hash_to_curve<P: HashToCurveSuite>(
     msg: &[u8],
     pk: &GroupAffine<P>,
 ) -> Result<GroupAffine<P>, HashToCurveError> {
-    let b = hex::decode(&pk.to_encoded_point(true)).expect(super::EXPECT_MSG_DECODE);
-    let x = [msg, b.as_slice()];
-    let x = x.concat().clone();
-    let x = x.as_slice();
+    hash_to_curve_with_dst::<P>(msg, pk, P::DST)
+}
+
+/// [`hash_to_curve`] of `msg || pk` under the tag `dst` instead of [`HashToCurveSuite::DST`].
+pub fn hash_to_curve_with_dst<P: HashToCurveSuite>(
+    msg: &[u8],
+    pk: &GroupAffine<P>,
+    dst: &[u8],
+) -> Result<GroupAffine<P>, HashToCurveError> {
+    let b = crate::affine_to_bytes(pk);
+
+    // `expand_message` takes the input in pieces, so `msg || pk` is never copied into one buffer
+    hash_bytes_to_curve::<P>(&[msg, &b], dst)
+}
+
+/// Hashes the concatenation of `msgs` to the curve under the tag `dst`, as `hash_to_curve` of RFC 9380 does.
+pub fn hash_bytes_to_curve<P: HashToCurveSuite>(
+    msgs: &[&[u8]],
+    dst: &[u8],
+) -> Result<GroupAffine<P>, HashToCurveError> {
+    P::map_to_curve(msgs, dst).map(clear_cofactor::<P>)
+}
+
+/// `hash_to_curve` of RFC 9380 by a `_XMD:SHA-256_SSWU_RO_` suite on the curve `P`, of `A ⋅ B ≠ 0` and with the non-square `z` of the
+/// suite, such as P-256.
+///
+/// It's written straight from the RFC over the arkworks fields, so it isn't constant time; the input is public in PLUME.
+pub fn hash_to_curve_sswu<P: SWModelParameters>(
+    msgs: &[&[u8]],
+    dst: &[u8],
+    z: P::BaseField,
+) -> Result<GroupAffine<P>, HashToCurveError>
+where
+    P::BaseField: PrimeField,
+{
+    let [u0, u1] = hash_to_field::<P::BaseField, 2>(msgs, dst)?;
+    let mut sum = map_to_curve_simple_swu::<P>(u0, z)?.into_projective();
+    sum.add_assign_mixed(&map_to_curve_simple_swu::<P>(u1, z)?);
+    Ok(sum.into_affine())
+}
+
+/// `hash_to_curve` of RFC 9380 by a `_XMD:SHA-256_SVDW_RO_` suite on the curve `P`, with the `z` of the suite.
+///
+/// It's the construction of [`hash_to_curve_sswu`] with [`map_to_curve_svdw`], for the curves the simplified SWU map doesn't take
+/// without an isogeny.
+pub fn hash_to_curve_svdw<P: SWModelParameters>(
+    msgs: &[&[u8]],
+    dst: &[u8],
+    z: P::BaseField,
+) -> Result<GroupAffine<P>, HashToCurveError>
+where
+    P::BaseField: PrimeField,
+{
+    let [u0, u1] = hash_to_field::<P::BaseField, 2>(msgs, dst)?;
+    let mut sum = map_to_curve_svdw::<P>(u0, z)?.into_projective();
+    sum.add_assign_mixed(&map_to_curve_svdw::<P>(u1, z)?);
+    Ok(sum.into_affine())
+}
+
+/// `hash_to_field` of RFC 9380 with [`expand_message_xmd`]: `N` elements of the prime field `F`, each from `ceil((log2(p) + 128) / 8)`
+/// bytes.
+pub fn hash_to_field<F: PrimeField, const N: usize>(
+    msgs: &[&[u8]],
+    dst: &[u8],
+) -> Result<[F; N], HashToCurveError> {
+    let len = (F::size_in_bits() + 128 + 7) / 8;
+    let uniform_bytes = expand_message_xmd(msgs, dst, N * len)?;
+    Ok(core::array::from_fn(|i| {
+        F::from_be_bytes_mod_order(&uniform_bytes[i * len..(i + 1) * len])
+    }))
+}
 
-    let pt: ProjectivePoint = Secp256k1::hash_from_bytes::<ExpandMsgXmd<Sha256>>(
-        &[x],
-        b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_",
-    )
-    .map_err(|_| HashToCurveError::Legacy)?;
+/// The simplified SWU map of RFC 9380 to the curve `P`, of `A ⋅ B ≠ 0`, with the non-square `z`.
+pub fn map_to_curve_simple_swu<P: SWModelParameters>(
+    u: P::BaseField,
+    z: P::BaseField,
+) -> Result<GroupAffine<P>, HashToCurveError>
+where
+    P::BaseField: PrimeField,
+{
+    let (a, b) = (P::COEFF_A, P::COEFF_B);
+    if a.is_zero() || b.is_zero() {
+        return Err(HashToCurveError::UnsupportedCurveError(
+            "the simplified SWU map needs A and B to be nonzero".to_string(),
+        ));
+    }
+    let g = |x: P::BaseField| (x.square() + a) * x + b;
+
+    let z_u2 = z * u.square();
+    let x1 = match (z_u2.square() + z_u2).inverse() {
+        Some(tv1) => -b / a * (P::BaseField::one() + tv1),
+        None => b / (z * a),
+    };
+    let (x, y) = match g(x1).sqrt() {
+        Some(y1) => (x1, y1),
+        None => {
+            let x2 = z_u2 * x1;
+            // `g(x1) ⋅ g(x2)` is a square when `z` is a non-square, so one of the two is
+            let y2 = g(x2).sqrt().ok_or_else(|| {
+                HashToCurveError::MapToCurveError("z isn't a non-square of the field".to_string())
+            })?;
+            (x2, y2)
+        }
+    };
+    let y = if sgn0(&u) == sgn0(&y) { y } else { -y };
+    Ok(GroupAffine::new(x, y, false))
+}
 
-    let pt_affine = pt.to_affine();
+/// The Shallue-van de Woestijne map of RFC 9380 to the curve `P`, `y^2 = g(x)`, with `z` such that `g(z) ≠ 0`, `-(3z^2 + 4A) / 4g(z)`
+/// is a nonzero square, and `g(z)` or `g(-z / 2)` is a square, as `find_z_svdw` of the RFC picks it.
+///
+/// The constants of the map are derived from `z` on each call rather than taken from the suite.
+pub fn map_to_curve_svdw<P: SWModelParameters>(
+    u: P::BaseField,
+    z: P::BaseField,
+) -> Result<GroupAffine<P>, HashToCurveError>
+where
+    P::BaseField: PrimeField,
+{
+    let (a, b) = (P::COEFF_A, P::COEFF_B);
+    let g = |x: P::BaseField| (x.square() + a) * x + b;
+    let is_square = |x: P::BaseField| !x.legendre().is_qnr();
+    let invalid_z = || {
+        HashToCurveError::MapToCurveError("z doesn't meet the criteria of the SvdW map".to_string())
+    };
+    let one = P::BaseField::one();
 
-    k256_affine_to_arkworks_secp256k1_affine::<P>(pt_affine)
+    let c1 = g(z);
+    let three_z2_four_a = z.square() * P::BaseField::from(3u64) + a.double().double();
+    let c2 = -z * one.double().inverse().ok_or_else(invalid_z)?;
+    let c3 = (-c1 * three_z2_four_a).sqrt().ok_or_else(invalid_z)?;
+    let c3 = if sgn0(&c3) { -c3 } else { c3 };
+    let c4 = -c1.double().double() * three_z2_four_a.inverse().ok_or_else(invalid_z)?;
+
+    let tv1 = u.square() * c1;
+    let tv2 = one + tv1;
+    let tv1 = one - tv1;
+    let tv3 = (tv1 * tv2).inverse().unwrap_or_else(P::BaseField::zero);
+    let tv4 = u * tv1 * tv3 * c3;
+    let x1 = c2 - tv4;
+    let x2 = c2 + tv4;
+    let x = if is_square(g(x1)) {
+        x1
+    } else if is_square(g(x2)) {
+        x2
+    } else {
+        c4 * (tv2.square() * tv3).square() + z
+    };
+    let y = g(x).sqrt().ok_or_else(invalid_z)?;
+    let y = if sgn0(&u) == sgn0(&y) { y } else { -y };
+    Ok(GroupAffine::new(x, y, false))
+}
+
+/// `sgn0` of RFC 9380: the parity of the element.
+fn sgn0<F: PrimeField>(x: &F) -> bool {
+    x.into_repr().is_odd()
 }
 
-pub fn k256_affine_to_arkworks_secp256k1_affine<P: ark_ec::SWModelParameters>(
+/// `expand_message_xmd` of RFC 9380 with SHA-256: `len_in_bytes` uniform bytes from the concatenation of `msgs` under the tag `dst`.
+///
+/// A tag longer than 255 bytes is hashed down first, as the RFC specifies. An empty tag, and a length over 255 blocks of the hash,
+/// are rejected.
+pub fn expand_message_xmd(
+    msgs: &[&[u8]],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, HashToCurveError> {
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+
+    check_dst(dst)?;
+    if len_in_bytes > EXPAND_MESSAGE_MAX_LEN {
+        return Err(HashToCurveError::InvalidInput {
+            len: len_in_bytes,
+            max: EXPAND_MESSAGE_MAX_LEN,
+        });
+    }
+    let ell = (len_in_bytes + B_IN_BYTES - 1) / B_IN_BYTES;
+
+    let hashed_dst;
+    let dst = if dst.len() > 255 {
+        hashed_dst = Sha256::new()
+            .chain_update(OVERSIZE_DST_PREFIX)
+            .chain_update(dst)
+            .finalize();
+        hashed_dst.as_slice()
+    } else {
+        dst
+    };
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let mut hasher = Sha256::new().chain_update([0u8; S_IN_BYTES]);
+    for msg in msgs {
+        hasher.update(msg);
+    }
+    let b_0 = hasher
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mixed: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = Sha256::new()
+            .chain_update(mixed)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+fn check_dst(dst: &[u8]) -> Result<(), HashToCurveError> {
+    if dst.is_empty() {
+        return Err(HashToCurveError::InvalidDst {
+            reason: "the tag is empty",
+        });
+    }
+    Ok(())
+}
+
+/// Clears the cofactor of a point produced by the map to the curve, so the output lands in the prime order subgroup.
+///
+/// The cofactor is taken from `P::COFACTOR`, so each curve gets its own clearing. `secp256k1` is of prime order,
+/// hence its cofactor is one and the point is returned as is.
+pub fn clear_cofactor<P: SWModelParameters>(point: GroupAffine<P>) -> GroupAffine<P> {
+    if P::COFACTOR == [1u64] {
+        point
+    } else {
+        point.mul_by_cofactor()
+    }
+}
+
+#[doc(hidden)]
+pub fn k256_affine_to_arkworks_secp256k1_affine<P: SWModelParameters>(
     k_pt: AffinePoint,
 ) -> Result<GroupAffine<P>, HashToCurveError> {
     let encoded_pt = k_pt.to_encoded_point(false);
@@ -37,40 +317,68 @@ pub fn k256_affine_to_arkworks_secp256k1_affine<P: ark_ec::SWModelParameters>(
     let num_field_bytes = 40;
 
     // extract k_pt.x
-    let k_pt_x_bytes = encoded_pt.x().ok_or(HashToCurveError::Legacy)?;
+    let k_pt_x_bytes = encoded_pt
+        .x()
+        .ok_or(HashToCurveError::InvalidFieldElement { coordinate: "x" })?;
 
     // pad x bytes
     let mut k_pt_x_bytes_vec = vec![0u8; num_field_bytes];
     for (i, _) in k_pt_x_bytes.clone().iter().enumerate() {
-        let _ = std::mem::replace(
+        let _ = core::mem::replace(
             &mut k_pt_x_bytes_vec[i],
             k_pt_x_bytes[k_pt_x_bytes.len() - 1 - i],
         );
     }
-    let reader = std::io::BufReader::new(k_pt_x_bytes_vec.as_slice());
-    let g_x = P::BaseField::read(reader).map_err(|_| HashToCurveError::Legacy)?;
+    // read straight from the slice; a `BufReader` allocates an 8 KiB buffer for the 40 bytes
+    let g_x = P::BaseField::read(k_pt_x_bytes_vec.as_slice())
+        .map_err(|_| HashToCurveError::InvalidFieldElement { coordinate: "x" })?;
 
     // extract k_pt.y
-    let k_pt_y_bytes = encoded_pt.y().ok_or(HashToCurveError::Legacy)?;
+    let k_pt_y_bytes = encoded_pt
+        .y()
+        .ok_or(HashToCurveError::InvalidFieldElement { coordinate: "y" })?;
 
     // pad y bytes
     let mut k_pt_y_bytes_vec = vec![0u8; num_field_bytes];
     for (i, _) in k_pt_y_bytes.clone().iter().enumerate() {
-        let _ = std::mem::replace(
+        let _ = core::mem::replace(
             &mut k_pt_y_bytes_vec[i],
             k_pt_y_bytes[k_pt_y_bytes.len() - 1 - i],
         );
     }
 
-    let reader = std::io::BufReader::new(k_pt_y_bytes_vec.as_slice());
-    let g_y = P::BaseField::read(reader).map_err(|_| HashToCurveError::Legacy)?;
+    let g_y = P::BaseField::read(k_pt_y_bytes_vec.as_slice())
+        .map_err(|_| HashToCurveError::InvalidFieldElement { coordinate: "y" })?;
 
     Ok(GroupAffine::<P>::new(g_x, g_y, false))
 }
 
+/// The iteration limit of [`_try_and_increment`], which is also the most a limit can be as the nonce is a single byte.
+#[doc(hidden)]
+pub const TRY_AND_INCREMENT_MAX_ITERATIONS: usize = 256;
+
 /// Kobi's hash_to_curve function, here for reference only
+#[doc(hidden)]
 pub fn _try_and_increment<C: ProjectiveCurve>(msg: &[u8]) -> Result<C::Affine, HashToCurveError> {
-    for nonce in 0u8..=255 {
+    _try_and_increment_with_limit::<C>(msg, TRY_AND_INCREMENT_MAX_ITERATIONS)
+}
+
+/// [`_try_and_increment`] giving up after `max_iterations` attempts, capped at [`TRY_AND_INCREMENT_MAX_ITERATIONS`].
+#[doc(hidden)]
+pub fn _try_and_increment_with_limit<C: ProjectiveCurve>(
+    msg: &[u8],
+    max_iterations: usize,
+) -> Result<C::Affine, HashToCurveError> {
+    try_and_increment_counted::<C>(msg, max_iterations).map(|(p, _)| p)
+}
+
+/// Returns the point along with the number of attempts it took.
+pub(crate) fn try_and_increment_counted<C: ProjectiveCurve>(
+    msg: &[u8],
+    max_iterations: usize,
+) -> Result<(C::Affine, usize), HashToCurveError> {
+    let iterations = max_iterations.min(TRY_AND_INCREMENT_MAX_ITERATIONS);
+    for nonce in (0u8..=255).take(iterations) {
         let mut h = Shake::v128();
         h.update(&[nonce]);
         h.update(msg.as_ref());
@@ -79,9 +387,9 @@ pub fn _try_and_increment<C: ProjectiveCurve>(msg: &[u8]) -> Result<C::Affine, H
         h.squeeze(&mut output);
 
         if let Some(p) = C::Affine::from_random_bytes(&output) {
-            return Ok(p);
+            return Ok((p.mul_by_cofactor(), usize::from(nonce) + 1));
         }
     }
 
-    Err(HashToCurveError::ReferenceTryAndIncrement)
+    Err(HashToCurveError::IterationLimitExceeded { iterations })
 }
@@ -0,0 +1,88 @@
+//! The hash function of the challenge `c`.
+//!
+//! SHA-256 is the hash of the specification, and what [`PlumeSignature::sign`](crate::PlumeSignature::sign) uses. [`Keccak256`] is the
+//! one an EVM verifier computes cheaply, about a twentieth of the gas of the SHA-256 precompile; it's selected with
+//! [`PlumeSignature::sign_with_hasher`](crate::PlumeSignature::sign_with_hasher) and the `_with_hasher` verifications. Its preimage starts
+//! with [`KECCAK256_TAG`], so a Keccak signature is never one of the SHA-256 versions.
+//!
+//! Any [`Digest`] is a [`PlumeHasher`], e.g. [`Sha512`], which the original PLUME implementations used. A signature only verifies with the
+//! hash it was made with, so where the hash isn't fixed by the deployment it travels with the signature as a [`ChallengeHash`], which the
+//! version codes of [`crate::offline`] carry.
+
+use alloc::vec::Vec;
+use sha2::Digest;
+use tiny_keccak::{Hasher, Keccak};
+
+pub use sha2::{Sha256, Sha512};
+
+/// The hash of the challenge of the specification, with `secp256k1` and its hash-to-curve suite.
+pub type SpecHasher = Sha256;
+
+/// The domain tag the Keccak-256 challenge preimage starts with.
+pub const KECCAK256_TAG: &[u8] = b"PLUME/keccak256-challenge";
+
+/// A hash function computing the challenge `c` from its preimage.
+///
+/// The challenge computation absorbs the preimage and takes the digest, so an implementation can drive a SHA-256 peripheral or a zkVM
+/// precompile instead of the software hash. The hash inside hash-to-curve isn't covered: it's fixed by the hash-to-curve suite.
+pub trait PlumeHasher: Default {
+    /// Absorbs `data` into the hash state.
+    fn absorb(&mut self, data: &[u8]);
+    /// Consumes the state returning the digest.
+    fn finish(self) -> Vec<u8>;
+
+    /// Hashes `data` in one go.
+    fn hash(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::default();
+        hasher.absorb(data);
+        hasher.finish()
+    }
+}
+
+/// The software hashes of the RustCrypto crates, such as the SHA-256 of the `sha2` crate which is the hash of the PLUME specification.
+impl<D: Digest + Default> PlumeHasher for D {
+    fn absorb(&mut self, data: &[u8]) {
+        Digest::update(self, data)
+    }
+
+    fn finish(self) -> Vec<u8> {
+        Digest::finalize(self).to_vec()
+    }
+}
+
+/// A challenge hash a serialized signature names, for deployments mixing them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChallengeHash {
+    /// [`Sha256`], the hash of the specification.
+    #[default]
+    Sha256,
+    /// [`Sha512`].
+    Sha512,
+    /// [`Keccak256`].
+    Keccak256,
+}
+
+/// Keccak-256, the hash of the EVM, of [`KECCAK256_TAG`] followed by the preimage: `c` is `keccak256(abi.encodePacked(tag, preimage))`
+/// in a Solidity verifier.
+#[derive(Clone)]
+pub struct Keccak256(Keccak);
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        let mut keccak = Keccak::v256();
+        keccak.update(KECCAK256_TAG);
+        Self(keccak)
+    }
+}
+
+impl PlumeHasher for Keccak256 {
+    fn absorb(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    fn finish(self) -> Vec<u8> {
+        let mut digest = alloc::vec![0u8; 32];
+        self.0.finalize(&mut digest);
+        digest
+    }
+}
@@ -0,0 +1,86 @@
+//! Conversions between the `secp256k1` keys, points and scalars of this crate and those of [`k256`], so a wallet holding RustCrypto keys
+//! can compute their nullifiers without handling the bytes itself.
+//!
+//! The types on both sides are foreign to this crate, so the conversions can't be `From` and `TryFrom` impls; they're functions instead,
+//! named after the direction. Converting to this crate never fails, as every `k256` value has a counterpart; converting back fails on the
+//! values `k256` can't represent, the identity as a public key and zero as a secret key. The `k256` types are those of the version this
+//! crate depends on, re-exported as [`k256`](self::k256).
+
+use crate::encoding;
+use crate::error::EncodingError;
+use crate::optimizations::to_k256_point;
+use crate::{PublicKey, SecretKeyMaterial};
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ff::{PrimeField, Zero};
+use elliptic_curve::ops::Reduce;
+use elliptic_curve::sec1::ToEncodedPoint;
+use secp256k1::curves::Secp256k1Parameters;
+use secp256k1::fields::{Fq, Fr};
+use zeroize::Zeroizing;
+
+pub use ::k256;
+
+/// The point of `point`; the identity maps to the identity.
+pub fn point_from_k256(point: &k256::AffinePoint) -> GroupAffine<Secp256k1Parameters> {
+    let encoded = point.to_encoded_point(false);
+    match (encoded.x(), encoded.y()) {
+        (Some(x), Some(y)) => GroupAffine::new(
+            Fq::from_be_bytes_mod_order(x),
+            Fq::from_be_bytes_mod_order(y),
+            false,
+        ),
+        _ => GroupAffine::zero(),
+    }
+}
+
+/// The `k256` point of `point`, failing with [`EncodingError::NotOnCurve`] on a point which isn't on the curve.
+pub fn point_to_k256(
+    point: &GroupAffine<Secp256k1Parameters>,
+) -> Result<k256::AffinePoint, EncodingError> {
+    to_k256_point(point)
+        .map(|point| point.to_affine())
+        .ok_or(EncodingError::NotOnCurve)
+}
+
+/// The public key of `public_key`.
+pub fn public_key_from_k256(public_key: &k256::PublicKey) -> PublicKey<Secp256k1Parameters> {
+    point_from_k256(public_key.as_affine())
+}
+
+/// The `k256` public key of `public_key`, failing with [`EncodingError::Identity`] on the identity, which `k256` doesn't accept as a key.
+pub fn public_key_to_k256(
+    public_key: &PublicKey<Secp256k1Parameters>,
+) -> Result<k256::PublicKey, EncodingError> {
+    if public_key.infinity {
+        return Err(EncodingError::Identity);
+    }
+    k256::PublicKey::from_affine(point_to_k256(public_key)?).map_err(|_| EncodingError::Identity)
+}
+
+/// The scalar of `scalar`.
+pub fn scalar_from_k256(scalar: &k256::Scalar) -> Fr {
+    Fr::from_be_bytes_mod_order(&scalar.to_bytes())
+}
+
+/// The `k256` scalar of `scalar`.
+pub fn scalar_to_k256(scalar: &Fr) -> k256::Scalar {
+    let bytes = k256::FieldBytes::clone_from_slice(&encoding::field_to_bytes(scalar));
+    <k256::Scalar as Reduce<k256::U256>>::from_be_bytes_reduced(bytes)
+}
+
+/// The secret key of `secret_key`, wiped on drop.
+pub fn secret_key_from_k256(
+    secret_key: &k256::SecretKey,
+) -> Zeroizing<SecretKeyMaterial<Secp256k1Parameters>> {
+    let mut bytes = Zeroizing::new([0; 32]);
+    bytes.copy_from_slice(&secret_key.to_be_bytes());
+    Zeroizing::new(Fr::from_be_bytes_mod_order(&*bytes))
+}
+
+/// The `k256` secret key of `secret_key`, failing with [`EncodingError::ZeroScalar`] on zero, which `k256` doesn't accept as a key.
+pub fn secret_key_to_k256(
+    secret_key: &SecretKeyMaterial<Secp256k1Parameters>,
+) -> Result<k256::SecretKey, EncodingError> {
+    let bytes = Zeroizing::new(encoding::field_to_bytes(secret_key));
+    k256::SecretKey::from_be_bytes(&bytes).map_err(|_| EncodingError::ZeroScalar)
+}
@@ -0,0 +1,81 @@
+//! Importing existing keys from their raw bytes, so a wallet can compute the nullifiers of the keys it already holds.
+//!
+//! [`SecretKeyMaterial`] and [`PublicKey`] are aliases of the arkworks types, so the constructors are extension traits:
+//! `SecretKeyMaterial::<P>::from_bytes` with [`SecretKeyBytes`] in scope, and `PublicKey::<P>::from_sec1_bytes` with [`PublicKeySec1`].
+//! [`PlumeSignature::keypair_from_bytes`] imports a secret key and derives its public key in one go.
+
+use crate::encoding::{self, field_byte_len};
+use crate::error::EncodingError;
+use crate::{secret_mul, validate, Parameters, PlumeSignature, PublicKey, SecretKeyMaterial};
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{PrimeField, Zero};
+use zeroize::Zeroizing;
+
+/// The SEC1 tag of an uncompressed point; the compressed tags are those of [`mod@encoding`].
+const TAG_UNCOMPRESSED: u8 = 0x04;
+
+/// A secret key from its 32 big-endian bytes, the form wallets export a `secp256k1` key in.
+pub trait SecretKeyBytes: Sized {
+    /// Decodes `bytes`, rejecting a key which isn't less than the group order, and the zero key. Fails with
+    /// [`EncodingError::InvalidLength`] on a curve whose scalars aren't 32 bytes wide.
+    fn from_bytes(bytes: &[u8; 32]) -> Result<Self, EncodingError>;
+}
+
+impl<F: PrimeField> SecretKeyBytes for F {
+    fn from_bytes(bytes: &[u8; 32]) -> Result<Self, EncodingError> {
+        let secret_key = Zeroizing::new(encoding::field_from_bytes::<F>(bytes)?);
+        if secret_key.is_zero() {
+            return Err(EncodingError::ZeroScalar);
+        }
+        Ok(*secret_key)
+    }
+}
+
+/// A public key from its SEC1 encoding, compressed or uncompressed.
+pub trait PublicKeySec1: Sized {
+    /// Decodes `0x02`/`0x03` followed by `x`, or `0x04` followed by `x` and `y`, the coordinates big-endian. The point has to be on the
+    /// curve and in the prime order subgroup; the identity, `0x00` in SEC1, is rejected.
+    fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, EncodingError>;
+}
+
+impl<P: SWModelParameters> PublicKeySec1 for GroupAffine<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, EncodingError> {
+        match bytes.split_first() {
+            Some((&TAG_UNCOMPRESSED, coordinates)) => {
+                let width = field_byte_len::<P::BaseField>();
+                if coordinates.len() != 2 * width {
+                    return Err(EncodingError::InvalidLength {
+                        expected: 1 + 2 * width,
+                        actual: bytes.len(),
+                    });
+                }
+                let (x, y) = coordinates.split_at(width);
+                let pk = GroupAffine::new(
+                    encoding::field_from_bytes(x)?,
+                    encoding::field_from_bytes(y)?,
+                    false,
+                );
+                validate::public_key(&pk)?;
+                Ok(pk)
+            }
+            Some((&0x00, [])) => Err(EncodingError::Identity),
+            _ => encoding::point_from_bytes(bytes),
+        }
+    }
+}
+
+impl<P: SWModelParameters> PlumeSignature<P> {
+    /// Imports the secret key of `bytes`, as [`SecretKeyBytes::from_bytes`], and derives its public key; the secret key is wiped on drop,
+    /// as the one of [`Self::keygen_zeroizing`].
+    pub fn keypair_from_bytes(
+        pp: &Parameters<P>,
+        bytes: &[u8; 32],
+    ) -> Result<(PublicKey<P>, Zeroizing<SecretKeyMaterial<P>>), EncodingError> {
+        let secret_key = Zeroizing::new(SecretKeyMaterial::<P>::from_bytes(bytes)?);
+        let public_key = secret_mul(&pp.g_point, &*secret_key);
+        Ok((public_key, secret_key))
+    }
+}
@@ -0,0 +1,368 @@
+//! Ethereum keystore files, the encrypted JSON of Web3 Secret Storage version 3, with the `keystore` feature.
+//!
+//! A nullifier key is stored the way wallets store an Ethereum key, so the tooling users trust for those keeps it too; [`decrypt`] of an
+//! existing keystore gives the keypair to sign with. The key is encrypted with AES-128-CTR under the first half of a key derived from the
+//! password with scrypt or PBKDF2-HMAC-SHA-256, and authenticated by the Keccak-256 of the second half and the ciphertext. A file's scrypt
+//! cost is bounded by [`MAX_SCRYPT_MEMORY`] and [`MAX_SCRYPT_PARALLELISM`], and its PBKDF2 cost by [`MAX_PBKDF2_ITERATIONS`], so a crafted
+//! file can't take the memory or the time of the process.
+
+use crate::keys::SecretKeyBytes;
+use crate::recovery;
+use crate::{encoding, secret_mul, EncodingError, PublicKey, SecretKeyMaterial};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ark_ec::AffineCurve;
+use core::fmt;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use generic_array::GenericArray;
+use rand_core::{CryptoRng, RngCore};
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use tiny_keccak::{Hasher, Keccak};
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// The version of Web3 Secret Storage this module reads and writes.
+const VERSION: u8 = 3;
+
+const CIPHER: &str = "aes-128-ctr";
+
+/// The length of the derived key: the cipher key and the MAC key.
+const DKLEN: usize = 32;
+
+/// The most memory [`decrypt`] lets the scrypt of a file take, `128 ⋅ r ⋅ n` bytes: 1 GiB, four times that of the standard cost.
+pub const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+/// The most `p` [`decrypt`] takes for scrypt, for which the time grows linearly; geth's light cost is `6`.
+pub const MAX_SCRYPT_PARALLELISM: u32 = 16;
+
+/// The most iterations [`decrypt`] takes for PBKDF2, about four times those of the OWASP recommendation; the vectors use `262144`.
+pub const MAX_PBKDF2_ITERATIONS: u32 = 1 << 21;
+
+/// The key derivation of a keystore, with its cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with `n = 2^log_n`.
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA-256 with `c` iterations.
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// The cost geth writes by default, `n = 2^18, r = 8, p = 1`.
+    pub const SCRYPT_STANDARD: Self = Self::Scrypt {
+        log_n: 18,
+        r: 8,
+        p: 1,
+    };
+    /// The cost of geth's `--lightkdf`, `n = 2^12, r = 8, p = 6`, for constrained devices.
+    pub const SCRYPT_LIGHT: Self = Self::Scrypt {
+        log_n: 12,
+        r: 8,
+        p: 6,
+    };
+}
+
+/// Why a keystore couldn't be written or read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeystoreError {
+    /// The JSON isn't a keystore of version 3, or a field of it is malformed.
+    Malformed,
+    /// The cipher isn't `aes-128-ctr`.
+    UnsupportedCipher(String),
+    /// The key derivation isn't scrypt or PBKDF2-HMAC-SHA-256.
+    UnsupportedKdf(String),
+    /// The cost of the key derivation is invalid, or above [`MAX_SCRYPT_MEMORY`], [`MAX_SCRYPT_PARALLELISM`] or
+    /// [`MAX_PBKDF2_ITERATIONS`].
+    InvalidKdfParams,
+    /// The MAC doesn't match: the password is wrong, or the file is corrupted.
+    WrongPassword,
+    /// The decrypted key isn't a valid secret key.
+    InvalidKey(EncodingError),
+    /// The key doesn't have the address of the file.
+    AddressMismatch,
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "the keystore is malformed"),
+            Self::UnsupportedCipher(cipher) => write!(f, "unsupported cipher `{cipher}`"),
+            Self::UnsupportedKdf(kdf) => write!(f, "unsupported key derivation `{kdf}`"),
+            Self::InvalidKdfParams => write!(f, "the key derivation parameters are invalid"),
+            Self::WrongPassword => write!(f, "the password is wrong"),
+            Self::InvalidKey(e) => write!(f, "invalid key: {e:?}"),
+            Self::AddressMismatch => write!(f, "the key doesn't have the address of the keystore"),
+        }
+    }
+}
+
+impl core::error::Error for KeystoreError {}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreJson {
+    // geth wrote `Crypto` before version 1.4
+    #[serde(alias = "Crypto")]
+    crypto: CryptoJson,
+    id: String,
+    version: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CryptoJson {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: KdfParams,
+    mac: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u64,
+        p: u32,
+        r: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: usize,
+        prf: String,
+        salt: String,
+    },
+}
+
+fn hex(field: &str) -> Result<Vec<u8>, KeystoreError> {
+    encoding::hex_digits(field).map_err(|_| KeystoreError::Malformed)
+}
+
+/// The key `kdf` derives from `password` and `salt`.
+fn derive_key(
+    kdf: Kdf,
+    password: &[u8],
+    salt: &[u8],
+) -> Result<Zeroizing<[u8; DKLEN]>, KeystoreError> {
+    let mut key = Zeroizing::new([0u8; DKLEN]);
+    match kdf {
+        Kdf::Scrypt { log_n, r, p } => {
+            if log_n >= 64
+                || (128 * u128::from(r)) << log_n > u128::from(MAX_SCRYPT_MEMORY)
+                || p > MAX_SCRYPT_PARALLELISM
+            {
+                return Err(KeystoreError::InvalidKdfParams);
+            }
+            let params = scrypt::Params::new(log_n, r, p, DKLEN)
+                .map_err(|_| KeystoreError::InvalidKdfParams)?;
+            scrypt::scrypt(password, salt, &params, &mut *key)
+                .map_err(|_| KeystoreError::InvalidKdfParams)?;
+        }
+        Kdf::Pbkdf2 { c } => {
+            if c == 0 || c > MAX_PBKDF2_ITERATIONS {
+                return Err(KeystoreError::InvalidKdfParams);
+            }
+            pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, c, &mut *key);
+        }
+    }
+    Ok(key)
+}
+
+fn mac(key: &[u8; DKLEN], ciphertext: &[u8]) -> [u8; 32] {
+    let mut mac = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(&key[16..]);
+    keccak.update(ciphertext);
+    keccak.finalize(&mut mac);
+    mac
+}
+
+/// AES-128-CTR under the first half of `key`, which encrypts and decrypts alike.
+fn apply_cipher(key: &[u8; DKLEN], iv: &[u8; 16], data: &mut [u8]) {
+    let mut cipher = Aes128Ctr::new(
+        GenericArray::from_slice(&key[..16]),
+        GenericArray::from_slice(iv),
+    );
+    cipher.apply_keystream(data);
+}
+
+/// A random UUID of version 4, the `id` of a keystore.
+fn uuid<R: RngCore>(rng: &mut R) -> String {
+    let mut bytes = [0u8; 16];
+    rng.fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = encoding::to_hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// The keystore JSON of `secret_key` under `password`, with a random salt, IV and id. The key's address is included, as wallets write it.
+pub fn encrypt<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    secret_key: &SecretKeyMaterial<Secp256k1Parameters>,
+    password: &[u8],
+    kdf: Kdf,
+) -> Result<String, KeystoreError> {
+    let mut salt = [0u8; 32];
+    rng.fill_bytes(&mut salt);
+    let mut iv = [0u8; 16];
+    rng.fill_bytes(&mut iv);
+
+    let key = derive_key(kdf, password, &salt)?;
+    let mut ciphertext = Zeroizing::new(encoding::field_to_bytes(secret_key));
+    apply_cipher(&key, &iv, &mut ciphertext);
+    let public_key = secret_mul(&Affine::prime_subgroup_generator(), secret_key);
+    let address = recovery::address(&public_key).map_err(KeystoreError::InvalidKey)?;
+
+    let salt = encoding::to_hex(&salt);
+    let kdfparams = match kdf {
+        Kdf::Scrypt { log_n, r, p } => KdfParams::Scrypt {
+            dklen: DKLEN,
+            n: 1u64
+                .checked_shl(u32::from(log_n))
+                .ok_or(KeystoreError::InvalidKdfParams)?,
+            p,
+            r,
+            salt,
+        },
+        Kdf::Pbkdf2 { c } => KdfParams::Pbkdf2 {
+            c,
+            dklen: DKLEN,
+            prf: "hmac-sha256".to_string(),
+            salt,
+        },
+    };
+    let keystore = KeystoreJson {
+        crypto: CryptoJson {
+            cipher: CIPHER.to_string(),
+            cipherparams: CipherParams {
+                iv: encoding::to_hex(&iv),
+            },
+            ciphertext: encoding::to_hex(&ciphertext),
+            kdf: match kdf {
+                Kdf::Scrypt { .. } => "scrypt",
+                Kdf::Pbkdf2 { .. } => "pbkdf2",
+            }
+            .to_string(),
+            kdfparams,
+            mac: encoding::to_hex(&mac(&key, &ciphertext)),
+        },
+        id: uuid(rng),
+        version: VERSION,
+        address: Some(encoding::to_hex(&address)),
+    };
+    serde_json::to_string(&keystore).map_err(|_| KeystoreError::Malformed)
+}
+
+/// The keypair of the keystore `json` under `password`; the secret key is wiped on drop. The address of the file, if it has one, has to be
+/// that of the key.
+pub fn decrypt(
+    json: &str,
+    password: &[u8],
+) -> Result<
+    (
+        PublicKey<Secp256k1Parameters>,
+        Zeroizing<SecretKeyMaterial<Secp256k1Parameters>>,
+    ),
+    KeystoreError,
+> {
+    let keystore: KeystoreJson =
+        serde_json::from_str(json).map_err(|_| KeystoreError::Malformed)?;
+    if keystore.version != VERSION {
+        return Err(KeystoreError::Malformed);
+    }
+    let crypto = keystore.crypto;
+    if crypto.cipher != CIPHER {
+        return Err(KeystoreError::UnsupportedCipher(crypto.cipher));
+    }
+    let (kdf, salt) = match (crypto.kdf.as_str(), crypto.kdfparams) {
+        (
+            "scrypt",
+            KdfParams::Scrypt {
+                dklen,
+                n,
+                p,
+                r,
+                salt,
+            },
+        ) if dklen == DKLEN => {
+            if !n.is_power_of_two() || n < 2 {
+                return Err(KeystoreError::InvalidKdfParams);
+            }
+            // `n` is below `2^64`, so its log is below 64
+            let log_n = n.trailing_zeros() as u8;
+            (Kdf::Scrypt { log_n, r, p }, salt)
+        }
+        (
+            "pbkdf2",
+            KdfParams::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            },
+        ) if dklen == DKLEN => {
+            if prf != "hmac-sha256" {
+                return Err(KeystoreError::UnsupportedKdf(prf));
+            }
+            (Kdf::Pbkdf2 { c }, salt)
+        }
+        ("scrypt" | "pbkdf2", _) => return Err(KeystoreError::InvalidKdfParams),
+        (kdf, _) => return Err(KeystoreError::UnsupportedKdf(kdf.to_string())),
+    };
+    let iv: [u8; 16] = hex(&crypto.cipherparams.iv)?
+        .try_into()
+        .map_err(|_| KeystoreError::Malformed)?;
+    let mut plaintext = Zeroizing::new(hex(&crypto.ciphertext)?);
+    let expected_mac = hex(&crypto.mac)?;
+
+    let key = derive_key(kdf, password, &hex(&salt)?)?;
+    if !bool::from(mac(&key, &plaintext)[..].ct_eq(&expected_mac[..])) {
+        return Err(KeystoreError::WrongPassword);
+    }
+    apply_cipher(&key, &iv, &mut plaintext);
+    let bytes: Zeroizing<[u8; 32]> =
+        Zeroizing::new(plaintext.as_slice().try_into().map_err(|_| {
+            KeystoreError::InvalidKey(EncodingError::InvalidLength {
+                expected: 32,
+                actual: plaintext.len(),
+            })
+        })?);
+    let secret_key = Zeroizing::new(
+        SecretKeyMaterial::<Secp256k1Parameters>::from_bytes(&bytes)
+            .map_err(KeystoreError::InvalidKey)?,
+    );
+    let public_key = secret_mul(&Affine::prime_subgroup_generator(), &*secret_key);
+
+    if let Some(address) = keystore.address {
+        let address = address
+            .strip_prefix("0x")
+            .unwrap_or(&address)
+            .to_ascii_lowercase();
+        let address = hex(&address)?;
+        if address[..] != recovery::address(&public_key).map_err(KeystoreError::InvalidKey)?[..] {
+            return Err(KeystoreError::AddressMismatch);
+        }
+    }
+    Ok((public_key, secret_key))
+}
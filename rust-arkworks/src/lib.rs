@@ -1,11 +1,19 @@
+#![cfg_attr(
+    not(test),
+    deny(clippy::panic, clippy::unwrap_used, clippy::expect_used)
+)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 /// This crate provides the PLUME signature scheme.
 ///
 /// See <https://blog.aayushg.com/nullifier> for more information.
-/// 
+///
 /// Find RustCrypto crate as `plume_rustcrypto`.
-
-pub use crate::error::HashToCurveError;
-use crate::hash_to_curve::hash_to_curve;
+pub use crate::error::{EncodingError, HashToCurveError, PlumeVerifyError};
+pub use crate::hash_to_curve::HashToCurveSuite;
+use crate::hash_to_curve::{hash_to_curve, hash_to_curve_with_dst};
 
 /// Re-exports the `GroupAffine` and `SWModelParameters` types from the `ark_ec` crate.
 ///
@@ -17,109 +25,312 @@ pub use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAff
 /// `Rng` provides methods for generating random values.
 pub use ark_std::rand::Rng;
 
-use ark_ec::{AffineCurve, ProjectiveCurve};
-use ark_ff::PrimeField;
+use alloc::{vec, vec::Vec};
+use ark_ff::ToBytes;
+#[cfg(feature = "legacy-ark-serialize")]
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 use ark_std::UniformRand;
-use secp256k1::sec1::Sec1EncodePoint;
-use sha2::digest::Output;
-use sha2::{Digest, Sha256};
-
+/// The strategies mapping the challenge preimage to a scalar; see [`PlumeSignature::sign_with_reduction`].
+pub use challenge::{ChallengeDerivation, ChallengeReduction, CurveDefault};
+use hasher::PlumeHasher;
+use hasher::SpecHasher;
+use zeroize::Zeroizing;
+
+#[cfg(feature = "eth-recovery")]
+pub mod account;
+pub mod audit;
+pub mod babyjubjub;
+pub mod batch;
+pub mod bn254;
+#[cfg(feature = "std")]
+pub mod cache;
+#[doc(hidden)]
+pub mod challenge;
+pub mod commitment;
+pub mod conformance;
+#[cfg(feature = "constant-time")]
+pub mod constant_time;
+pub mod derivation;
+pub mod deterministic;
+pub mod digest;
+pub mod encoding;
+pub mod envelope;
 mod error;
-mod hash_to_curve;
-
-const EXPECT_MSG_DECODE: &str = "the value decoded have been generated by a function which is improbable to output a malformed hexstring (still a place for refactoring)";
-
-/// An `enum` representing the variant of the PLUME protocol.
-pub enum PlumeVersion {
-    V1,
-    V2,
-}
+pub mod formats;
+pub mod hash_to_curve;
+pub mod hasher;
+pub mod k256_interop;
+pub mod keys;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+pub mod linkage;
+pub mod memory;
+pub mod migration;
+#[cfg(feature = "bip39")]
+pub mod mnemonic;
+pub mod multi;
+pub mod offline;
+mod optimizations;
+pub mod p256;
+pub mod pasta;
+#[cfg(feature = "poseidon")]
+pub mod poseidon;
+pub mod prelude;
+pub mod reader;
+#[cfg(feature = "eth-recovery")]
+pub mod recovery;
+pub mod scope;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod signer;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod v1;
+pub mod v2;
+pub mod validate;
+pub mod witness;
+pub mod xonly;
+
+/// The variant of the PLUME protocol; defined in `plume-core`.
+pub use plume_core::PlumeVersion;
 
 /// Converts an affine point on the curve to the byte representation.
 ///
-/// Serializes the affine point to its SEC1 encoding and returns the raw bytes.
+/// Serializes the affine point to its SEC1 compressed encoding and returns the raw bytes. The point at infinity is encoded as the single
+/// byte `0x00`. Curves over prime fields only.
+#[doc(hidden)]
 pub fn affine_to_bytes<P: SWModelParameters>(point: &GroupAffine<P>) -> Vec<u8> {
-    hex::decode(point.to_encoded_point(true))
-        .expect(EXPECT_MSG_DECODE)
-        .to_vec()
+    if point.infinity {
+        return vec![0x00];
+    }
+
+    let width = base_field_width::<P>();
+    let y = le_bytes(&point.y);
+    let tag = 0x02 | (y.first().copied().unwrap_or_default() & 1);
+    [tag]
+        .into_iter()
+        .chain(le_bytes(&point.x).into_iter().take(width).rev())
+        .collect()
+}
+
+/// The width in bytes of the modulus of the base field, which its representation is padded above.
+fn base_field_width<P: SWModelParameters>() -> usize {
+    let modulus = P::BaseField::characteristic();
+    let bits = modulus
+        .iter()
+        .rposition(|limb| *limb != 0)
+        .map_or(0, |i| 64 * (i + 1) - modulus[i].leading_zeros() as usize);
+    (bits + 7) / 8
+}
+
+fn le_bytes<F: ToBytes>(element: &F) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // writing to a `Vec` doesn't fail
+    let _ = ToBytes::write(element, &mut bytes);
+    bytes
 }
 
-fn compute_h<'a, C: ProjectiveCurve, Fq: PrimeField, P: SWModelParameters>(
+/// The base `h = htc(message, pk)` of the nullifier of `message` by `pk`, as the signatures hash it to the curve.
+pub fn compute_h<P: HashToCurveSuite>(
     pk: &GroupAffine<P>,
-    message: &'a [u8],
+    message: &[u8],
 ) -> Result<GroupAffine<P>, HashToCurveError> {
-    //let pk_affine_bytes_vec = affine_to_bytes::<P>(pk);
-    //let m_pk = [message, pk_affine_bytes_vec.as_slice()].concat();
-    //hash_to_curve::try_and_increment::<C>(m_pk.as_slice())
-    hash_to_curve::<Fq, P>(message, pk)
+    hash_to_curve::<P>(message, pk)
+}
+
+/// The nullifier `h^sk` a signature on `message` by `sk` publishes, without signing; e.g. for a wallet to show it, or look it up in a
+/// registry, before the user signs.
+///
+/// It's the nullifier of [`PlumeSignature::sign`] with any version. Fails if the message and the key can't be hashed to the curve.
+pub fn compute_nullifier<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    sk: &SecretKeyMaterial<P>,
+    message: Message,
+) -> Result<Nullifier<P>, HashToCurveError> {
+    let pk = secret_mul(&pp.g_point, sk);
+    Ok(secret_mul(&compute_h::<P>(&pk, message)?, sk))
+}
+
+/// What a signature is bound to besides the message and the key: the tag the message is hashed to the curve with, and the context
+/// hashed into the challenge after the points.
+#[derive(Clone, Copy)]
+pub(crate) struct Domain<'a> {
+    pub(crate) dst: &'a [u8],
+    pub(crate) context: &'a [u8],
 }
 
-fn compute_c_v1<P: SWModelParameters>(
+impl Domain<'static> {
+    /// The plain protocol: the DST of the suite of `P` and no context.
+    pub(crate) fn plain<P: HashToCurveSuite>() -> Self {
+        Self {
+            dst: P::DST,
+            context: &[],
+        }
+    }
+}
+
+fn compute_c_v1<P: SWModelParameters, H: PlumeHasher, R: ChallengeReduction>(
     g_point: &GroupAffine<P>,
     pk: &GroupAffine<P>,
     hashed_to_curve: &GroupAffine<P>,
     nullifier: &GroupAffine<P>,
     r_point: &GroupAffine<P>,
     hashed_to_curve_r: &GroupAffine<P>,
-) -> Output<Sha256> {
-    // Compute c = sha512([g, pk, h, nul, g^r, z])
-    let c_preimage_vec = [
+    framed: bool,
+    context: &[u8],
+    reduction: &R,
+) -> P::ScalarField {
+    // Compute c = H([g, pk, h, nul, g^r, z])
+    let mut elements = vec![
         affine_to_bytes::<P>(g_point),
         affine_to_bytes::<P>(pk),
         affine_to_bytes::<P>(hashed_to_curve),
         affine_to_bytes::<P>(nullifier),
         affine_to_bytes::<P>(r_point),
         affine_to_bytes::<P>(hashed_to_curve_r),
-    ]
-    .concat();
+    ];
+    push_context(&mut elements, context);
+    let c_preimage_vec = plume_core::transcript::join(&elements, framed);
 
-    Sha256::digest(c_preimage_vec.as_slice())
+    reduction.reduce::<P::ScalarField, H>(c_preimage_vec.as_slice())
 }
 
-fn compute_c_v2<P: SWModelParameters>(
+fn compute_c_v2<P: SWModelParameters, H: PlumeHasher, R: ChallengeReduction>(
     nullifier: &GroupAffine<P>,
     r_point: &GroupAffine<P>,
     hashed_to_curve_r: &GroupAffine<P>,
-) -> Output<Sha256> {
-    // Compute c = sha512([nul, g^r, z])
+    framed: bool,
+    context: &[u8],
+    reduction: &R,
+) -> P::ScalarField {
+    // Compute c = H([nul, g^r, z])
     let nul_bytes = affine_to_bytes::<P>(nullifier);
     let g_r_bytes = affine_to_bytes::<P>(r_point);
     let z_bytes = affine_to_bytes::<P>(hashed_to_curve_r);
 
-    let c_preimage_vec = [nul_bytes, g_r_bytes, z_bytes].concat();
+    let mut elements = vec![nul_bytes, g_r_bytes, z_bytes];
+    push_context(&mut elements, context);
+    let c_preimage_vec = plume_core::transcript::join(&elements, framed);
+
+    reduction.reduce::<P::ScalarField, H>(c_preimage_vec.as_slice())
+}
+
+/// Appends the context to the elements of the challenge preimage; an empty one leaves the preimage of the plain protocol.
+///
+/// The points are of a fixed length and the context comes last, so it's unambiguous even in the versions which don't frame the elements.
+fn push_context(elements: &mut Vec<Vec<u8>>, context: &[u8]) {
+    if !context.is_empty() {
+        elements.push(context.to_vec());
+    }
+}
+
+/// Multiplies `point` by a secret scalar, in constant time with the `constant-time` feature; see [`mod@constant_time`].
+#[cfg(feature = "constant-time")]
+fn secret_mul<P: SWModelParameters>(
+    point: &GroupAffine<P>,
+    scalar: &P::ScalarField,
+) -> GroupAffine<P> {
+    constant_time::mul(point, scalar)
+}
+
+/// Multiplies `point` by a secret scalar, wiping the representation it's multiplied by.
+#[cfg(not(feature = "constant-time"))]
+fn secret_mul<P: SWModelParameters>(
+    point: &GroupAffine<P>,
+    scalar: &P::ScalarField,
+) -> GroupAffine<P> {
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::PrimeField;
+
+    let repr = Zeroizing::new(scalar.into_repr());
+    point.mul(*repr).into_affine()
+}
+
+/// The response `r + sk ⋅ c`, in constant time with the `constant-time` feature.
+#[cfg(feature = "constant-time")]
+fn secret_response<P: SWModelParameters>(
+    r: &P::ScalarField,
+    sk: &P::ScalarField,
+    c: &P::ScalarField,
+) -> P::ScalarField {
+    constant_time::response::<P>(r, sk, c)
+}
 
-    Sha256::digest(c_preimage_vec.as_slice())
+/// The response `r + sk ⋅ c`, wiping `sk ⋅ c`.
+#[cfg(not(feature = "constant-time"))]
+fn secret_response<P: SWModelParameters>(
+    r: &P::ScalarField,
+    sk: &P::ScalarField,
+    c: &P::ScalarField,
+) -> P::ScalarField {
+    let sk_c = Zeroizing::new(*sk * c);
+    *r + *sk_c
 }
 
 /// A struct containing parameters for the SW model, including the generator point `g_point`.
-/// This struct implements traits for (de)serialization.
-#[derive(
-    Copy,
-    Clone,
-    ark_serialize_derive::CanonicalSerialize,
-    ark_serialize_derive::CanonicalDeserialize,
+/// The `CanonicalSerialize` and `CanonicalDeserialize` traits are implemented with the `legacy-ark-serialize` feature, and the `serde` ones
+/// with the `serde` feature; see [`mod@serialization`].
+#[derive(Copy, Clone)]
+#[cfg_attr(
+    feature = "legacy-ark-serialize",
+    derive(
+        ark_serialize_derive::CanonicalSerialize,
+        ark_serialize_derive::CanonicalDeserialize,
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(deny_unknown_fields, bound = "P::BaseField: ark_ff::PrimeField")
 )]
 pub struct Parameters<P: SWModelParameters> {
     /// The generator point for the SW model parameters.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "g", with = "crate::serialization::point")
+    )]
     pub g_point: GroupAffine<P>,
 }
 
 /// A struct containing the PLUME signature data
-#[derive(
-    Copy,
-    Clone,
-    ark_serialize_derive::CanonicalSerialize,
-    ark_serialize_derive::CanonicalDeserialize,
+///
+/// The `CanonicalSerialize` and `CanonicalDeserialize` traits are implemented with the `legacy-ark-serialize` feature; note that their output
+/// follows the `ark-serialize` version. Use [`mod@encoding`] for the values which should be readable across versions.
+///
+/// With `serde` the fields are written under the ERC-7524 names: `hashMPKPowR`, `gPowR`, `s`, `c`, and `nullifier`; see
+/// [`mod@serialization`].
+#[derive(Copy, Clone)]
+#[cfg_attr(
+    feature = "legacy-ark-serialize",
+    derive(
+        ark_serialize_derive::CanonicalSerialize,
+        ark_serialize_derive::CanonicalDeserialize,
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(deny_unknown_fields, bound = "P::BaseField: ark_ff::PrimeField")
 )]
 pub struct PlumeSignature<P: SWModelParameters> {
     /// The hash-to-curve output multiplied by the random `r`.  
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "hashMPKPowR", with = "crate::serialization::point")
+    )]
     pub hashed_to_curve_r: GroupAffine<P>,
     /// The randomness `r` represented as the curve point.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "gPowR", with = "crate::serialization::point")
+    )]
     pub r_point: GroupAffine<P>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::field"))]
     pub s: P::ScalarField,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::field"))]
     pub c: P::ScalarField,
     /// The nullifier.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::point"))]
     pub nullifier: GroupAffine<P>,
 }
 
@@ -132,56 +343,121 @@ pub type Message<'a> = &'a [u8];
 pub type PublicKey<P: SWModelParameters> = GroupAffine<P>;
 /// The scalar field element representing the secret key.
 pub type SecretKeyMaterial<P: SWModelParameters> = P::ScalarField;
+/// The nullifier `h^sk`.
+pub type Nullifier<P: SWModelParameters> = GroupAffine<P>;
+/// A signature of the suite of the specification: `secp256k1`, its hash-to-curve suite, and the challenge hashed with
+/// [`hasher::SpecHasher`], which [`PlumeSignature::sign`] and [`PlumeSignature::verify`] use.
+pub type SpecSignature = PlumeSignature<secp256k1::curves::Secp256k1Parameters>;
 
 impl<P: SWModelParameters> PlumeSignature<P> {
     /// Generate the public key and a private key.
     /// # HAZMAT
-    /// No measures yet taken for the [`SecretKeyMaterial`] protection
+    /// [`SecretKeyMaterial`] is a plain scalar, which is `Copy` and isn't wiped; use [`Self::keygen_zeroizing`] for a key held for long.
     pub fn keygen(pp: &Parameters<P>, rng: &mut impl Rng) -> (PublicKey<P>, SecretKeyMaterial<P>) {
-        let secret_key = SecretKeyMaterial::<P>::rand(rng);
-        let public_key = pp.g_point.mul(secret_key).into();
+        let (public_key, secret_key) = Self::keygen_zeroizing(pp, rng);
+        (public_key, *secret_key)
+    }
+
+    /// Generate the public key and a private key which is wiped on drop.
+    ///
+    /// The signing functions take `&secret_key` through the deref. Copies made out of the wrapper aren't tracked, so keep the key in it.
+    pub fn keygen_zeroizing(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+    ) -> (PublicKey<P>, Zeroizing<SecretKeyMaterial<P>>) {
+        let secret_key = Zeroizing::new(SecretKeyMaterial::<P>::rand(rng));
+        let public_key = secret_mul(&pp.g_point, &*secret_key);
         (public_key, secret_key)
     }
+}
 
+impl<P: HashToCurveSuite> PlumeSignature<P> {
     /// Sign a message using the specified `r` value
-    fn sign_with_r(
+    ///
+    /// # HAZMAT
+    /// `r` has to be uniformly random and never reused: two signatures with the same `r` reveal the secret key. It's for test vectors and
+    /// for callers deriving `r` deterministically themselves; use [`Self::sign`] otherwise.
+    pub fn sign_with_r(
+        pp: &Parameters<P>,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        r_scalar: P::ScalarField,
+        version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        Self::sign_with_r_and_hasher::<SpecHasher>(pp, keypair, message, r_scalar, version)
+    }
+
+    /// Sign a message using the specified `r` value, hashing the challenge with `H`; see [`Self::sign_with_r`].
+    pub fn sign_with_r_and_hasher<H: PlumeHasher>(
         pp: &Parameters<P>,
         keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
         message: Message,
         r_scalar: P::ScalarField,
         version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        let r_scalar = Zeroizing::new(r_scalar);
+        Self::sign_with_context::<H, _>(
+            pp,
+            keypair,
+            message,
+            &*r_scalar,
+            version,
+            Domain::plain::<P>(),
+            &CurveDefault,
+        )
+    }
+
+    /// Signs with the message hashed to the curve under the DST of `domain`, and its context hashed into the challenge after the points;
+    /// the context is bound to the signature without changing the nullifier.
+    ///
+    /// The nonce is borrowed, so the caller's [`Zeroizing`] is its only copy; the representations the points are multiplied by are wiped
+    /// here.
+    pub(crate) fn sign_with_context<H: PlumeHasher, R: ChallengeReduction>(
+        pp: &Parameters<P>,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        r_scalar: &P::ScalarField,
+        version: PlumeVersion,
+        domain: Domain,
+        reduction: &R,
     ) -> Result<Self, HashToCurveError> {
         let g_point = pp.g_point;
-        let r_point = g_point.mul(r_scalar).into_affine();
+        let r_point = secret_mul(&g_point, r_scalar);
 
         // Compute h = htc([m, pk])
-        let hashed_to_curve =
-            compute_h::<secp256k1::Projective, secp256k1::fields::Fq, P>(&keypair.0, &message)?;
+        let hashed_to_curve = hash_to_curve_with_dst::<P>(message, keypair.0, domain.dst)?;
 
         // Compute z = h^r
-        let hashed_to_curve_r = hashed_to_curve.mul(r_scalar).into_affine();
+        let hashed_to_curve_r = secret_mul(&hashed_to_curve, r_scalar);
 
         // Compute nul = h^sk
-        let nullifier = hashed_to_curve.mul(*keypair.1).into_affine();
+        let nullifier = secret_mul(&hashed_to_curve, keypair.1);
 
-        // Compute c = sha512([g, pk, h, nul, g^r, z])
-        let c = match version {
-            PlumeVersion::V1 => compute_c_v1::<P>(
+        // Compute c = H([g, pk, h, nul, g^r, z])
+        let c_scalar = match version {
+            PlumeVersion::V1 | PlumeVersion::V1_1 => compute_c_v1::<P, H, R>(
                 &g_point,
                 keypair.0,
                 &hashed_to_curve,
                 &nullifier,
                 &r_point,
                 &hashed_to_curve_r,
+                version.is_framed(),
+                domain.context,
+                reduction,
+            ),
+            // the other versions hash the points of V2; see `PlumeVersion::is_v1`
+            _ => compute_c_v2::<P, H, R>(
+                &nullifier,
+                &r_point,
+                &hashed_to_curve_r,
+                version.is_framed(),
+                domain.context,
+                reduction,
             ),
-            PlumeVersion::V2 => compute_c_v2(&nullifier, &r_point, &hashed_to_curve_r),
         };
-        let c_scalar = P::ScalarField::from_be_bytes_mod_order(c.as_ref());
         // Compute s = r + sk ⋅ c
-        let sk_c = keypair.1.into_repr().into() * c_scalar.into_repr().into();
-        let s = r_scalar.into_repr().into() + sk_c;
-
-        let s_scalar = P::ScalarField::from(s);
+        let s_scalar = secret_response::<P>(r_scalar, keypair.1, &c_scalar);
 
         let signature = PlumeSignature {
             hashed_to_curve_r,
@@ -194,7 +470,25 @@ impl<P: SWModelParameters> PlumeSignature<P> {
     }
 
     /// Sign a message.
-    fn sign(
+    ///
+    /// The signature is made with `version`, which the verifier has to use as well:
+    /// - V1 (and V1_1) hash `g, pk, h, nul, g^r, z` into the challenge; the verifier checks `g^r` and `z` of the signature;
+    /// - V2 (and V2_1) hash only `nul, g^r, z`, which is cheaper to prove in a circuit; [`v2::Signature`] drops `g^r` and `z` since a verifier
+    ///   recomputes them.
+    ///
+    /// The nullifier `h^sk` is the same for all the versions. Fails if the message and the key can't be hashed to the curve.
+    pub fn sign(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        Self::sign_with_hasher::<SpecHasher>(pp, rng, keypair, message, version)
+    }
+
+    /// Sign a message, hashing the challenge with `H`; see [`Self::sign`].
+    pub fn sign_with_hasher<H: PlumeHasher>(
         pp: &Parameters<P>,
         rng: &mut impl Rng,
         keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
@@ -202,13 +496,43 @@ impl<P: SWModelParameters> PlumeSignature<P> {
         version: PlumeVersion,
     ) -> Result<Self, HashToCurveError> {
         // Pick a random r from Fp
-        let r_scalar = P::ScalarField::rand(rng);
+        let r_scalar = Zeroizing::new(P::ScalarField::rand(rng));
+
+        Self::sign_with_context::<H, _>(
+            pp,
+            keypair,
+            message,
+            &*r_scalar,
+            version,
+            Domain::plain::<P>(),
+            &CurveDefault,
+        )
+    }
 
-        Self::sign_with_r(pp, keypair, message, r_scalar, version)
+    /// Sign a message, mapping the challenge to a scalar by `reduction` instead of [`CurveDefault`]; see [`Self::sign`] and
+    /// [`mod@challenge`].
+    pub fn sign_with_reduction<H: PlumeHasher, R: ChallengeReduction>(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        version: PlumeVersion,
+        reduction: &R,
+    ) -> Result<Self, HashToCurveError> {
+        let r_scalar = Zeroizing::new(P::ScalarField::rand(rng));
+        Self::sign_with_context::<H, R>(
+            pp,
+            keypair,
+            message,
+            &*r_scalar,
+            version,
+            Domain::plain::<P>(),
+            reduction,
+        )
     }
 
     /// Verifies a PLUME signature.
-    /// Returns `true` if the signature is valid, `false` otherwise.
+    /// Returns `Ok(())` if the signature is valid, and otherwise the [`PlumeVerifyError`] of the check which failed.
     ///
     /// Computes the curve points and scalars needed for verification from the
     /// signature parameters. Then performs the verification steps:
@@ -216,62 +540,219 @@ impl<P: SWModelParameters> PlumeSignature<P> {
     /// - Confirm h^s * nul^-c = z
     /// - Confirm c = c'
     ///
-    /// Rejects if any check fails.
-    fn verify_non_zk(
-        self,
+    /// The checks run in this order, and the first to fail is returned, after the key and the points of the signature are
+    /// [validated](crate::validate). "Non-ZK" since the verifier learns `pk` and the message; in a zero-knowledge setting these checks run in
+    /// a circuit instead.
+    pub fn verify_non_zk(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.verify_non_zk_with_hasher::<SpecHasher>(pp, pk, message, version)
+    }
+
+    /// Verifies the signature on `message` by `pk`, made with `version`; the same as [`Self::verify_non_zk`].
+    ///
+    /// An invalid signature is the `Err` of the check it fails; [`PlumeVerifyError::HashToCurve`] means the message and the key couldn't
+    /// be hashed to the curve.
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.verify_non_zk(pp, pk, message, version)
+    }
+
+    /// Verifies a PLUME signature which challenge is hashed with `H`; see [`Self::verify_non_zk`].
+    pub fn verify_non_zk_with_hasher<H: PlumeHasher>(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.verify_with_context::<H, _>(
+            pp,
+            pk,
+            message,
+            version,
+            Domain::plain::<P>(),
+            &CurveDefault,
+        )
+    }
+
+    /// Verifies a signature made with [`Self::sign_with_reduction`] and `reduction`; see [`Self::verify_non_zk`].
+    pub fn verify_non_zk_with_reduction<H: PlumeHasher, R: ChallengeReduction>(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+        reduction: &R,
+    ) -> Result<(), PlumeVerifyError> {
+        self.verify_with_context::<H, R>(pp, pk, message, version, Domain::plain::<P>(), reduction)
+    }
+
+    /// Checks the group equations of the signature on `message` by `pk`: `g^s ⋅ pk^{-c} = g^r` and `h^s ⋅ nul^{-c} = z`, after
+    /// [validating](crate::validate) the key and the points of the signature.
+    ///
+    /// It's the algebraic half of [`Self::verify_non_zk`], for a circuit which recomputes `c` itself; the signature is valid only once
+    /// [`Self::check_challenge`] passes as well, in or out of the circuit. The `c` of the signature is taken as it is.
+    pub fn check_ec_equations(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+    ) -> Result<(), PlumeVerifyError> {
+        validate::public_key(pk).map_err(PlumeVerifyError::InvalidPublicKey)?;
+        validate::signature(self).map_err(PlumeVerifyError::InvalidSignature)?;
+        let hashed_to_curve = hash_to_curve_with_dst::<P>(message, pk, Domain::plain::<P>().dst)?;
+        self.check_equations(pp, pk, &hashed_to_curve)
+    }
+
+    /// Checks the `c` of the signature on `message` by `pk` is the challenge of `version`: the other half of [`Self::verify`] besides
+    /// [`Self::check_ec_equations`]. Fails with [`PlumeVerifyError::ChallengeMismatch`] otherwise.
+    ///
+    /// It's [`Self::check_challenge_with`] of the [`SpecHasher`] and [`CurveDefault`], the challenge of [`Self::sign`].
+    pub fn check_challenge(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.check_challenge_with::<SpecHasher, _>(pp, pk, message, version, &CurveDefault)
+    }
+
+    /// [`Self::check_challenge`] of a challenge hashed with `H` and mapped to a scalar by `reduction`, as [`Self::sign_with_reduction`]
+    /// makes it; the half of [`Self::verify_non_zk_with_reduction`] besides [`Self::check_ec_equations`].
+    pub fn check_challenge_with<H: PlumeHasher, R: ChallengeReduction>(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+        reduction: &R,
+    ) -> Result<(), PlumeVerifyError> {
+        let domain = Domain::plain::<P>();
+        let hashed_to_curve = hash_to_curve_with_dst::<P>(message, pk, domain.dst)?;
+        let c_scalar = self.recompute_challenge::<H, R>(
+            pp,
+            pk,
+            &hashed_to_curve,
+            version,
+            domain.context,
+            reduction,
+        );
+        if c_scalar != self.c {
+            return Err(PlumeVerifyError::ChallengeMismatch);
+        }
+        Ok(())
+    }
+
+    /// Verifies a signature made with [`Self::sign_with_context`].
+    pub(crate) fn verify_with_context<H: PlumeHasher, R: ChallengeReduction>(
+        &self,
         pp: &Parameters<P>,
         pk: &PublicKey<P>,
         message: Message,
         version: PlumeVersion,
-    ) -> Result<bool, HashToCurveError> {
+        domain: Domain,
+        reduction: &R,
+    ) -> Result<(), PlumeVerifyError> {
+        // Reject points off the curve, out of the subgroup, or the identity
+        validate::public_key(pk).map_err(PlumeVerifyError::InvalidPublicKey)?;
+        validate::signature(self).map_err(PlumeVerifyError::InvalidSignature)?;
+
         // Compute h = htc([m, pk])
-        let hashed_to_curve =
-            compute_h::<secp256k1::Projective, secp256k1::fields::Fq, P>(pk, message)?;
-
-        // TODO [replace SHA-512](https://github.com/plume-sig/zk-nullifier-sig/issues/39#issuecomment-1732497672)
-        // Compute c' = sha512([g, pk, h, nul, g^r, z]) for v1
-        //         c' = sha512([nul, g^r, z]) for v2
-        let c = match version {
-            PlumeVersion::V1 => compute_c_v1::<P>(
+        let hashed_to_curve = hash_to_curve_with_dst::<P>(message, pk, domain.dst)?;
+
+        let c_scalar = self.recompute_challenge::<H, R>(
+            pp,
+            pk,
+            &hashed_to_curve,
+            version,
+            domain.context,
+            reduction,
+        );
+
+        self.check_equations(pp, pk, &hashed_to_curve)?;
+
+        // Reject if c != c'
+        if c_scalar != self.c {
+            return Err(PlumeVerifyError::ChallengeMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Computes `c'` from the points of the signature and `h`.
+    fn recompute_challenge<H: PlumeHasher, R: ChallengeReduction>(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        hashed_to_curve: &GroupAffine<P>,
+        version: PlumeVersion,
+        context: &[u8],
+        reduction: &R,
+    ) -> P::ScalarField {
+        // Compute c' = H([g, pk, h, nul, g^r, z]) for v1
+        //         c' = H([nul, g^r, z]) for v2
+        match version {
+            PlumeVersion::V1 | PlumeVersion::V1_1 => compute_c_v1::<P, H, R>(
                 &pp.g_point,
                 pk,
-                &hashed_to_curve,
+                hashed_to_curve,
                 &self.nullifier,
                 &self.r_point,
                 &self.hashed_to_curve_r,
+                version.is_framed(),
+                context,
+                reduction,
             ),
-            PlumeVersion::V2 => {
-                compute_c_v2(&self.nullifier, &self.r_point, &self.hashed_to_curve_r)
-            }
-        };
-        let c_scalar = P::ScalarField::from_be_bytes_mod_order(c.as_ref());
+            // the other versions hash the points of V2; see `PlumeVersion::is_v1`
+            _ => compute_c_v2::<P, H, R>(
+                &self.nullifier,
+                &self.r_point,
+                &self.hashed_to_curve_r,
+                version.is_framed(),
+                context,
+                reduction,
+            ),
+        }
+    }
 
+    /// Checks the group equations for `h`, in the order of [`Self::verify_non_zk`].
+    fn check_equations(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        hashed_to_curve: &GroupAffine<P>,
+    ) -> Result<(), PlumeVerifyError> {
         // Reject if g^s ⋅ pk^{-c} != g^r
-        let g_s = pp.g_point.mul(self.s);
-        let pk_c = pk.mul(self.c);
-        let g_s_pk_c = g_s - pk_c;
+        let g_s_pk_c = optimizations::key_double_mul(&pp.g_point, self.s, pk, -self.c);
 
         if self.r_point != g_s_pk_c {
-            return Ok(false);
+            return Err(PlumeVerifyError::RPointMismatch);
         }
 
         // Reject if h^s ⋅ nul^{-c} = z
-        let h_s = hashed_to_curve.mul(self.s);
-        let nul_c = self.nullifier.mul(self.c);
-        let h_s_nul_c = h_s - nul_c;
+        let h_s_nul_c =
+            optimizations::double_mul(hashed_to_curve, self.s, &self.nullifier, -self.c);
 
         if self.hashed_to_curve_r != h_s_nul_c {
-            return Ok(false);
+            return Err(PlumeVerifyError::HashedPointMismatch);
         }
 
-        // Reject if c != c'
-        if c_scalar != self.c {
-            return Ok(false);
-        }
-
-        Ok(true)
+        Ok(())
     }
 }
 
+#[cfg(test)]
+mod reference;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,122 @@
+//! Voluntary linkage of nullifiers produced for different messages.
+//!
+//! By design, PLUME nullifiers for different messages (scopes) can't be linked. A signer can still show that two nullifiers come from the same key
+//! by proving they share one discrete logarithm with respect to their bases `h = htc(message, pk)`. That's a Chaum-Pedersen DLEQ proof.
+//! It reveals nothing about the secret key.
+//!
+//! The verifier has to know the bases are bound to the key. A proof over arbitrary bases is worthless, since anyone can pick
+//! `h_b = nullifier_b^(1/sk)`. [`SameSignerProof::verify`] recomputes the bases from the public key. [`SameSignerProof::verify_with_bases`]
+//! is for bases which are established in another way, e.g. as public outputs of the nullifier circuits.
+//!
+//! Neither checks the signatures themselves; only the nullifiers are linked.
+
+use crate::challenge::ChallengeDerivation;
+use crate::{
+    affine_to_bytes, compute_h, secret_mul, secret_response, validate, HashToCurveError,
+    HashToCurveSuite, Message, PlumeSignature, PlumeVerifyError, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+const DOMAIN_TAG: &[u8] = b"PLUME_SAME_SIGNER_DLEQ";
+
+/// A proof that two nullifiers were produced by the same secret key.
+#[derive(Copy, Clone)]
+pub struct SameSignerProof<P: SWModelParameters> {
+    /// The challenge.
+    pub c: P::ScalarField,
+    /// The response `k + sk ⋅ c` for the nonce `k`.
+    pub s: P::ScalarField,
+}
+
+impl<P: HashToCurveSuite> SameSignerProof<P> {
+    /// Proves that the nullifiers of `signature_a` and `signature_b` come from the secret key of `keypair`.
+    pub fn prove(
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        (message_a, signature_a): (Message, &PlumeSignature<P>),
+        (message_b, signature_b): (Message, &PlumeSignature<P>),
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Result<Self, HashToCurveError> {
+        let hashed_to_curve_a = compute_h::<P>(keypair.0, message_a)?;
+        let hashed_to_curve_b = compute_h::<P>(keypair.0, message_b)?;
+
+        let k = Zeroizing::new(P::ScalarField::rand(rng));
+        let c = challenge::<P>([
+            &hashed_to_curve_a,
+            &signature_a.nullifier,
+            &hashed_to_curve_b,
+            &signature_b.nullifier,
+            &secret_mul(&hashed_to_curve_a, &*k),
+            &secret_mul(&hashed_to_curve_b, &*k),
+        ]);
+
+        Ok(Self {
+            c,
+            s: secret_response::<P>(&*k, keypair.1, &c),
+        })
+    }
+
+    /// Verifies the proof for the signatures of `message_a` and `message_b` by the `pk` owner, computing the bases from them.
+    pub fn verify(
+        &self,
+        pk: &PublicKey<P>,
+        (message_a, signature_a): (Message, &PlumeSignature<P>),
+        (message_b, signature_b): (Message, &PlumeSignature<P>),
+    ) -> Result<(), PlumeVerifyError> {
+        validate::public_key(pk).map_err(PlumeVerifyError::InvalidPublicKey)?;
+        let hashed_to_curve_a = compute_h::<P>(pk, message_a)?;
+        let hashed_to_curve_b = compute_h::<P>(pk, message_b)?;
+
+        self.verify_with_bases(
+            (&hashed_to_curve_a, &signature_a.nullifier),
+            (&hashed_to_curve_b, &signature_b.nullifier),
+        )
+    }
+
+    /// Verifies the proof for the pairs of a base and its nullifier. The caller is responsible for the bases being bound to the key.
+    ///
+    /// A nullifier which is the identity or outside the prime order subgroup is an [`PlumeVerifyError::InvalidSignature`], and a proof
+    /// which doesn't link the nullifiers a [`PlumeVerifyError::ChallengeMismatch`].
+    pub fn verify_with_bases(
+        &self,
+        (hashed_to_curve_a, nullifier_a): (&GroupAffine<P>, &GroupAffine<P>),
+        (hashed_to_curve_b, nullifier_b): (&GroupAffine<P>, &GroupAffine<P>),
+    ) -> Result<(), PlumeVerifyError> {
+        validate::point(nullifier_a).map_err(PlumeVerifyError::InvalidSignature)?;
+        validate::point(nullifier_b).map_err(PlumeVerifyError::InvalidSignature)?;
+
+        // Recover the commitments h^k = h^s ⋅ nul^{-c}
+        let commitment_a = (hashed_to_curve_a.mul(self.s) - nullifier_a.mul(self.c)).into_affine();
+        let commitment_b = (hashed_to_curve_b.mul(self.s) - nullifier_b.mul(self.c)).into_affine();
+
+        let expected = challenge::<P>([
+            hashed_to_curve_a,
+            nullifier_a,
+            hashed_to_curve_b,
+            nullifier_b,
+            &commitment_a,
+            &commitment_b,
+        ]);
+        if self.c == expected {
+            Ok(())
+        } else {
+            Err(PlumeVerifyError::ChallengeMismatch)
+        }
+    }
+}
+
+fn challenge<P: SWModelParameters>(points: [&GroupAffine<P>; 6]) -> P::ScalarField {
+    let preimage = [DOMAIN_TAG.to_vec()]
+        .into_iter()
+        .chain(points.iter().map(|point| affine_to_bytes::<P>(point)))
+        .collect::<Vec<_>>()
+        .concat();
+
+    ChallengeDerivation::for_field::<P::ScalarField>()
+        .derive::<P::ScalarField, Sha256>(preimage.as_slice())
+}
@@ -0,0 +1,51 @@
+//! Estimates of the heap usage of the major operations.
+//!
+//! The operations allocate only small temporaries: the encodings of the points and the challenge preimage. [`estimated_heap_usage`] bounds
+//! the peak of what's live at once, for sizing constrained (e.g. WASM) environments. `tests/heap.rs` checks the bound with a counting
+//! allocator.
+//!
+//! The peak of a sign or a verify on `secp256k1` used to be over 8 KiB; it's now within the bound, by
+//! - reading the coordinates of the hashed point straight from their bytes, where a `BufReader` took an 8 KiB buffer per coordinate;
+//! - passing `msg || pk` to `expand_message` in pieces, where it was concatenated and cloned, two copies of the message;
+//! - writing the challenge preimage into one buffer of its final size.
+
+use crate::PlumeVersion;
+use ark_ec::models::SWModelParameters;
+
+/// An allowance for the temporaries besides the challenge preimage: the field representations the points are encoded from, the hash output,
+/// and the scalar reduction.
+const TEMPORARIES: usize = 1024;
+
+/// An operation of the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Hashing the message and the key to the curve.
+    HashToCurve,
+    /// Signing a message.
+    Sign,
+    /// Verifying a signature.
+    Verify,
+}
+
+/// An upper bound of the peak heap usage in bytes of `operation` with `version` on the curve `P`.
+///
+/// It doesn't depend on the message length, which is never copied.
+pub fn estimated_heap_usage<P: SWModelParameters>(
+    operation: Operation,
+    version: PlumeVersion,
+) -> usize {
+    let point_len = 1 + crate::base_field_width::<P>();
+    match operation {
+        Operation::HashToCurve => point_len + TEMPORARIES,
+        Operation::Sign | Operation::Verify => {
+            let points = match version {
+                PlumeVersion::V2 | PlumeVersion::V2_1 => 3,
+                // the bound of V1 for the versions this doesn't know of
+                _ => 6,
+            };
+            let prefix_len = if version.is_framed() { 8 } else { 0 };
+            // the encoded points and the preimage joined from them are live at once
+            2 * points * (prefix_len + point_len) + TEMPORARIES
+        }
+    }
+}
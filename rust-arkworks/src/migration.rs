@@ -0,0 +1,151 @@
+//! Consensual linkage of nullifiers across a key migration, possibly to another curve.
+//!
+//! A user moving to a new key (e.g. from `secp256k1` to a P-256 passkey) gets new nullifiers for the same scopes. [`KeyMigration`] links
+//! the old and the new nullifier for one scope, once: it proves that the same party knows both secret keys and that each nullifier is
+//! `h^sk` for its key. That's a Chaum-Pedersen DLEQ proof on each curve, made non-interactive with one challenge over the transcript of both,
+//! so neither half can be reused with another.
+//!
+//! The curves are generic; each hashes the scope to itself by its own [`HashToCurveSuite`].
+
+use crate::challenge::ChallengeDerivation;
+use crate::{
+    affine_to_bytes, compute_h, optimizations, scope, HashToCurveError, HashToCurveSuite, Message,
+    PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_std::{rand::Rng, UniformRand};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+const MIGRATION_TAG: &[u8] = b"PLUME/key-migration";
+
+/// The link of the nullifiers of the old key on `P` and the new key on `Q` for a scope.
+#[derive(Copy, Clone)]
+pub struct KeyMigration<P: SWModelParameters, Q: SWModelParameters> {
+    pub old_pk: PublicKey<P>,
+    /// The nullifier of the old key for the scope.
+    pub old_nullifier: GroupAffine<P>,
+    pub new_pk: PublicKey<Q>,
+    /// The nullifier of the new key for the scope.
+    pub new_nullifier: GroupAffine<Q>,
+    /// The shared challenge, reduced to the scalar field of `P`.
+    pub c_old: P::ScalarField,
+    /// The shared challenge, reduced to the scalar field of `Q`.
+    pub c_new: Q::ScalarField,
+    /// The response `k_old + sk_old ⋅ c_old`.
+    pub s_old: P::ScalarField,
+    /// The response `k_new + sk_new ⋅ c_new`.
+    pub s_new: Q::ScalarField,
+}
+
+impl<P: HashToCurveSuite, Q: HashToCurveSuite> KeyMigration<P, Q> {
+    /// Links the nullifiers of the `old` and the `new` key for `scope`.
+    pub fn prove(
+        g_old: &GroupAffine<P>,
+        old: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        g_new: &GroupAffine<Q>,
+        new: (&PublicKey<Q>, &SecretKeyMaterial<Q>),
+        scope: Message,
+        rng: &mut impl Rng,
+    ) -> Result<Self, HashToCurveError> {
+        let h_old = compute_h::<P>(old.0, scope)?;
+        let h_new = compute_h::<Q>(new.0, scope)?;
+        let old_nullifier = h_old.mul(*old.1).into_affine();
+        let new_nullifier = h_new.mul(*new.1).into_affine();
+
+        let k_old = Zeroizing::new(P::ScalarField::rand(rng));
+        let k_new = Zeroizing::new(Q::ScalarField::rand(rng));
+        let (c_old, c_new) = challenge::<P, Q>(
+            scope,
+            [
+                old.0,
+                &old_nullifier,
+                &g_old.mul(*k_old).into_affine(),
+                &h_old.mul(*k_old).into_affine(),
+            ],
+            [
+                new.0,
+                &new_nullifier,
+                &g_new.mul(*k_new).into_affine(),
+                &h_new.mul(*k_new).into_affine(),
+            ],
+        );
+
+        Ok(Self {
+            old_pk: *old.0,
+            old_nullifier,
+            new_pk: *new.0,
+            new_nullifier,
+            c_old,
+            c_new,
+            s_old: *k_old + *Zeroizing::new(c_old * old.1),
+            s_new: *k_new + *Zeroizing::new(c_new * new.1),
+        })
+    }
+
+    /// Verifies the link for `scope`.
+    pub fn verify(
+        &self,
+        g_old: &GroupAffine<P>,
+        g_new: &GroupAffine<Q>,
+        scope: Message,
+    ) -> Result<bool, HashToCurveError> {
+        let h_old = compute_h::<P>(&self.old_pk, scope)?;
+        let h_new = compute_h::<Q>(&self.new_pk, scope)?;
+
+        // Recover the commitments g^k = g^s ⋅ pk^{-c} and h^k = h^s ⋅ nul^{-c} on each curve
+        let old_commitments = [
+            optimizations::double_mul(g_old, self.s_old, &self.old_pk, -self.c_old),
+            optimizations::double_mul(&h_old, self.s_old, &self.old_nullifier, -self.c_old),
+        ];
+        let new_commitments = [
+            optimizations::double_mul(g_new, self.s_new, &self.new_pk, -self.c_new),
+            optimizations::double_mul(&h_new, self.s_new, &self.new_nullifier, -self.c_new),
+        ];
+
+        Ok((self.c_old, self.c_new)
+            == challenge::<P, Q>(
+                scope,
+                [
+                    &self.old_pk,
+                    &self.old_nullifier,
+                    &old_commitments[0],
+                    &old_commitments[1],
+                ],
+                [
+                    &self.new_pk,
+                    &self.new_nullifier,
+                    &new_commitments[0],
+                    &new_commitments[1],
+                ],
+            ))
+    }
+}
+
+/// Derives the challenge for both curves from one transcript of the scope, the keys, the nullifiers and the commitments.
+fn challenge<P: SWModelParameters, Q: SWModelParameters>(
+    scope: Message,
+    old: [&GroupAffine<P>; 4],
+    new: [&GroupAffine<Q>; 4],
+) -> (P::ScalarField, Q::ScalarField) {
+    let points = old
+        .iter()
+        .map(|point| affine_to_bytes::<P>(point))
+        .chain(new.iter().map(|point| affine_to_bytes::<Q>(point)))
+        .collect::<Vec<_>>();
+    let preimage = scope::framed(
+        &[MIGRATION_TAG, scope]
+            .into_iter()
+            .chain(points.iter().map(Vec::as_slice))
+            .collect::<Vec<_>>(),
+    );
+
+    (
+        ChallengeDerivation::for_field::<P::ScalarField>()
+            .derive::<P::ScalarField, Sha256>(&preimage),
+        ChallengeDerivation::for_field::<Q::ScalarField>()
+            .derive::<Q::ScalarField, Sha256>(&preimage),
+    )
+}
@@ -0,0 +1,98 @@
+//! Nullifier keys from a BIP-39 mnemonic and passphrase, so a user backs up words rather than a scalar.
+//!
+//! A nullifier key has to be recoverable for as long as its nullifiers are in use: a lost key can't sign for its place in an anonymity set
+//! again. The mnemonic's seed is the master key of [`mod@crate::derivation`], and [`derive_plume_key`] takes it down the path of
+//! [`DerivationPath::plume`](derivation::DerivationPath::plume), so the same words and passphrase give the same key in every BIP-39
+//! wallet. The mnemonics are English; the mnemonic and the passphrase are normalized to NFKD before hashing, as BIP-39 requires.
+
+use crate::derivation::{self, DerivationError, ExtendedSecretKey};
+use crate::{PublicKey, SecretKeyMaterial};
+use alloc::string::{String, ToString};
+use bip39::{Language, Mnemonic};
+use core::fmt;
+use rand_core::{CryptoRng, RngCore};
+use secp256k1::curves::Secp256k1Parameters;
+use zeroize::Zeroizing;
+
+/// Why a mnemonic couldn't be made or turned into a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MnemonicError {
+    /// The number of words isn't 12, 15, 18, 21 or 24.
+    InvalidWordCount(usize),
+    /// The mnemonic has a word off the English list, or its checksum doesn't match.
+    InvalidMnemonic,
+    /// The key couldn't be derived from the seed.
+    Derivation(DerivationError),
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidWordCount(count) => write!(f, "a mnemonic can't have {count} words"),
+            Self::InvalidMnemonic => write!(f, "the mnemonic is invalid"),
+            Self::Derivation(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl core::error::Error for MnemonicError {}
+
+impl From<DerivationError> for MnemonicError {
+    fn from(e: DerivationError) -> Self {
+        Self::Derivation(e)
+    }
+}
+
+/// A new mnemonic of `word_count` words, of entropy drawn from `rng`.
+pub fn generate_mnemonic<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    word_count: usize,
+) -> Result<Zeroizing<String>, MnemonicError> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err(MnemonicError::InvalidWordCount(word_count));
+    }
+    // each word is 11 bits, of which one in 33 is the checksum
+    let mut entropy = Zeroizing::new([0u8; 32]);
+    let entropy = &mut entropy[..word_count * 4 / 3];
+    rng.fill_bytes(entropy);
+    let mnemonic = Mnemonic::from_entropy_in(Language::English, entropy)
+        .map_err(|_| MnemonicError::InvalidWordCount(word_count))?;
+    Ok(Zeroizing::new(mnemonic.to_string()))
+}
+
+/// The 64 byte seed of `mnemonic` and `passphrase`, wiped on drop; the passphrase is empty when there's none.
+pub fn seed_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<[u8; 64]>, MnemonicError> {
+    let mnemonic = Mnemonic::parse_in(Language::English, mnemonic).map_err(|e| match e {
+        bip39::Error::BadWordCount(count) => MnemonicError::InvalidWordCount(count),
+        _ => MnemonicError::InvalidMnemonic,
+    })?;
+    Ok(Zeroizing::new(mnemonic.to_seed(passphrase)))
+}
+
+impl ExtendedSecretKey {
+    /// The master key of the seed of `mnemonic` and `passphrase`, as [`seed_from_mnemonic`].
+    pub fn from_mnemonic(mnemonic: &str, passphrase: &str) -> Result<Self, MnemonicError> {
+        let seed = seed_from_mnemonic(mnemonic, passphrase)?;
+        Ok(Self::from_seed(&*seed)?)
+    }
+}
+
+/// The nullifier key of `account` under `mnemonic` and `passphrase`, at
+/// [`DerivationPath::plume`](derivation::DerivationPath::plume); the secret key is wiped on drop.
+pub fn derive_plume_key(
+    mnemonic: &str,
+    passphrase: &str,
+    account: u32,
+) -> Result<
+    (
+        PublicKey<Secp256k1Parameters>,
+        Zeroizing<SecretKeyMaterial<Secp256k1Parameters>>,
+    ),
+    MnemonicError,
+> {
+    let seed = seed_from_mnemonic(mnemonic, passphrase)?;
+    Ok(derivation::derive_plume_key(&*seed, account)?)
+}
@@ -0,0 +1,169 @@
+//! One signature yielding the nullifiers of several messages at once, e.g. to register in the nullifier sets of several epochs or
+//! applications.
+//!
+//! The bases are `h_i = htc(m_i, pk)` and the nullifiers `nul_i = h_i^sk`, the ones of plain signatures on the messages. A
+//! [`MultiNullifierSignature`] proves that they all share the discrete logarithm of `pk` with a single batched DLEQ proof: the pairs are
+//! combined into `H = Σ e_i ⋅ h_i` and `N = Σ e_i ⋅ nul_i` by weights `e_i` hashed from the whole statement, and a Chaum-Pedersen proof
+//! shows that `(g, pk)` and `(H, N)` share the logarithm. A nullifier other than `h_i^sk` gives `N ≠ H^sk` but for weights the prover
+//! can't steer, so the proof is sound up to the hash. Whatever the number of messages, the proof is the two scalars `c` and `s` next to the
+//! nullifiers, where independent signatures carry five elements each.
+//!
+//! The challenge is hashed under the tag `PLUME/multi-nullifier`, so the proof is never a PLUME signature of any version, nor the other way
+//! around.
+
+use crate::challenge::ChallengeDerivation;
+use crate::{
+    affine_to_bytes, compute_h, optimizations, scope, secret_mul, secret_response, validate,
+    HashToCurveError, HashToCurveSuite, Message, Nullifier, Parameters, PublicKey,
+    SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ec::models::SWModelParameters;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::ProjectiveCurve;
+use ark_ff::PrimeField;
+use ark_std::{rand::Rng, UniformRand};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const MULTI_NULLIFIER_TAG: &[u8] = b"PLUME/multi-nullifier";
+const MULTI_WEIGHT_TAG: &[u8] = b"PLUME/multi-nullifier-weight";
+
+/// The nullifiers of several messages by one key, with the batched proof that they're all of the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiNullifierSignature<P: SWModelParameters> {
+    /// The nullifier of each message, in the order of the messages.
+    pub nullifiers: Vec<Nullifier<P>>,
+    /// The challenge.
+    pub c: P::ScalarField,
+    /// The response `r + sk ⋅ c` for the nonce `r`.
+    pub s: P::ScalarField,
+}
+
+impl<P: HashToCurveSuite> MultiNullifierSignature<P> {
+    /// Signs `messages` at once; the nullifiers are those of [`PlumeSignature::sign`](crate::PlumeSignature::sign) on each message.
+    ///
+    /// Fails if a message and the key can't be hashed to the curve. A signature of no messages never verifies.
+    pub fn sign(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        messages: &[Message],
+    ) -> Result<Self, HashToCurveError> {
+        let bases = bases::<P>(keypair.0, messages)?;
+        let nullifiers: Vec<_> = bases.iter().map(|h| secret_mul(h, keypair.1)).collect();
+
+        let statement = statement_digest::<P>(&pp.g_point, keypair.0, &bases, &nullifiers);
+        let base = combine::<P>(&bases, &weights::<P>(&statement, bases.len()));
+
+        let r_scalar = Zeroizing::new(P::ScalarField::rand(rng));
+        let c = challenge::<P>(
+            &statement,
+            &secret_mul(&pp.g_point, &*r_scalar),
+            &secret_mul(&base, &*r_scalar),
+        );
+        Ok(Self {
+            nullifiers,
+            c,
+            s: secret_response::<P>(&*r_scalar, keypair.1, &c),
+        })
+    }
+
+    /// Verifies the signature of `messages` by `pk`: `Ok(true)` if every nullifier is the one of its message.
+    ///
+    /// `Ok(false)` for a count of nullifiers other than that of the messages, no messages, or a key or nullifier which isn't a valid point;
+    /// an `Err` if a message and the key can't be hashed to the curve.
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        messages: &[Message],
+    ) -> Result<bool, HashToCurveError> {
+        if messages.is_empty()
+            || self.nullifiers.len() != messages.len()
+            || validate::public_key(pk).is_err()
+            || self.nullifiers.iter().any(|n| validate::point(n).is_err())
+        {
+            return Ok(false);
+        }
+        let bases = bases::<P>(pk, messages)?;
+
+        let statement = statement_digest::<P>(&pp.g_point, pk, &bases, &self.nullifiers);
+        let weights = weights::<P>(&statement, bases.len());
+        let base = combine::<P>(&bases, &weights);
+        let nullifier = combine::<P>(&self.nullifiers, &weights);
+
+        // Recover the commitments g^r = g^s ⋅ pk^{-c} and H^r = H^s ⋅ N^{-c}
+        let r_point = optimizations::key_double_mul(&pp.g_point, self.s, pk, -self.c);
+        let base_r = optimizations::double_mul(&base, self.s, &nullifier, -self.c);
+
+        Ok(self.c == challenge::<P>(&statement, &r_point, &base_r))
+    }
+}
+
+fn bases<P: HashToCurveSuite>(
+    pk: &PublicKey<P>,
+    messages: &[Message],
+) -> Result<Vec<GroupAffine<P>>, HashToCurveError> {
+    messages
+        .iter()
+        .map(|message| compute_h::<P>(pk, message))
+        .collect()
+}
+
+/// The SHA-256 of the tag, `g`, `pk`, and each base followed by its nullifier, framed.
+fn statement_digest<P: SWModelParameters>(
+    g_point: &GroupAffine<P>,
+    pk: &PublicKey<P>,
+    bases: &[GroupAffine<P>],
+    nullifiers: &[Nullifier<P>],
+) -> [u8; 32] {
+    let points: Vec<Vec<u8>> = [g_point, pk]
+        .into_iter()
+        .chain(bases.iter().zip(nullifiers).flat_map(|(h, nul)| [h, nul]))
+        .map(affine_to_bytes::<P>)
+        .collect();
+    let fields: Vec<&[u8]> = [MULTI_NULLIFIER_TAG]
+        .into_iter()
+        .chain(points.iter().map(Vec::as_slice))
+        .collect();
+    Sha256::digest(scope::framed(&fields)).into()
+}
+
+fn weights<P: SWModelParameters>(statement: &[u8; 32], len: usize) -> Vec<P::ScalarField> {
+    (0..len as u64)
+        .map(|index| {
+            ChallengeDerivation::for_field::<P::ScalarField>().derive::<P::ScalarField, Sha256>(
+                &scope::framed(&[MULTI_WEIGHT_TAG, &statement[..], &index.to_be_bytes()[..]]),
+            )
+        })
+        .collect()
+}
+
+/// `Σ e_i ⋅ p_i` of the points `p_i` and the weights `e_i`.
+fn combine<P: SWModelParameters>(
+    points: &[GroupAffine<P>],
+    weights: &[P::ScalarField],
+) -> GroupAffine<P> {
+    let weights = weights
+        .iter()
+        .map(PrimeField::into_repr)
+        .collect::<Vec<_>>();
+    VariableBaseMSM::multi_scalar_mul(points, &weights).into_affine()
+}
+
+fn challenge<P: SWModelParameters>(
+    statement: &[u8; 32],
+    r_point: &GroupAffine<P>,
+    base_r: &GroupAffine<P>,
+) -> P::ScalarField {
+    ChallengeDerivation::for_field::<P::ScalarField>().derive::<P::ScalarField, Sha256>(
+        &scope::framed(&[
+            MULTI_NULLIFIER_TAG,
+            &statement[..],
+            &affine_to_bytes::<P>(r_point),
+            &affine_to_bytes::<P>(base_r),
+        ]),
+    )
+}
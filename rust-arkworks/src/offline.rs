@@ -0,0 +1,537 @@
+//! Requests and responses for signing on an air-gapped machine.
+//!
+//! The online machine writes a [`SigningRequest`]: the message, the scope it's under, the version, and the hash of the [`Parameters`] the
+//! signature is for. The offline machine checks the request against its own parameters, signs, and writes a [`SigningResponse`] bound to
+//! the request by its hash; back online, [`SigningResponse::verify`] checks it against the request.
+//!
+//! Both are deterministic CBOR maps with small unsigned integer keys in ascending order, so a value has a single encoding, and decoding
+//! rejects anything else. To get through a camera they're split by [`to_parts`] into QR-friendly parts in the style of Uniform Resources:
+//! `UR:<TYPE>/<SEQ>-<TOTAL>/<CHECKSUM>/<FRAGMENT>`, all in the QR alphanumeric alphabet. The fragment is uppercase hex rather than the
+//! bytewords of Uniform Resources, and the checksum is the first 4 bytes of the SHA-256 of the whole payload.
+//!
+//! | key | request                         | response                                          |
+//! |-----|---------------------------------|---------------------------------------------------|
+//! | 1   | the message                     | the hash of the request                           |
+//! | 2   | the scope                       | the public key, [encoded](crate::encoding)        |
+//! | 3   | the version: `1` V1, `2` V2, `3` V1_1, `4` V2_1 | the signature in [`Format::Binary`] |
+//! | 4   | the hash of the parameters      |                                                   |
+//!
+//! The version code is of a SHA-256 challenge; it's `4` more with [`ChallengeHash::Sha512`], and `8` more with
+//! [`ChallengeHash::Keccak256`], so the offline machine and the verifier use the hash of the request.
+
+use crate::error::validity;
+use crate::formats::{self, Format, FormatError, BINARY_VERSION};
+use crate::hasher::{ChallengeHash, Keccak256, Sha512};
+use crate::{encoding, EncodingError, HashToCurveError, Parameters, PlumeSignature, PlumeVersion};
+use crate::{HashToCurveSuite, PublicKey, SecretKeyMaterial};
+use alloc::string::String;
+use alloc::{format, vec, vec::Vec};
+use ark_ec::models::SWModelParameters;
+use ark_ff::PrimeField;
+use ark_std::rand::Rng;
+use core::fmt;
+use sha2::{Digest, Sha256};
+
+/// The type of the parts of a [`SigningRequest`].
+pub const REQUEST_UR_TYPE: &str = "PLUME-REQUEST";
+/// The type of the parts of a [`SigningResponse`].
+pub const RESPONSE_UR_TYPE: &str = "PLUME-RESPONSE";
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_MAP: u8 = 5;
+
+/// A request to sign `message` under `scope`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningRequest {
+    /// The message to sign; it starts with the scope.
+    pub message: Vec<u8>,
+    /// The scope, shown to the operator of the offline machine.
+    pub scope: Vec<u8>,
+    pub version: PlumeVersion,
+    /// The hash of the challenge.
+    pub hash: ChallengeHash,
+    /// The SHA-256 of the parameters as [`encoding::parameters_to_bytes`] writes them.
+    pub parameters_hash: [u8; 32],
+}
+
+/// The signature answering a [`SigningRequest`].
+#[derive(Clone)]
+pub struct SigningResponse<P: SWModelParameters> {
+    /// The SHA-256 of the CBOR of the request.
+    pub request_hash: [u8; 32],
+    pub pk: PublicKey<P>,
+    pub signature: PlumeSignature<P>,
+}
+
+/// The SHA-256 of `pp` as [`encoding::parameters_to_bytes`] writes them, header included.
+pub fn parameters_hash<P: HashToCurveSuite>(pp: &Parameters<P>) -> Result<[u8; 32], EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    Ok(Sha256::digest(encoding::parameters_to_bytes(pp)?).into())
+}
+
+impl SigningRequest {
+    /// A request to sign `message` with the parameters `pp` and a SHA-256 challenge; fails if the message isn't under `scope`.
+    pub fn new<P: HashToCurveSuite>(
+        pp: &Parameters<P>,
+        message: &[u8],
+        scope: &[u8],
+        version: PlumeVersion,
+    ) -> Result<Self, OfflineError>
+    where
+        P::BaseField: PrimeField,
+    {
+        if !message.starts_with(scope) {
+            return Err(OfflineError::OutOfScope);
+        }
+        Ok(Self {
+            message: message.to_vec(),
+            scope: scope.to_vec(),
+            version,
+            hash: ChallengeHash::Sha256,
+            parameters_hash: parameters_hash(pp)?,
+        })
+    }
+
+    /// The request with the challenge hashed with `hash`.
+    pub fn with_hash(self, hash: ChallengeHash) -> Self {
+        Self { hash, ..self }
+    }
+
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut cbor = Vec::with_capacity(self.message.len() + self.scope.len() + 64);
+        cbor_head(&mut cbor, MAJOR_MAP, 4);
+        cbor_head(&mut cbor, MAJOR_UINT, 1);
+        cbor_bytes(&mut cbor, &self.message);
+        cbor_head(&mut cbor, MAJOR_UINT, 2);
+        cbor_bytes(&mut cbor, &self.scope);
+        cbor_head(&mut cbor, MAJOR_UINT, 3);
+        cbor_head(&mut cbor, MAJOR_UINT, version_code(self.version, self.hash));
+        cbor_head(&mut cbor, MAJOR_UINT, 4);
+        cbor_bytes(&mut cbor, &self.parameters_hash);
+        cbor
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, OfflineError> {
+        let mut reader = CborReader { bytes };
+        reader.map(4)?;
+        let message = reader.field(1, CborReader::bytes)?.to_vec();
+        let scope = reader.field(2, CborReader::bytes)?.to_vec();
+        let version = reader.field(3, CborReader::uint)?;
+        let (version, hash) = version_from_code(version).ok_or(OfflineError::Cbor)?;
+        let parameters_hash = reader
+            .field(4, CborReader::bytes)?
+            .try_into()
+            .map_err(|_| OfflineError::Cbor)?;
+        reader.finish()?;
+        if !message.starts_with(&scope) {
+            return Err(OfflineError::OutOfScope);
+        }
+        Ok(Self {
+            message,
+            scope,
+            version,
+            hash,
+            parameters_hash,
+        })
+    }
+
+    /// The SHA-256 of [`Self::to_cbor`], which the response refers to.
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.to_cbor()).into()
+    }
+
+    /// Signs the request, on the offline machine; fails if it's for other parameters than `pp`.
+    pub fn sign<P: HashToCurveSuite>(
+        &self,
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+    ) -> Result<SigningResponse<P>, OfflineError>
+    where
+        P::BaseField: PrimeField,
+    {
+        if parameters_hash(pp)? != self.parameters_hash {
+            return Err(OfflineError::ParametersMismatch);
+        }
+        Ok(SigningResponse {
+            request_hash: self.hash(),
+            pk: *keypair.0,
+            signature: sign_with(pp, rng, keypair, &self.message, self.version, self.hash)
+                .map_err(OfflineError::HashToCurve)?,
+        })
+    }
+}
+
+impl<P: SWModelParameters> SigningResponse<P>
+where
+    P::BaseField: PrimeField,
+{
+    pub fn to_cbor(&self) -> Result<Vec<u8>, OfflineError> {
+        let pk = encoding::point_to_bytes(&self.pk)?;
+        let signature = formats::encode(
+            &self.signature,
+            Format::Binary {
+                version: BINARY_VERSION,
+            },
+        )?;
+        let mut cbor = Vec::with_capacity(pk.len() + signature.len() + 48);
+        cbor_head(&mut cbor, MAJOR_MAP, 3);
+        cbor_head(&mut cbor, MAJOR_UINT, 1);
+        cbor_bytes(&mut cbor, &self.request_hash);
+        cbor_head(&mut cbor, MAJOR_UINT, 2);
+        cbor_bytes(&mut cbor, &pk);
+        cbor_head(&mut cbor, MAJOR_UINT, 3);
+        cbor_bytes(&mut cbor, &signature);
+        Ok(cbor)
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, OfflineError> {
+        let mut reader = CborReader { bytes };
+        reader.map(3)?;
+        let request_hash = reader
+            .field(1, CborReader::bytes)?
+            .try_into()
+            .map_err(|_| OfflineError::Cbor)?;
+        let pk = encoding::point_from_bytes(reader.field(2, CborReader::bytes)?)?;
+        let signature = reader.field(3, CborReader::bytes)?;
+        if formats::detect_format::<P>(signature)
+            != Some(Format::Binary {
+                version: BINARY_VERSION,
+            })
+        {
+            return Err(OfflineError::Format(FormatError::Unknown));
+        }
+        let signature = formats::decode(signature)?;
+        reader.finish()?;
+        Ok(Self {
+            request_hash,
+            pk,
+            signature,
+        })
+    }
+}
+
+impl<P: HashToCurveSuite> SigningResponse<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Checks the response answers `request` with a valid signature under the parameters `pp`.
+    ///
+    /// `Ok(false)` is a response to another request, or an invalid signature.
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        request: &SigningRequest,
+    ) -> Result<bool, OfflineError> {
+        if parameters_hash(pp)? != request.parameters_hash {
+            return Err(OfflineError::ParametersMismatch);
+        }
+        if self.request_hash != request.hash() {
+            return Ok(false);
+        }
+        let (message, version) = (&request.message[..], request.version);
+        validity(match request.hash {
+            ChallengeHash::Sha256 => self.signature.verify(pp, &self.pk, message, version),
+            ChallengeHash::Sha512 => self
+                .signature
+                .verify_non_zk_with_hasher::<Sha512>(pp, &self.pk, message, version),
+            ChallengeHash::Keccak256 => self
+                .signature
+                .verify_non_zk_with_hasher::<Keccak256>(pp, &self.pk, message, version),
+        })
+        .map_err(OfflineError::HashToCurve)
+    }
+}
+
+/// Splits `payload` into parts of `ur_type` carrying at most `max_fragment_len` bytes each.
+pub fn to_parts(ur_type: &str, payload: &[u8], max_fragment_len: usize) -> Vec<String> {
+    let checksum = hex::encode_upper(checksum(payload));
+    let fragments: Vec<&[u8]> = if payload.is_empty() {
+        vec![payload]
+    } else {
+        payload.chunks(max_fragment_len.max(1)).collect()
+    };
+    let total = fragments.len();
+    fragments
+        .iter()
+        .enumerate()
+        .map(|(i, fragment)| {
+            format!(
+                "UR:{}/{}-{total}/{checksum}/{}",
+                ur_type.to_ascii_uppercase(),
+                i + 1,
+                hex::encode_upper(fragment)
+            )
+        })
+        .collect()
+}
+
+/// Joins the parts of `ur_type` made by [`to_parts`], in any order; a part may be scanned more than once.
+pub fn from_parts<S: AsRef<str>>(ur_type: &str, parts: &[S]) -> Result<Vec<u8>, OfflineError> {
+    let prefix = format!("UR:{}/", ur_type.to_ascii_uppercase());
+    let mut fragments: Vec<Option<Vec<u8>>> = Vec::new();
+    let mut expected_checksum = None;
+    for part in parts {
+        let fields = part
+            .as_ref()
+            .strip_prefix(prefix.as_str())
+            .ok_or(OfflineError::Parts)?;
+        let (seq, total, part_checksum, fragment) = match fields.split('/').collect::<Vec<_>>()[..]
+        {
+            [position, part_checksum, fragment] => {
+                let (seq, total) = position.split_once('-').ok_or(OfflineError::Parts)?;
+                (seq, total, part_checksum, fragment)
+            }
+            _ => return Err(OfflineError::Parts),
+        };
+        let parse = |n: &str| n.parse::<usize>().map_err(|_| OfflineError::Parts);
+        let (seq, total) = (parse(seq)?, parse(total)?);
+        let fragment = decode_upper_hex(fragment)?;
+        let part_checksum = decode_upper_hex(part_checksum)?;
+
+        // each part carries one fragment, so more than that can't be complete
+        if total > parts.len() {
+            return Err(OfflineError::Parts);
+        }
+        if fragments.is_empty() {
+            fragments = vec![None; total];
+        }
+        if *expected_checksum.get_or_insert(part_checksum.clone()) != part_checksum
+            || total != fragments.len()
+            || seq == 0
+            || seq > total
+        {
+            return Err(OfflineError::Parts);
+        }
+        if fragments[seq - 1]
+            .as_ref()
+            .is_some_and(|seen| *seen != fragment)
+        {
+            return Err(OfflineError::Parts);
+        }
+        fragments[seq - 1] = Some(fragment);
+    }
+
+    let payload = fragments
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .filter(|fragments| !fragments.is_empty())
+        .ok_or(OfflineError::Parts)?
+        .concat();
+    if Some(checksum(&payload).to_vec()) != expected_checksum {
+        return Err(OfflineError::Parts);
+    }
+    Ok(payload)
+}
+
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let digest = Sha256::digest(payload);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// Only the uppercase hex [`to_parts`] writes.
+fn decode_upper_hex(hex: &str) -> Result<Vec<u8>, OfflineError> {
+    if hex.bytes().any(|b| b.is_ascii_lowercase()) {
+        return Err(OfflineError::Parts);
+    }
+    hex::decode(hex).map_err(|_| OfflineError::Parts)
+}
+
+/// Signs with the challenge hashed with `hash`.
+fn sign_with<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    rng: &mut impl Rng,
+    keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+    message: &[u8],
+    version: PlumeVersion,
+    hash: ChallengeHash,
+) -> Result<PlumeSignature<P>, HashToCurveError> {
+    match hash {
+        ChallengeHash::Sha256 => PlumeSignature::sign(pp, rng, keypair, message, version),
+        ChallengeHash::Sha512 => {
+            PlumeSignature::sign_with_hasher::<Sha512>(pp, rng, keypair, message, version)
+        }
+        ChallengeHash::Keccak256 => {
+            PlumeSignature::sign_with_hasher::<Keccak256>(pp, rng, keypair, message, version)
+        }
+    }
+}
+
+pub(crate) fn version_code(version: PlumeVersion, hash: ChallengeHash) -> u64 {
+    let code = u64::from(version.code());
+    match hash {
+        ChallengeHash::Sha256 => code,
+        ChallengeHash::Sha512 => code + 4,
+        ChallengeHash::Keccak256 => code + 8,
+    }
+}
+
+fn version_from_code(code: u64) -> Option<(PlumeVersion, ChallengeHash)> {
+    let index = code.checked_sub(1)?;
+    let hash = match index / 4 {
+        0 => ChallengeHash::Sha256,
+        1 => ChallengeHash::Sha512,
+        2 => ChallengeHash::Keccak256,
+        _ => return None,
+    };
+    let version = PlumeVersion::from_code((index % 4) as u8 + 1)?;
+    Some((version, hash))
+}
+
+/// Writes the head of a CBOR item in its shortest form.
+fn cbor_head(cbor: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => cbor.push(major | value as u8),
+        24..=0xff => cbor.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            cbor.push(major | 25);
+            cbor.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            cbor.push(major | 26);
+            cbor.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            cbor.push(major | 27);
+            cbor.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+fn cbor_bytes(cbor: &mut Vec<u8>, bytes: &[u8]) {
+    cbor_head(cbor, MAJOR_BYTES, bytes.len() as u64);
+    cbor.extend_from_slice(bytes);
+}
+
+/// Reads the deterministic CBOR written here, rejecting heads which aren't in the shortest form.
+struct CborReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> CborReader<'a> {
+    fn head(&mut self) -> Result<(u8, u64), OfflineError> {
+        let (first, rest) = self.bytes.split_first().ok_or(OfflineError::Cbor)?;
+        let (major, info) = (first >> 5, first & 0x1f);
+        let (value, rest) = match info {
+            0..=23 => (u64::from(info), rest),
+            24..=27 => {
+                let len = 1 << (info - 24);
+                let (value, rest) = rest.split_at_checked(len).ok_or(OfflineError::Cbor)?;
+                let value = value
+                    .iter()
+                    .fold(0u64, |value, byte| (value << 8) | u64::from(*byte));
+                let shortest = match len {
+                    1 => 24,
+                    2 => 0x100,
+                    4 => 0x1_0000,
+                    _ => 0x1_0000_0000,
+                };
+                if value < shortest {
+                    return Err(OfflineError::Cbor);
+                }
+                (value, rest)
+            }
+            _ => return Err(OfflineError::Cbor),
+        };
+        self.bytes = rest;
+        Ok((major, value))
+    }
+
+    fn uint(&mut self) -> Result<u64, OfflineError> {
+        match self.head()? {
+            (MAJOR_UINT, value) => Ok(value),
+            _ => Err(OfflineError::Cbor),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], OfflineError> {
+        let len = match self.head()? {
+            (MAJOR_BYTES, len) => usize::try_from(len).map_err(|_| OfflineError::Cbor)?,
+            _ => return Err(OfflineError::Cbor),
+        };
+        let (bytes, rest) = self.bytes.split_at_checked(len).ok_or(OfflineError::Cbor)?;
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    fn map(&mut self, len: u64) -> Result<(), OfflineError> {
+        match self.head()? {
+            (MAJOR_MAP, map_len) if map_len == len => Ok(()),
+            _ => Err(OfflineError::Cbor),
+        }
+    }
+
+    /// Reads the entry of the map which has to come next, `key` and the value read by `read`.
+    fn field<T>(
+        &mut self,
+        key: u64,
+        read: impl FnOnce(&mut Self) -> Result<T, OfflineError>,
+    ) -> Result<T, OfflineError> {
+        if self.uint()? != key {
+            return Err(OfflineError::Cbor);
+        }
+        read(self)
+    }
+
+    fn finish(&self) -> Result<(), OfflineError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(OfflineError::Cbor)
+        }
+    }
+}
+
+/// An error making or reading a request or a response.
+#[derive(Debug, Clone)]
+pub enum OfflineError {
+    /// The CBOR isn't the deterministic encoding of a request or a response.
+    Cbor,
+    /// The parts are malformed, inconsistent, or some are missing.
+    Parts,
+    /// The message doesn't start with the scope.
+    OutOfScope,
+    /// The request is for other parameters.
+    ParametersMismatch,
+    /// A key or the parameters couldn't be encoded or decoded.
+    Encoding(EncodingError),
+    /// The signature couldn't be encoded or decoded.
+    Format(FormatError),
+    /// The message and the key couldn't be hashed to the curve.
+    HashToCurve(HashToCurveError),
+}
+
+impl fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cbor => write!(f, "the CBOR isn't a valid request or response"),
+            Self::Parts => write!(f, "the parts are malformed, inconsistent, or incomplete"),
+            Self::OutOfScope => write!(f, "the message is out of the scope"),
+            Self::ParametersMismatch => write!(f, "the request is for other parameters"),
+            Self::Encoding(e) => write!(f, "invalid encoding: {e:?}"),
+            Self::Format(e) => write!(f, "{e}"),
+            Self::HashToCurve(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl core::error::Error for OfflineError {}
+
+impl From<EncodingError> for OfflineError {
+    fn from(e: EncodingError) -> Self {
+        Self::Encoding(e)
+    }
+}
+
+impl From<FormatError> for OfflineError {
+    fn from(e: FormatError) -> Self {
+        match e {
+            FormatError::Encoding(e) => Self::Encoding(e),
+            e => Self::Format(e),
+        }
+    }
+}
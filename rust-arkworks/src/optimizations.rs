@@ -0,0 +1,135 @@
+//! Curve specific fast paths behind the generic API.
+//!
+//! The generic arithmetic of arkworks can't use curve specific speedups such as the GLV endomorphism of `secp256k1`. [`CurveOptimizations`]
+//! collects the operations a known curve does faster, and the crate dispatches to them by checking the curve parameters `P` against the
+//! known curves with [`Any`]; other curves take the generic path. Callers stay generic over `P` either way.
+
+use crate::encoding;
+use crate::hash_to_curve::k256_affine_to_arkworks_secp256k1_affine;
+use alloc::vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::Zero;
+use core::any::Any;
+use elliptic_curve::ops::LinearCombination;
+use elliptic_curve::sec1::FromEncodedPoint;
+use secp256k1::curves::Secp256k1Parameters;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The operations a curve can do faster than the generic arithmetic.
+///
+/// It's sealed: the implementations are the known curves of this crate.
+pub trait CurveOptimizations: SWModelParameters + sealed::Sealed {
+    /// Computes `a^x ⋅ b^y`.
+    fn double_mul(
+        a: &GroupAffine<Self>,
+        x: Self::ScalarField,
+        b: &GroupAffine<Self>,
+        y: Self::ScalarField,
+    ) -> GroupAffine<Self>;
+}
+
+impl sealed::Sealed for Secp256k1Parameters {}
+
+/// Uses the linear combination of `k256`, which splits the scalars with the GLV endomorphism and interleaves the two multiplications.
+impl CurveOptimizations for Secp256k1Parameters {
+    fn double_mul(
+        a: &GroupAffine<Self>,
+        x: Self::ScalarField,
+        b: &GroupAffine<Self>,
+        y: Self::ScalarField,
+    ) -> GroupAffine<Self> {
+        let result = match (
+            to_k256_point(a),
+            to_k256_scalar(&x),
+            to_k256_point(b),
+            to_k256_scalar(&y),
+        ) {
+            (Some(a), Some(x), Some(b), Some(y)) => {
+                k256::ProjectivePoint::lincomb(&a, &x, &b, &y).to_affine()
+            }
+            _ => return generic_double_mul(a, x, b, y),
+        };
+        if result == k256::AffinePoint::IDENTITY {
+            return GroupAffine::zero();
+        }
+        k256_affine_to_arkworks_secp256k1_affine(result)
+            .unwrap_or_else(|_| generic_double_mul(a, x, b, y))
+    }
+}
+
+/// Computes `a^x ⋅ b^y`, taking the fast path of `P` if it's a known curve.
+pub(crate) fn double_mul<P: SWModelParameters>(
+    a: &GroupAffine<P>,
+    x: P::ScalarField,
+    b: &GroupAffine<P>,
+    y: P::ScalarField,
+) -> GroupAffine<P> {
+    if let (Some(a), Some(x), Some(b), Some(y)) = (
+        downcast::<_, GroupAffine<Secp256k1Parameters>>(a),
+        downcast::<_, <Secp256k1Parameters as ark_ec::ModelParameters>::ScalarField>(&x),
+        downcast::<_, GroupAffine<Secp256k1Parameters>>(b),
+        downcast::<_, <Secp256k1Parameters as ark_ec::ModelParameters>::ScalarField>(&y),
+    ) {
+        let result = Secp256k1Parameters::double_mul(a, *x, b, *y);
+        if let Some(result) = downcast::<_, GroupAffine<P>>(&result) {
+            return *result;
+        }
+    }
+    generic_double_mul(a, x, b, y)
+}
+
+/// Computes `g^x ⋅ pk^y` for a verification, from the tables of [`mod@crate::cache`] if both points are warm on this thread.
+pub(crate) fn key_double_mul<P: SWModelParameters>(
+    g: &GroupAffine<P>,
+    x: P::ScalarField,
+    pk: &GroupAffine<P>,
+    y: P::ScalarField,
+) -> GroupAffine<P> {
+    #[cfg(feature = "std")]
+    if let Some(result) = crate::cache::double_mul(g, x, pk, y) {
+        return result;
+    }
+    double_mul(g, x, pk, y)
+}
+
+fn generic_double_mul<P: SWModelParameters>(
+    a: &GroupAffine<P>,
+    x: P::ScalarField,
+    b: &GroupAffine<P>,
+    y: P::ScalarField,
+) -> GroupAffine<P> {
+    (a.mul(x) + b.mul(y)).into_affine()
+}
+
+pub(crate) fn downcast<T: Any, U: Any>(value: &T) -> Option<&U> {
+    (value as &dyn Any).downcast_ref::<U>()
+}
+
+pub(crate) fn to_k256_point(
+    point: &GroupAffine<Secp256k1Parameters>,
+) -> Option<k256::ProjectivePoint> {
+    if point.infinity {
+        return Some(k256::ProjectivePoint::IDENTITY);
+    }
+    let bytes = [
+        vec![0x04],
+        encoding::field_to_bytes(&point.x),
+        encoding::field_to_bytes(&point.y),
+    ]
+    .concat();
+    let encoded = k256::EncodedPoint::from_bytes(bytes).ok()?;
+    Option::from(k256::AffinePoint::from_encoded_point(&encoded)).map(k256::ProjectivePoint::from)
+}
+
+pub(crate) fn to_k256_scalar(
+    scalar: &<Secp256k1Parameters as ark_ec::ModelParameters>::ScalarField,
+) -> Option<k256::Scalar> {
+    let bytes = k256::FieldBytes::from_exact_iter(encoding::field_to_bytes(scalar))?;
+    Option::from(<k256::Scalar as elliptic_curve::ff::PrimeField>::from_repr(
+        bytes,
+    ))
+}
@@ -0,0 +1,256 @@
+//! NIST P-256 (`secp256r1`), the curve of WebAuthn passkeys and of the Secure Enclave.
+//!
+//! arkworks `0.3` has no crate for it, so the fields and the curve are defined here, with the constants in Montgomery form as `ark-ff`
+//! expects. It hashes by the `P256_XMD:SHA-256_SSWU_RO_` suite of RFC 9380, implemented over these fields by
+//! [`hash_to_curve_sswu`]; its domain separation tag follows the one of `secp256k1`. The group is of prime order, so there's no cofactor
+//! to clear. Signing and verifying take [`P256Parameters`] as any curve: `Parameters::<P256Parameters>` with the generator
+//! `Affine::prime_subgroup_generator()`.
+
+use crate::error::HashToCurveError;
+use crate::hash_to_curve::{hash_to_curve_sswu, HashToCurveSuite};
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{ModelParameters, SWModelParameters};
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::fields::{FftParameters, Fp256, Fp256Parameters, FpParameters};
+use plume_core::consts;
+
+/// The base field, of the prime `2^256 - 2^224 + 2^192 + 2^96 - 1`.
+pub type Fq = Fp256<FqParameters>;
+/// The scalar field, of the order of the group.
+pub type Fr = Fp256<FrParameters>;
+/// A point of P-256 in affine coordinates.
+pub type Affine = GroupAffine<P256Parameters>;
+/// A point of P-256 in Jacobian coordinates.
+pub type Projective = GroupProjective<P256Parameters>;
+
+/// The RFC 9380 identifier of the suite.
+pub const SUITE: &str = consts::P256.suite;
+
+/// The domain separation tag the signatures hash with.
+pub const DST: &[u8] = consts::P256.dst;
+
+/// `Z = -10`, the non-square of the simplified SWU map of the suite.
+const SSWU_Z: Fq = Fq::new(BigInteger([
+    0xfffffffffffffff5,
+    0x0000000affffffff,
+    0x0000000000000000,
+    0xfffffff50000000b,
+]));
+
+/// The parameters of the base field.
+pub struct FqParameters;
+
+impl Fp256Parameters for FqParameters {}
+
+impl FftParameters for FqParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 1;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+        0xfffffffffffffffe,
+        0x00000001ffffffff,
+        0x0000000000000000,
+        0xfffffffe00000002,
+    ]);
+}
+
+impl FpParameters for FqParameters {
+    const MODULUS: BigInteger = BigInteger([
+        0xffffffffffffffff,
+        0x00000000ffffffff,
+        0x0000000000000000,
+        0xffffffff00000001,
+    ]);
+
+    const MODULUS_BITS: u32 = 256;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 0;
+
+    const R: BigInteger = BigInteger([
+        0x0000000000000001,
+        0xffffffff00000000,
+        0xffffffffffffffff,
+        0x00000000fffffffe,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0x0000000000000003,
+        0xfffffffbffffffff,
+        0xfffffffffffffffe,
+        0x00000004fffffffd,
+    ]);
+
+    const INV: u64 = 0x0000000000000001;
+
+    /// `6`
+    const GENERATOR: BigInteger = BigInteger([
+        0x0000000000000006,
+        0xfffffffa00000000,
+        0xffffffffffffffff,
+        0x00000005fffffff9,
+    ]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xffffffffffffffff,
+        0x000000007fffffff,
+        0x8000000000000000,
+        0x7fffffff80000000,
+    ]);
+
+    const T: BigInteger = BigInteger([
+        0xffffffffffffffff,
+        0x000000007fffffff,
+        0x8000000000000000,
+        0x7fffffff80000000,
+    ]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xffffffffffffffff,
+        0x000000003fffffff,
+        0x4000000000000000,
+        0x3fffffffc0000000,
+    ]);
+}
+
+/// The parameters of the scalar field.
+pub struct FrParameters;
+
+impl Fp256Parameters for FrParameters {}
+
+impl FftParameters for FrParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 4;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+        0x1015708f7e368fe1,
+        0x31c6c5456ecc4511,
+        0x5281fe8998a19ea1,
+        0x0279089e10c63fe8,
+    ]);
+}
+
+impl FpParameters for FrParameters {
+    const MODULUS: BigInteger = BigInteger([
+        0xf3b9cac2fc632551,
+        0xbce6faada7179e84,
+        0xffffffffffffffff,
+        0xffffffff00000000,
+    ]);
+
+    const MODULUS_BITS: u32 = 256;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 0;
+
+    const R: BigInteger = BigInteger([
+        0x0c46353d039cdaaf,
+        0x4319055258e8617b,
+        0x0000000000000000,
+        0x00000000ffffffff,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0x83244c95be79eea2,
+        0x4699799c49bd6fa6,
+        0x2845b2392b6bec59,
+        0x66e12d94f3d95620,
+    ]);
+
+    const INV: u64 = 0xccd1c8aaee00bc4f;
+
+    /// `7`
+    const GENERATOR: BigInteger = BigInteger([
+        0x55eb74ab1949fac9,
+        0xd5af25406e5aaa5d,
+        0x0000000000000001,
+        0x00000006fffffff9,
+    ]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x79dce5617e3192a8,
+        0xde737d56d38bcf42,
+        0x7fffffffffffffff,
+        0x7fffffff80000000,
+    ]);
+
+    const T: BigInteger = BigInteger([
+        0x4f3b9cac2fc63255,
+        0xfbce6faada7179e8,
+        0x0fffffffffffffff,
+        0x0ffffffff0000000,
+    ]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x279dce5617e3192a,
+        0xfde737d56d38bcf4,
+        0x07ffffffffffffff,
+        0x07fffffff8000000,
+    ]);
+}
+
+/// The parameters of P-256, `y^2 = x^3 - 3x + b`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct P256Parameters;
+
+impl ModelParameters for P256Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for P256Parameters {
+    /// `-3`
+    const COEFF_A: Fq = Fq::new(BigInteger([
+        0xfffffffffffffffc,
+        0x00000003ffffffff,
+        0x0000000000000000,
+        0xfffffffc00000004,
+    ]));
+
+    /// `0x5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b`
+    const COEFF_B: Fq = Fq::new(BigInteger([
+        0xd89cdf6229c4bddf,
+        0xacf005cd78843090,
+        0xe5a220abf7212ed6,
+        0xdc30061d04874834,
+    ]));
+
+    const COFACTOR: &'static [u64] = &[1];
+
+    /// `1`
+    const COFACTOR_INV: Fr = Fr::new(BigInteger([
+        0x0c46353d039cdaaf,
+        0x4319055258e8617b,
+        0x0000000000000000,
+        0x00000000ffffffff,
+    ]));
+
+    /// The generator of SEC 2, `x = 0x6b17d1f2…d898c296`, `y = 0x4fe342e2…37bf51f5`.
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) = (
+        Fq::new(BigInteger([
+            0x79e730d418a9143c,
+            0x75ba95fc5fedb601,
+            0x79fb732b77622510,
+            0x18905f76a53755c6,
+        ])),
+        Fq::new(BigInteger([
+            0xddf25357ce95560a,
+            0x8b4ab8e4ba19e45c,
+            0xd2e88688dd21f325,
+            0x8571ff1825885d85,
+        ])),
+    );
+}
+
+impl HashToCurveSuite for P256Parameters {
+    const SUITE: &'static str = SUITE;
+    const DST: &'static [u8] = DST;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        hash_to_curve_sswu::<Self>(msgs, dst, SSWU_Z)
+    }
+}
@@ -0,0 +1,312 @@
+//! The Pasta curves Pallas and Vesta, the cycle of the Halo 2 and Kimchi proof systems.
+//!
+//! Each curve's base field is the other's scalar field, so a circuit over one of the fields verifies the nullifier relation on the curve
+//! over it natively. arkworks `0.3` has no crate for them, so the fields and the curves are defined here, with the constants in
+//! Montgomery form as `ark-ff` expects. Both curves are `y^2 = x^3 + 5` of prime order, with the generator `(-1, 2)` as in
+//! `pasta_curves`. RFC 9380 has no suite for them, and the simplified SWU map needs `A ≠ 0`, so they hash by the Shallue-van de Woestijne
+//! map of the RFC, [`hash_to_curve_svdw`], with `Z = 1` as its `find_z_svdw` picks, under the suites `pallas_XMD:SHA-256_SVDW_RO_` and
+//! `vesta_XMD:SHA-256_SVDW_RO_` named after the ones of the RFC.
+
+use crate::error::HashToCurveError;
+use crate::hash_to_curve::{hash_to_curve_svdw, HashToCurveSuite};
+use ark_ec::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+use ark_ec::{ModelParameters, SWModelParameters};
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::fields::{FftParameters, Fp256, Fp256Parameters, FpParameters};
+use ark_ff::One;
+use plume_core::consts;
+
+/// The base field of Pallas and the scalar field of Vesta, of the prime `2^254 + 45560315531419706090280762371685220353`.
+pub type Fp = Fp256<PallasBaseParameters>;
+/// The base field of Vesta and the scalar field of Pallas, of the prime `2^254 + 45560315531506369815346746415080538113`.
+pub type Fq = Fp256<VestaBaseParameters>;
+/// A point of Pallas in affine coordinates.
+pub type PallasAffine = GroupAffine<PallasParameters>;
+/// A point of Pallas in Jacobian coordinates.
+pub type PallasProjective = GroupProjective<PallasParameters>;
+/// A point of Vesta in affine coordinates.
+pub type VestaAffine = GroupAffine<VestaParameters>;
+/// A point of Vesta in Jacobian coordinates.
+pub type VestaProjective = GroupProjective<VestaParameters>;
+
+/// The parameters of [`Fp`].
+pub struct PallasBaseParameters;
+
+impl Fp256Parameters for PallasBaseParameters {}
+
+impl FftParameters for PallasBaseParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 32;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+        0xa28db849bad6dbf0,
+        0x9083cd03d3b539df,
+        0xfba6b9ca9dc8448e,
+        0x3ec928747b89c6da,
+    ]);
+}
+
+impl FpParameters for PallasBaseParameters {
+    const MODULUS: BigInteger = BigInteger([
+        0x992d30ed00000001,
+        0x224698fc094cf91b,
+        0x0000000000000000,
+        0x4000000000000000,
+    ]);
+
+    const MODULUS_BITS: u32 = 255;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 1;
+
+    const R: BigInteger = BigInteger([
+        0x34786d38fffffffd,
+        0x992c350be41914ad,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0x8c78ecb30000000f,
+        0xd7d30dbd8b0de0e7,
+        0x7797a99bc3c95d18,
+        0x096d41af7b9cb714,
+    ]);
+
+    const INV: u64 = 0x992d30ecffffffff;
+
+    /// `5`
+    const GENERATOR: BigInteger = BigInteger([
+        0xa1a55e68ffffffed,
+        0x74c2a54b4f4982f3,
+        0xfffffffffffffffd,
+        0x3fffffffffffffff,
+    ]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xcc96987680000000,
+        0x11234c7e04a67c8d,
+        0x0000000000000000,
+        0x2000000000000000,
+    ]);
+
+    const T: BigInteger = BigInteger([
+        0x094cf91b992d30ed,
+        0x00000000224698fc,
+        0x0000000000000000,
+        0x0000000040000000,
+    ]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x04a67c8dcc969876,
+        0x0000000011234c7e,
+        0x0000000000000000,
+        0x0000000020000000,
+    ]);
+}
+
+/// The parameters of [`Fq`].
+pub struct VestaBaseParameters;
+
+impl Fp256Parameters for VestaBaseParameters {}
+
+impl FftParameters for VestaBaseParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 32;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+        0x218077428c9942de,
+        0xcc49578921b60494,
+        0xac2e5d27b2efbee2,
+        0x0b79fa897f2db056,
+    ]);
+}
+
+impl FpParameters for VestaBaseParameters {
+    const MODULUS: BigInteger = BigInteger([
+        0x8c46eb2100000001,
+        0x224698fc0994a8dd,
+        0x0000000000000000,
+        0x4000000000000000,
+    ]);
+
+    const MODULUS_BITS: u32 = 255;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 1;
+
+    const R: BigInteger = BigInteger([
+        0x5b2b3e9cfffffffd,
+        0x992c350be3420567,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0xfc9678ff0000000f,
+        0x67bb433d891a16e3,
+        0x7fae231004ccf590,
+        0x096d41af7ccfdaa9,
+    ]);
+
+    const INV: u64 = 0x8c46eb20ffffffff;
+
+    /// `5`
+    const GENERATOR: BigInteger = BigInteger([
+        0x96bc8c8cffffffed,
+        0x74c2a54b49f7778e,
+        0xfffffffffffffffd,
+        0x3fffffffffffffff,
+    ]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xc623759080000000,
+        0x11234c7e04ca546e,
+        0x0000000000000000,
+        0x2000000000000000,
+    ]);
+
+    const T: BigInteger = BigInteger([
+        0x0994a8dd8c46eb21,
+        0x00000000224698fc,
+        0x0000000000000000,
+        0x0000000040000000,
+    ]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x04ca546ec6237590,
+        0x0000000011234c7e,
+        0x0000000000000000,
+        0x0000000020000000,
+    ]);
+}
+
+/// The parameters of Pallas, `y^2 = x^3 + 5` over [`Fp`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PallasParameters;
+
+impl ModelParameters for PallasParameters {
+    type BaseField = Fp;
+    type ScalarField = Fq;
+}
+
+impl SWModelParameters for PallasParameters {
+    /// `0`
+    const COEFF_A: Fp = Fp::new(BigInteger([
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+    ]));
+
+    /// `5`
+    const COEFF_B: Fp = Fp::new(BigInteger([
+        0xa1a55e68ffffffed,
+        0x74c2a54b4f4982f3,
+        0xfffffffffffffffd,
+        0x3fffffffffffffff,
+    ]));
+
+    const COFACTOR: &'static [u64] = &[1];
+
+    /// `1`
+    const COFACTOR_INV: Fq = Fq::new(BigInteger([
+        0x5b2b3e9cfffffffd,
+        0x992c350be3420567,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ]));
+
+    /// `(-1, 2)`
+    const AFFINE_GENERATOR_COEFFS: (Fp, Fp) = (
+        Fp::new(BigInteger([
+            0x64b4c3b400000004,
+            0x891a63f02533e46e,
+            0x0000000000000000,
+            0x0000000000000000,
+        ])),
+        Fp::new(BigInteger([
+            0xcfc3a984fffffff9,
+            0x1011d11bbee5303e,
+            0xffffffffffffffff,
+            0x3fffffffffffffff,
+        ])),
+    );
+}
+
+impl HashToCurveSuite for PallasParameters {
+    const SUITE: &'static str = consts::PALLAS.suite;
+    const DST: &'static [u8] = consts::PALLAS.dst;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        // `Z = 1`
+        hash_to_curve_svdw::<Self>(msgs, dst, Fp::one())
+    }
+}
+
+/// The parameters of Vesta, `y^2 = x^3 + 5` over [`Fq`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VestaParameters;
+
+impl ModelParameters for VestaParameters {
+    type BaseField = Fq;
+    type ScalarField = Fp;
+}
+
+impl SWModelParameters for VestaParameters {
+    /// `0`
+    const COEFF_A: Fq = Fq::new(BigInteger([
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+    ]));
+
+    /// `5`
+    const COEFF_B: Fq = Fq::new(BigInteger([
+        0x96bc8c8cffffffed,
+        0x74c2a54b49f7778e,
+        0xfffffffffffffffd,
+        0x3fffffffffffffff,
+    ]));
+
+    const COFACTOR: &'static [u64] = &[1];
+
+    /// `1`
+    const COFACTOR_INV: Fp = Fp::new(BigInteger([
+        0x34786d38fffffffd,
+        0x992c350be41914ad,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ]));
+
+    /// `(-1, 2)`
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) = (
+        Fq::new(BigInteger([
+            0x311bac8400000004,
+            0x891a63f02652a376,
+            0x0000000000000000,
+            0x0000000000000000,
+        ])),
+        Fq::new(BigInteger([
+            0x2a0f9218fffffff9,
+            0x1011d11bbcef61f1,
+            0xffffffffffffffff,
+            0x3fffffffffffffff,
+        ])),
+    );
+}
+
+impl HashToCurveSuite for VestaParameters {
+    const SUITE: &'static str = consts::VESTA.suite;
+    const DST: &'static [u8] = consts::VESTA.dst;
+
+    fn map_to_curve(msgs: &[&[u8]], dst: &[u8]) -> Result<GroupAffine<Self>, HashToCurveError> {
+        // `Z = 1`
+        hash_to_curve_svdw::<Self>(msgs, dst, Fq::one())
+    }
+}
@@ -0,0 +1,314 @@
+//! The challenge `c` hashed with Poseidon over the scalar field, with the `poseidon` feature, for circuits verifying the signatures.
+//!
+//! A circuit recomputing the SHA-256 challenge spends most of its constraints on the hash; Poseidon is a few hundred constraints per
+//! permutation over the native field. [`PlumeSignature::sign_poseidon`] and [`PlumeSignature::verify_poseidon`] hash the preimage of the
+//! version, the compressed points and the context, with [`hash_bytes`]: the bytes are packed into elements of 31 bytes, big-endian, after
+//! the element of [`POSEIDON_TAG`] and the byte length, so a Poseidon challenge is never one of the hashed ones. The nullifier is the same, as
+//! hash-to-curve doesn't change.
+//!
+//! The permutation is the one of circomlib with two inputs: the width is 3, with 8 full rounds, 57 partial rounds and the S-box `x^5`, and
+//! the round constants and the MDS matrix are generated by the Grain LFSR of the reference implementation for the modulus of the field.
+//! Over the scalar field of BN254 it matches circomlib's `Poseidon(2)`. The sponge absorbs two elements per permutation into the last two
+//! elements of the state, and squeezes the first; see [`PoseidonParameters::hash`].
+
+use crate::challenge::ChallengeReduction;
+use crate::hasher::PlumeHasher;
+use crate::{
+    HashToCurveError, HashToCurveSuite, Message, Parameters, PlumeSignature, PlumeVerifyError,
+    PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::rand::Rng;
+use sha2::Sha256;
+
+/// The domain tag of the Poseidon challenge, the first element hashed.
+pub const POSEIDON_TAG: &[u8] = b"PLUME/poseidon-challenge";
+
+/// The number of elements of the state.
+pub const WIDTH: usize = 3;
+/// The number of full rounds, half of them before the partial rounds.
+pub const FULL_ROUNDS: usize = 8;
+/// The number of partial rounds.
+pub const PARTIAL_ROUNDS: usize = 57;
+/// The exponent of the S-box.
+pub const ALPHA: u64 = 5;
+
+/// The number of bytes packed into an element; below the bit length of every supported field.
+const CHUNK: usize = 31;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A scalar field the permutation is defined for: of 254 bits or more, for the number of rounds, and with `x^5` a permutation.
+///
+/// It's sealed: the implementations are the scalar fields of the curves of this crate. The scalar field of BabyJubJub isn't one, as 5
+/// divides its order minus one.
+pub trait PoseidonField: PrimeField + sealed::Sealed {}
+
+macro_rules! poseidon_fields {
+    ($($field:ty),*) => {
+        $(
+            impl sealed::Sealed for $field {}
+            impl PoseidonField for $field {}
+        )*
+    };
+}
+
+poseidon_fields!(
+    secp256k1::fields::Fr,
+    crate::p256::Fr,
+    crate::pasta::Fp,
+    crate::pasta::Fq,
+    ark_bn254::Fr,
+    ark_bn254::Fq
+);
+
+/// The round constants and the MDS matrix of the permutation over `F`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseidonParameters<F: PrimeField> {
+    round_constants: Vec<F>,
+    mds: [[F; WIDTH]; WIDTH],
+}
+
+impl<F: PoseidonField> Default for PoseidonParameters<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PoseidonField> PoseidonParameters<F> {
+    /// Generates the parameters as the reference implementation: the round constants are the samples of the Grain LFSR below the modulus,
+    /// then the MDS matrix is the Cauchy matrix `1 / (x_i + y_j)` of the next `2 ⋅ WIDTH` samples, reduced.
+    pub fn new() -> Self {
+        Self::generate()
+    }
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    fn generate() -> Self {
+        let bits = F::size_in_bits();
+        let mut grain = Grain::new(bits);
+
+        let mut round_constants = Vec::with_capacity((FULL_ROUNDS + PARTIAL_ROUNDS) * WIDTH);
+        while round_constants.len() < (FULL_ROUNDS + PARTIAL_ROUNDS) * WIDTH {
+            if let Some(constant) = F::from_repr(F::BigInt::from_bits_be(&grain.sample(bits))) {
+                round_constants.push(constant);
+            }
+        }
+
+        // the samples are distinct, and no `x_i + y_j` is zero, for the supported fields at the first draw
+        let mds = loop {
+            let samples: Vec<F> = (0..2 * WIDTH)
+                .map(|_| F::from_be_bytes_mod_order(&bits_to_bytes(&grain.sample(bits))))
+                .collect();
+            let distinct = samples
+                .iter()
+                .enumerate()
+                .all(|(i, x)| samples[i + 1..].iter().all(|y| x != y));
+            let (xs, ys) = samples.split_at(WIDTH);
+            let mut mds = [[F::zero(); WIDTH]; WIDTH];
+            let mut invertible = true;
+            for (row, x) in mds.iter_mut().zip(xs) {
+                for (entry, y) in row.iter_mut().zip(ys) {
+                    match (*x + y).inverse() {
+                        Some(inverse) => *entry = inverse,
+                        None => invertible = false,
+                    }
+                }
+            }
+            if distinct && invertible {
+                break mds;
+            }
+        };
+
+        Self {
+            round_constants,
+            mds,
+        }
+    }
+
+    /// The round constants, `WIDTH` per round.
+    pub fn round_constants(&self) -> &[F] {
+        &self.round_constants
+    }
+
+    /// The MDS matrix, by rows.
+    pub fn mds(&self) -> &[[F; WIDTH]; WIDTH] {
+        &self.mds
+    }
+
+    /// Applies the permutation to `state`: each round adds its constants, applies the S-box to every element in the full rounds and to the
+    /// first one in the partial rounds, and multiplies by the MDS matrix.
+    pub fn permute(&self, state: &mut [F; WIDTH]) {
+        for (round, constants) in self.round_constants.chunks_exact(WIDTH).enumerate() {
+            for (element, constant) in state.iter_mut().zip(constants) {
+                *element += constant;
+            }
+            if round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + PARTIAL_ROUNDS {
+                state.iter_mut().for_each(sbox);
+            } else {
+                sbox(&mut state[0]);
+            }
+            let mixed = self.mds.map(|row| {
+                row.iter()
+                    .zip(state.iter())
+                    .fold(F::zero(), |sum, (m, element)| sum + *m * element)
+            });
+            *state = mixed;
+        }
+    }
+
+    /// Hashes `inputs`: from the zero state, each pair of elements is added to the last two elements of the state before a permutation,
+    /// an odd element paired with zero, and the hash is the first element. The hash of two elements is circomlib's `Poseidon(2)`.
+    pub fn hash(&self, inputs: &[F]) -> F {
+        let mut state = [F::zero(); WIDTH];
+        for pair in inputs.chunks(WIDTH - 1) {
+            for (element, input) in state[1..].iter_mut().zip(pair) {
+                *element += input;
+            }
+            self.permute(&mut state);
+        }
+        state[0]
+    }
+
+    /// Hashes `data`: the elements are the one of [`POSEIDON_TAG`], the byte length of `data`, and `data` in chunks of 31 bytes read
+    /// big-endian, the last one shorter.
+    pub fn hash_bytes(&self, data: &[u8]) -> F {
+        let mut inputs = Vec::with_capacity(2 + data.len().div_ceil(CHUNK));
+        inputs.push(F::from_be_bytes_mod_order(POSEIDON_TAG));
+        inputs.push(F::from(data.len() as u64));
+        inputs.extend(data.chunks(CHUNK).map(F::from_be_bytes_mod_order));
+        self.hash(&inputs)
+    }
+}
+
+/// Hashes `data` into `F` as [`PoseidonParameters::hash_bytes`], generating the parameters.
+pub fn hash_bytes<F: PoseidonField>(data: &[u8]) -> F {
+    PoseidonParameters::new().hash_bytes(data)
+}
+
+fn sbox<F: PrimeField>(element: &mut F) {
+    *element = element.square().square() * *element;
+}
+
+/// The big-endian bytes of `bits`, the most significant first, padded with zeros on the left.
+fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = alloc::vec![0u8; bits.len().div_ceil(8)];
+    let offset = bytes.len() * 8 - bits.len();
+    for (i, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        bytes[(offset + i) / 8] |= 0x80 >> ((offset + i) % 8);
+    }
+    bytes
+}
+
+/// The 80-bit LFSR generating the parameters, seeded with the field type (prime), the S-box (`x^α`), the bit length of the field, the width
+/// and the numbers of rounds.
+struct Grain {
+    state: [bool; 80],
+    head: usize,
+}
+
+impl Grain {
+    fn new(field_bits: usize) -> Self {
+        let fields = [
+            (1, 2),
+            (0, 4),
+            (field_bits, 12),
+            (WIDTH, 12),
+            (FULL_ROUNDS, 10),
+            (PARTIAL_ROUNDS, 10),
+        ];
+        let mut state = [true; 80];
+        let mut i = 0;
+        for (value, width) in fields {
+            for bit in (0..width).rev() {
+                state[i] = (value >> bit) & 1 == 1;
+                i += 1;
+            }
+        }
+        let mut grain = Self { state, head: 0 };
+        for _ in 0..160 {
+            grain.step();
+        }
+        grain
+    }
+
+    fn step(&mut self) -> bool {
+        let bit = |i: usize| self.state[(self.head + i) % 80];
+        let new = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state[self.head] = new;
+        self.head = (self.head + 1) % 80;
+        new
+    }
+
+    /// The next output bit: of each pair of bits, the second one is output when the first one is set.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.step();
+            let bit = self.step();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    fn sample(&mut self, bits: usize) -> Vec<bool> {
+        (0..bits).map(|_| self.next_bit()).collect()
+    }
+}
+
+/// Maps the challenge preimage to [`hash_bytes`], ignoring the hasher; the signatures below only use it with a [`PoseidonField`].
+struct PoseidonChallenge;
+
+impl ChallengeReduction for PoseidonChallenge {
+    fn reduce<F: PrimeField, H: PlumeHasher>(&self, preimage: &[u8]) -> F {
+        PoseidonParameters::<F>::generate().hash_bytes(preimage)
+    }
+}
+
+impl<P: HashToCurveSuite> PlumeSignature<P>
+where
+    P::ScalarField: PoseidonField,
+{
+    /// Sign a message with the challenge hashed by [`hash_bytes`]; see [`PlumeSignature::sign`] and [the module docs](self).
+    pub fn sign_poseidon(
+        pp: &Parameters<P>,
+        rng: &mut impl Rng,
+        keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<Self, HashToCurveError> {
+        Self::sign_with_reduction::<Sha256, _>(
+            pp,
+            rng,
+            keypair,
+            message,
+            version,
+            &PoseidonChallenge,
+        )
+    }
+
+    /// Verifies a signature made with [`Self::sign_poseidon`]; see [`PlumeSignature::verify_non_zk`].
+    pub fn verify_poseidon(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.verify_non_zk_with_reduction::<Sha256, _>(pp, pk, message, version, &PoseidonChallenge)
+    }
+
+    /// Checks the `c` of a signature made with [`Self::sign_poseidon`]; see [`PlumeSignature::check_challenge`].
+    pub fn check_challenge_poseidon(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+        version: PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> {
+        self.check_challenge_with::<Sha256, _>(pp, pk, message, version, &PoseidonChallenge)
+    }
+}
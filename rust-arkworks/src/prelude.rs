@@ -0,0 +1,14 @@
+//! The stable public API: `use plume_arkworks::prelude::*;`.
+//!
+//! What's exported here only changes with a breaking release; the rest of the crate may change between minor versions, and the items
+//! hidden from the docs (the curve fast paths, the internals of hashing to the curve and of the challenge) aren't part of the API at all.
+//! `tests/public_api.rs` pins the signatures of these items and of the extension points (hashing to the curve, the challenge reduction),
+//! so an accidental change fails the tests.
+
+pub use crate::keys::{PublicKeySec1, SecretKeyBytes};
+pub use crate::v1::{sign as sign_v1, Signature as PlumeSignatureV1};
+pub use crate::v2::{sign as sign_v2, Signature as PlumeSignatureV2};
+pub use crate::{
+    EncodingError, HashToCurveError, HashToCurveSuite, Message, Nullifier, Parameters,
+    PlumeSignature, PlumeVerifyError, PlumeVersion, PublicKey, SecretKeyMaterial,
+};
@@ -0,0 +1,188 @@
+//! Reading signatures in bulk from flat files, e.g. for indexers replaying millions of them.
+//!
+//! A file is a sequence of records, each a signature in the canonical encoding of [`PlumeSignature::to_bytes`]; the records are all
+//! [`signature_byte_len`] bytes, so the `i`-th one is found without reading the others, and a file is written by appending the encodings.
+//! [`SignatureReader`] works on the bytes in place. Over a memory-mapped file, such as a `memmap2::Mmap`, nothing is copied: a record is
+//! decoded straight from the mapping when it's reached, without allocating.
+//!
+//! The records hold the signatures alone. The key and the message of each are for the indexer to look up by the index of the record, so
+//! [`SignatureReader::verify_each`] and [`SignatureReader::verify_combined`] take them as a function of the index.
+
+use crate::batch::BatchVerifyError;
+use crate::formats::{self, signature_byte_len};
+use crate::{
+    EncodingError, HashToCurveSuite, Message, Parameters, PlumeSignature, PlumeVerifyError,
+    PlumeVersion, PublicKey,
+};
+use alloc::vec::Vec;
+use ark_ec::models::SWModelParameters;
+use ark_ff::PrimeField;
+use ark_std::rand::{CryptoRng, Rng};
+use core::fmt;
+use core::marker::PhantomData;
+
+/// The number of signatures [`SignatureReader::verify_combined`] decodes and checks at a time.
+pub const COMBINED_CHUNK_LEN: usize = 1024;
+
+/// The bytes aren't a whole number of records; e.g. a file cut off while being written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TruncatedError {
+    /// The length of the bytes.
+    pub len: usize,
+    /// The length of a record, [`signature_byte_len`].
+    pub record_len: usize,
+}
+
+impl fmt::Display for TruncatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes aren't a whole number of {}-byte signature records",
+            self.len, self.record_len
+        )
+    }
+}
+
+impl core::error::Error for TruncatedError {}
+
+/// The signatures on the curve `P` stored one after the other in `bytes`; see [the module docs](self).
+pub struct SignatureReader<'a, P: SWModelParameters> {
+    bytes: &'a [u8],
+    _curve: PhantomData<P>,
+}
+
+impl<'a, P: SWModelParameters> SignatureReader<'a, P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Reads the records of `bytes`. Only the length is checked here; each record is checked as it's decoded.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TruncatedError> {
+        let record_len = signature_byte_len::<P>();
+        if bytes.len() % record_len != 0 {
+            return Err(TruncatedError {
+                len: bytes.len(),
+                record_len,
+            });
+        }
+        Ok(Self {
+            bytes,
+            _curve: PhantomData,
+        })
+    }
+
+    /// The number of records.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / signature_byte_len::<P>()
+    }
+
+    /// Whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The bytes of the record at `index`, borrowed from the input.
+    pub fn record(&self, index: usize) -> Option<&'a [u8]> {
+        let record_len = signature_byte_len::<P>();
+        self.bytes
+            .get(index.checked_mul(record_len)?..)?
+            .get(..record_len)
+    }
+
+    /// The bytes of each record, in order, borrowed from the input.
+    pub fn records(&self) -> impl ExactSizeIterator<Item = &'a [u8]> + 'a {
+        self.bytes.chunks_exact(signature_byte_len::<P>())
+    }
+
+    /// Decodes the record at `index`, checking it as [`PlumeSignature::from_bytes`] does.
+    pub fn get(&self, index: usize) -> Option<Result<PlumeSignature<P>, EncodingError>> {
+        self.record(index).map(formats::decode_binary::<P>)
+    }
+
+    /// Decodes each record, in order.
+    pub fn signatures(
+        &self,
+    ) -> impl ExactSizeIterator<Item = Result<PlumeSignature<P>, EncodingError>> + 'a {
+        self.records().map(formats::decode_binary::<P>)
+    }
+}
+
+impl<'a, P: HashToCurveSuite> SignatureReader<'a, P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Verifies each record with [`PlumeSignature::verify_non_zk`] against the key and the message `statement` gives for its index; the
+    /// results are in the order of the records, as for [`batch::verify_batch`](crate::batch::verify_batch).
+    ///
+    /// A record which doesn't decode is an [`PlumeVerifyError::InvalidSignature`].
+    pub fn verify_each<'s, F>(
+        &self,
+        pp: &'s Parameters<P>,
+        statement: F,
+        version: PlumeVersion,
+    ) -> impl Iterator<Item = Result<(), PlumeVerifyError>> + 's
+    where
+        'a: 's,
+        F: Fn(usize) -> (Message<'s>, &'s PublicKey<P>) + 's,
+    {
+        self.signatures()
+            .enumerate()
+            .map(move |(index, signature)| {
+                let (message, pk) = statement(index);
+                signature
+                    .map_err(PlumeVerifyError::InvalidSignature)?
+                    .verify_non_zk(pp, pk, message, version)
+            })
+    }
+
+    /// Verifies all the records with [`PlumeSignature::verify_batch_combined`] against the key and the message `statement` gives for
+    /// their index; `Ok(())` only if every one is valid.
+    ///
+    /// The records are decoded and combined [`COMBINED_CHUNK_LEN`] at a time, so the memory doesn't grow with the file. An
+    /// [`BatchVerifyError::Item`] has the index of the record in the whole file; a record which doesn't decode is an
+    /// [`PlumeVerifyError::InvalidSignature`]. A [`BatchVerifyError::Combination`] only says that some chunk doesn't verify;
+    /// [`Self::verify_each`] says which record.
+    pub fn verify_combined<'s>(
+        &self,
+        pp: &Parameters<P>,
+        rng: &mut (impl Rng + CryptoRng),
+        statement: impl Fn(usize) -> (Message<'s>, &'s PublicKey<P>),
+        version: PlumeVersion,
+    ) -> Result<(), BatchVerifyError> {
+        let record_len = signature_byte_len::<P>();
+        let mut signatures = Vec::with_capacity(COMBINED_CHUNK_LEN.min(self.len()));
+        for (chunk, bytes) in self
+            .bytes
+            .chunks(COMBINED_CHUNK_LEN * record_len)
+            .enumerate()
+        {
+            let start = chunk * COMBINED_CHUNK_LEN;
+            signatures.clear();
+            for (index, record) in (start..).zip(bytes.chunks_exact(record_len)) {
+                let signature =
+                    formats::decode_binary::<P>(record).map_err(|e| BatchVerifyError::Item {
+                        index,
+                        error: PlumeVerifyError::InvalidSignature(e),
+                    })?;
+                signatures.push(signature);
+            }
+
+            let items: Vec<_> = (start..)
+                .zip(&signatures)
+                .map(|(index, signature)| {
+                    let (message, pk) = statement(index);
+                    (message, pk, signature)
+                })
+                .collect();
+            PlumeSignature::verify_batch_combined(pp, rng, &items, version).map_err(|error| {
+                match error {
+                    BatchVerifyError::Item { index, error } => BatchVerifyError::Item {
+                        index: start + index,
+                        error,
+                    },
+                    error => error,
+                }
+            })?;
+        }
+        Ok(())
+    }
+}
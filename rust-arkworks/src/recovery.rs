@@ -0,0 +1,183 @@
+//! Public keys of Ethereum addresses, recovered from their ECDSA signatures, with the `eth-recovery` feature.
+//!
+//! Anonymity sets are usually given as addresses, while PLUME needs the keys. A key is recovered from any signature the address made, such
+//! as that of one of its transactions: [`SignatureSource`] is the trait an RPC client implements to list those, and [`recover_keys`] walks
+//! them until a key hashing to the address turns up. Every key is checked against its address, so a source can't slip another key in;
+//! [`anonymity_set`] then feeds the keys to [`scope::anonymity_set_id`].
+//!
+//! A signature comes with the digest it's made over, such as the signing hash of a transaction; computing that from a raw transaction is
+//! left to the source. The recovery id `v` is taken raw or with the offsets of Ethereum, `27` of the legacy ones and `35 + 2 ⋅ chain_id` of
+//! EIP-155. The rare `r` above the group order, of the recovery ids `2` and `3`, isn't supported.
+
+use crate::encoding;
+use crate::error::EncodingError;
+use crate::{optimizations, scope, validate, Parameters, PublicKey};
+use alloc::vec::Vec;
+use ark_ec::models::SWModelParameters;
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use core::fmt;
+use tiny_keccak::{Hasher, Keccak};
+
+/// An Ethereum address, the last 20 bytes of the Keccak-256 of the uncompressed key.
+pub type Address = [u8; 20];
+
+/// An ECDSA signature with its recovery id and the digest it's made over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoverableSignature {
+    /// The 32-byte digest signed, e.g. the signing hash of a transaction.
+    pub digest: [u8; 32],
+    /// The big-endian `r`, of the scalar field's width.
+    pub r: Vec<u8>,
+    /// The big-endian `s`, of the scalar field's width.
+    pub s: Vec<u8>,
+    /// The recovery id: the parity of the `y` of the nonce point, possibly with an Ethereum offset.
+    pub v: u64,
+}
+
+/// A provider of signatures made by addresses, e.g. an RPC client fetching their transactions.
+pub trait SignatureSource {
+    /// The error of a failed request.
+    type Error;
+
+    /// Some signatures made by `address`; one of them suffices.
+    fn signatures(&self, address: &Address) -> Result<Vec<RecoverableSignature>, Self::Error>;
+}
+
+/// The reason the key of an address isn't recovered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryError<E> {
+    /// The key recovered from a signature given for the address has another address.
+    AddressMismatch(Address),
+    /// The source has no signature of the address.
+    NoSignature(Address),
+    /// The request to the source failed.
+    Source(E),
+    /// A recovered key can't be encoded.
+    Encoding(EncodingError),
+}
+
+impl<E: fmt::Display> fmt::Display for RecoveryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AddressMismatch(address) => write!(
+                f,
+                "a signature of 0x{} is made by another address",
+                encoding::to_hex(address)
+            ),
+            Self::NoSignature(address) => {
+                write!(
+                    f,
+                    "no signature of 0x{} is found",
+                    encoding::to_hex(address)
+                )
+            }
+            Self::Source(e) => write!(f, "the signature source failed: {e}"),
+            Self::Encoding(e) => write!(f, "invalid key: {e:?}"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for RecoveryError<E> {}
+
+/// The address of `pk`; fails for the point at infinity.
+pub fn address<P: SWModelParameters>(pk: &PublicKey<P>) -> Result<Address, EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    if pk.is_zero() {
+        return Err(EncodingError::Identity);
+    }
+    let mut digest = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(&encoding::field_to_bytes(&pk.x));
+    keccak.update(&encoding::field_to_bytes(&pk.y));
+    keccak.finalize(&mut digest);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&digest[12..]);
+    Ok(address)
+}
+
+/// The key `signature` verifies with: `r^-1 ⋅ (s ⋅ R - z ⋅ g)` for the nonce point `R` with the `x` of `r`; `None` for a signature with
+/// values out of range or which no key verifies.
+pub fn recover<P: SWModelParameters>(
+    pp: &Parameters<P>,
+    signature: &RecoverableSignature,
+) -> Option<PublicKey<P>>
+where
+    P::BaseField: PrimeField,
+{
+    let y_odd = match signature.v {
+        0 | 1 => signature.v == 1,
+        27 | 28 => signature.v == 28,
+        v if v >= 35 => (v - 35) % 2 == 1,
+        _ => return None,
+    };
+    let r = encoding::field_from_bytes::<P::ScalarField>(&signature.r).ok()?;
+    let s = encoding::field_from_bytes::<P::ScalarField>(&signature.s).ok()?;
+    let r_inv = r.inverse()?;
+    if s.is_zero() {
+        return None;
+    }
+
+    // `r` is below the group order, which is below the field modulus for the curves of cofactor one
+    let x = encoding::field_from_bytes::<P::BaseField>(&signature.r).ok()?;
+    let nonce_point = GroupAffine::<P>::get_point_from_x(x, false)?;
+    let nonce_point = if BigInteger::is_odd(&nonce_point.y.into_repr()) == y_odd {
+        nonce_point
+    } else {
+        -nonce_point
+    };
+
+    let z = P::ScalarField::from_be_bytes_mod_order(&signature.digest);
+    let pk = optimizations::double_mul(&nonce_point, s, &pp.g_point, -z)
+        .mul(r_inv)
+        .into_affine();
+    validate::public_key(&pk).ok()?;
+    Some(pk)
+}
+
+/// The keys of `addresses`, in their order, each recovered from the first of its signatures in `source` which yields its address.
+///
+/// Invalid signatures are skipped. A valid one which doesn't recover to the address fails the import with
+/// [`RecoveryError::AddressMismatch`] rather than being skipped, as the source is then serving signatures of other accounts.
+pub fn recover_keys<P: SWModelParameters, S: SignatureSource>(
+    pp: &Parameters<P>,
+    source: &S,
+    addresses: &[Address],
+) -> Result<Vec<PublicKey<P>>, RecoveryError<S::Error>>
+where
+    P::BaseField: PrimeField,
+{
+    addresses
+        .iter()
+        .map(|expected| {
+            for signature in source.signatures(expected).map_err(RecoveryError::Source)? {
+                let Some(pk) = recover(pp, &signature) else {
+                    continue;
+                };
+                if address(&pk).ok().as_ref() != Some(expected) {
+                    return Err(RecoveryError::AddressMismatch(*expected));
+                }
+                return Ok(pk);
+            }
+            Err(RecoveryError::NoSignature(*expected))
+        })
+        .collect()
+}
+
+/// The keys of `addresses` as in [`recover_keys`], with the [`scope::anonymity_set_id`] of the set they make.
+pub fn anonymity_set<P: SWModelParameters, S: SignatureSource>(
+    pp: &Parameters<P>,
+    source: &S,
+    addresses: &[Address],
+) -> Result<(Vec<PublicKey<P>>, [u8; 32]), RecoveryError<S::Error>>
+where
+    P::BaseField: PrimeField,
+{
+    let pks = recover_keys(pp, source, addresses)?;
+    let set_id = scope::anonymity_set_id(&pks).map_err(RecoveryError::Encoding)?;
+    Ok((pks, set_id))
+}
@@ -0,0 +1,69 @@
+//! A slow reference of the curve arithmetic, written from the textbook formulas, which the optimized paths are tested against.
+//!
+//! Everything is in affine coordinates with the point at infinity as `None`, and scalars are multiplied bit by bit. Nothing here is
+//! constant time or fast; it's meant to be obviously correct.
+
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+
+type Point<P> = Option<(
+    <P as ark_ec::ModelParameters>::BaseField,
+    <P as ark_ec::ModelParameters>::BaseField,
+)>;
+
+fn from_affine<P: SWModelParameters>(point: &GroupAffine<P>) -> Point<P> {
+    (!point.infinity).then_some((point.x, point.y))
+}
+
+fn to_affine<P: SWModelParameters>(point: Point<P>) -> GroupAffine<P> {
+    match point {
+        Some((x, y)) => GroupAffine::new(x, y, false),
+        None => GroupAffine::zero(),
+    }
+}
+
+/// `a + b` by the chord and tangent rule.
+fn add<P: SWModelParameters>(a: Point<P>, b: Point<P>) -> Point<P> {
+    let ((x1, y1), (x2, y2)) = match (a, b) {
+        (None, b) => return b,
+        (a, None) => return a,
+        (Some(a), Some(b)) => (a, b),
+    };
+    let lambda = if x1 == x2 {
+        if y1 != y2 || y1.is_zero() {
+            // `b = -a`
+            return None;
+        }
+        // the tangent: (3 x^2 + a) / 2y
+        let three = P::BaseField::one() + P::BaseField::one() + P::BaseField::one();
+        (three * x1 * x1 + P::COEFF_A) * (y1 + y1).inverse()?
+    } else {
+        (y2 - y1) * (x2 - x1).inverse()?
+    };
+    let x3 = lambda * lambda - x1 - x2;
+    let y3 = lambda * (x1 - x3) - y1;
+    Some((x3, y3))
+}
+
+/// `k ⋅ point` by double-and-add from the most significant bit.
+pub fn mul<P: SWModelParameters>(point: &GroupAffine<P>, k: P::ScalarField) -> GroupAffine<P> {
+    let k = k.into_repr();
+    let base = from_affine(point);
+    let mut acc = None;
+    for i in (0..P::ScalarField::size_in_bits()).rev() {
+        acc = add::<P>(acc, acc);
+        if k.get_bit(i) {
+            acc = add::<P>(acc, base);
+        }
+    }
+    to_affine(acc)
+}
+
+/// `Σ k_i ⋅ points_i`, one multiplication at a time.
+pub fn linear_combination<P: SWModelParameters>(
+    terms: &[(GroupAffine<P>, P::ScalarField)],
+) -> GroupAffine<P> {
+    to_affine(terms.iter().fold(None, |acc, (point, k)| {
+        add::<P>(acc, from_affine(&mul(point, *k)))
+    }))
+}
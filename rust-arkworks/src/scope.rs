@@ -0,0 +1,278 @@
+//! Layouts of the messages nullifiers are produced for.
+//!
+//! A nullifier is determined by the key and the message, so the message layout of an application decides what its nullifiers stand for.
+//! The helpers here fix such layouts, so that independent implementations agree on them byte for byte. Every layout is a sequence of fields,
+//! each prefixed with its length as a big-endian `u64`; the first field is a tag naming the layout.
+
+use crate::error::validity;
+use crate::{
+    compute_h, encoding, secret_mul, EncodingError, HashToCurveError, HashToCurveSuite, Parameters,
+    PlumeSignature, PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use alloc::vec::Vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::PrimeField;
+use ark_std::rand::Rng;
+use core::ops::Range;
+use sha2::{Digest, Sha256};
+
+const WEIGHTED_UNIT_TAG: &[u8] = b"PLUME/weighted-unit";
+const ANONYMITY_SET_TAG: &[u8] = b"PLUME/anonymity-set";
+const SET_SCOPE_TAG: &[u8] = b"PLUME/set-scope";
+const SEQUENCE_TAG: &[u8] = b"PLUME/sequence";
+const EXTERNAL_NULLIFIER_TAG: &[u8] = b"PLUME/external-nullifier";
+
+pub(crate) use plume_core::transcript::framed;
+
+/// The message for the unit `index` of a weighted anonymity set member under `scope`.
+///
+/// Stake-weighted applications give a member one nullifier per unit of its weight: signing the messages for the indices `0..weight` yields
+/// `weight` distinct nullifiers, and each index can be spent once. The fields are the tag `PLUME/weighted-unit`, `scope`, and `index`
+/// as a big-endian `u64`.
+pub fn weighted_unit_message(scope: &[u8], index: u64) -> Vec<u8> {
+    framed(&[WEIGHTED_UNIT_TAG, scope, &index.to_be_bytes()])
+}
+
+/// The message hashed to the curve for `message` under the external nullifier `scope`, such as the id of an epoch or of a poll.
+///
+/// The nullifier `hash[scoped_message(scope, message), pk]^sk` is then one per scope, as the external nullifiers of Semaphore: the same key
+/// and message have unrelated nullifiers in two scopes. The fields are the tag `PLUME/external-nullifier`, `scope`, and `message`.
+pub fn scoped_message(scope: &[u8], message: &[u8]) -> Vec<u8> {
+    framed(&[EXTERNAL_NULLIFIER_TAG, scope, message])
+}
+
+/// Signs `message` under the external nullifier `scope`, i.e. [`scoped_message`]; see [`PlumeSignature::sign`].
+pub fn sign_scoped<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    rng: &mut impl Rng,
+    keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+    scope: &[u8],
+    message: &[u8],
+    version: PlumeVersion,
+) -> Result<PlumeSignature<P>, HashToCurveError> {
+    PlumeSignature::sign(pp, rng, keypair, &scoped_message(scope, message), version)
+}
+
+/// Verifies a signature made with [`sign_scoped`] on `message` under `scope` by `pk`.
+pub fn verify_scoped<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    pk: &PublicKey<P>,
+    scope: &[u8],
+    message: &[u8],
+    signature: &PlumeSignature<P>,
+    version: PlumeVersion,
+) -> Result<bool, HashToCurveError> {
+    validity(signature.verify_non_zk(pp, pk, &scoped_message(scope, message), version))
+}
+
+/// The nullifier of `message` under `scope` computed from the secret key alone, e.g. for a wallet to check whether it took part in a poll.
+pub fn scoped_nullifier<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    sk: &SecretKeyMaterial<P>,
+    scope: &[u8],
+    message: &[u8],
+) -> Result<GroupAffine<P>, HashToCurveError> {
+    let pk = secret_mul(&pp.g_point, sk);
+    let hashed_to_curve = compute_h::<P>(&pk, &scoped_message(scope, message))?;
+    Ok(secret_mul(&hashed_to_curve, sk))
+}
+
+/// The message for the position `index` of the nullifier sequence under `scope`.
+///
+/// Protocols spending notes in order give a key one nullifier per note index. The fields are the tag `PLUME/sequence`, `scope`, and `index`
+/// as a big-endian `u64`.
+pub fn sequence_message(scope: &[u8], index: u64) -> Vec<u8> {
+    framed(&[SEQUENCE_TAG, scope, &index.to_be_bytes()])
+}
+
+/// The nullifier at `index` of the sequence under `scope`, i.e. the nullifier of a signature on [`sequence_message`].
+///
+/// It's computed from the secret key alone, without signing, e.g. for a wallet to find which notes are spent.
+pub fn nullifier_at_index<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    sk: &SecretKeyMaterial<P>,
+    scope: &[u8],
+    index: u64,
+) -> Result<GroupAffine<P>, HashToCurveError> {
+    let pk = pp.g_point.mul(*sk).into_affine();
+    sequence_nullifier(&pk, sk, scope, index)
+}
+
+/// The nullifiers at the indices of `range` of the sequence under `scope`; see [`nullifier_at_index`].
+pub fn nullifiers_in_range<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    sk: &SecretKeyMaterial<P>,
+    scope: &[u8],
+    range: Range<u64>,
+) -> Result<Vec<GroupAffine<P>>, HashToCurveError> {
+    let pk = pp.g_point.mul(*sk).into_affine();
+    range
+        .map(|index| sequence_nullifier(&pk, sk, scope, index))
+        .collect()
+}
+
+fn sequence_nullifier<P: HashToCurveSuite>(
+    pk: &PublicKey<P>,
+    sk: &SecretKeyMaterial<P>,
+    scope: &[u8],
+    index: u64,
+) -> Result<GroupAffine<P>, HashToCurveError> {
+    let hashed_to_curve = compute_h::<P>(pk, &sequence_message(scope, index))?;
+    Ok(hashed_to_curve.mul(*sk).into_affine())
+}
+
+/// Verifies a signature on the position `index` of the sequence under `scope`, which has `len` positions.
+///
+/// Returns `false` for an index outside `0..len`, even if the signature itself is valid.
+pub fn verify_at_index<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    pk: &PublicKey<P>,
+    scope: &[u8],
+    index: u64,
+    len: u64,
+    signature: &PlumeSignature<P>,
+    version: PlumeVersion,
+) -> Result<bool, HashToCurveError> {
+    if index >= len {
+        return Ok(false);
+    }
+    validity(signature.verify_non_zk(pp, pk, &sequence_message(scope, index), version))
+}
+
+/// Verifies signatures on consecutive positions of the sequence under `scope`, the first one being at `start`.
+///
+/// Returns `false` if any position is outside `0..len` or any signature is invalid.
+pub fn verify_sequence<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    pk: &PublicKey<P>,
+    scope: &[u8],
+    start: u64,
+    len: u64,
+    signatures: &[PlumeSignature<P>],
+    version: PlumeVersion,
+) -> Result<bool, HashToCurveError> {
+    for (index, signature) in (start..).zip(signatures) {
+        if !verify_at_index(pp, pk, scope, index, len, signature, version)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The identifier of the exact set of `pks`.
+///
+/// It's the SHA-256 of the tag `PLUME/anonymity-set` followed by the compressed keys, sorted and deduplicated, so that neither the order nor
+/// repetitions change the identifier.
+pub fn anonymity_set_id<P: SWModelParameters>(
+    pks: &[PublicKey<P>],
+) -> Result<[u8; 32], EncodingError>
+where
+    P::BaseField: PrimeField,
+{
+    let mut encodings = pks
+        .iter()
+        .map(encoding::point_to_bytes)
+        .collect::<Result<Vec<_>, _>>()?;
+    encodings.sort();
+    encodings.dedup();
+
+    let fields: Vec<&[u8]> = [ANONYMITY_SET_TAG]
+        .into_iter()
+        .chain(encodings.iter().map(Vec::as_slice))
+        .collect();
+    let mut id = [0u8; 32];
+    id.copy_from_slice(&Sha256::digest(framed(&fields)));
+    Ok(id)
+}
+
+/// Binds `scope` to the anonymity set `set_id` of [`anonymity_set_id`].
+///
+/// The output is a scope on its own: sign it directly, or pass it to the other layouts such as [`weighted_unit_message`]. Nullifiers for the
+/// same scope then differ between sets, so a proof can't be replayed against another set. The fields are the tag `PLUME/set-scope`,
+/// `set_id`, and `scope`.
+pub fn set_scope(set_id: &[u8; 32], scope: &[u8]) -> Vec<u8> {
+    framed(&[SET_SCOPE_TAG, set_id, scope])
+}
+
+/// The leaf data of a weighted anonymity set member: its public key and the number of units it holds.
+pub struct WeightedLeaf<P: SWModelParameters> {
+    /// The member's public key.
+    pub pk: PublicKey<P>,
+    /// The number of units; valid indices are `0..weight`.
+    pub weight: u64,
+}
+
+impl<P: SWModelParameters> WeightedLeaf<P> {
+    /// Whether `index` is within the weight of the leaf.
+    pub fn contains_unit(&self, index: u64) -> bool {
+        index < self.weight
+    }
+}
+
+impl<P: HashToCurveSuite> WeightedLeaf<P> {
+    /// Verifies a signature on the unit `index` under `scope` by the leaf's key.
+    ///
+    /// Returns `false` for an index outside the weight, even if the signature itself is valid.
+    pub fn verify_unit(
+        &self,
+        pp: &Parameters<P>,
+        scope: &[u8],
+        index: u64,
+        signature: &PlumeSignature<P>,
+        version: PlumeVersion,
+    ) -> Result<bool, HashToCurveError> {
+        if !self.contains_unit(index) {
+            return Ok(false);
+        }
+        validity(signature.verify_non_zk(
+            pp,
+            &self.pk,
+            &weighted_unit_message(scope, index),
+            version,
+        ))
+    }
+}
+
+impl<P: SWModelParameters> WeightedLeaf<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Encodes the leaf as the compressed public key followed by the weight as a big-endian `u64`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EncodingError> {
+        Ok([
+            encoding::point_to_bytes(&self.pk)?,
+            self.weight.to_be_bytes().to_vec(),
+        ]
+        .concat())
+    }
+
+    /// The hex of [`Self::to_bytes`].
+    pub fn to_hex(&self) -> Result<String, EncodingError> {
+        Ok(encoding::to_hex(&self.to_bytes()?))
+    }
+
+    /// Decodes the hex of [`Self::to_bytes`]; see [`encoding::from_hex`].
+    pub fn from_hex(hex: &str) -> Result<Self, EncodingError> {
+        Self::from_bytes(&encoding::from_hex(hex)?)
+    }
+
+    /// Decodes the leaf from the encoding of [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EncodingError> {
+        let expected = encoding::point_byte_len::<P>() + 8;
+        if bytes.len() != expected {
+            return Err(EncodingError::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        let (pk_bytes, weight_bytes) = bytes.split_at(expected - 8);
+
+        let mut weight = [0u8; 8];
+        weight.copy_from_slice(weight_bytes);
+        Ok(Self {
+            pk: encoding::point_from_bytes(pk_bytes)?,
+            weight: u64::from_be_bytes(weight),
+        })
+    }
+}
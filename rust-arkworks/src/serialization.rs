@@ -0,0 +1,124 @@
+//! `serde` support, with the `serde` feature.
+//!
+//! Values are written in the encodings of [`mod@crate::encoding`]: as lowercase hex strings in human-readable formats such as JSON, and as
+//! bytes in binary ones. Decoding is as strict as there, and hex with uppercase digits is rejected, so a value has a single accepted form.
+//!
+//! [`PlumeSignature`](crate::PlumeSignature) and [`Parameters`](crate::Parameters) implement the traits. Public keys and nullifiers are
+//! `GroupAffine` of arkworks, which this crate can't implement them for; a field of them takes
+//! `#[serde(with = "plume_arkworks::serialization::point")]`.
+
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{Deserializer, Error, SeqAccess, Visitor};
+use serde::Serializer;
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&crate::encoding::to_hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    max_len: usize,
+) -> Result<Vec<u8>, D::Error> {
+    let visitor = BytesVisitor { max_len };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+/// The bytes of a value encoded to at most `max_len` bytes.
+struct BytesVisitor {
+    max_len: usize,
+}
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a lowercase hex string or bytes")
+    }
+
+    // the digits alone, without the `0x` which `encoding::from_hex` tolerates, for a single accepted form
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        crate::encoding::hex_digits(v)
+            .map_err(|_| E::custom("the string isn't an even number of lowercase hex digits"))
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    // formats without a bytes type write them as a sequence, whose length hint is the input's to choose
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default().min(self.max_len));
+        while let Some(byte) = seq.next_element()? {
+            if bytes.len() == self.max_len {
+                return Err(A::Error::invalid_length(self.max_len + 1, &self));
+            }
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+/// A curve point as [`encoding::point_to_bytes`](crate::encoding::point_to_bytes); for `#[serde(with = "...")]`.
+pub mod point {
+    use crate::encoding;
+    use alloc::format;
+    use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+    use ark_ff::PrimeField;
+    use serde::de::Error;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<P: SWModelParameters, S: Serializer>(
+        point: &GroupAffine<P>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        P::BaseField: PrimeField,
+    {
+        let bytes = encoding::point_to_bytes(point)
+            .map_err(|e| <S::Error as serde::ser::Error>::custom(format!("{e:?}")))?;
+        super::serialize_bytes(&bytes, serializer)
+    }
+
+    pub fn deserialize<'de, P: SWModelParameters, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<GroupAffine<P>, D::Error>
+    where
+        P::BaseField: PrimeField,
+    {
+        let bytes = super::deserialize_bytes(deserializer, encoding::point_byte_len::<P>())?;
+        encoding::point_from_bytes(&bytes).map_err(|e| D::Error::custom(format!("{e:?}")))
+    }
+}
+
+/// A prime field element, such as a scalar, as [`encoding::field_to_bytes`](crate::encoding::field_to_bytes); for
+/// `#[serde(with = "...")]`.
+pub mod field {
+    use crate::encoding;
+    use alloc::format;
+    use ark_ff::PrimeField;
+    use serde::de::Error;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<F: PrimeField, S: Serializer>(
+        element: &F,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_bytes(&encoding::field_to_bytes(element), serializer)
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<F, D::Error> {
+        let bytes = super::deserialize_bytes(deserializer, encoding::field_byte_len::<F>())?;
+        encoding::field_from_bytes(&bytes).map_err(|e| D::Error::custom(format!("{e:?}")))
+    }
+}
@@ -0,0 +1,93 @@
+//! The [`signature`] crate traits over [`PlumeSignature`], for code which is generic over them.
+//!
+//! [`PlumeSigner`] implements [`RandomizedSigner`], and [`Signer`] with the nonce from [`rand::thread_rng`]; [`PlumeVerifier`] implements
+//! [`Verifier`]. Both are bound to the parameters, the key, and the version, so the traits' `msg` is all that's left. A failure to hash
+//! to the curve is the source of the [`signature::Error`] with the `std` feature; `Signer` needs it as well.
+
+use crate::HashToCurveSuite;
+use crate::{Parameters, PlumeSignature, PlumeVersion, PublicKey, SecretKeyMaterial};
+use ark_ec::models::SWModelParameters;
+use ark_ec::AffineCurve;
+use signature::rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
+use signature::Signer;
+use signature::{Error, RandomizedSigner, Verifier};
+
+/// Signs with a key pair and a version fixed at construction.
+pub struct PlumeSigner<'a, P: SWModelParameters> {
+    pp: &'a Parameters<P>,
+    pk: PublicKey<P>,
+    sk: &'a SecretKeyMaterial<P>,
+    /// The version of the signatures.
+    pub version: PlumeVersion,
+}
+
+impl<'a, P: SWModelParameters> PlumeSigner<'a, P> {
+    /// A signer with `sk`; its public key is computed once here.
+    pub fn new(pp: &'a Parameters<P>, sk: &'a SecretKeyMaterial<P>, version: PlumeVersion) -> Self {
+        Self {
+            pp,
+            pk: pp.g_point.mul(*sk).into(),
+            sk,
+            version,
+        }
+    }
+
+    /// The [`PlumeVerifier`] of the signatures of this signer.
+    pub fn verifier(&self) -> PlumeVerifier<'a, P> {
+        PlumeVerifier::new(self.pp, self.pk, self.version)
+    }
+}
+
+impl<'a, P: HashToCurveSuite> RandomizedSigner<PlumeSignature<P>> for PlumeSigner<'a, P> {
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        msg: &[u8],
+    ) -> Result<PlumeSignature<P>, Error> {
+        PlumeSignature::sign(self.pp, rng, (&self.pk, self.sk), msg, self.version)
+            .map_err(with_source)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, P: HashToCurveSuite> Signer<PlumeSignature<P>> for PlumeSigner<'a, P> {
+    fn try_sign(&self, msg: &[u8]) -> Result<PlumeSignature<P>, Error> {
+        self.try_sign_with_rng(&mut rand::thread_rng(), msg)
+    }
+}
+
+/// Verifies the signatures of a public key made with a version fixed at construction.
+pub struct PlumeVerifier<'a, P: SWModelParameters> {
+    pp: &'a Parameters<P>,
+    /// The key the signatures are checked against.
+    pub pk: PublicKey<P>,
+    /// The version the signatures are expected to be made with.
+    pub version: PlumeVersion,
+}
+
+impl<'a, P: SWModelParameters> PlumeVerifier<'a, P> {
+    /// A verifier of the signatures of `pk` made with `version`.
+    pub fn new(pp: &'a Parameters<P>, pk: PublicKey<P>, version: PlumeVersion) -> Self {
+        Self { pp, pk, version }
+    }
+}
+
+impl<'a, P: HashToCurveSuite> Verifier<PlumeSignature<P>> for PlumeVerifier<'a, P> {
+    fn verify(&self, msg: &[u8], signature: &PlumeSignature<P>) -> Result<(), Error> {
+        signature
+            .verify(self.pp, &self.pk, msg, self.version)
+            .map_err(with_source)
+    }
+}
+
+// `signature::Error` only carries a source with `std`
+fn with_source(e: impl core::error::Error + Send + Sync + 'static) -> Error {
+    #[cfg(feature = "std")]
+    return Error::from_source(e);
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = e;
+        Error::new()
+    }
+}
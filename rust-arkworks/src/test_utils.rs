@@ -0,0 +1,61 @@
+//! Deterministic key material and signatures for tests, behind the `test-utils` feature.
+//!
+//! Everything here is derived from a `u64` seed with SHA-256, so fixtures are the same on every run and every platform. The keys are
+//! public knowledge: never use them outside of tests.
+
+use crate::{
+    HashToCurveError, HashToCurveSuite, Message, Parameters, PlumeSignature, PlumeVersion,
+    PublicKey, SecretKeyMaterial,
+};
+use ark_ec::{models::SWModelParameters, AffineCurve, ProjectiveCurve};
+use ark_ff::PrimeField;
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+use sha2::{Digest, Sha256};
+
+const KEY_TAG: &[u8] = b"PLUME/test-key";
+const NONCE_TAG: &[u8] = b"PLUME/test-nonce";
+
+/// Generates a deterministic keypair from a seed.
+///
+/// `plume_test_keypair!(seed)` is for `secp256k1` with the standard generator; `plume_test_keypair!(pp, seed)` takes the parameters.
+#[macro_export]
+macro_rules! plume_test_keypair {
+    ($seed:expr) => {
+        $crate::test_utils::keypair(&$crate::test_utils::parameters(), $seed)
+    };
+    ($pp:expr, $seed:expr) => {
+        $crate::test_utils::keypair($pp, $seed)
+    };
+}
+
+/// The `secp256k1` parameters with the standard generator.
+pub fn parameters() -> Parameters<Secp256k1Parameters> {
+    Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    }
+}
+
+/// The keypair for `seed`.
+pub fn keypair<P: SWModelParameters>(
+    pp: &Parameters<P>,
+    seed: u64,
+) -> (PublicKey<P>, SecretKeyMaterial<P>) {
+    let sk = derive_scalar::<P::ScalarField>(&[KEY_TAG, &seed.to_be_bytes()]);
+    (pp.g_point.mul(sk).into_affine(), sk)
+}
+
+/// A signature on `message` by the keypair for `seed`, with the nonce derived from the seed and the message.
+pub fn signature<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    seed: u64,
+    message: Message,
+    version: PlumeVersion,
+) -> Result<PlumeSignature<P>, HashToCurveError> {
+    let (pk, sk) = keypair(pp, seed);
+    let r = derive_scalar::<P::ScalarField>(&[NONCE_TAG, &seed.to_be_bytes(), message]);
+    PlumeSignature::sign_with_r(pp, (&pk, &sk), message, r, version)
+}
+
+fn derive_scalar<F: PrimeField>(fields: &[&[u8]]) -> F {
+    F::from_be_bytes_mod_order(&Sha256::digest(crate::scope::framed(fields)))
+}
@@ -1,9 +1,15 @@
-use crate::hash_to_curve::{hash_to_curve, k256_affine_to_arkworks_secp256k1_affine};
-use crate::{PlumeSignature, PlumeVersion};
+use crate::challenge::ChallengeDerivation;
+use crate::hash_to_curve::{
+    clear_cofactor, hash_to_curve, k256_affine_to_arkworks_secp256k1_affine,
+};
+use crate::linkage::SameSignerProof;
+use crate::scope::{self, WeightedLeaf};
+use crate::{encoding, EncodingError, PlumeSignature, PlumeVerifyError, PlumeVersion};
 use ark_ec::models::short_weierstrass_jacobian::GroupAffine;
 use ark_ec::{AffineCurve, ProjectiveCurve};
 use ark_ff::biginteger;
 use ark_ff::bytes::{FromBytes, ToBytes};
+use ark_ff::Zero;
 use ark_std::rand;
 use k256::{ProjectivePoint, Scalar};
 use rand::{prelude::ThreadRng, thread_rng};
@@ -103,7 +109,7 @@ pub fn test_sign_and_verify() {
     .unwrap();
 
     let is_valid = sig.verify_non_zk(&pp, &keypair.0, message, PlumeVersion::V1);
-    assert!(is_valid.unwrap());
+    assert!(is_valid.is_ok());
 
     let sig = PlumeSignature::sign(
         &pp,
@@ -115,7 +121,7 @@ pub fn test_sign_and_verify() {
     .unwrap();
 
     let is_valid = sig.verify_non_zk(&pp, &keypair.0, message, PlumeVersion::V2);
-    assert!(is_valid.unwrap());
+    assert!(is_valid.is_ok());
 }
 
 pub fn compute_h() -> GroupAffine<Secp256k1Parameters> {
@@ -127,7 +133,7 @@ pub fn compute_h() -> GroupAffine<Secp256k1Parameters> {
     let pk_projective = g.mul(sk);
     let pk = GroupAffine::<Secp256k1Parameters>::from(pk_projective);
 
-    hash_to_curve::<secp256k1::fields::Fq, Secp256k1Parameters>(message, &pk).unwrap()
+    hash_to_curve::<Secp256k1Parameters>(message, &pk).unwrap()
 }
 
 #[test]
@@ -166,7 +172,99 @@ pub fn test_against_zk_nullifier_sig_g_r() {
     );
 }
 
-//TODO: add test vectors for hash_to_curve
+#[test]
+pub fn test_hash_to_curve_rfc9380_vectors() {
+    use crate::hash_to_curve::{expand_message_xmd, hash_bytes_to_curve, DST, SUITE};
+    use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander};
+
+    // RFC 9380, K.1
+    let expander_dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    for (msg, uniform_bytes) in [
+        (
+            &b""[..],
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+        ),
+        (
+            &b"abc"[..],
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        ),
+        (
+            &b"abcdef0123456789"[..],
+            "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+        ),
+    ] {
+        assert_eq!(
+            hex::encode(expand_message_xmd(&[msg], expander_dst, 0x20).unwrap()),
+            uniform_bytes
+        );
+    }
+
+    // the same output as the expander of `elliptic-curve`, over the block boundaries and a tag which has to be hashed down
+    let long_dst = [b'D'; 300];
+    for dst in [&expander_dst[..], &long_dst[..]] {
+        for len in [1, 32, 33, 64, 97, 255] {
+            let mut expected = vec![0u8; len];
+            ExpandMsgXmd::<k256::sha2::Sha256>::expand_message(&[&b"plume"[..], b"!"], dst, len)
+                .unwrap()
+                .fill_bytes(&mut expected);
+            assert_eq!(
+                expand_message_xmd(&[&b"plume"[..], b"!"], dst, len).unwrap(),
+                expected
+            );
+        }
+    }
+    assert!(matches!(
+        expand_message_xmd(&[b"abc"], b"", 32),
+        Err(crate::HashToCurveError::InvalidDst { .. })
+    ));
+    assert!(expand_message_xmd(&[b"abc"], expander_dst, 255 * 32).is_ok());
+    assert!(matches!(
+        expand_message_xmd(&[b"abc"], expander_dst, 256 * 32),
+        Err(crate::HashToCurveError::InvalidInput {
+            len: 8192,
+            max: 8160
+        })
+    ));
+
+    // RFC 9380, J.8.1
+    assert!(DST.ends_with(SUITE.as_bytes()));
+    for (msg, x, y) in [
+        (
+            &b""[..],
+            "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+            "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+        ),
+        (
+            &b"abc"[..],
+            "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+            "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+        ),
+        (
+            &b"abcdef0123456789"[..],
+            "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+            "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+        ),
+    ] {
+        let p = hash_bytes_to_curve::<Secp256k1Parameters>(&[msg], DST).unwrap();
+        assert_eq!(coord_to_hex(p.x.into()), format!("{:0>80}", x));
+        assert_eq!(coord_to_hex(p.y.into()), format!("{:0>80}", y));
+    }
+
+    // the tag separates the outputs
+    let p = hash_bytes_to_curve::<Secp256k1Parameters>(&[b"abc"], DST).unwrap();
+    let q = hash_bytes_to_curve::<Secp256k1Parameters>(&[b"abc"], b"PLUME/test").unwrap();
+    assert_ne!(p, q);
+    assert_eq!(
+        q,
+        hash_bytes_to_curve::<Secp256k1Parameters>(&[&b"ab"[..], b"c"], b"PLUME/test").unwrap()
+    );
+    // an empty tag is reported as such, not as a failure of the map of `k256`
+    assert!(matches!(
+        hash_bytes_to_curve::<Secp256k1Parameters>(&[b"abc"], b""),
+        Err(crate::HashToCurveError::InvalidDst { .. })
+    ));
+}
+
 #[test]
 pub fn test_against_zk_nullifier_sig_h() {
     let h = compute_h();
@@ -245,7 +343,7 @@ pub fn test_against_zk_nullifier_sig_c_and_s() {
     let sig =
         PlumeSignature::sign_with_r(&pp, (&keypair.0, &keypair.1), message, r, PlumeVersion::V2)
             .unwrap();
-    
+
     assert_eq!(
         coord_to_hex(sig.c.into()),
         "00000000000000003dbfb717705010d4f44a70720c95e74b475bd3a783ab0b9e8a6b3b363434eb96"
@@ -255,3 +353,3226 @@ pub fn test_against_zk_nullifier_sig_c_and_s() {
         "0000000000000000528e8fbb6452f82200797b1a73b2947a92524bd611085a920f1177cb8098136b"
     );
 }
+
+#[test]
+pub fn test_hash_to_curve_output_in_prime_order_subgroup() {
+    let (_, g) = test_template();
+    let pk = g.mul(hex_to_fr(&hardcoded_sk())).into_affine();
+
+    for message in [&b""[..], &b"Message"[..], hardcoded_msg().as_bytes()] {
+        let h = hash_to_curve::<Secp256k1Parameters>(message, &pk).unwrap();
+
+        assert!(h.is_on_curve());
+        assert!(h.is_in_correct_subgroup_assuming_on_curve());
+        // `secp256k1` has no cofactor, so clearing it must not change the point
+        assert_eq!(clear_cofactor(h), h);
+    }
+}
+
+#[test]
+pub fn test_try_and_increment_iterations() {
+    use crate::hash_to_curve::{_try_and_increment_with_limit, try_and_increment_counted};
+    use crate::HashToCurveError;
+    use ark_std::rand::RngCore;
+
+    // about half of the candidates decode to a point, so the attempts are geometrically distributed
+    let mut rng = thread_rng();
+    let mut histogram = [0usize; 256];
+    for _ in 0..1000 {
+        let mut msg = [0u8; 32];
+        rng.fill_bytes(&mut msg);
+        let (_, iterations) =
+            try_and_increment_counted::<secp256k1::Projective>(&msg, 256).unwrap();
+        histogram[iterations - 1] += 1;
+    }
+    let max = histogram.iter().rposition(|count| *count != 0).unwrap() + 1;
+    let mean = histogram
+        .iter()
+        .enumerate()
+        .map(|(i, count)| (i + 1) * count)
+        .sum::<usize>() as f64
+        / 1000.0;
+    assert!(mean < 3.0);
+    assert!(max < 32);
+
+    // a message needing more than one attempt fails under the limit of one
+    let msg = (0u64..)
+        .map(|i| i.to_be_bytes())
+        .find(|msg| {
+            try_and_increment_counted::<secp256k1::Projective>(msg, 256)
+                .unwrap()
+                .1
+                > 1
+        })
+        .unwrap();
+    assert!(matches!(
+        _try_and_increment_with_limit::<secp256k1::Projective>(&msg, 1),
+        Err(HashToCurveError::IterationLimitExceeded { iterations: 1 })
+    ));
+}
+
+#[test]
+pub fn test_hash_to_curve_error_context() {
+    use crate::HashToCurveError;
+
+    let err = HashToCurveError::IterationLimitExceeded { iterations: 256 };
+    assert!(err.to_string().contains("256"));
+    let err = HashToCurveError::UnsupportedSuite {
+        suite: "QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_".to_owned(),
+        reason: "crypto error".to_owned(),
+    };
+    assert!(err
+        .to_string()
+        .contains("QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_"));
+}
+
+#[test]
+pub fn test_affine_to_bytes_matches_sec1() {
+    use secp256k1::sec1::Sec1EncodePoint;
+
+    let g = Affine::prime_subgroup_generator();
+    for i in 1..20u64 {
+        let point = g.mul(secp256k1::fields::Fr::from(i)).into_affine();
+        assert_eq!(
+            crate::affine_to_bytes(&point),
+            hex::decode(point.to_encoded_point(true)).unwrap()
+        );
+    }
+    assert_eq!(crate::affine_to_bytes(&Affine::zero()), vec![0x00]);
+}
+
+#[test]
+pub fn test_s_reduces_in_scalar_field() {
+    type Fr = secp256k1::fields::Fr;
+
+    let (_, g) = test_template();
+    let pp = Parameters { g_point: g };
+    // `r` and `sk` at the top of the field, so `r + sk ⋅ c` wraps the modulus
+    let r = -Fr::from(1u64);
+    let sk = -Fr::from(2u64);
+    let pk = g.mul(sk).into_affine();
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig = PlumeSignature::sign_with_r(&pp, (&pk, &sk), b"Message", r, version).unwrap();
+        assert_eq!(sig.s, r + sk * sig.c);
+        assert!(sig.verify_non_zk(&pp, &pk, b"Message", version).is_ok());
+    }
+}
+
+#[test]
+pub fn test_encoding_round_trip() {
+    let (_, g) = test_template();
+    let sk = hex_to_fr(&hardcoded_sk());
+    let pk = g.mul(sk).into_affine();
+
+    let g_bytes = encoding::point_to_bytes(&g).unwrap();
+    assert_eq!(
+        hex::encode(&g_bytes),
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+    for point in [g, pk, -pk] {
+        let bytes = encoding::point_to_bytes(&point).unwrap();
+        assert_eq!(bytes.len(), 33);
+        assert_eq!(
+            encoding::point_from_bytes::<Secp256k1Parameters>(&bytes).unwrap(),
+            point
+        );
+    }
+
+    let sk_bytes = encoding::field_to_bytes(&sk);
+    assert_eq!(hex::encode(&sk_bytes), hardcoded_sk());
+    assert_eq!(
+        encoding::field_from_bytes::<secp256k1::fields::Fr>(&sk_bytes).unwrap(),
+        sk
+    );
+}
+
+#[test]
+pub fn test_encoding_rejects_malformed() {
+    let (_, g) = test_template();
+    let mut g_bytes = encoding::point_to_bytes(&g).unwrap();
+
+    assert_eq!(
+        encoding::point_from_bytes::<Secp256k1Parameters>(&g_bytes[1..]),
+        Err(EncodingError::InvalidLength {
+            expected: 33,
+            actual: 32
+        })
+    );
+    assert_eq!(
+        encoding::point_to_bytes(&GroupAffine::<Secp256k1Parameters>::zero()),
+        Err(EncodingError::Identity)
+    );
+
+    g_bytes[0] = 0x04;
+    assert_eq!(
+        encoding::point_from_bytes::<Secp256k1Parameters>(&g_bytes),
+        Err(EncodingError::InvalidTag(0x04))
+    );
+
+    // there's no point with `x = 5` on `secp256k1`
+    let mut not_on_curve = [0u8; 33];
+    not_on_curve[0] = 0x02;
+    not_on_curve[32] = 5;
+    assert_eq!(
+        encoding::point_from_bytes::<Secp256k1Parameters>(&not_on_curve),
+        Err(EncodingError::NotOnCurve)
+    );
+
+    // the group order isn't a canonical scalar
+    let order =
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+    assert_eq!(
+        encoding::field_from_bytes::<secp256k1::fields::Fr>(&order),
+        Err(EncodingError::NonCanonicalField)
+    );
+}
+
+#[test]
+pub fn test_challenge_derivation() {
+    use ark_ff::PrimeField;
+    use sha2::{Digest, Sha256};
+    type Fr = secp256k1::fields::Fr;
+
+    // `secp256k1` keeps the original derivation; it's what the vectors above rely on
+    assert_eq!(
+        ChallengeDerivation::for_field::<Fr>(),
+        ChallengeDerivation::ModOrder
+    );
+
+    let preimage = hardcoded_msg();
+    let preimage = preimage.as_bytes();
+    let mod_order: Fr = ChallengeDerivation::ModOrder.derive::<_, Sha256>(preimage);
+    assert_eq!(
+        mod_order,
+        Fr::from_be_bytes_mod_order(&Sha256::digest(preimage))
+    );
+    // the digest is below the order, so truncation keeps it as it is
+    assert_eq!(
+        ChallengeDerivation::TruncateAndRetry.derive::<Fr, Sha256>(preimage),
+        mod_order
+    );
+
+    let wide: Fr = ChallengeDerivation::WideReduction.derive::<_, Sha256>(preimage);
+    assert_ne!(wide, mod_order);
+    assert_eq!(
+        ChallengeDerivation::WideReduction.derive::<Fr, Sha256>(preimage),
+        wide
+    );
+}
+
+/// The scalar field of BLS12-381, of 255 bits: reducing a 256-bit digest modulo its order is biased, unlike with `secp256k1`.
+mod bls12_381_fr {
+    use ark_ff::biginteger::BigInteger256 as BigInteger;
+    use ark_ff::fields::{FftParameters, Fp256, Fp256Parameters, FpParameters};
+
+    pub type Fr = Fp256<FrParameters>;
+
+    pub struct FrParameters;
+
+    impl Fp256Parameters for FrParameters {}
+
+    impl FftParameters for FrParameters {
+        type BigInt = BigInteger;
+        const TWO_ADICITY: u32 = 32;
+        const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+            0xb9b58d8c5f0e466a,
+            0x5b1b4c801819d7ec,
+            0x0af53ae352a31e64,
+            0x5bf3adda19e9b27b,
+        ]);
+    }
+
+    impl FpParameters for FrParameters {
+        const MODULUS: BigInteger = BigInteger([
+            0xffffffff00000001,
+            0x53bda402fffe5bfe,
+            0x3339d80809a1d805,
+            0x73eda753299d7d48,
+        ]);
+        const MODULUS_BITS: u32 = 255;
+        const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+        const REPR_SHAVE_BITS: u32 = 1;
+        const R: BigInteger = BigInteger([
+            0x00000001fffffffe,
+            0x5884b7fa00034802,
+            0x998c4fefecbc4ff5,
+            0x1824b159acc5056f,
+        ]);
+        const R2: BigInteger = BigInteger([
+            0xc999e990f3f29c6d,
+            0x2b6cedcb87925c23,
+            0x05d314967254398f,
+            0x0748d9d99f59ff11,
+        ]);
+        const INV: u64 = 0xfffffffeffffffff;
+        const GENERATOR: BigInteger = BigInteger([
+            0x0000000efffffff1,
+            0x17e363d300189c0f,
+            0xff9c57876f8457b0,
+            0x351332208fc5a8c4,
+        ]);
+        const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+            0x7fffffff80000000,
+            0xa9ded2017fff2dff,
+            0x199cec0404d0ec02,
+            0x39f6d3a994cebea4,
+        ]);
+        const T: BigInteger = BigInteger([
+            0xfffe5bfeffffffff,
+            0x09a1d80553bda402,
+            0x299d7d483339d808,
+            0x0000000073eda753,
+        ]);
+        const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+            0x7fff2dff7fffffff,
+            0x04d0ec02a9ded201,
+            0x94cebea4199cec04,
+            0x0000000039f6d3a9,
+        ]);
+    }
+}
+
+#[test]
+pub fn test_challenge_bias() {
+    use ark_ff::PrimeField;
+    use bls12_381_fr::Fr;
+    use sha2::Sha256;
+    const SAMPLES: u32 = 10_000;
+
+    let two_to_minus = |k: u32| (0..k).fold(1.0, |acc, _| acc / 2.0);
+    assert!(
+        ChallengeDerivation::ModOrder.statistical_distance::<secp256k1::fields::Fr>()
+            < two_to_minus(127)
+    );
+    assert_eq!(
+        ChallengeDerivation::for_field::<Fr>(),
+        ChallengeDerivation::WideReduction
+    );
+    let distance = ChallengeDerivation::ModOrder.statistical_distance::<Fr>();
+    assert!((distance - 0.074_668_831_897_216_3).abs() < 1e-12);
+    let distance = ChallengeDerivation::WideReduction.statistical_distance::<Fr>();
+    assert!(distance > two_to_minus(262) && distance < two_to_minus(260));
+    assert_eq!(
+        ChallengeDerivation::TruncateAndRetry.statistical_distance::<Fr>(),
+        0.0
+    );
+
+    // the scalars below `2^256 mod n` are the ones reducing the digest makes more likely; their share tells the bias
+    let mut one_then_zeros = [0u8; 33];
+    one_then_zeros[0] = 1;
+    let threshold = Fr::from_be_bytes_mod_order(&one_then_zeros).into_repr();
+    let uniform_share = 0.208_260_830_025_090_44;
+    // 5 standard deviations of the share of `SAMPLES` uniform draws
+    let tolerance = 5.0 * (uniform_share * (1.0 - uniform_share) / f64::from(SAMPLES)).sqrt();
+    for derivation in [
+        ChallengeDerivation::ModOrder,
+        ChallengeDerivation::TruncateAndRetry,
+        ChallengeDerivation::WideReduction,
+    ] {
+        let below = (0..SAMPLES)
+            .filter(|i| {
+                let c: Fr = derivation.derive::<Fr, Sha256>(&i.to_be_bytes());
+                c.into_repr() < threshold
+            })
+            .count();
+        let share = below as f64 / f64::from(SAMPLES);
+        let expected = uniform_share + derivation.statistical_distance::<Fr>();
+        assert!(
+            (share - expected).abs() < tolerance,
+            "{derivation:?}: {share} of the scalars are below 2^256 mod n, expected {expected}"
+        );
+    }
+}
+
+#[test]
+pub fn test_challenge_reduction() {
+    use crate::challenge::{ChallengeReduction, CurveDefault};
+    use sha2::Sha256;
+    type Fr = secp256k1::fields::Fr;
+
+    let preimage = hardcoded_msg();
+    assert_eq!(
+        CurveDefault.reduce::<Fr, Sha256>(preimage.as_bytes()),
+        ChallengeDerivation::ModOrder.derive::<Fr, Sha256>(preimage.as_bytes())
+    );
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig = PlumeSignature::sign_with_reduction::<Sha256, _>(
+            &pp,
+            &mut rng,
+            (&pk, &sk),
+            message,
+            version,
+            &ChallengeDerivation::WideReduction,
+        )
+        .unwrap();
+        assert!(sig
+            .verify_non_zk_with_reduction::<Sha256, _>(
+                &pp,
+                &pk,
+                message,
+                version,
+                &ChallengeDerivation::WideReduction
+            )
+            .is_ok());
+        // the verifier has to reduce the same way
+        assert!(sig.verify_non_zk(&pp, &pk, message, version).is_err());
+
+        let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+        assert!(sig
+            .verify_non_zk_with_reduction::<Sha256, _>(&pp, &pk, message, version, &CurveDefault)
+            .is_ok());
+    }
+}
+
+#[test]
+pub fn test_custom_hasher() {
+    use crate::hasher::PlumeHasher;
+    use sha2::{Digest, Sha256};
+
+    // a stand-in for an accelerated backend: any `PlumeHasher` must be used on both sides
+    #[derive(Default)]
+    struct Sha256Twice(Sha256);
+    impl PlumeHasher for Sha256Twice {
+        fn absorb(&mut self, data: &[u8]) {
+            self.0.update(data)
+        }
+        fn finish(self) -> Vec<u8> {
+            Sha256::digest(self.0.finalize()).to_vec()
+        }
+    }
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig = PlumeSignature::sign_with_hasher::<Sha256Twice>(
+            &pp,
+            &mut rng,
+            (&keypair.0, &keypair.1),
+            message,
+            version,
+        )
+        .unwrap();
+        assert!(sig
+            .verify_non_zk_with_hasher::<Sha256Twice>(&pp, &keypair.0, message, version)
+            .is_ok());
+        assert!(sig
+            .verify_non_zk(&pp, &keypair.0, message, version)
+            .is_err());
+    }
+
+    // the default hasher is plain SHA-256
+    let sig = PlumeSignature::sign(
+        &pp,
+        &mut rng,
+        (&keypair.0, &keypair.1),
+        message,
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    assert!(sig
+        .verify_non_zk_with_hasher::<Sha256>(&pp, &keypair.0, message, PlumeVersion::V2)
+        .is_ok());
+}
+
+#[test]
+pub fn test_message_commitment() {
+    use crate::commitment::Opening;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+
+    let opening = Opening::new(b"revealed later", &mut rng);
+    let (sig, commitment) = PlumeSignature::sign_commitment(
+        &pp,
+        &mut rng,
+        (&keypair.0, &keypair.1),
+        &opening,
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    assert!(sig
+        .verify_opening(&pp, &keypair.0, &commitment, &opening, PlumeVersion::V2)
+        .unwrap());
+
+    let mut wrong_message = opening.clone();
+    wrong_message.message = b"something else".to_vec();
+    assert!(!sig
+        .verify_opening(
+            &pp,
+            &keypair.0,
+            &commitment,
+            &wrong_message,
+            PlumeVersion::V2
+        )
+        .unwrap());
+    let mut wrong_salt = opening.clone();
+    wrong_salt.salt[0] ^= 1;
+    assert!(!sig
+        .verify_opening(&pp, &keypair.0, &commitment, &wrong_salt, PlumeVersion::V2)
+        .unwrap());
+
+    // a fresh salt hides the message
+    assert_ne!(
+        Opening::new(b"revealed later", &mut rng).commitment(),
+        commitment
+    );
+}
+
+#[test]
+pub fn test_versioned_modules() {
+    use crate::{v1, v2};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let other_keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    let sig = v1::sign(&pp, &mut rng, (&keypair.0, &keypair.1), message).unwrap();
+    assert!(sig.verify(&pp, &keypair.0, message).is_ok());
+    assert!(sig.verify(&pp, &other_keypair.0, message).is_err());
+    assert!(sig.verify(&pp, &keypair.0, b"Other message").is_err());
+
+    let sig = v2::sign(&pp, &mut rng, (&keypair.0, &keypair.1), message).unwrap();
+    assert!(sig.verify(&pp, &keypair.0, message).is_ok());
+    assert!(sig.verify(&pp, &other_keypair.0, message).is_err());
+    assert!(sig.verify(&pp, &keypair.0, b"Other message").is_err());
+
+    let tampered = v2::Signature {
+        s: sig.s + secp256k1::fields::Fr::from(1u64),
+        ..sig
+    };
+    assert!(matches!(
+        tampered.verify(&pp, &keypair.0, message),
+        Err(PlumeVerifyError::ChallengeMismatch)
+    ));
+}
+
+#[test]
+pub fn test_deterministic_fixtures() {
+    use crate::test_utils;
+
+    let pp = test_utils::parameters();
+    let (pk, sk) = crate::plume_test_keypair!(7);
+    assert_eq!(crate::plume_test_keypair!(&pp, 7), (pk, sk));
+    assert_eq!(pp.g_point.mul(sk).into_affine(), pk);
+    assert_ne!(crate::plume_test_keypair!(8).0, pk);
+
+    let sig = test_utils::signature(&pp, 7, b"Message", PlumeVersion::V2).unwrap();
+    let again = test_utils::signature(&pp, 7, b"Message", PlumeVersion::V2).unwrap();
+    assert_eq!((sig.c, sig.s), (again.c, again.s));
+    assert!(sig
+        .verify_non_zk(&pp, &pk, b"Message", PlumeVersion::V2)
+        .is_ok());
+}
+
+#[test]
+pub fn test_framed_versions() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let r = secp256k1::fields::Fr::from(hex_to_fr(&hardcoded_r()));
+
+    for (plain, framed) in [
+        (PlumeVersion::V1, PlumeVersion::V1_1),
+        (PlumeVersion::V2, PlumeVersion::V2_1),
+    ] {
+        assert!(framed.is_framed() && !plain.is_framed());
+        let sig = PlumeSignature::sign_with_r(&pp, (&keypair.0, &keypair.1), b"Message", r, framed)
+            .unwrap();
+        let plain_sig =
+            PlumeSignature::sign_with_r(&pp, (&keypair.0, &keypair.1), b"Message", r, plain)
+                .unwrap();
+        assert_ne!(sig.c, plain_sig.c);
+        assert!(sig
+            .verify_non_zk(&pp, &keypair.0, b"Message", framed)
+            .is_ok());
+        assert!(sig
+            .verify_non_zk(&pp, &keypair.0, b"Message", plain)
+            .is_err());
+    }
+}
+
+#[test]
+pub fn test_secp256k1_double_mul() {
+    use crate::optimizations::{double_mul, CurveOptimizations};
+    use ark_std::UniformRand;
+    type Fr = secp256k1::fields::Fr;
+
+    let (mut rng, g) = test_template();
+    for _ in 0..10 {
+        let (x, y) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let b = g.mul(Fr::rand(&mut rng)).into_affine();
+        let expected = (g.mul(x) + b.mul(y)).into_affine();
+        assert_eq!(Secp256k1Parameters::double_mul(&g, x, &b, y), expected);
+        assert_eq!(double_mul(&g, x, &b, y), expected);
+    }
+
+    let x = Fr::rand(&mut rng);
+    assert_eq!(double_mul(&g, x, &g, -x), Affine::zero());
+    assert_eq!(
+        double_mul(&g, x, &Affine::zero(), x),
+        g.mul(x).into_affine()
+    );
+    assert_eq!(double_mul(&g, Fr::zero(), &g, x), g.mul(x).into_affine());
+}
+
+#[test]
+pub fn test_batch_results_per_item() {
+    use crate::batch::{self, BatchItem};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let other_keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let messages = [&b"a"[..], &b"b"[..], &b"c"[..]];
+
+    let signatures: Vec<_> = batch::sign_batch(
+        &pp,
+        &mut rng,
+        (&keypair.0, &keypair.1),
+        messages,
+        PlumeVersion::V2,
+    )
+    .into_iter()
+    .map(Result::unwrap)
+    .collect();
+
+    let pks = [keypair.0, other_keypair.0, keypair.0];
+    let results = batch::verify_batch(
+        &pp,
+        pks.iter()
+            .zip(messages)
+            .zip(&signatures)
+            .map(|((pk, message), signature)| BatchItem {
+                pk,
+                message,
+                signature,
+                version: PlumeVersion::V2,
+            }),
+    );
+    assert!(matches!(
+        results[..],
+        [Ok(()), Err(PlumeVerifyError::RPointMismatch), Ok(())]
+    ));
+    assert_eq!(batch::failed_indices(&results), vec![1]);
+}
+
+#[test]
+pub fn test_x_only_nullifiers() {
+    use crate::xonly::{XOnlyNullifier, XOnlySignature};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+
+    // sign until both parities of the nullifier were seen
+    let mut parities = [false; 2];
+    for i in 0..64u8 {
+        let message = [b"x-only ".as_slice(), &[i][..]].concat();
+        let sig = crate::v2::sign(&pp, &mut rng, (&keypair.0, &keypair.1), &message).unwrap();
+        let odd = ark_ff::BigInteger::is_odd(&ark_ff::PrimeField::into_repr(&sig.nullifier.y));
+        parities[usize::from(odd)] = true;
+
+        let x_only = XOnlySignature::from_signature(&sig).unwrap();
+        let bytes = x_only.nullifier.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            XOnlyNullifier::<Secp256k1Parameters>::byte_len()
+        );
+        assert_eq!(
+            bytes,
+            encoding::point_to_bytes(&sig.nullifier).unwrap()[1..].to_vec()
+        );
+        let decoded = XOnlyNullifier::<Secp256k1Parameters>::from_bytes(&bytes).unwrap();
+        assert!(decoded.matches(&sig.nullifier));
+        assert!(decoded.matches(&-sig.nullifier));
+        assert_eq!(decoded.to_point().unwrap() == sig.nullifier, !odd);
+
+        let x_only = XOnlySignature {
+            nullifier: decoded,
+            ..x_only
+        };
+        assert_eq!(
+            x_only.verify(&pp, &keypair.0, &message).unwrap(),
+            Some(sig.nullifier)
+        );
+        assert_eq!(x_only.verify(&pp, &keypair.0, b"other").unwrap(), None);
+    }
+    assert_eq!(parities, [true, true]);
+
+    // an `x` without a point on the curve
+    let mut x = [0u8; 32];
+    while XOnlyNullifier::<Secp256k1Parameters>::from_bytes(&x).is_ok() {
+        x[31] += 1;
+    }
+    assert!(matches!(
+        XOnlyNullifier::<Secp256k1Parameters>::from_bytes(&x),
+        Err(EncodingError::NotOnCurve)
+    ));
+    assert!(matches!(
+        XOnlyNullifier::<Secp256k1Parameters>::from_bytes(&[0u8; 33]),
+        Err(EncodingError::InvalidLength { .. })
+    ));
+}
+
+#[test]
+pub fn test_linkage_audit() {
+    use crate::audit::{audit, AuditEntry, Finding};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let entries: [(&[u8], &[u8]); 4] = [
+        (b"app/vote", b"app/vote/1"),
+        (b"app/vote", b"app/vote/2"),
+        (b"app/airdrop", b"app/vote/1"),
+        (b"app", b"app-x"),
+    ];
+    let signatures = entries
+        .iter()
+        .map(|(_, message)| {
+            PlumeSignature::sign(
+                &pp,
+                &mut rng,
+                (&keypair.0, &keypair.1),
+                message,
+                PlumeVersion::V2,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let report = audit(
+        entries
+            .iter()
+            .zip(&signatures)
+            .map(|((scope, message), signature)| AuditEntry {
+                scope,
+                message,
+                signature,
+            }),
+    );
+    assert_eq!(
+        report.findings,
+        vec![
+            Finding::DuplicateScope {
+                indices: vec![0, 1]
+            },
+            Finding::ReusedMessage {
+                indices: vec![0, 2]
+            },
+            Finding::SharedNullifier {
+                indices: vec![0, 2]
+            },
+            Finding::ScopeNotInMessage { index: 2 },
+            Finding::ScopePrefix { prefix: 3, of: 0 },
+            Finding::ScopePrefix { prefix: 3, of: 1 },
+            Finding::ScopePrefix { prefix: 3, of: 2 },
+        ]
+    );
+
+    // the framed scope messages are fine
+    let messages = [b"vote".as_slice(), b"airdrop".as_slice()]
+        .map(|name| scope::framed(&[name, b"round 1".as_slice()]));
+    let signatures = messages
+        .iter()
+        .map(|message| {
+            PlumeSignature::sign(
+                &pp,
+                &mut rng,
+                (&keypair.0, &keypair.1),
+                message,
+                PlumeVersion::V2,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let report = audit(
+        [b"vote".as_slice(), b"airdrop".as_slice()]
+            .iter()
+            .zip(&messages)
+            .zip(&signatures)
+            .map(|((scope, message), signature)| AuditEntry {
+                scope,
+                message,
+                signature,
+            }),
+    );
+    assert!(report.is_clean());
+}
+
+#[test]
+pub fn test_formats() {
+    use crate::formats::{self, Format, FormatError};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let sig = PlumeSignature::sign(
+        &pp,
+        &mut rng,
+        (&keypair.0, &keypair.1),
+        b"formats",
+        PlumeVersion::V1,
+    )
+    .unwrap();
+    let binary = Format::Binary {
+        version: formats::BINARY_VERSION,
+    };
+
+    let legacy_bytes = formats::encode(&sig, Format::LegacyArk).unwrap();
+    let binary_bytes = formats::encode(&sig, binary).unwrap();
+    #[cfg(feature = "legacy-ark-serialize")]
+    {
+        use ark_serialize::CanonicalSerialize;
+
+        let mut derived = Vec::new();
+        sig.serialize(&mut derived).unwrap();
+        assert_eq!(derived, legacy_bytes);
+    }
+    assert_eq!(
+        formats::detect_format::<Secp256k1Parameters>(&legacy_bytes),
+        Some(Format::LegacyArk)
+    );
+    assert_eq!(
+        formats::detect_format::<Secp256k1Parameters>(&binary_bytes),
+        Some(binary)
+    );
+
+    let migrated = formats::migrate::<Secp256k1Parameters>(&legacy_bytes, binary).unwrap();
+    assert_eq!(migrated, binary_bytes);
+    assert_eq!(
+        formats::migrate::<Secp256k1Parameters>(&migrated, Format::LegacyArk).unwrap(),
+        legacy_bytes
+    );
+    let decoded = formats::decode::<Secp256k1Parameters>(&binary_bytes).unwrap();
+    assert_eq!(decoded.nullifier, sig.nullifier);
+    assert_eq!(decoded.r_point, sig.r_point);
+    assert_eq!(decoded.hashed_to_curve_r, sig.hashed_to_curve_r);
+    assert_eq!((decoded.c, decoded.s), (sig.c, sig.s));
+
+    assert_eq!(
+        formats::decode::<Secp256k1Parameters>(b"not a signature").err(),
+        Some(FormatError::Unknown)
+    );
+    let mut future = binary_bytes.clone();
+    future[4] = formats::BINARY_VERSION + 1;
+    assert_eq!(
+        formats::decode::<Secp256k1Parameters>(&future).err(),
+        Some(FormatError::Unsupported(Format::Binary {
+            version: formats::BINARY_VERSION + 1
+        }))
+    );
+}
+
+#[test]
+pub fn test_key_migration() {
+    use crate::migration::KeyMigration;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let old = PlumeSignature::keygen(&pp, &mut rng);
+    let new = PlumeSignature::keygen(&pp, &mut rng);
+    let scope = b"migration scope";
+
+    let migration = KeyMigration::<Secp256k1Parameters, Secp256k1Parameters>::prove(
+        &g,
+        (&old.0, &old.1),
+        &g,
+        (&new.0, &new.1),
+        scope,
+        &mut rng,
+    )
+    .unwrap();
+    let old_sig =
+        PlumeSignature::sign(&pp, &mut rng, (&old.0, &old.1), scope, PlumeVersion::V2).unwrap();
+    let new_sig =
+        PlumeSignature::sign(&pp, &mut rng, (&new.0, &new.1), scope, PlumeVersion::V2).unwrap();
+    assert_eq!(migration.old_nullifier, old_sig.nullifier);
+    assert_eq!(migration.new_nullifier, new_sig.nullifier);
+    assert!(migration.verify(&g, &g, scope).unwrap());
+
+    assert!(!migration.verify(&g, &g, b"another scope").unwrap());
+    let swapped = KeyMigration {
+        old_nullifier: migration.new_nullifier,
+        new_nullifier: migration.old_nullifier,
+        ..migration
+    };
+    assert!(!swapped.verify(&g, &g, scope).unwrap());
+}
+
+#[test]
+pub fn test_verify_batch_combined() {
+    use crate::batch::BatchVerifyError;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypairs = (0..3)
+        .map(|_| PlumeSignature::keygen(&pp, &mut rng))
+        .collect::<Vec<_>>();
+    let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2_1] {
+        let signatures = keypairs
+            .iter()
+            .zip(messages)
+            .map(|(keypair, message)| {
+                PlumeSignature::sign(&pp, &mut rng, (&keypair.0, &keypair.1), message, version)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let items = messages
+            .iter()
+            .zip(&keypairs)
+            .zip(&signatures)
+            .map(|((message, keypair), sig)| (*message, &keypair.0, sig))
+            .collect::<Vec<_>>();
+        assert!(PlumeSignature::verify_batch_combined(&pp, &mut rng, &items, version).is_ok());
+        assert!(PlumeSignature::verify_batch_combined(&pp, &mut rng, &[], version).is_ok());
+
+        // the challenge still matches, only the combined equations fail
+        let mut tampered = signatures[1];
+        tampered.s += <Secp256k1Parameters as ark_ec::ModelParameters>::ScalarField::from(1u64);
+        let mut tampered_items = items.clone();
+        tampered_items[1].2 = &tampered;
+        assert!(matches!(
+            PlumeSignature::verify_batch_combined(&pp, &mut rng, &tampered_items, version),
+            Err(BatchVerifyError::Combination)
+        ));
+
+        // a challenge which doesn't match is located
+        let mut swapped = items.clone();
+        swapped[2].0 = messages[1];
+        assert!(matches!(
+            PlumeSignature::verify_batch_combined(&pp, &mut rng, &swapped, version),
+            Err(BatchVerifyError::Item {
+                index: 2,
+                error: PlumeVerifyError::ChallengeMismatch
+            })
+        ));
+    }
+}
+
+#[test]
+pub fn test_signature_reader() {
+    use crate::batch::{failed_indices, BatchVerifyError};
+    use crate::formats::signature_byte_len;
+    use crate::reader::{SignatureReader, TruncatedError};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypairs = (0..3)
+        .map(|_| PlumeSignature::keygen(&pp, &mut rng))
+        .collect::<Vec<_>>();
+    let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+    let signatures = keypairs
+        .iter()
+        .zip(messages)
+        .map(|(keypair, message)| {
+            PlumeSignature::sign(
+                &pp,
+                &mut rng,
+                (&keypair.0, &keypair.1),
+                message,
+                PlumeVersion::V2,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let mut file = signatures
+        .iter()
+        .flat_map(|sig| sig.to_bytes().unwrap())
+        .collect::<Vec<_>>();
+    let len = signature_byte_len::<Secp256k1Parameters>();
+    let statement = |index: usize| (messages[index], &keypairs[index].0);
+
+    let reader = SignatureReader::<Secp256k1Parameters>::new(&file).unwrap();
+    assert_eq!(reader.len(), 3);
+    assert_eq!(reader.record(1), Some(&file[len..2 * len]));
+    assert_eq!(reader.records().count(), 3);
+    assert_eq!(
+        reader.get(2).unwrap().unwrap().nullifier,
+        signatures[2].nullifier
+    );
+    assert!(reader.get(3).is_none());
+    assert!(reader
+        .verify_each(&pp, statement, PlumeVersion::V2)
+        .all(|result| result.is_ok()));
+    assert!(reader
+        .verify_combined(&pp, &mut rng, statement, PlumeVersion::V2)
+        .is_ok());
+
+    // a record which doesn't decode is located by its index in the file
+    file[len] ^= 1;
+    let reader = SignatureReader::<Secp256k1Parameters>::new(&file).unwrap();
+    let results = reader
+        .verify_each(&pp, statement, PlumeVersion::V2)
+        .collect::<Vec<_>>();
+    assert_eq!(failed_indices(&results), [1]);
+    assert!(matches!(
+        reader.verify_combined(&pp, &mut rng, statement, PlumeVersion::V2),
+        Err(BatchVerifyError::Item {
+            index: 1,
+            error: PlumeVerifyError::InvalidSignature(EncodingError::InvalidHeader)
+        })
+    ));
+
+    // a file cut off in a record
+    assert_eq!(
+        SignatureReader::<Secp256k1Parameters>::new(&file[..len + 1]).err(),
+        Some(TruncatedError {
+            len: len + 1,
+            record_len: len
+        })
+    );
+}
+
+#[test]
+pub fn test_parameters_header() {
+    use crate::encoding::{curve_id, parameters_from_bytes, parameters_to_bytes, ParametersHeader};
+
+    let (_, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let bytes = parameters_to_bytes(&pp).unwrap();
+    assert_eq!(
+        parameters_from_bytes::<Secp256k1Parameters>(&bytes)
+            .unwrap()
+            .g_point,
+        g
+    );
+    assert_eq!(
+        ParametersHeader::for_curve::<Secp256k1Parameters>().curve_id,
+        curve_id::<Secp256k1Parameters>()
+    );
+
+    // a header of another curve
+    let field = |value: &[u8]| [&(value.len() as u64).to_be_bytes()[..], value].concat();
+    let mut fields = bytes[b"PLUME/parameters".len()..].to_vec();
+    fields[8 + 1 + 8 + 31] ^= 1;
+    assert_eq!(
+        parameters_from_bytes::<Secp256k1Parameters>(
+            &[&b"PLUME/parameters"[..], fields.as_slice()].concat()
+        )
+        .err(),
+        Some(EncodingError::ParameterMismatch { field: "curve_id" })
+    );
+
+    // another hash to curve suite
+    let header = ParametersHeader::for_curve::<Secp256k1Parameters>();
+    let forked = [
+        b"PLUME/parameters".to_vec(),
+        field(&[header.version]),
+        field(&header.curve_id),
+        field(&header.hash_suite),
+        field(b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SVDW_RO_"),
+        field(&encoding::point_to_bytes(&g).unwrap()),
+    ]
+    .concat();
+    assert_eq!(
+        parameters_from_bytes::<Secp256k1Parameters>(&forked).err(),
+        Some(EncodingError::ParameterMismatch { field: "htc_suite" })
+    );
+
+    assert_eq!(
+        parameters_from_bytes::<Secp256k1Parameters>(&bytes[1..]).err(),
+        Some(EncodingError::InvalidHeader)
+    );
+    assert_eq!(
+        parameters_from_bytes::<Secp256k1Parameters>(&bytes[..bytes.len() - 1]).err(),
+        Some(EncodingError::InvalidHeader)
+    );
+}
+
+#[test]
+pub fn test_against_reference_arithmetic() {
+    use crate::{optimizations, reference};
+    use ark_ec::msm::VariableBaseMSM;
+    use ark_ff::PrimeField;
+    use ark_std::UniformRand;
+
+    type Fr = <Secp256k1Parameters as ark_ec::ModelParameters>::ScalarField;
+
+    let (mut rng, g) = test_template();
+    let identity = GroupAffine::<Secp256k1Parameters>::zero();
+    for _ in 0..16 {
+        let (x, y) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let a = g.mul(Fr::rand(&mut rng)).into_affine();
+        let b = g.mul(Fr::rand(&mut rng)).into_affine();
+
+        assert_eq!(a.mul(x).into_affine(), reference::mul(&a, x));
+        assert_eq!(
+            optimizations::double_mul(&a, x, &b, y),
+            reference::linear_combination(&[(a, x), (b, y)])
+        );
+        // the terms cancel out
+        assert_eq!(optimizations::double_mul(&a, x, &a, -x), identity);
+        assert_eq!(
+            optimizations::double_mul(&identity, x, &b, y),
+            reference::mul(&b, y)
+        );
+
+        let terms = (0..8)
+            .map(|_| (g.mul(Fr::rand(&mut rng)).into_affine(), Fr::rand(&mut rng)))
+            .collect::<Vec<_>>();
+        let (bases, scalars): (Vec<_>, Vec<_>) = terms
+            .iter()
+            .map(|(point, k)| (*point, k.into_repr()))
+            .unzip();
+        assert_eq!(
+            VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine(),
+            reference::linear_combination(&terms)
+        );
+    }
+    assert_eq!(reference::mul(&g, Fr::zero()), identity);
+
+    // the signature's points
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let r = Fr::rand(&mut rng);
+    let sig =
+        PlumeSignature::sign_with_r(&pp, (&pk, &sk), b"reference", r, PlumeVersion::V2).unwrap();
+    let h = crate::compute_h::<Secp256k1Parameters>(&pk, b"reference").unwrap();
+    assert_eq!(pk, reference::mul(&g, sk));
+    assert_eq!(sig.r_point, reference::mul(&g, r));
+    assert_eq!(sig.nullifier, reference::mul(&h, sk));
+    assert_eq!(sig.hashed_to_curve_r, reference::mul(&h, r));
+    assert_eq!(
+        sig.r_point,
+        reference::linear_combination(&[(g, sig.s), (pk, -sig.c)])
+    );
+}
+
+#[test]
+pub fn test_signature_traits() {
+    use crate::signer::PlumeSigner;
+    use signature::{RandomizedSigner, Signer, Verifier};
+
+    // code generic over the traits, which knows nothing of PLUME
+    fn sign_and_verify<S: Signer<Sig>, V: Verifier<Sig>, Sig>(signer: &S, verifier: &V) -> bool {
+        let sig = signer.sign(b"generic");
+        verifier.verify(b"generic", &sig).is_ok() && verifier.verify(b"other", &sig).is_err()
+    }
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    for version in [PlumeVersion::V1, PlumeVersion::V2, PlumeVersion::V2_1] {
+        let signer = PlumeSigner::new(&pp, &sk, version);
+        let verifier = signer.verifier();
+        assert_eq!(verifier.pk, pk);
+        assert!(sign_and_verify(&signer, &verifier));
+
+        let sig = signer.sign_with_rng(&mut rng, b"randomized");
+        assert!(sig.verify(&pp, &pk, b"randomized", version).is_ok());
+        assert!(verifier.verify(b"randomized", &sig).is_ok());
+    }
+
+    // a signature of another version doesn't pass
+    let sig = PlumeSigner::new(&pp, &sk, PlumeVersion::V1).sign(b"generic");
+    let verifier = PlumeSigner::new(&pp, &sk, PlumeVersion::V2).verifier();
+    assert!(verifier.verify(b"generic", &sig).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn test_serde() {
+    use crate::serialization;
+    use secp256k1::fields::Fr;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), b"serde", PlumeVersion::V1).unwrap();
+
+    // hex in JSON
+    let json = serde_json::to_value(&sig).unwrap();
+    assert_eq!(
+        json["nullifier"],
+        hex::encode(encoding::point_to_bytes(&sig.nullifier).unwrap())
+    );
+    assert_eq!(json["s"], hex::encode(encoding::field_to_bytes(&sig.s)));
+    let decoded: PlumeSignature<Secp256k1Parameters> = serde_json::from_value(json).unwrap();
+    assert!(decoded.verify(&pp, &pk, b"serde", PlumeVersion::V1).is_ok());
+    assert_eq!(
+        (decoded.nullifier, decoded.c, decoded.r_point),
+        (sig.nullifier, sig.c, sig.r_point)
+    );
+
+    // bytes in binary formats
+    let binary = bincode::serialize(&sig).unwrap();
+    assert!(binary.len() < serde_json::to_vec(&sig).unwrap().len());
+    let decoded: PlumeSignature<Secp256k1Parameters> = bincode::deserialize(&binary).unwrap();
+    assert_eq!(decoded.hashed_to_curve_r, sig.hashed_to_curve_r);
+
+    let pp_json = serde_json::to_string(&pp).unwrap();
+    let decoded: Parameters = serde_json::from_str(&pp_json).unwrap();
+    assert_eq!(decoded.g_point, g);
+
+    // the keys through `with`
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Entry {
+        #[serde(with = "serialization::point")]
+        pk: GroupAffine<Secp256k1Parameters>,
+    }
+    let entry = serde_json::to_string(&Entry { pk }).unwrap();
+    assert_eq!(serde_json::from_str::<Entry>(&entry).unwrap().pk, pk);
+
+    // a single accepted form
+    let mut json = serde_json::to_value(&sig).unwrap();
+    json["c"] = json["c"].as_str().unwrap().to_uppercase().into();
+    assert!(serde_json::from_value::<PlumeSignature<Secp256k1Parameters>>(json).is_err());
+    let mut json = serde_json::to_value(&sig).unwrap();
+    json["extra"] = 0.into();
+    assert!(serde_json::from_value::<PlumeSignature<Secp256k1Parameters>>(json).is_err());
+    assert!(serde_json::from_str::<Entry>(r#"{"pk":"04"}"#).is_err());
+
+    // a sequence of bytes is read up to the encoded length, whatever its length hint
+    let seq = |bytes: Vec<u8>| {
+        serde::de::value::SeqDeserializer::<_, serde::de::value::Error>::new(bytes.into_iter())
+    };
+    let bytes = encoding::field_to_bytes(&sig.c);
+    assert_eq!(
+        serialization::field::deserialize::<Fr, _>(seq(bytes.clone())).unwrap(),
+        sig.c
+    );
+    let too_long = seq([bytes, vec![0]].concat());
+    assert!(serialization::field::deserialize::<Fr, _>(too_long).is_err());
+}
+
+#[test]
+pub fn test_offline_signing() {
+    use crate::offline::{
+        from_parts, to_parts, OfflineError, SigningRequest, SigningResponse, REQUEST_UR_TYPE,
+        RESPONSE_UR_TYPE,
+    };
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+
+    // online
+    let request = SigningRequest::new(&pp, b"vote/1/yes", b"vote/1", PlumeVersion::V2_1).unwrap();
+    let parts = to_parts(REQUEST_UR_TYPE, &request.to_cbor(), 16);
+    assert!(parts.len() > 1);
+    assert!(parts.iter().all(|part| part
+        .bytes()
+        .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b"-/:".contains(&b))));
+
+    // offline, scanning the parts in any order and some twice
+    let mut scanned = parts.clone();
+    scanned.reverse();
+    scanned.push(parts[0].clone());
+    let received =
+        SigningRequest::from_cbor(&from_parts(REQUEST_UR_TYPE, &scanned).unwrap()).unwrap();
+    assert_eq!(received, request);
+    let response = received.sign(&pp, &mut rng, (&pk, &sk)).unwrap();
+    let parts = to_parts(RESPONSE_UR_TYPE, &response.to_cbor().unwrap(), 32);
+
+    // online again
+    let response = SigningResponse::<Secp256k1Parameters>::from_cbor(
+        &from_parts(RESPONSE_UR_TYPE, &parts).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.pk, pk);
+    assert!(response.verify(&pp, &request).unwrap());
+    let other = SigningRequest::new(&pp, b"vote/1/no", b"vote/1", PlumeVersion::V2_1).unwrap();
+    assert!(!response.verify(&pp, &other).unwrap());
+
+    // incomplete, mixed up, or tampered parts
+    assert!(matches!(
+        from_parts(RESPONSE_UR_TYPE, &parts[1..]),
+        Err(OfflineError::Parts)
+    ));
+    assert!(from_parts(REQUEST_UR_TYPE, &parts).is_err());
+    let mut tampered = parts.clone();
+    let tampered_last = tampered[0].pop().unwrap();
+    tampered[0].push(if tampered_last == '0' { '1' } else { '0' });
+    assert!(from_parts(RESPONSE_UR_TYPE, &tampered).is_err());
+
+    // a request for other parameters isn't signed
+    let other_pp = Parameters {
+        g_point: g.mul(secp256k1::fields::Fr::from(2u64)).into_affine(),
+    };
+    assert!(matches!(
+        request.sign(&other_pp, &mut rng, (&pk, &sk)),
+        Err(OfflineError::ParametersMismatch)
+    ));
+    assert!(matches!(
+        SigningRequest::new(&pp, b"vote/2/yes", b"vote/1", PlumeVersion::V2),
+        Err(OfflineError::OutOfScope)
+    ));
+
+    // only the deterministic encoding is read
+    let cbor = request.to_cbor();
+    assert!(SigningRequest::from_cbor(&[cbor.as_slice(), &[0]].concat()).is_err());
+    // the key `1` in two bytes
+    let non_shortest = [&cbor[..1], &[0x18, 0x01], &cbor[2..]].concat();
+    assert!(SigningRequest::from_cbor(&non_shortest).is_err());
+}
+
+#[test]
+pub fn test_validate() {
+    use crate::validate;
+    use ark_ff::One;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    validate::public_key(&keypair.0).unwrap();
+
+    let off_curve = GroupAffine::<Secp256k1Parameters>::new(g.x, g.y + Fq::one(), false);
+    let identity = GroupAffine::<Secp256k1Parameters>::zero();
+    assert_eq!(validate::point(&off_curve), Err(EncodingError::NotOnCurve));
+    assert_eq!(validate::point(&identity), Err(EncodingError::Identity));
+    // every point of `secp256k1` is in the subgroup
+    assert_eq!(validate::subgroup(&g), Ok(()));
+
+    // there's no point with `x = 0`, as `7` isn't a square
+    let mut bytes = encoding::point_to_bytes(&g).unwrap();
+    bytes[1..].fill(0);
+    assert_eq!(
+        encoding::point_from_bytes::<Secp256k1Parameters>(&bytes),
+        Err(EncodingError::NotOnCurve)
+    );
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig =
+            PlumeSignature::sign(&pp, &mut rng, (&keypair.0, &keypair.1), b"Message", version)
+                .unwrap();
+        validate::signature(&sig).unwrap();
+        assert!(sig
+            .verify_non_zk(&pp, &keypair.0, b"Message", version)
+            .is_ok());
+
+        for point in [identity, off_curve] {
+            let mut invalid = sig;
+            invalid.nullifier = point;
+            assert!(validate::signature(&invalid).is_err());
+            assert!(invalid
+                .verify_non_zk(&pp, &keypair.0, b"Message", version)
+                .is_err());
+            assert!(matches!(
+                PlumeSignature::verify_batch_combined(
+                    &pp,
+                    &mut rng,
+                    &[(&b"Message"[..], &keypair.0, &invalid)],
+                    version
+                ),
+                Err(crate::batch::BatchVerifyError::Item {
+                    index: 0,
+                    error: PlumeVerifyError::InvalidSignature(_)
+                })
+            ));
+        }
+        assert!(sig
+            .verify_non_zk(&pp, &identity, b"Message", version)
+            .is_err());
+    }
+}
+
+#[test]
+pub fn test_envelope() {
+    use crate::envelope::{Envelope, EnvelopeError, EnvelopedSignature, FixedClock};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let envelope = Envelope {
+        not_before: Some(100),
+        not_after: Some(200),
+        key_id: b"key-1".to_vec(),
+    };
+    assert_eq!(
+        Envelope::from_bytes(&envelope.to_bytes()).unwrap(),
+        envelope
+    );
+    assert_eq!(
+        Envelope::from_bytes(&Envelope::default().to_bytes()).unwrap(),
+        Envelope::default()
+    );
+    assert!(matches!(
+        Envelope::from_bytes(&scope::framed(&[
+            &b"PLUME/envelope"[..],
+            &[0u8; 4][..],
+            &b""[..],
+            &b""[..]
+        ])),
+        Err(EnvelopeError::Malformed)
+    ));
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2_1] {
+        let enveloped = EnvelopedSignature::sign(
+            &pp,
+            &mut rng,
+            (&pk, &sk),
+            b"Message",
+            envelope.clone(),
+            version,
+        )
+        .unwrap();
+        // the envelope doesn't change the nullifier
+        let plain = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), b"Message", version).unwrap();
+        assert_eq!(enveloped.signature.nullifier, plain.nullifier);
+
+        for now in [100, 150, 200] {
+            enveloped
+                .verify(&pp, &pk, b"Message", version, &FixedClock(now))
+                .unwrap();
+        }
+        assert!(matches!(
+            enveloped.verify(&pp, &pk, b"Message", version, &FixedClock(99)),
+            Err(EnvelopeError::NotYetValid)
+        ));
+        assert!(matches!(
+            enveloped.verify(&pp, &pk, b"Message", version, &FixedClock(201)),
+            Err(EnvelopeError::Expired)
+        ));
+
+        // the envelope is covered by the challenge
+        let mut extended = enveloped.clone();
+        extended.envelope.not_after = Some(300);
+        assert!(matches!(
+            extended.verify(&pp, &pk, b"Message", version, &FixedClock(150)),
+            Err(EnvelopeError::InvalidSignature)
+        ));
+        let mut other_key = enveloped.clone();
+        other_key.envelope.key_id = b"key-2".to_vec();
+        assert!(matches!(
+            other_key.verify(&pp, &pk, b"Message", version, &FixedClock(150)),
+            Err(EnvelopeError::InvalidSignature)
+        ));
+
+        // an enveloped signature isn't a plain one, and the other way around
+        assert!(enveloped
+            .signature
+            .verify_non_zk(&pp, &pk, b"Message", version)
+            .is_err());
+        let unbounded = EnvelopedSignature {
+            signature: plain,
+            envelope: Envelope::default(),
+        };
+        assert!(matches!(
+            unbounded.verify(&pp, &pk, b"Message", version, &FixedClock(150)),
+            Err(EnvelopeError::InvalidSignature)
+        ));
+    }
+}
+
+#[test]
+pub fn test_keygen_zeroizing() {
+    use zeroize::Zeroize;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, mut sk) = PlumeSignature::keygen_zeroizing(&pp, &mut rng);
+    assert_eq!(g.mul(*sk).into_affine(), pk);
+    let sig =
+        PlumeSignature::sign(&pp, &mut rng, (&pk, &*sk), b"Message", PlumeVersion::V2).unwrap();
+    assert!(sig
+        .verify_non_zk(&pp, &pk, b"Message", PlumeVersion::V2)
+        .is_ok());
+
+    sk.zeroize();
+    assert!(sk.is_zero());
+}
+
+#[cfg(feature = "eth-recovery")]
+#[test]
+pub fn test_eth_recovery() {
+    use crate::recovery::{self, Address, RecoverableSignature, RecoveryError, SignatureSource};
+    use ark_ff::{BigInteger, Field, One, PrimeField};
+    use ark_std::UniformRand;
+    use secp256k1::fields::Fr;
+
+    struct Source(Vec<(Address, Vec<RecoverableSignature>)>);
+    impl SignatureSource for Source {
+        type Error = &'static str;
+
+        fn signatures(&self, address: &Address) -> Result<Vec<RecoverableSignature>, Self::Error> {
+            self.0
+                .iter()
+                .find(|(a, _)| a == address)
+                .map(|(_, signatures)| signatures.clone())
+                .ok_or("unknown address")
+        }
+    }
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    // the address of the secret key `1`
+    assert_eq!(
+        hex::encode(recovery::address(&g).unwrap()),
+        "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+    );
+
+    let ecdsa = |sk: Fr, digest: [u8; 32], rng: &mut ThreadRng| {
+        let k = Fr::rand(rng);
+        let nonce_point = g.mul(k).into_affine();
+        let r = Fr::from_be_bytes_mod_order(&encoding::field_to_bytes(&nonce_point.x));
+        let s = k.inverse().unwrap() * (Fr::from_be_bytes_mod_order(&digest) + r * sk);
+        RecoverableSignature {
+            digest,
+            r: encoding::field_to_bytes(&r),
+            s: encoding::field_to_bytes(&s),
+            v: nonce_point.y.into_repr().is_odd() as u64,
+        }
+    };
+
+    let keys = [(); 2].map(|_| PlumeSignature::keygen(&pp, &mut rng));
+    let addresses = keys.map(|(pk, _)| recovery::address(&pk).unwrap());
+    let signature = ecdsa(keys[0].1, [7; 32], &mut rng);
+    assert_eq!(recovery::recover(&pp, &signature), Some(keys[0].0));
+    // the Ethereum offsets of legacy and EIP-155 (chain `1`) transactions
+    for offset in [27, 37] {
+        let signature = RecoverableSignature {
+            v: signature.v + offset,
+            ..signature.clone()
+        };
+        assert_eq!(recovery::recover(&pp, &signature), Some(keys[0].0));
+    }
+    for invalid in [
+        RecoverableSignature {
+            v: 2,
+            ..signature.clone()
+        },
+        RecoverableSignature {
+            s: vec![0; 32],
+            ..signature.clone()
+        },
+        RecoverableSignature {
+            r: signature.r[1..].to_vec(),
+            ..signature.clone()
+        },
+    ] {
+        assert_eq!(
+            recovery::recover::<Secp256k1Parameters>(&pp, &invalid),
+            None
+        );
+    }
+
+    // invalid signatures are skipped
+    let source = Source(vec![
+        (
+            addresses[0],
+            vec![
+                RecoverableSignature {
+                    v: 2,
+                    ..signature.clone()
+                },
+                signature.clone(),
+            ],
+        ),
+        (addresses[1], vec![ecdsa(keys[1].1, [8; 32], &mut rng)]),
+    ]);
+    let (pks, set_id) = recovery::anonymity_set(&pp, &source, &addresses).unwrap();
+    assert_eq!(pks, keys.map(|(pk, _)| pk));
+    assert_eq!(set_id, scope::anonymity_set_id(&pks).unwrap());
+
+    // a signature of another key, or over another digest, recovers to another address
+    let another = ecdsa(Fr::one(), [7; 32], &mut rng);
+    let another_digest = RecoverableSignature {
+        digest: [9; 32],
+        ..signature.clone()
+    };
+    for signature in [another, another_digest] {
+        let source = Source(vec![(addresses[0], vec![signature])]);
+        assert_eq!(
+            recovery::recover_keys::<Secp256k1Parameters, _>(&pp, &source, &addresses[..1]),
+            Err(RecoveryError::AddressMismatch(addresses[0]))
+        );
+    }
+    let source = Source(vec![(addresses[0], vec![])]);
+    assert_eq!(
+        recovery::recover_keys::<Secp256k1Parameters, _>(&pp, &source, &addresses),
+        Err(RecoveryError::NoSignature(addresses[0]))
+    );
+    assert_eq!(
+        recovery::recover_keys::<Secp256k1Parameters, _>(&pp, &Source(vec![]), &addresses),
+        Err(RecoveryError::Source("unknown address"))
+    );
+}
+
+#[test]
+pub fn test_sign_deterministic() {
+    use crate::deterministic;
+
+    let (_, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let sk = hex_to_fr(&hardcoded_sk());
+    let pk = g.mul(sk).into_affine();
+    let message = hardcoded_msg();
+    let message = message.as_bytes();
+
+    // the HMAC-DRBG output, computed independently
+    for (version, r) in [
+        (
+            PlumeVersion::V1,
+            "f08225d1515d1e8d21af048196f0c928a272633f1976687401a5a7287e75cd4c",
+        ),
+        (
+            PlumeVersion::V2,
+            "76ab2f29d906a33ce26fa7ff155ff4c50866e6fe0cbea8150ebefbfeffe12c05",
+        ),
+    ] {
+        let nonce = deterministic::nonce(&sk, message, version);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&nonce)), r);
+
+        let sig = PlumeSignature::sign_deterministic(&pp, (&pk, &sk), message, version).unwrap();
+        assert_eq!(sig.r_point, g.mul(nonce).into_affine());
+        assert!(sig.verify_non_zk(&pp, &pk, message, version).is_ok());
+        let again = PlumeSignature::sign_deterministic(&pp, (&pk, &sk), message, version).unwrap();
+        assert_eq!((again.c, again.s), (sig.c, sig.s));
+    }
+
+    // the nonce differs between the versions and the messages
+    let versions = [
+        PlumeVersion::V1,
+        PlumeVersion::V2,
+        PlumeVersion::V1_1,
+        PlumeVersion::V2_1,
+    ];
+    let mut nonces = versions
+        .iter()
+        .map(|version| deterministic::nonce(&sk, message, *version))
+        .chain([deterministic::nonce(&sk, b"Message", PlumeVersion::V1)])
+        .map(|nonce| encoding::field_to_bytes(&nonce))
+        .collect::<Vec<_>>();
+    nonces.sort();
+    nonces.dedup();
+    assert_eq!(nonces.len(), 5);
+}
+
+#[test]
+pub fn test_hex() {
+    use crate::commitment::MessageCommitment;
+    use crate::formats::FormatError;
+    use crate::xonly::XOnlyNullifier;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let sk = hex_to_fr(&hardcoded_sk());
+    let pk = g.mul(sk).into_affine();
+
+    let g_hex = encoding::point_to_hex(&g).unwrap();
+    assert_eq!(
+        g_hex,
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+    assert_eq!(
+        encoding::point_from_hex::<Secp256k1Parameters>(&g_hex),
+        Ok(g)
+    );
+    assert_eq!(
+        encoding::point_from_hex::<Secp256k1Parameters>(&format!("0x{g_hex}")),
+        Ok(g)
+    );
+    assert_eq!(encoding::field_to_hex(&sk), hardcoded_sk());
+    assert_eq!(encoding::field_from_hex(&hardcoded_sk()), Ok(sk));
+
+    // a dropped leading zero, an odd length, uppercase, and other prefixes
+    let one = encoding::field_to_hex(&secp256k1::fields::Fr::from(1u64));
+    assert_eq!(
+        encoding::field_from_hex::<secp256k1::fields::Fr>(&one[2..]),
+        Err(EncodingError::InvalidLength {
+            expected: 32,
+            actual: 31
+        })
+    );
+    for invalid in [
+        &one[1..],
+        hardcoded_sk().to_uppercase().as_str(),
+        format!("0X{one}").as_str(),
+        format!("0x0x{one}").as_str(),
+        format!(" {one}").as_str(),
+    ] {
+        assert_eq!(
+            encoding::field_from_hex::<secp256k1::fields::Fr>(invalid),
+            Err(EncodingError::InvalidHex)
+        );
+    }
+
+    assert_eq!(
+        Parameters::from_hex(&pp.to_hex().unwrap()).unwrap().g_point,
+        g
+    );
+
+    let sig =
+        PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), b"Message", PlumeVersion::V1).unwrap();
+    let sig_hex = sig.to_hex().unwrap();
+    let decoded = PlumeSignature::<Secp256k1Parameters>::from_hex(&sig_hex).unwrap();
+    assert_eq!(decoded.to_hex().unwrap(), sig_hex);
+    assert!(decoded
+        .verify_non_zk(&pp, &pk, b"Message", PlumeVersion::V1)
+        .is_ok());
+    assert!(matches!(
+        PlumeSignature::<Secp256k1Parameters>::from_hex(&sig_hex[2..]),
+        Err(FormatError::Unknown)
+    ));
+
+    let nullifier = XOnlyNullifier::from_point(&sig.nullifier).unwrap();
+    let nullifier_hex = nullifier.to_hex();
+    assert_eq!(nullifier_hex.len(), 64);
+    assert!(
+        XOnlyNullifier::<Secp256k1Parameters>::from_hex(&nullifier_hex)
+            .unwrap()
+            .matches(&sig.nullifier)
+    );
+
+    let commitment = MessageCommitment([0xab; 32]);
+    assert_eq!(
+        MessageCommitment::from_hex(&commitment.to_hex()),
+        Ok(commitment)
+    );
+    assert_eq!(
+        MessageCommitment::from_hex("abab"),
+        Err(EncodingError::InvalidLength {
+            expected: 32,
+            actual: 2
+        })
+    );
+}
+
+/// `secp256k1` under another name, hashing by try-and-increment: a curve with a suite from outside the crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct TryAndIncrementParameters;
+
+impl ark_ec::ModelParameters for TryAndIncrementParameters {
+    type BaseField = <Secp256k1Parameters as ark_ec::ModelParameters>::BaseField;
+    type ScalarField = <Secp256k1Parameters as ark_ec::ModelParameters>::ScalarField;
+}
+
+impl ark_ec::SWModelParameters for TryAndIncrementParameters {
+    const COEFF_A: Self::BaseField = <Secp256k1Parameters as ark_ec::SWModelParameters>::COEFF_A;
+    const COEFF_B: Self::BaseField = <Secp256k1Parameters as ark_ec::SWModelParameters>::COEFF_B;
+    const COFACTOR: &'static [u64] = <Secp256k1Parameters as ark_ec::SWModelParameters>::COFACTOR;
+    const COFACTOR_INV: Self::ScalarField =
+        <Secp256k1Parameters as ark_ec::SWModelParameters>::COFACTOR_INV;
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        <Secp256k1Parameters as ark_ec::SWModelParameters>::AFFINE_GENERATOR_COEFFS;
+}
+
+impl crate::HashToCurveSuite for TryAndIncrementParameters {
+    const SUITE: &'static str = "try-and-increment";
+    const DST: &'static [u8] = b"PLUME/test-try-and-increment";
+
+    fn map_to_curve(
+        msgs: &[&[u8]],
+        dst: &[u8],
+    ) -> Result<GroupAffine<Self>, crate::HashToCurveError> {
+        crate::hash_to_curve::_try_and_increment::<
+            ark_ec::short_weierstrass_jacobian::GroupProjective<Self>,
+        >(&[dst, msgs.concat().as_slice()].concat())
+    }
+}
+
+#[test]
+pub fn test_hash_to_curve_suite() {
+    use crate::encoding::{parameters_from_bytes, parameters_to_bytes, ParametersHeader};
+    use crate::HashToCurveSuite;
+    type Q = TryAndIncrementParameters;
+
+    let mut rng = thread_rng();
+    let pp = crate::Parameters::<Q> {
+        g_point: GroupAffine::<Q>::prime_subgroup_generator(),
+    };
+    let (pk, sk) = PlumeSignature::<Q>::keygen(&pp, &mut rng);
+    let message = hardcoded_msg();
+    let h = hash_to_curve::<Q>(message.as_bytes(), &pk).unwrap();
+    for version in [
+        PlumeVersion::V1,
+        PlumeVersion::V2,
+        PlumeVersion::V1_1,
+        PlumeVersion::V2_1,
+    ] {
+        let sig =
+            PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message.as_bytes(), version).unwrap();
+        assert!(sig
+            .verify_non_zk(&pp, &pk, message.as_bytes(), version)
+            .is_ok());
+        assert!(sig
+            .verify_non_zk(&pp, &pk, b"another message", version)
+            .is_err());
+        // the nullifier is over the point of the curve's own suite
+        assert_eq!(sig.nullifier, h.mul(sk).into_affine());
+    }
+
+    // the curve is the same as `secp256k1`, only the suite tells their parameters apart
+    assert_eq!(ParametersHeader::for_curve::<Q>().htc_suite, Q::DST);
+    let (_, g) = test_template();
+    let bytes = parameters_to_bytes(&Parameters { g_point: g }).unwrap();
+    assert_eq!(
+        parameters_from_bytes::<Q>(&bytes).err(),
+        Some(EncodingError::ParameterMismatch { field: "htc_suite" })
+    );
+}
+
+#[test]
+pub fn test_p256_hash_to_curve() {
+    use crate::hash_to_curve::hash_bytes_to_curve;
+    use crate::p256::{Affine, P256Parameters, DST};
+
+    let g = Affine::prime_subgroup_generator();
+    assert!(g.is_on_curve() && g.is_in_correct_subgroup_assuming_on_curve());
+
+    // RFC 9380, J.1.1
+    let q128 = [&b"q128_"[..], &[b'q'; 128]].concat();
+    let a512 = [&b"a512_"[..], &[b'a'; 512]].concat();
+    for (msg, x, y) in [
+        (
+            &b""[..],
+            "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+            "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+        ),
+        (
+            &b"abc"[..],
+            "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+            "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+        ),
+        (
+            &b"abcdef0123456789"[..],
+            "65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+            "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+        ),
+        (
+            q128.as_slice(),
+            "4be61ee205094282ba8a2042bcb48d88dfbb609301c49aa8b078533dc65a0b5d",
+            "98f8df449a072c4721d241a3b1236d3caccba603f916ca680f4539d2bfb3c29e",
+        ),
+        (
+            a512.as_slice(),
+            "457ae2981f70ca85d8e24c308b14db22f3e3862c5ea0f652ca38b5e49cd64bc5",
+            "ecb9f0eadc9aeed232dabc53235368c1394c78de05dd96893eefa62b0f4757dc",
+        ),
+    ] {
+        let p = hash_bytes_to_curve::<P256Parameters>(&[msg], DST).unwrap();
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.x)), x);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.y)), y);
+    }
+}
+
+#[test]
+pub fn test_p256_vectors() {
+    use crate::p256::{Affine, Fr, P256Parameters};
+
+    let pp = crate::Parameters::<P256Parameters> {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let sk = encoding::field_from_bytes::<Fr>(&hex::decode(hardcoded_sk()).unwrap()).unwrap();
+    let r = encoding::field_from_bytes::<Fr>(
+        &hex::decode("93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808").unwrap(),
+    )
+    .unwrap();
+    let pk: Affine = pp.g_point.mul(sk).into_affine();
+    let point_hex = |point: &Affine| hex::encode(encoding::point_to_bytes(point).unwrap());
+    assert_eq!(
+        point_hex(&pk),
+        "031ccbe91c075fc7f4f033bfa248db8fccd3565de94bbfb12f3c59ff46c271bf83"
+    );
+    assert_eq!(
+        point_hex(&hash_to_curve::<P256Parameters>(hardcoded_msg().as_bytes(), &pk).unwrap()),
+        "035526c1f70487be768b313122b51abd5717c72e3d0b2cc54d8cb8ab6fa362c63b"
+    );
+
+    for (version, c, s) in [
+        (
+            PlumeVersion::V1,
+            "74f435d0d3acd6cb2f2e3af0cb0974aeb16c9063e319f5be3ced9963222858b8",
+            "3633626eaf06648315ad0925b751c4b916d23c505cb254a2f797dda3a11acb91",
+        ),
+        (
+            PlumeVersion::V2,
+            "513233958e2ccb9a6bcc65d5b61aef278116683077f1081cd07339e03b40cd43",
+            "01be1c370b8282359032b42e8c1bba44904178f65261745859f5732f724aa784",
+        ),
+    ] {
+        let sig =
+            PlumeSignature::sign_with_r(&pp, (&pk, &sk), hardcoded_msg().as_bytes(), r, version)
+                .unwrap();
+        assert_eq!(
+            point_hex(&sig.nullifier),
+            "02b4830c4d6b4088ecd4ba44cca7c15fcd94b65ad8522348cb437476db0a5e64c1"
+        );
+        assert_eq!(
+            point_hex(&sig.r_point),
+            "02c5b190a3bb50a4da9f826d6348a09a8e5995f464fdcbfbf572b69905b4a05f7d"
+        );
+        assert_eq!(
+            point_hex(&sig.hashed_to_curve_r),
+            "025662bed3f19eb243ef2e4cb57ed779e446747f588cd09c30c06eea2ab0f5fbfe"
+        );
+        assert_eq!(hex::encode(encoding::field_to_bytes(&sig.c)), c);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&sig.s)), s);
+        assert!(sig
+            .verify_non_zk(&pp, &pk, hardcoded_msg().as_bytes(), version)
+            .is_ok());
+        assert!(sig
+            .verify_non_zk(&pp, &pk, b"another message", version)
+            .is_err());
+    }
+}
+
+#[test]
+pub fn test_pasta_hash_to_curve() {
+    use crate::hash_to_curve::hash_bytes_to_curve;
+    use crate::pasta::{PallasAffine, PallasParameters, VestaAffine, VestaParameters};
+    use crate::HashToCurveSuite;
+
+    let pallas = PallasAffine::prime_subgroup_generator();
+    assert!(pallas.is_on_curve() && pallas.is_in_correct_subgroup_assuming_on_curve());
+    let vesta = VestaAffine::prime_subgroup_generator();
+    assert!(vesta.is_on_curve() && vesta.is_in_correct_subgroup_assuming_on_curve());
+
+    // computed with an implementation of the SvdW map of RFC 9380 independent of this crate
+    for (msg, x, y) in [
+        (
+            &b""[..],
+            "1423a0571acf500ab8d3a005094c64122dfdf7ef6d1b5eb4a22157e8b52dc85e",
+            "1763f47b33839cdbb0a46616b5baea85d4b0b96e3949b9e98b3c926a202ec531",
+        ),
+        (
+            &b"abc"[..],
+            "2796512377c9aed015722cfe793240070e9e34165a2460515c21a7bcb6fff25c",
+            "3f85ca679b982bd96508794392904df6385717ff57a81f9ffd1f9b9640332d45",
+        ),
+    ] {
+        let p = hash_bytes_to_curve::<PallasParameters>(&[msg], PallasParameters::DST).unwrap();
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.x)), x);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.y)), y);
+    }
+    for (msg, x, y) in [
+        (
+            &b""[..],
+            "107720b60a7ff51d41c9286845e96367cd41dd154b526b93f82531f14ea6f063",
+            "1edb7214afe9ffe0307821525b7f426e13d4f9eb41a7417f6cf99c5afd537282",
+        ),
+        (
+            &b"abc"[..],
+            "096ec518f6c33c90dcc0feabb91d7d65da37c0afddec8d1a887ee1c688359edd",
+            "218f0fdf57ea80f9390a950c61b8bbbacf16a21e88d9e8a7e0708e87df23569d",
+        ),
+    ] {
+        let p = hash_bytes_to_curve::<VestaParameters>(&[msg], VestaParameters::DST).unwrap();
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.x)), x);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.y)), y);
+    }
+}
+
+/// Checks the signatures with the hardcoded key and the hardcoded nonce, both reduced into the scalar field of `P`.
+fn check_signature_vectors<P: crate::HashToCurveSuite>(
+    [pk, h, nullifier, r_point, hashed_to_curve_r]: [&str; 5],
+    challenges: [(PlumeVersion, &str, &str); 2],
+) where
+    P::BaseField: ark_ff::PrimeField,
+{
+    use ark_ff::PrimeField;
+
+    let pp = crate::Parameters::<P> {
+        g_point: GroupAffine::<P>::prime_subgroup_generator(),
+    };
+    let sk = P::ScalarField::from_be_bytes_mod_order(&hex::decode(hardcoded_sk()).unwrap());
+    let r = P::ScalarField::from_be_bytes_mod_order(
+        &hex::decode("93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808").unwrap(),
+    );
+    let pk_point = pp.g_point.mul(sk).into_affine();
+    let point_hex = |point: &GroupAffine<P>| hex::encode(encoding::point_to_bytes(point).unwrap());
+    assert_eq!(point_hex(&pk_point), pk);
+    assert_eq!(
+        point_hex(&hash_to_curve::<P>(hardcoded_msg().as_bytes(), &pk_point).unwrap()),
+        h
+    );
+
+    for (version, c, s) in challenges {
+        let sig = PlumeSignature::sign_with_r(
+            &pp,
+            (&pk_point, &sk),
+            hardcoded_msg().as_bytes(),
+            r,
+            version,
+        )
+        .unwrap();
+        assert_eq!(point_hex(&sig.nullifier), nullifier);
+        assert_eq!(point_hex(&sig.r_point), r_point);
+        assert_eq!(point_hex(&sig.hashed_to_curve_r), hashed_to_curve_r);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&sig.c)), c);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&sig.s)), s);
+        assert!(sig
+            .verify_non_zk(&pp, &pk_point, hardcoded_msg().as_bytes(), version)
+            .is_ok());
+        assert!(sig
+            .verify_non_zk(&pp, &pk_point, b"another message", version)
+            .is_err());
+    }
+}
+
+#[test]
+pub fn test_pasta_vectors() {
+    use crate::pasta::{PallasParameters, VestaParameters};
+
+    check_signature_vectors::<PallasParameters>(
+        [
+            "03147ec6210003954d3993148740e5094ad72ea775f77cd6ffafe91ce2cb75beec",
+            "022aff3d461fbe91de9174e631fe2d6728a6439063eda3c8c296e81c528164220b",
+            "0300a90dfdd30bf9e0292ee122f0527023b79966b381203e7422e9524abc1ec789",
+            "02226016a916f09e07ae9fe52c4878ed8820434accef96698a0f50f7694c1f701c",
+            "03080a863bb36cdee898315a54399968dde11f590f6949d77a283610ccdff18714",
+        ],
+        [
+            (
+                PlumeVersion::V1,
+                "0c0398efe14f230517e67ceeb23e69fe1fe62c484e6f78d37d3b3b09f4bc46de",
+                "0c0043ec99f3af4ece9f3e5f059e85422c29650e0babc2251177ea811d144c7a",
+            ),
+            (
+                PlumeVersion::V2,
+                "371e04034d393a514858180649faae3b476a0949da0a23037907c51de49cf2d7",
+                "1118597af5238959e8a7b0b0478c4d35de8988a2f9887b6ee3bbe1e683ff7ddc",
+            ),
+        ],
+    );
+    check_signature_vectors::<VestaParameters>(
+        [
+            "0207ed77bac8652f255cc38c7d376e9eaaeeb69189cf4468babea44911a0fb24cd",
+            "0222b1d6ede9095d6143a12718e1b6558f28fc136c5acafee93162257d449a8f1e",
+            "021e8170fc1d14f9cc3b7652b3bdf6a489dee126d2f6b322c14cd3cb7b268d0942",
+            "023de2a51a0b5504c85057e4c71b9179cebce3035bab2e1eab9ac718300998171a",
+            "033e924cef7ecc5ec6b3a03185a4ff06b9f14e7f69e51c2e2179e413d0f9178c2e",
+        ],
+        [
+            (
+                PlumeVersion::V1,
+                "0b82753a0a894bd53075e5cec7f5e8da0848d8538cc7f6eda16066292593d7f8",
+                "12f83967d4d91c9f6eb71a8f2c44db9ba0e9145db4a3ca1c04fc990909aa4769",
+            ),
+            (
+                PlumeVersion::V2,
+                "0f81451342a3a20d0fdeca660a354db1b88216fe1bea4ce23aeb1e6b1862ec97",
+                "2ff72c9d4dce5fdd304a785549c624baa150ca8c996228663cb4013c8b7866b2",
+            ),
+        ],
+    );
+}
+
+#[test]
+pub fn test_bn254_hash_to_curve() {
+    use crate::bn254::{Bn254Parameters, G1Affine, GrumpkinAffine, GrumpkinParameters};
+    use crate::hash_to_curve::hash_bytes_to_curve;
+    use crate::HashToCurveSuite;
+
+    let grumpkin = GrumpkinAffine::prime_subgroup_generator();
+    assert!(grumpkin.is_on_curve() && grumpkin.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G1Affine::prime_subgroup_generator().is_on_curve());
+
+    // computed with an implementation of the SvdW map of RFC 9380 independent of this crate
+    for (msg, x, y) in [
+        (
+            &b""[..],
+            "0a976ab906170db1f9638d376514dbf8c42aef256a54bbd48521f20749e59e86",
+            "02925ead66b9e68bfc309b014398640ab55f6619ab59bc1fab2210ad4c4d53d5",
+        ),
+        (
+            &b"abc"[..],
+            "23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1",
+            "04142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d",
+        ),
+    ] {
+        let p = hash_bytes_to_curve::<Bn254Parameters>(&[msg], Bn254Parameters::DST).unwrap();
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.x)), x);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.y)), y);
+    }
+    for (msg, x, y) in [
+        (
+            &b""[..],
+            "27aff6ffd2d7594ac576ed76358b2a23622603d5a780f1a753ccfc76d027f90b",
+            "137a32ce5a1678a15ea77285dbe1bd0936b154bc2acface1522cc73633568414",
+        ),
+        (
+            &b"abc"[..],
+            "04655961a025a26dbc3c0324d9c913c6a951b0dd6b4513e11eb86b1a726f7d3a",
+            "0f1ec4b428acdf15c426f61d906beef4f10382650e3fac9e031becc7abb338ef",
+        ),
+    ] {
+        let p = hash_bytes_to_curve::<GrumpkinParameters>(&[msg], GrumpkinParameters::DST).unwrap();
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.x)), x);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.y)), y);
+    }
+}
+
+#[test]
+pub fn test_bn254_vectors() {
+    use crate::bn254::{Bn254Parameters, GrumpkinParameters};
+
+    check_signature_vectors::<Bn254Parameters>(
+        [
+            "03022a9ee73909e577e2670cec620777d600aecfc1c288cee36d49b71019390997",
+            "03175fa41dd33ee3f4418f3cbaffb09d1c3b134a217d25cef75be2724c7d5379d7",
+            "020d59a9db8b72c0b97766a79deb3228ec1196d7b2a6f3c3b1c0994b81e16ce738",
+            "02153ab740868aad291e684fece6275232b0ddb70274d8ae01398f593aef6a4fb8",
+            "02225877edd3d5db9b3215c66f9f725f0ebfff2106b2c9ed1cc666eb1bd7a2fb97",
+        ],
+        [
+            (
+                PlumeVersion::V1,
+                "136cfea6b9b6bbd2798e9af30e28557d6309a73a5542be11b6566683702edb7e",
+                "1aad5b3131de8eb46bf9198d8e7da84470bd10a96c8e5978cb66745b778c67ad",
+            ),
+            (
+                PlumeVersion::V2,
+                "224f277da38402fdf770a4d64cb323197f2d4a53835d364956df3bf064b5257d",
+                "05c87fef898f981f4a3047bb87ada293a36d0f67c86d1bf0883d0ac66b61f106",
+            ),
+        ],
+    );
+    check_signature_vectors::<GrumpkinParameters>(
+        [
+            "020cd11bf6397443d375e2c792a839d02df7a554d2c269b022a27a4ce6c4d22d1d",
+            "02021ef9001e885ac8110295abf42414f24cad03c86676582df802b9c1390315c2",
+            "021f33b6af2dfc1996ee256b753d4a39cb84140607e1df103fe59af2e790c06dfd",
+            "0323c5f7ff734d8a75805e04a7ddabf0b6605daebfd1cfa78136258ab8546ee809",
+            "020a51c22cfb452b3210f9efd61d184f41a56905d20c04c401b4dcdb388ea3b66a",
+        ],
+        [
+            (
+                PlumeVersion::V1,
+                "0990e1d64bd08c4ee493ff5a2b89271804f62ceb90f4f2cfdc27a8f4d02d0c1b",
+                "081b49fd75fcd4fb0a5e25e4f61a23896fd16b3ec942c46a8d3f9b1b7266cb3a",
+            ),
+            (
+                PlumeVersion::V2,
+                "296c3dedaef80ae9e09050142b10c39ebe85e4bbe363362d994b6b02e951c6a9",
+                "287347bdf45b7baa3332a5ecf8547d6e99c744e2ca3b2c9192712245d1fa5972",
+            ),
+        ],
+    );
+}
+
+#[test]
+pub fn test_babyjubjub_hash_to_curve() {
+    use crate::babyjubjub::{self, Affine, BabyJubJubParameters, Fq};
+    use crate::hash_to_curve::hash_bytes_to_curve;
+    use crate::HashToCurveSuite;
+    use ark_ff::One;
+
+    let fq = |hex: &str| encoding::field_from_bytes::<Fq>(&hex::decode(hex).unwrap()).unwrap();
+    let edwards = |point: &Affine| {
+        let (x, y) = babyjubjub::to_edwards(point);
+        (
+            hex::encode(encoding::field_to_bytes(&x)),
+            hex::encode(encoding::field_to_bytes(&y)),
+        )
+    };
+
+    // `Base8` of circomlib
+    let (base8_x, base8_y) = (
+        "0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051",
+        "25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b",
+    );
+    let g = Affine::prime_subgroup_generator();
+    assert!(g.is_on_curve() && g.is_in_correct_subgroup_assuming_on_curve());
+    assert_eq!(edwards(&g), (base8_x.to_string(), base8_y.to_string()));
+    assert_eq!(babyjubjub::from_edwards(fq(base8_x), fq(base8_y)), Ok(g));
+
+    // the identity, and the point of order 2
+    assert_eq!(
+        babyjubjub::from_edwards(Fq::zero(), Fq::one()),
+        Err(EncodingError::Identity)
+    );
+    assert_eq!(
+        babyjubjub::from_edwards(Fq::zero(), -Fq::one()),
+        Err(EncodingError::NotInSubgroup)
+    );
+    assert_eq!(
+        babyjubjub::from_edwards(Fq::one(), Fq::one()),
+        Err(EncodingError::NotOnCurve)
+    );
+
+    // computed with an implementation of the simplified SWU map of RFC 9380 independent of this crate, with the twisted Edwards coordinates
+    for (msg, x, y, edwards_x, edwards_y) in [
+        (
+            &b""[..],
+            "218926410728f0048af3020cba41d8ba78394726179ab89a84e20ec3388d7582",
+            "1a164b27f0830ea1faf1bc48b2463b3364d3c3fd65bc1566fa0c1924439482e6",
+            "1b0e3699b78edfde8b2cc70d170966700cecb03ca692dade19f0043934be637a",
+            "04e6ce09a90f760c53baf429e03681e16c3d7acf05f0e74e0505b258c7c2aac6",
+        ),
+        (
+            &b"abc"[..],
+            "28df77da923f248589356e8a545609856e12a4955fbe63c16189b09a2cc82c7a",
+            "00335e728efe3cb2e2ca4b300d8c6af62670fcdd419c924195ef52d528db7b60",
+            "034adb5a16e15bc151009cda3cf0285cee8e775f99f32d0e46445eeea8c9e260",
+            "1fd108a6a9867ed9d2008adeca25d1cb6ab0def071b159b24884955751ca700d",
+        ),
+    ] {
+        let p =
+            hash_bytes_to_curve::<BabyJubJubParameters>(&[msg], BabyJubJubParameters::DST).unwrap();
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.x)), x);
+        assert_eq!(hex::encode(encoding::field_to_bytes(&p.y)), y);
+        assert_eq!(edwards(&p), (edwards_x.to_string(), edwards_y.to_string()));
+        assert_eq!(
+            babyjubjub::from_edwards(fq(edwards_x), fq(edwards_y)),
+            Ok(p)
+        );
+    }
+}
+
+#[test]
+pub fn test_babyjubjub_vectors() {
+    use crate::babyjubjub::{self, BabyJubJubParameters};
+
+    check_signature_vectors::<BabyJubJubParameters>(
+        [
+            "03180409708343c2b6c9cacc1a1b73379a4f1d060faba28bfdae38e0a23ac489b9",
+            "0208095668ad269b1f9a7f9c669aa2275d3edfd1ae18d2bd2279203e8e1654abf2",
+            "030d956b8deae2305cdca176b35b3972a7ce17764ed1489f33a56b54ee3fd59b1b",
+            "0227a54bd5b6225ccaacecf7ef1c211c84000f45e4ce81acbd9a4513ed61b19402",
+            "0225f9333c77f33b3ef7288105d218a3443abed52789cf6bfd552c8d1f7e89570b",
+        ],
+        [
+            (
+                PlumeVersion::V1,
+                "012c96ebcf726e82b3a1f9459fd102acc4966bbabea2b244eee194be5b57f20b",
+                "035f7aa422b4602adebeaf95ec19289aa1f9dca32ae4bdfb510038ac5192f63e",
+            ),
+            (
+                PlumeVersion::V2,
+                "03a39820ae6724b0de797941d067c371927157d077d9bb97332317f16696c95b",
+                "0044fe9f4509c774dbcebb0b5c6b8c656cd59fcc8268188136174d32f5d89114",
+            ),
+        ],
+    );
+
+    // the key in the coordinates of circomlib
+    let pk = encoding::point_from_hex::<BabyJubJubParameters>(
+        "03180409708343c2b6c9cacc1a1b73379a4f1d060faba28bfdae38e0a23ac489b9",
+    )
+    .unwrap();
+    let (x, y) = babyjubjub::to_edwards(&pk);
+    assert_eq!(
+        hex::encode(encoding::field_to_bytes(&x)),
+        "2b6bb92e9b34d4f01f4842deb96d66b8427755cfddc962645faacb0e0cdc1d38"
+    );
+    assert_eq!(
+        hex::encode(encoding::field_to_bytes(&y)),
+        "2ed6535f255c75ef184a0ce5c27a2a578e041905c7615d9ba0f4ae36eb1a10e7"
+    );
+}
+
+fn check_conformance<P: crate::HashToCurveSuite>()
+where
+    P::BaseField: ark_ff::PrimeField,
+{
+    use crate::conformance::{run_all, ArkworksBackend};
+
+    let report = run_all(&ArkworksBackend::<P>::new().unwrap());
+    assert!(report.passed(), "{}", report.to_json());
+}
+
+#[test]
+pub fn test_conformance() {
+    check_conformance::<Secp256k1Parameters>();
+    check_conformance::<crate::p256::P256Parameters>();
+    check_conformance::<crate::pasta::PallasParameters>();
+    check_conformance::<crate::pasta::VestaParameters>();
+    check_conformance::<crate::bn254::Bn254Parameters>();
+    check_conformance::<crate::bn254::GrumpkinParameters>();
+    check_conformance::<crate::babyjubjub::BabyJubJubParameters>();
+}
+
+fn check_curve_constants<P: crate::HashToCurveSuite>(constants: &plume_core::consts::CurveConstants)
+where
+    P::BaseField: ark_ff::PrimeField,
+{
+    use ark_ff::{BigInteger, FpParameters, PrimeField};
+
+    let g = GroupAffine::<P>::prime_subgroup_generator();
+    assert_eq!(
+        encoding::to_hex(&encoding::field_to_bytes(&g.x)),
+        constants.generator_x
+    );
+    assert_eq!(
+        encoding::to_hex(&encoding::field_to_bytes(&g.y)),
+        constants.generator_y
+    );
+    assert_eq!(
+        encoding::to_hex(&<P::BaseField as PrimeField>::Params::MODULUS.to_bytes_be()),
+        constants.field_modulus
+    );
+    assert_eq!(
+        encoding::to_hex(&<P::ScalarField as PrimeField>::Params::MODULUS.to_bytes_be()),
+        constants.order
+    );
+    assert_eq!(
+        encoding::field_byte_len::<P::ScalarField>(),
+        constants.scalar_len()
+    );
+    assert_eq!(
+        encoding::point_to_bytes(&g).unwrap().len(),
+        constants.compressed_point_len()
+    );
+    assert_eq!(P::SUITE, constants.suite);
+    assert_eq!(P::DST, constants.dst);
+}
+
+#[test]
+pub fn test_curve_constants() {
+    check_curve_constants::<Secp256k1Parameters>(&plume_core::consts::SECP256K1);
+    check_curve_constants::<crate::p256::P256Parameters>(&plume_core::consts::P256);
+    check_curve_constants::<crate::pasta::PallasParameters>(&plume_core::consts::PALLAS);
+    check_curve_constants::<crate::pasta::VestaParameters>(&plume_core::consts::VESTA);
+    check_curve_constants::<crate::bn254::Bn254Parameters>(&plume_core::consts::BN254);
+    check_curve_constants::<crate::bn254::GrumpkinParameters>(&plume_core::consts::GRUMPKIN);
+    check_curve_constants::<crate::babyjubjub::BabyJubJubParameters>(
+        &plume_core::consts::BABYJUBJUB,
+    );
+}
+
+#[test]
+pub fn test_sign_digest() {
+    use crate::digest::{digest_dst, DIGEST_LEN};
+    use crate::hash_to_curve::hash_to_curve_with_dst;
+    use sha2::{Digest, Sha256};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let sk = hex_to_fr(&hardcoded_sk());
+    let pk = g.mul(sk).into_affine();
+    let digest: [u8; DIGEST_LEN] = Sha256::digest(hardcoded_msg().as_bytes()).into();
+
+    assert_eq!(
+        digest_dst::<Secp256k1Parameters>(),
+        b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_DIGEST_"
+    );
+    let h = hash_to_curve_with_dst(&digest, &pk, &digest_dst::<Secp256k1Parameters>()).unwrap();
+    assert_ne!(h, hash_to_curve(&digest, &pk).unwrap());
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2, PlumeVersion::V2_1] {
+        let sig = PlumeSignature::sign_digest(&pp, &mut rng, (&pk, &sk), &digest, version).unwrap();
+        assert_eq!(sig.nullifier, h.mul(sk).into_affine());
+        assert!(sig.verify_digest(&pp, &pk, &digest, version).is_ok());
+        assert!(sig
+            .verify_digest(&pp, &pk, &[0u8; DIGEST_LEN], version)
+            .is_err());
+        // the digest isn't the message of the plain protocol, either way
+        assert!(sig.verify_non_zk(&pp, &pk, &digest, version).is_err());
+        let as_message = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), &digest, version).unwrap();
+        assert_ne!(as_message.nullifier, sig.nullifier);
+        assert!(as_message
+            .verify_digest(&pp, &pk, &digest, version)
+            .is_err());
+    }
+}
+
+#[test]
+pub fn test_cache() {
+    use crate::cache::{self, PointTable, StandardParameters};
+    use crate::p256::P256Parameters;
+    use ark_std::UniformRand;
+    use secp256k1::fields::Fr;
+
+    let mut rng = thread_rng();
+    let pp = Secp256k1Parameters::parameters();
+    assert_eq!(pp.g_point, Affine::prime_subgroup_generator());
+    assert!(core::ptr::eq(pp, Secp256k1Parameters::parameters()));
+    // every thread shares the parameters of the process
+    let address = pp as *const _ as usize;
+    let other = std::thread::spawn(|| Secp256k1Parameters::parameters() as *const _ as usize);
+    assert_eq!(other.join().unwrap(), address);
+
+    let table = PointTable::new(&pp.g_point);
+    for scalar in [
+        Fr::zero(),
+        Fr::from(1u64),
+        -Fr::from(1u64),
+        Fr::rand(&mut rng),
+    ] {
+        assert_eq!(table.mul(&scalar), pp.g_point.mul(scalar));
+    }
+    assert_eq!(
+        Secp256k1Parameters::generator_table().mul(&Fr::from(7u64)),
+        pp.g_point.mul(Fr::from(7u64))
+    );
+
+    let message = b"Message";
+    let (pk, sk) = PlumeSignature::keygen(pp, &mut rng);
+    let sig = PlumeSignature::sign(pp, &mut rng, (&pk, &sk), message, PlumeVersion::V1).unwrap();
+
+    cache::clear();
+    cache::warm_generator::<Secp256k1Parameters>();
+    cache::warm(&pk).unwrap();
+    cache::warm(&pk).unwrap();
+    assert_eq!(cache::len(), 2);
+    assert!(cache::is_warm(&pk) && cache::is_warm(&pp.g_point));
+    assert!(sig
+        .verify_non_zk(pp, &pk, message, PlumeVersion::V1)
+        .is_ok());
+    assert!(sig
+        .verify_non_zk(pp, &pk, b"Other", PlumeVersion::V1)
+        .is_err());
+    let (other_pk, _) = PlumeSignature::keygen(pp, &mut rng);
+    cache::warm(&other_pk).unwrap();
+    assert!(sig
+        .verify_non_zk(pp, &other_pk, message, PlumeVersion::V1)
+        .is_err());
+
+    // the caches are per thread, and per curve
+    assert_eq!(std::thread::spawn(cache::len).join().unwrap(), 0);
+    assert!(!cache::is_warm(&P256Parameters::parameters().g_point));
+    assert_eq!(cache::warm(&Affine::zero()), Err(EncodingError::Identity));
+
+    assert!(cache::evict(&pk));
+    assert!(!cache::evict(&pk));
+    assert!(sig
+        .verify_non_zk(pp, &pk, message, PlumeVersion::V1)
+        .is_ok());
+    cache::clear();
+    assert_eq!(cache::len(), 0);
+}
+
+#[cfg(feature = "constant-time")]
+fn check_constant_time_mul<P: crate::HashToCurveSuite>() {
+    use crate::constant_time;
+    use ark_ff::{Field, One};
+    use ark_std::UniformRand;
+
+    let mut rng = thread_rng();
+    let g = GroupAffine::<P>::prime_subgroup_generator();
+    let h = crate::hash_to_curve::hash_to_curve::<P>(b"Message", &g).unwrap();
+    let random = P::ScalarField::rand(&mut rng);
+    for scalar in [
+        P::ScalarField::zero(),
+        P::ScalarField::one(),
+        P::ScalarField::one().double(),
+        -P::ScalarField::one(),
+        random,
+        random.double(),
+    ] {
+        for point in [g, h] {
+            assert_eq!(
+                constant_time::mul(&point, &scalar),
+                point.mul(scalar).into_affine()
+            );
+        }
+        assert_eq!(
+            constant_time::response::<P>(&random, &scalar, &random.double()),
+            random + scalar * random.double()
+        );
+    }
+    assert!(constant_time::mul(&GroupAffine::<P>::zero(), &random).is_zero());
+}
+
+#[cfg(feature = "constant-time")]
+#[test]
+pub fn test_constant_time_mul() {
+    check_constant_time_mul::<Secp256k1Parameters>();
+    check_constant_time_mul::<crate::p256::P256Parameters>();
+    check_constant_time_mul::<crate::pasta::PallasParameters>();
+    check_constant_time_mul::<crate::bn254::Bn254Parameters>();
+    check_constant_time_mul::<crate::babyjubjub::BabyJubJubParameters>();
+}
+
+#[test]
+pub fn test_verify_errors() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let other_keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let sig = PlumeSignature::sign(
+        &pp,
+        &mut rng,
+        (&keypair.0, &keypair.1),
+        b"Message",
+        PlumeVersion::V1,
+    )
+    .unwrap();
+    assert!(sig
+        .verify_non_zk(&pp, &keypair.0, b"Message", PlumeVersion::V1)
+        .is_ok());
+    assert!(matches!(
+        sig.verify_non_zk(&pp, &Affine::zero(), b"Message", PlumeVersion::V1),
+        Err(PlumeVerifyError::InvalidPublicKey(EncodingError::Identity))
+    ));
+    let mut invalid = sig;
+    invalid.nullifier = Affine::zero();
+    assert!(matches!(
+        invalid.verify_non_zk(&pp, &keypair.0, b"Message", PlumeVersion::V1),
+        Err(PlumeVerifyError::InvalidSignature(EncodingError::Identity))
+    ));
+    assert!(matches!(
+        sig.verify_non_zk(&pp, &other_keypair.0, b"Message", PlumeVersion::V1),
+        Err(PlumeVerifyError::RPointMismatch)
+    ));
+    // another message is another `h`, with `g^r` still matching
+    assert!(matches!(
+        sig.verify_non_zk(&pp, &keypair.0, b"Other message", PlumeVersion::V1),
+        Err(PlumeVerifyError::HashedPointMismatch)
+    ));
+    // both equations hold, but the framed challenge is another one
+    let result = sig.verify_non_zk(&pp, &keypair.0, b"Message", PlumeVersion::V1_1);
+    assert!(matches!(result, Err(PlumeVerifyError::ChallengeMismatch)));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "the challenge doesn't match the points"
+    );
+}
+
+#[test]
+pub fn test_key_import() {
+    use crate::keys::{PublicKeySec1, SecretKeyBytes};
+    use crate::{PublicKey, SecretKeyMaterial};
+
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let sk_bytes: [u8; 32] =
+        encoding::from_hex("519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let sk = SecretKeyMaterial::<Secp256k1Parameters>::from_bytes(&sk_bytes).unwrap();
+    let (pk, imported) = PlumeSignature::keypair_from_bytes(&pp, &sk_bytes).unwrap();
+    assert_eq!(*imported, sk);
+    assert_eq!(
+        encoding::point_to_hex(&pk).unwrap(),
+        "030cec028ee08d09e02672a68310814354f9eabfff0de6dacc1cd3a774496076ae"
+    );
+    // the imported key signs
+    let sig = PlumeSignature::sign(
+        &pp,
+        &mut thread_rng(),
+        (&pk, &sk),
+        b"import",
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    assert!(sig.verify(&pp, &pk, b"import", PlumeVersion::V2).is_ok());
+
+    assert_eq!(
+        SecretKeyMaterial::<Secp256k1Parameters>::from_bytes(&[0; 32]),
+        Err(EncodingError::ZeroScalar)
+    );
+    // the group order
+    let order: [u8; 32] =
+        encoding::from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    assert_eq!(
+        SecretKeyMaterial::<Secp256k1Parameters>::from_bytes(&order),
+        Err(EncodingError::NonCanonicalField)
+    );
+
+    let compressed = encoding::point_to_bytes(&pk).unwrap();
+    let uncompressed = [
+        vec![0x04],
+        encoding::field_to_bytes(&pk.x),
+        encoding::field_to_bytes(&pk.y),
+    ]
+    .concat();
+    for bytes in [&compressed, &uncompressed] {
+        assert_eq!(
+            PublicKey::<Secp256k1Parameters>::from_sec1_bytes(bytes),
+            Ok(pk)
+        );
+    }
+
+    let mut off_curve = uncompressed.clone();
+    off_curve[64] ^= 1;
+    assert_eq!(
+        PublicKey::<Secp256k1Parameters>::from_sec1_bytes(&off_curve),
+        Err(EncodingError::NotOnCurve)
+    );
+    assert_eq!(
+        PublicKey::<Secp256k1Parameters>::from_sec1_bytes(&uncompressed[..64]),
+        Err(EncodingError::InvalidLength {
+            expected: 65,
+            actual: 64
+        })
+    );
+    assert_eq!(
+        PublicKey::<Secp256k1Parameters>::from_sec1_bytes(&[0x00]),
+        Err(EncodingError::Identity)
+    );
+    let mut unknown_tag = compressed.clone();
+    unknown_tag[0] = 0x05;
+    assert_eq!(
+        PublicKey::<Secp256k1Parameters>::from_sec1_bytes(&unknown_tag),
+        Err(EncodingError::InvalidTag(0x05))
+    );
+}
+
+#[test]
+pub fn test_k256_conversions() {
+    use crate::k256_interop::{self, k256};
+    use elliptic_curve::sec1::ToEncodedPoint;
+    use secp256k1::fields::Fr;
+
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let sk_hex = "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464";
+    let k256_sk = k256::SecretKey::from_be_bytes(&encoding::from_hex(sk_hex).unwrap()).unwrap();
+    let sk = k256_interop::secret_key_from_k256(&k256_sk);
+    assert_eq!(encoding::field_to_hex(&*sk), sk_hex);
+    assert_eq!(k256_interop::secret_key_to_k256(&sk).unwrap(), k256_sk);
+
+    // the public key of the k256 key is the one this crate derives
+    let pk = pp.g_point.mul(*sk).into_affine();
+    let k256_pk = k256_sk.public_key();
+    assert_eq!(k256_interop::public_key_from_k256(&k256_pk), pk);
+    assert_eq!(k256_interop::public_key_to_k256(&pk).unwrap(), k256_pk);
+    assert_eq!(
+        encoding::from_hex("030cec028ee08d09e02672a68310814354f9eabfff0de6dacc1cd3a774496076ae")
+            .unwrap(),
+        k256_pk.to_encoded_point(true).as_bytes()
+    );
+
+    let scalar = -Fr::from(1u64);
+    let k256_scalar = k256_interop::scalar_to_k256(&scalar);
+    assert_eq!(k256_scalar, -k256::Scalar::ONE);
+    assert_eq!(k256_interop::scalar_from_k256(&k256_scalar), scalar);
+
+    // the values k256 can't represent
+    assert_eq!(
+        k256_interop::point_from_k256(&k256::AffinePoint::IDENTITY),
+        GroupAffine::zero()
+    );
+    assert_eq!(
+        k256_interop::point_to_k256(&GroupAffine::zero()),
+        Ok(k256::AffinePoint::IDENTITY)
+    );
+    assert_eq!(
+        k256_interop::public_key_to_k256(&GroupAffine::zero()),
+        Err(EncodingError::Identity)
+    );
+    assert_eq!(
+        k256_interop::secret_key_to_k256(&Fr::zero()),
+        Err(EncodingError::ZeroScalar)
+    );
+    let off_curve = GroupAffine::new(pk.x, pk.y + Fq::from(1u64), false);
+    assert_eq!(
+        k256_interop::point_to_k256(&off_curve),
+        Err(EncodingError::NotOnCurve)
+    );
+}
+
+#[cfg(feature = "eth-recovery")]
+#[test]
+pub fn test_ethereum_account() {
+    use crate::account::{self, EthereumAccount};
+
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let account = EthereumAccount::from_private_key_hex(
+        "0x519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464",
+    )
+    .unwrap();
+    assert_eq!(
+        account::checksum_address(account.address()),
+        "0xeec0A732F1B50Be7aB46A0237088DA7F4680910C"
+    );
+    assert_eq!(
+        encoding::point_to_hex(account.public_key()).unwrap(),
+        "030cec028ee08d09e02672a68310814354f9eabfff0de6dacc1cd3a774496076ae"
+    );
+    let (address, checksummed) = account::public_key_address(account.public_key()).unwrap();
+    assert_eq!(&address, account.address());
+    assert_eq!(account::parse_address(&checksummed), Ok(address));
+    // the key of the Web3 Secret Storage vectors, uppercase and without `0x`
+    let other = EthereumAccount::from_private_key_hex(
+        "7A28B5BA57C53603B0B07B56BBA752F7784BF506FA95EDC395F5CF6C7514FE9D",
+    )
+    .unwrap();
+    assert_eq!(
+        encoding::to_hex(other.address()),
+        "008aeeda4d805471df9b2a5b0f38a0c3bcba786b"
+    );
+
+    // the account signs for its address
+    let sig = PlumeSignature::sign(
+        &pp,
+        &mut thread_rng(),
+        account.keypair(),
+        b"claim",
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    assert!(sig
+        .verify(&pp, account.public_key(), b"claim", PlumeVersion::V2)
+        .is_ok());
+
+    // the vectors of EIP-55
+    for address in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let parsed = account::parse_address(address).unwrap();
+        assert_eq!(account::checksum_address(&parsed), address);
+        assert_eq!(
+            account::parse_address(&address.to_ascii_lowercase()),
+            Ok(parsed)
+        );
+    }
+    assert_eq!(
+        account::parse_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Err(EncodingError::InvalidChecksum)
+    );
+    assert!(matches!(
+        account::parse_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea"),
+        Err(EncodingError::InvalidLength { .. })
+    ));
+    assert!(matches!(
+        EthereumAccount::from_private_key_hex("0x519b42"),
+        Err(EncodingError::InvalidLength { .. })
+    ));
+    assert!(matches!(
+        EthereumAccount::from_private_key_hex(&"00".repeat(32)),
+        Err(EncodingError::ZeroScalar)
+    ));
+}
+
+#[test]
+pub fn test_keccak_challenge() {
+    use crate::hasher::{Keccak256, KECCAK256_TAG};
+    use ark_ff::PrimeField;
+    use tiny_keccak::{Hasher, Keccak};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig = PlumeSignature::sign_with_hasher::<Keccak256>(
+            &pp,
+            &mut rng,
+            (&pk, &sk),
+            message,
+            version,
+        )
+        .unwrap();
+        assert!(sig
+            .verify_non_zk_with_hasher::<Keccak256>(&pp, &pk, message, version)
+            .is_ok());
+        // a Keccak signature is none of the SHA-256 versions
+        for sha256_version in [
+            PlumeVersion::V1,
+            PlumeVersion::V2,
+            PlumeVersion::V1_1,
+            PlumeVersion::V2_1,
+        ] {
+            assert!(sig.verify(&pp, &pk, message, sha256_version).is_err());
+        }
+    }
+
+    // `c` of V2 is `keccak256(tag || nul || g^r || z)` modulo the order, as an EVM verifier computes it
+    let sig = PlumeSignature::sign_with_hasher::<Keccak256>(
+        &pp,
+        &mut rng,
+        (&pk, &sk),
+        message,
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    let mut digest = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(KECCAK256_TAG);
+    for point in [&sig.nullifier, &sig.r_point, &sig.hashed_to_curve_r] {
+        keccak.update(&encoding::point_to_bytes(point).unwrap());
+    }
+    keccak.finalize(&mut digest);
+    assert_eq!(
+        sig.c,
+        secp256k1::fields::Fr::from_be_bytes_mod_order(&digest)
+    );
+}
+
+#[test]
+pub fn test_sha512_challenge() {
+    use crate::hasher::{ChallengeHash, Sha512};
+    use crate::offline::{SigningRequest, SigningResponse};
+    use ark_ff::PrimeField;
+    use sha2::Digest;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig =
+            PlumeSignature::sign_with_hasher::<Sha512>(&pp, &mut rng, (&pk, &sk), message, version)
+                .unwrap();
+        assert!(sig
+            .verify_non_zk_with_hasher::<Sha512>(&pp, &pk, message, version)
+            .is_ok());
+        assert!(sig.verify(&pp, &pk, message, version).is_err());
+    }
+
+    // `c` of V2 is the SHA-512 of `nul || g^r || z` modulo the order
+    let sig = PlumeSignature::sign_with_hasher::<Sha512>(
+        &pp,
+        &mut rng,
+        (&pk, &sk),
+        message,
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    let mut hasher = Sha512::new();
+    for point in [&sig.nullifier, &sig.r_point, &sig.hashed_to_curve_r] {
+        hasher.update(encoding::point_to_bytes(point).unwrap());
+    }
+    assert_eq!(
+        sig.c,
+        secp256k1::fields::Fr::from_be_bytes_mod_order(&hasher.finalize())
+    );
+
+    // the hash is part of the version code of an offline request
+    let request = SigningRequest::new(&pp, message, b"", PlumeVersion::V2)
+        .unwrap()
+        .with_hash(ChallengeHash::Sha512);
+    // the map, the message and the empty scope, then the key `3` and the code of V2 with SHA-512
+    assert_eq!(request.to_cbor()[message.len() + 5..][..2], [0x03, 0x06]);
+    let received = SigningRequest::from_cbor(&request.to_cbor()).unwrap();
+    assert_eq!(received.hash, ChallengeHash::Sha512);
+    let response = received.sign(&pp, &mut rng, (&pk, &sk)).unwrap();
+    assert!(response.verify(&pp, &request).unwrap());
+    assert!(response
+        .signature
+        .verify_non_zk_with_hasher::<Sha512>(&pp, &pk, message, PlumeVersion::V2)
+        .is_ok());
+    let sha256 = SigningResponse {
+        signature: PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, PlumeVersion::V2)
+            .unwrap(),
+        ..response
+    };
+    assert!(!sha256.verify(&pp, &request).unwrap());
+}
+
+#[test]
+pub fn test_multi_nullifier_signature() {
+    use crate::multi::MultiNullifierSignature;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let messages: [&[u8]; 3] = [b"epoch 1", b"epoch 2", b"app"];
+
+    let sig = MultiNullifierSignature::sign(&pp, &mut rng, (&pk, &sk), &messages).unwrap();
+    assert!(sig.verify(&pp, &pk, &messages).unwrap());
+    // the nullifiers are those of the plain signatures
+    for (message, nullifier) in messages.iter().zip(&sig.nullifiers) {
+        let plain =
+            PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, PlumeVersion::V2).unwrap();
+        assert_eq!(plain.nullifier, *nullifier);
+    }
+
+    // other messages, order, key, or a swapped nullifier
+    assert!(!sig.verify(&pp, &pk, &messages[..2]).unwrap());
+    assert!(!sig
+        .verify(&pp, &pk, &[messages[1], messages[0], messages[2]])
+        .unwrap());
+    let (other_pk, other_sk) = PlumeSignature::keygen(&pp, &mut rng);
+    assert!(!sig.verify(&pp, &other_pk, &messages).unwrap());
+    let other =
+        MultiNullifierSignature::sign(&pp, &mut rng, (&other_pk, &other_sk), &messages).unwrap();
+    let mut tampered = sig.clone();
+    tampered.nullifiers[1] = other.nullifiers[1];
+    assert!(!tampered.verify(&pp, &pk, &messages).unwrap());
+
+    let empty = MultiNullifierSignature::sign(&pp, &mut rng, (&pk, &sk), &[]).unwrap();
+    assert!(!empty.verify(&pp, &pk, &[]).unwrap());
+}
+
+#[test]
+pub fn test_compute_nullifier() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    let nullifier = crate::compute_nullifier(&pp, &sk, message).unwrap();
+    for version in [PlumeVersion::V1, PlumeVersion::V2, PlumeVersion::V2_1] {
+        let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+        assert_eq!(sig.nullifier, nullifier);
+    }
+    let h = crate::compute_h::<Secp256k1Parameters>(&pk, message).unwrap();
+    assert_eq!(h.mul(sk).into_affine(), nullifier);
+    assert_ne!(
+        crate::compute_nullifier(&pp, &sk, b"Other").unwrap(),
+        nullifier
+    );
+}
+
+#[test]
+pub fn test_split_verification() {
+    use crate::challenge::CurveDefault;
+    use crate::hasher::{Keccak256, Sha256};
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+        assert!(sig.check_ec_equations(&pp, &pk, message).is_ok());
+        assert!(sig.check_challenge(&pp, &pk, message, version).is_ok());
+
+        // the equations don't depend on the challenge computation
+        let mut other_c = sig;
+        other_c.c += secp256k1::fields::Fr::from(1u64);
+        other_c.s += sk;
+        assert!(other_c.check_ec_equations(&pp, &pk, message).is_ok());
+        assert!(matches!(
+            other_c.check_challenge(&pp, &pk, message, version),
+            Err(PlumeVerifyError::ChallengeMismatch)
+        ));
+        assert!(other_c.verify(&pp, &pk, message, version).is_err());
+    }
+
+    // the challenge of V2 doesn't cover `h`, so only the equations reject another message
+    let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, PlumeVersion::V2).unwrap();
+    assert!(matches!(
+        sig.check_ec_equations(&pp, &pk, b"Other"),
+        Err(PlumeVerifyError::HashedPointMismatch)
+    ));
+    assert!(sig
+        .check_challenge(&pp, &pk, b"Other", PlumeVersion::V2)
+        .is_ok());
+
+    // the challenge of another hasher or reduction is checked with the same ones
+    let keccak = PlumeSignature::sign_with_hasher::<Keccak256>(
+        &pp,
+        &mut rng,
+        (&pk, &sk),
+        message,
+        PlumeVersion::V1,
+    )
+    .unwrap();
+    assert!(keccak.check_ec_equations(&pp, &pk, message).is_ok());
+    assert!(keccak
+        .check_challenge_with::<Keccak256, _>(&pp, &pk, message, PlumeVersion::V1, &CurveDefault)
+        .is_ok());
+    assert!(keccak
+        .check_challenge(&pp, &pk, message, PlumeVersion::V1)
+        .is_err());
+    let wide = ChallengeDerivation::WideReduction;
+    let sig = PlumeSignature::sign_with_reduction::<Sha256, _>(
+        &pp,
+        &mut rng,
+        (&pk, &sk),
+        message,
+        PlumeVersion::V2,
+        &wide,
+    )
+    .unwrap();
+    assert!(sig
+        .check_challenge_with::<Sha256, _>(&pp, &pk, message, PlumeVersion::V2, &wide)
+        .is_ok());
+}
+
+#[test]
+pub fn test_plume_witness() {
+    use crate::witness::{self, SHA256_BLOCK_LEN};
+    use ark_ff::PrimeField;
+
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    for (version, points) in [(PlumeVersion::V1, 6), (PlumeVersion::V2, 3)] {
+        let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+        let witness = witness::compute_witness(&pp, &pk, message, &sig, version).unwrap();
+        let signature = witness.signature();
+        assert_eq!(
+            (
+                signature.nullifier,
+                signature.r_point,
+                signature.hashed_to_curve_r
+            ),
+            (sig.nullifier, sig.r_point, sig.hashed_to_curve_r)
+        );
+        assert_eq!((signature.c, signature.s), (sig.c, sig.s));
+        assert_eq!(witness.pk, pk);
+        assert_eq!(
+            witness.hashed_to_curve,
+            crate::compute_h::<Secp256k1Parameters>(&pk, message).unwrap()
+        );
+        assert_eq!(witness.hashed_to_curve.mul(sk).into_affine(), sig.nullifier);
+
+        // `c` is the digest of the preimage modulo the order
+        assert_eq!(witness.challenge_preimage.len(), 33 * points);
+        assert_eq!(
+            witness.c,
+            secp256k1::fields::Fr::from_be_bytes_mod_order(&witness.challenge_digest)
+        );
+        let padded = witness.sha256_blocks.concat();
+        assert_eq!(padded.len() % SHA256_BLOCK_LEN, 0);
+        assert_eq!(
+            witness.sha256_blocks.len(),
+            (witness.challenge_preimage.len() + 9).div_ceil(SHA256_BLOCK_LEN)
+        );
+        assert!(padded.starts_with(&witness.challenge_preimage));
+        assert_eq!(padded[witness.challenge_preimage.len()], 0x80);
+        assert_eq!(
+            padded[padded.len() - 8..],
+            (witness.challenge_preimage.len() as u64 * 8).to_be_bytes()
+        );
+
+        // no witness for an invalid signature
+        assert!(matches!(
+            witness::compute_witness(&pp, &pk, b"Other", &sig, version),
+            Err(PlumeVerifyError::HashedPointMismatch)
+        ));
+    }
+    // a full block of padding when the length doesn't fit
+    assert_eq!(witness::sha256_blocks(&[0; 55]).len(), 1);
+    assert_eq!(witness::sha256_blocks(&[0; 56]).len(), 2);
+    assert_eq!(witness::sha256_blocks(&[]).len(), 1);
+}
+
+#[test]
+pub fn test_same_signer_proof() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let other_keypair = PlumeSignature::keygen(&pp, &mut rng);
+
+    let (message_a, message_b) = (&b"scope A"[..], &b"scope B"[..]);
+    let sign = |keypair: &(GroupAffine<Secp256k1Parameters>, _), message, rng: &mut ThreadRng| {
+        PlumeSignature::sign(
+            &pp,
+            rng,
+            (&keypair.0, &keypair.1),
+            message,
+            PlumeVersion::V2,
+        )
+        .unwrap()
+    };
+    let sig_a = sign(&keypair, message_a, &mut rng);
+    let sig_b = sign(&keypair, message_b, &mut rng);
+
+    let proof = SameSignerProof::prove(
+        (&keypair.0, &keypair.1),
+        (message_a, &sig_a),
+        (message_b, &sig_b),
+        &mut rng,
+    )
+    .unwrap();
+    assert!(proof
+        .verify(&keypair.0, (message_a, &sig_a), (message_b, &sig_b))
+        .is_ok());
+
+    // a nullifier of another key can't be linked
+    let other_sig_b = sign(&other_keypair, message_b, &mut rng);
+    let proof = SameSignerProof::prove(
+        (&keypair.0, &keypair.1),
+        (message_a, &sig_a),
+        (message_b, &other_sig_b),
+        &mut rng,
+    )
+    .unwrap();
+    assert!(matches!(
+        proof.verify(&keypair.0, (message_a, &sig_a), (message_b, &other_sig_b)),
+        Err(PlumeVerifyError::ChallengeMismatch)
+    ));
+
+    // nor the identity
+    let mut identity_b = sig_b;
+    identity_b.nullifier = GroupAffine::zero();
+    assert!(matches!(
+        proof.verify(&keypair.0, (message_a, &sig_a), (message_b, &identity_b)),
+        Err(PlumeVerifyError::InvalidSignature(EncodingError::Identity))
+    ));
+}
+
+#[test]
+pub fn test_sequence_nullifiers() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let notes = &b"notes"[..];
+
+    let signatures: Vec<_> = (0..3)
+        .map(|index| {
+            PlumeSignature::sign(
+                &pp,
+                &mut rng,
+                (&keypair.0, &keypair.1),
+                &scope::sequence_message(notes, index),
+                PlumeVersion::V2,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let nullifiers = scope::nullifiers_in_range(&pp, &keypair.1, notes, 0..3).unwrap();
+    for (index, (signature, nullifier)) in signatures.iter().zip(&nullifiers).enumerate() {
+        assert_eq!(signature.nullifier, *nullifier);
+        assert_eq!(
+            scope::nullifier_at_index(&pp, &keypair.1, notes, index as u64).unwrap(),
+            *nullifier
+        );
+    }
+    assert_ne!(nullifiers[0], nullifiers[1]);
+
+    assert!(scope::verify_at_index(
+        &pp,
+        &keypair.0,
+        notes,
+        2,
+        3,
+        &signatures[2],
+        PlumeVersion::V2
+    )
+    .unwrap());
+    // out of bounds
+    assert!(!scope::verify_at_index(
+        &pp,
+        &keypair.0,
+        notes,
+        2,
+        2,
+        &signatures[2],
+        PlumeVersion::V2
+    )
+    .unwrap());
+    assert!(
+        scope::verify_sequence(&pp, &keypair.0, notes, 0, 3, &signatures, PlumeVersion::V2)
+            .unwrap()
+    );
+    assert!(
+        !scope::verify_sequence(&pp, &keypair.0, notes, 1, 3, &signatures, PlumeVersion::V2)
+            .unwrap()
+    );
+}
+
+#[test]
+pub fn test_scoped_nullifiers() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let message = &b"vote"[..];
+
+    let sig = scope::sign_scoped(
+        &pp,
+        &mut rng,
+        (&keypair.0, &keypair.1),
+        b"poll 1",
+        message,
+        PlumeVersion::V2,
+    )
+    .unwrap();
+    assert!(
+        scope::verify_scoped(&pp, &keypair.0, b"poll 1", message, &sig, PlumeVersion::V2).unwrap()
+    );
+    assert!(
+        !scope::verify_scoped(&pp, &keypair.0, b"poll 2", message, &sig, PlumeVersion::V2).unwrap()
+    );
+    assert!(sig
+        .verify(&pp, &keypair.0, message, PlumeVersion::V2)
+        .is_err());
+    assert_eq!(
+        sig.nullifier,
+        scope::scoped_nullifier(&pp, &keypair.1, b"poll 1", message).unwrap()
+    );
+
+    // one nullifier per scope, and the fields don't run into each other
+    let nullifier = |poll: &[u8], message: &[u8]| {
+        scope::scoped_nullifier(&pp, &keypair.1, poll, message).unwrap()
+    };
+    assert_ne!(nullifier(b"poll 1", message), nullifier(b"poll 2", message));
+    assert_ne!(nullifier(b"poll 1", b"vote"), nullifier(b"poll 1v", b"ote"));
+    assert_ne!(
+        nullifier(b"", message),
+        PlumeSignature::sign(
+            &pp,
+            &mut rng,
+            (&keypair.0, &keypair.1),
+            message,
+            PlumeVersion::V2
+        )
+        .unwrap()
+        .nullifier
+    );
+}
+
+#[test]
+pub fn test_anonymity_set_id() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let pks: Vec<_> = (0..3)
+        .map(|_| PlumeSignature::keygen(&pp, &mut rng).0)
+        .collect();
+
+    let id = scope::anonymity_set_id(&pks).unwrap();
+    let reordered = [pks[2], pks[0], pks[1], pks[0]];
+    assert_eq!(scope::anonymity_set_id(&reordered).unwrap(), id);
+    assert_ne!(scope::anonymity_set_id(&pks[..2]).unwrap(), id);
+
+    let other_id = scope::anonymity_set_id(&pks[1..]).unwrap();
+    assert_ne!(
+        scope::set_scope(&id, b"round 1"),
+        scope::set_scope(&other_id, b"round 1")
+    );
+    assert!(scope::anonymity_set_id(&[GroupAffine::<Secp256k1Parameters>::zero()]).is_err());
+}
+
+#[test]
+pub fn test_weighted_units() {
+    let (mut rng, g) = test_template();
+    let pp = Parameters { g_point: g };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let leaf = WeightedLeaf {
+        pk: keypair.0,
+        weight: 2,
+    };
+    let round = b"staking round 1";
+
+    let sigs = (0..3)
+        .map(|index| {
+            PlumeSignature::sign(
+                &pp,
+                &mut rng,
+                (&keypair.0, &keypair.1),
+                &scope::weighted_unit_message(round, index),
+                PlumeVersion::V1,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    assert!(leaf
+        .verify_unit(&pp, round, 0, &sigs[0], PlumeVersion::V1)
+        .unwrap());
+    assert!(leaf
+        .verify_unit(&pp, round, 1, &sigs[1], PlumeVersion::V1)
+        .unwrap());
+    assert_ne!(sigs[0].nullifier, sigs[1].nullifier);
+    // the signature is valid, but the index is outside the weight
+    assert!(!leaf
+        .verify_unit(&pp, round, 2, &sigs[2], PlumeVersion::V1)
+        .unwrap());
+    // a signature for one unit doesn't verify for another
+    assert!(!leaf
+        .verify_unit(&pp, round, 1, &sigs[0], PlumeVersion::V1)
+        .unwrap());
+
+    let leaf_bytes = leaf.to_bytes().unwrap();
+    let decoded = WeightedLeaf::<Secp256k1Parameters>::from_bytes(&leaf_bytes).unwrap();
+    assert_eq!((decoded.pk, decoded.weight), (leaf.pk, leaf.weight));
+}
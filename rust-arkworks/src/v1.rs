@@ -0,0 +1,67 @@
+//! PLUME V1: the challenge is `c = sha256(g, pk, h, nul, g^r, z)`.
+//!
+//! The signature carries `g^r` and `z = h^r`, so that a verifier can check the hash without recomputing them.
+//! Use [`crate::v2`] unless the verifier specifically expects V1.
+
+use crate::{
+    HashToCurveError, HashToCurveSuite, Message, Parameters, PlumeSignature, PlumeVerifyError,
+    PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_std::rand::Rng;
+
+/// A PLUME V1 signature.
+#[derive(Copy, Clone)]
+pub struct Signature<P: SWModelParameters> {
+    /// The nullifier `h^sk`.
+    pub nullifier: GroupAffine<P>,
+    pub c: P::ScalarField,
+    pub s: P::ScalarField,
+    /// The randomness `r` represented as the curve point.
+    pub r_point: GroupAffine<P>,
+    /// The hash-to-curve output multiplied by the random `r`.
+    pub hashed_to_curve_r: GroupAffine<P>,
+}
+
+/// Signs `message` with PLUME V1.
+pub fn sign<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    rng: &mut impl Rng,
+    keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+    message: Message,
+) -> Result<Signature<P>, HashToCurveError> {
+    PlumeSignature::sign(pp, rng, keypair, message, PlumeVersion::V1).map(Signature::from_core)
+}
+
+impl<P: HashToCurveSuite> Signature<P> {
+    /// Verifies the signature on `message` by `pk`; see [`PlumeSignature::verify_non_zk`] for the errors.
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+    ) -> Result<(), PlumeVerifyError> {
+        self.to_core()
+            .verify_non_zk(pp, pk, message, PlumeVersion::V1)
+    }
+
+    fn from_core(signature: PlumeSignature<P>) -> Self {
+        Self {
+            nullifier: signature.nullifier,
+            c: signature.c,
+            s: signature.s,
+            r_point: signature.r_point,
+            hashed_to_curve_r: signature.hashed_to_curve_r,
+        }
+    }
+
+    fn to_core(&self) -> PlumeSignature<P> {
+        PlumeSignature {
+            hashed_to_curve_r: self.hashed_to_curve_r,
+            r_point: self.r_point,
+            s: self.s,
+            c: self.c,
+            nullifier: self.nullifier,
+        }
+    }
+}
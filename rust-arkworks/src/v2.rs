@@ -0,0 +1,60 @@
+//! PLUME V2: the challenge is `c = sha256(nul, g^r, z)`.
+//!
+//! The signature is only the nullifier with `c` and `s`. The verifier recomputes `g^r = g^s ⋅ pk^{-c}` and `z = h^s ⋅ nul^{-c}` and checks
+//! they hash to `c`.
+
+use crate::{
+    compute_h, optimizations, HashToCurveError, HashToCurveSuite, Message, Parameters,
+    PlumeSignature, PlumeVerifyError, PlumeVersion, PublicKey, SecretKeyMaterial,
+};
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_std::rand::Rng;
+
+/// A PLUME V2 signature.
+#[derive(Copy, Clone)]
+pub struct Signature<P: SWModelParameters> {
+    /// The nullifier `h^sk`.
+    pub nullifier: GroupAffine<P>,
+    pub c: P::ScalarField,
+    pub s: P::ScalarField,
+}
+
+/// Signs `message` with PLUME V2.
+pub fn sign<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    rng: &mut impl Rng,
+    keypair: (&PublicKey<P>, &SecretKeyMaterial<P>),
+    message: Message,
+) -> Result<Signature<P>, HashToCurveError> {
+    PlumeSignature::sign(pp, rng, keypair, message, PlumeVersion::V2).map(|signature| Signature {
+        nullifier: signature.nullifier,
+        c: signature.c,
+        s: signature.s,
+    })
+}
+
+impl<P: HashToCurveSuite> Signature<P> {
+    /// Verifies the signature on `message` by `pk`.
+    ///
+    /// `g^r` and `z` are recomputed from the signature, so an invalid one fails with [`PlumeVerifyError::ChallengeMismatch`].
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+    ) -> Result<(), PlumeVerifyError> {
+        let hashed_to_curve = compute_h::<P>(pk, message)?;
+        let r_point = optimizations::key_double_mul(&pp.g_point, self.s, pk, -self.c);
+        let hashed_to_curve_r =
+            optimizations::double_mul(&hashed_to_curve, self.s, &self.nullifier, -self.c);
+
+        PlumeSignature {
+            hashed_to_curve_r,
+            r_point,
+            s: self.s,
+            c: self.c,
+            nullifier: self.nullifier,
+        }
+        .verify_non_zk(pp, pk, message, PlumeVersion::V2)
+    }
+}
@@ -0,0 +1,41 @@
+//! The group membership checks of the points a signature or a key is made of.
+//!
+//! A point has to be on the curve, not the identity, and in the prime order subgroup. The last check is a multiplication by the subgroup
+//! order, so it's skipped for the curves of cofactor one, such as `secp256k1`, where every point on the curve is in the subgroup. Decoding
+//! ([`mod@crate::encoding`]), verification, and the import of keys all go through here, so they can't disagree on what a valid point is.
+
+use crate::error::EncodingError;
+use crate::{PlumeSignature, PublicKey};
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::Zero;
+
+/// Checks `point` is on the curve, isn't the identity, and is in the prime order subgroup.
+pub fn point<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<(), EncodingError> {
+    if point.is_zero() {
+        return Err(EncodingError::Identity);
+    }
+    if !point.is_on_curve() {
+        return Err(EncodingError::NotOnCurve);
+    }
+    subgroup(point)
+}
+
+/// Checks a point known to be on the curve is in the prime order subgroup; free for the curves of cofactor one.
+pub fn subgroup<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<(), EncodingError> {
+    if P::COFACTOR != [1u64] && !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(EncodingError::NotInSubgroup);
+    }
+    Ok(())
+}
+
+/// Checks a public key; see [`point`].
+pub fn public_key<P: SWModelParameters>(pk: &PublicKey<P>) -> Result<(), EncodingError> {
+    point(pk)
+}
+
+/// Checks the points of `signature`: the nullifier, `g^r`, and `z`.
+pub fn signature<P: SWModelParameters>(signature: &PlumeSignature<P>) -> Result<(), EncodingError> {
+    point(&signature.nullifier)?;
+    point(&signature.r_point)?;
+    point(&signature.hashed_to_curve_r)
+}
@@ -0,0 +1,233 @@
+//! The values a circuit verifying a signature takes as its witness.
+//!
+//! The circuits of the repository take the points and scalars of a signature together with the intermediates of its verification: the
+//! hash to the curve `h`, and the preimage of the challenge `c` with its SHA-256 blocks. [`compute_witness`] verifies a signature and
+//! returns all of them as a [`PlumeWitness`], so an integrator doesn't recompute them from the internals of the signing functions.
+//!
+//! The challenge is the one of the plain protocol, hashed with SHA-256 and mapped to a scalar by
+//! [`CurveDefault`](crate::CurveDefault); over `secp256k1` that's [`PlumeWitness::challenge_digest`] modulo the order.
+//!
+//! A circuit may take some of the values in a layout of its own. [`PlumeWitness::circuit_extras`] gives them for the [`CircuitProfile`]
+//! of a circuit id; `vectors/circom-v1.json` holds those of the circom V1 circuit for the `secp256k1` vector of `vectors/plume.json`.
+
+use crate::{
+    affine_to_bytes, compute_h, HashToCurveSuite, Message, Nullifier, Parameters, PlumeSignature,
+    PlumeVerifyError, PlumeVersion, PublicKey,
+};
+use alloc::vec::Vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::PrimeField;
+use core::fmt;
+use secp256k1::curves::Secp256k1Parameters;
+use sha2::{Digest, Sha256};
+
+/// The byte length of a SHA-256 block.
+pub const SHA256_BLOCK_LEN: usize = 64;
+
+/// The number of limbs of a coordinate in the circom circuits, `k` of `plume_v1(n, k, message_length)`.
+pub const CIRCOM_LIMB_COUNT: usize = 4;
+
+/// A circuit a witness can be laid out for, beyond the values of [`PlumeWitness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitProfile {
+    /// `plume_v1(64, 4, message_length)` of `circuits/circom/verify_nullifier.circom`, of the id `circom-v1`; it verifies V1
+    /// signatures on `secp256k1`.
+    CircomV1,
+}
+
+impl CircuitProfile {
+    /// Every profile.
+    pub const ALL: [Self; 1] = [Self::CircomV1];
+
+    /// The id the profile is selected by.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::CircomV1 => "circom-v1",
+        }
+    }
+
+    /// The profile of `id`, if any.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.id() == id)
+    }
+
+    /// The version of the signatures the circuit verifies.
+    pub fn version(self) -> PlumeVersion {
+        match self {
+            Self::CircomV1 => PlumeVersion::V1,
+        }
+    }
+}
+
+/// The values a [`CircuitProfile`] takes beyond those of [`PlumeWitness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitExtras {
+    /// The extras of [`CircuitProfile::CircomV1`], as the circom tests compute them.
+    CircomV1 {
+        /// `h` uncompressed, `x` then `y`, each in 64-bit limbs from the least significant, as `pointToCircuitValue` of
+        /// `circuits/circom/utils.ts`.
+        hashed_to_curve: [[u64; CIRCOM_LIMB_COUNT]; 2],
+        /// The bits of [`PlumeWitness::sha256_blocks`], from the most significant of each byte, as `bufToSha256PaddedBitArr`.
+        sha256_padded_bits: Vec<u8>,
+        /// The bit length of [`PlumeWitness::challenge_preimage`], the last 64 bits of the padding.
+        sha256_preimage_bit_length: u64,
+    },
+}
+
+/// An error selecting the extras of a circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitProfileError {
+    /// No profile has the circuit id.
+    UnknownCircuit,
+    /// The circuit verifies signatures of another version than the witness.
+    Version {
+        profile: CircuitProfile,
+        version: PlumeVersion,
+    },
+}
+
+impl fmt::Display for CircuitProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCircuit => write!(f, "no circuit profile has this id"),
+            Self::Version { profile, version } => write!(
+                f,
+                "the circuit {} doesn't verify {version:?} signatures",
+                profile.id()
+            ),
+        }
+    }
+}
+
+impl core::error::Error for CircuitProfileError {}
+
+/// The public values and intermediates of a valid signature; see [the module docs](self).
+#[derive(Clone)]
+pub struct PlumeWitness<P: SWModelParameters> {
+    /// The version the signature is made with.
+    pub version: PlumeVersion,
+    /// The public key of the signer.
+    pub pk: PublicKey<P>,
+    /// The hash to the curve `h = htc(message, pk)`.
+    pub hashed_to_curve: GroupAffine<P>,
+    /// The nullifier `h^sk`.
+    pub nullifier: Nullifier<P>,
+    /// `g^r` of the nonce `r`.
+    pub r_point: GroupAffine<P>,
+    /// `z = h^r`.
+    pub hashed_to_curve_r: GroupAffine<P>,
+    /// The challenge.
+    pub c: P::ScalarField,
+    /// The response `r + sk ⋅ c`.
+    pub s: P::ScalarField,
+    /// The preimage of the challenge: the compressed points of the version, concatenated or framed.
+    pub challenge_preimage: Vec<u8>,
+    /// The SHA-256 of [`Self::challenge_preimage`].
+    pub challenge_digest: [u8; 32],
+    /// The blocks SHA-256 compresses for [`Self::challenge_preimage`]: the preimage, the byte `0x80`, zeros, and the bit length as a
+    /// big-endian `u64`.
+    pub sha256_blocks: Vec<[u8; SHA256_BLOCK_LEN]>,
+}
+
+/// The witness of `signature` on `message` by `pk`, made with `version`; fails with the error of
+/// [`PlumeSignature::verify_non_zk`] if the signature isn't valid, as the circuit wouldn't accept it either.
+pub fn compute_witness<P: HashToCurveSuite>(
+    pp: &Parameters<P>,
+    pk: &PublicKey<P>,
+    message: Message,
+    signature: &PlumeSignature<P>,
+    version: PlumeVersion,
+) -> Result<PlumeWitness<P>, PlumeVerifyError> {
+    signature.verify_non_zk(pp, pk, message, version)?;
+    let hashed_to_curve = compute_h::<P>(pk, message)?;
+
+    let challenge_preimage = plume_core::transcript::challenge_preimage(
+        version,
+        [
+            &pp.g_point,
+            pk,
+            &hashed_to_curve,
+            &signature.nullifier,
+            &signature.r_point,
+            &signature.hashed_to_curve_r,
+        ]
+        .map(affine_to_bytes::<P>),
+    );
+    Ok(PlumeWitness {
+        version,
+        pk: *pk,
+        hashed_to_curve,
+        nullifier: signature.nullifier,
+        r_point: signature.r_point,
+        hashed_to_curve_r: signature.hashed_to_curve_r,
+        c: signature.c,
+        s: signature.s,
+        challenge_digest: Sha256::digest(&challenge_preimage).into(),
+        sha256_blocks: sha256_blocks(&challenge_preimage),
+        challenge_preimage,
+    })
+}
+
+impl<P: SWModelParameters> PlumeWitness<P> {
+    /// The signature the witness is of.
+    pub fn signature(&self) -> PlumeSignature<P> {
+        PlumeSignature {
+            hashed_to_curve_r: self.hashed_to_curve_r,
+            s: self.s,
+            r_point: self.r_point,
+            c: self.c,
+            nullifier: self.nullifier,
+        }
+    }
+}
+
+impl PlumeWitness<Secp256k1Parameters> {
+    /// The extras of the circuit of `circuit_id`; see [`CircuitProfile::from_id`].
+    ///
+    /// It fails for an unknown id, and for a witness of another version than the circuit's.
+    pub fn circuit_extras(&self, circuit_id: &str) -> Result<CircuitExtras, CircuitProfileError> {
+        let profile =
+            CircuitProfile::from_id(circuit_id).ok_or(CircuitProfileError::UnknownCircuit)?;
+        if self.version != profile.version() {
+            return Err(CircuitProfileError::Version {
+                profile,
+                version: self.version,
+            });
+        }
+
+        match profile {
+            CircuitProfile::CircomV1 => Ok(CircuitExtras::CircomV1 {
+                hashed_to_curve: [
+                    self.hashed_to_curve.x.into_repr().0,
+                    self.hashed_to_curve.y.into_repr().0,
+                ],
+                sha256_padded_bits: self
+                    .sha256_blocks
+                    .iter()
+                    .flatten()
+                    .flat_map(|byte| (0..8).rev().map(move |bit| (byte >> bit) & 1))
+                    .collect(),
+                sha256_preimage_bit_length: self.challenge_preimage.len() as u64 * 8,
+            }),
+        }
+    }
+}
+
+/// The padded blocks of `data` as SHA-256 compresses them.
+pub fn sha256_blocks(data: &[u8]) -> Vec<[u8; SHA256_BLOCK_LEN]> {
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % SHA256_BLOCK_LEN != SHA256_BLOCK_LEN - 8 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&bit_len.to_be_bytes());
+    padded
+        .chunks_exact(SHA256_BLOCK_LEN)
+        .map(|chunk| {
+            let mut block = [0u8; SHA256_BLOCK_LEN];
+            block.copy_from_slice(chunk);
+            block
+        })
+        .collect()
+}
@@ -0,0 +1,138 @@
+//! x-only encoding of nullifiers, for protocols which ship many of them.
+//!
+//! A nullifier is encoded as its big-endian `x` alone, one byte shorter than the compressed form. The parity of `y` isn't recoverable from
+//! `x`, so the convention is that of BIP-340: decoding yields the point with the even `y`, and an x-only nullifier stands for both `±nul`.
+//! That's no loss for a nullifier's uniqueness, as a signer can only prove `h^sk` and not `h^{-sk}`.
+//!
+//! Verification semantics are unchanged: an [`XOnlySignature`] verifies exactly when the full V2 signature with one of the two parities
+//! does. The even point is tried first, so a nullifier with an odd `y` costs a second verification.
+
+use crate::encoding::{self, field_byte_len};
+use crate::error::{validity, EncodingError};
+use crate::{v2, validate, HashToCurveError, HashToCurveSuite, Message, Parameters, PublicKey};
+use alloc::vec::Vec;
+use ark_ec::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine};
+use ark_ff::{BigInteger, PrimeField, Zero};
+
+/// A nullifier without the parity of its `y`.
+#[derive(Copy, Clone)]
+pub struct XOnlyNullifier<P: SWModelParameters> {
+    x: P::BaseField,
+}
+
+impl<P: SWModelParameters> XOnlyNullifier<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Drops the parity of `nullifier`; fails for the point at infinity.
+    pub fn from_point(nullifier: &GroupAffine<P>) -> Result<Self, EncodingError> {
+        if nullifier.is_zero() {
+            return Err(EncodingError::Identity);
+        }
+        Ok(Self { x: nullifier.x })
+    }
+
+    /// The point with the even `y`, the one the encoding decodes to.
+    pub fn to_point(&self) -> Result<GroupAffine<P>, EncodingError> {
+        let point =
+            GroupAffine::<P>::get_point_from_x(self.x, false).ok_or(EncodingError::NotOnCurve)?;
+        let point = if BigInteger::is_odd(&point.y.into_repr()) {
+            -point
+        } else {
+            point
+        };
+        validate::subgroup(&point)?;
+        Ok(point)
+    }
+
+    /// Both points with this `x`, the even `y` first.
+    pub fn to_points(&self) -> Result<[GroupAffine<P>; 2], EncodingError> {
+        let even = self.to_point()?;
+        Ok([even, -even])
+    }
+
+    /// Whether `nullifier` is one of the points with this `x`.
+    pub fn matches(&self, nullifier: &GroupAffine<P>) -> bool {
+        !nullifier.is_zero() && nullifier.x == self.x
+    }
+
+    /// The big-endian `x`, of the base field's width.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::field_to_bytes(&self.x)
+    }
+
+    /// Decodes the big-endian `x`, checking there's a point in the prime order subgroup with it.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EncodingError> {
+        let nullifier = Self {
+            x: encoding::field_from_bytes(bytes)?,
+        };
+        nullifier.to_point()?;
+        Ok(nullifier)
+    }
+
+    /// The hex of [`Self::to_bytes`].
+    pub fn to_hex(&self) -> String {
+        encoding::to_hex(&self.to_bytes())
+    }
+
+    /// Decodes the hex of [`Self::to_bytes`]; see [`encoding::from_hex`].
+    pub fn from_hex(hex: &str) -> Result<Self, EncodingError> {
+        Self::from_bytes(&encoding::from_hex(hex)?)
+    }
+
+    /// The number of bytes of the encoding.
+    pub fn byte_len() -> usize {
+        field_byte_len::<P::BaseField>()
+    }
+}
+
+/// A PLUME V2 signature with an x-only nullifier.
+#[derive(Copy, Clone)]
+pub struct XOnlySignature<P: SWModelParameters> {
+    pub nullifier: XOnlyNullifier<P>,
+    pub c: P::ScalarField,
+    pub s: P::ScalarField,
+}
+
+impl<P: SWModelParameters> XOnlySignature<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Drops the parity of the nullifier of `signature`.
+    pub fn from_signature(signature: &v2::Signature<P>) -> Result<Self, EncodingError> {
+        Ok(Self {
+            nullifier: XOnlyNullifier::from_point(&signature.nullifier)?,
+            c: signature.c,
+            s: signature.s,
+        })
+    }
+}
+
+impl<P: HashToCurveSuite> XOnlySignature<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Verifies the signature on `message` by `pk`, returning the full nullifier it's valid with.
+    pub fn verify(
+        &self,
+        pp: &Parameters<P>,
+        pk: &PublicKey<P>,
+        message: Message,
+    ) -> Result<Option<GroupAffine<P>>, HashToCurveError> {
+        let candidates = match self.nullifier.to_points() {
+            Ok(candidates) => candidates,
+            Err(_) => return Ok(None),
+        };
+        for nullifier in candidates {
+            let signature = v2::Signature {
+                nullifier,
+                c: self.c,
+                s: self.s,
+            };
+            if validity(signature.verify(pp, pk, message))? {
+                return Ok(Some(nullifier));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,88 @@
+//! Checks the extras of the circuit profiles against `vectors/circom-v1.json`, the inputs of the circom V1 circuit as its tests compute
+//! them; see `vectors/README.md` for the format.
+
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::{AffineCurve, ModelParameters, ProjectiveCurve};
+use plume_arkworks::witness::{self, CircuitExtras, CircuitProfile, CircuitProfileError};
+use plume_arkworks::{encoding, Parameters, PlumeSignature, PlumeVersion};
+use secp256k1::curves::Secp256k1Parameters;
+use serde_json::Value;
+
+type Scalar = <Secp256k1Parameters as ModelParameters>::ScalarField;
+
+const CIRCOM_V1: &str = include_str!("../../vectors/circom-v1.json");
+
+fn field<'a>(vector: &'a Value, name: &str) -> &'a str {
+    vector[name]
+        .as_str()
+        .unwrap_or_else(|| panic!("`{name}` is missing or not a string"))
+}
+
+fn limbs(coordinate: &Value) -> Vec<u64> {
+    coordinate
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|limb| limb.as_str().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[test]
+pub fn test_circom_v1_extras() {
+    let file: Value = serde_json::from_str(CIRCOM_V1).unwrap();
+    assert_eq!(file["version"].as_u64(), Some(1));
+    let circuit_id = file["circuit"].as_str().unwrap();
+    assert_eq!(
+        CircuitProfile::from_id(circuit_id),
+        Some(CircuitProfile::CircomV1)
+    );
+    let vectors = file["vectors"].as_array().unwrap();
+    assert!(!vectors.is_empty());
+
+    let pp = Parameters {
+        g_point: GroupAffine::<Secp256k1Parameters>::prime_subgroup_generator(),
+    };
+    for vector in vectors {
+        assert_eq!(field(vector, "curve"), "secp256k1");
+        let sk: Scalar = encoding::field_from_hex(field(vector, "secretKey")).unwrap();
+        let r = encoding::field_from_hex(field(vector, "r")).unwrap();
+        let message = encoding::from_hex(field(vector, "message")).unwrap();
+        let pk = pp.g_point.mul(sk).into_affine();
+        let sig =
+            PlumeSignature::sign_with_r(&pp, (&pk, &sk), &message, r, PlumeVersion::V1).unwrap();
+        let witness = witness::compute_witness(&pp, &pk, &message, &sig, PlumeVersion::V1).unwrap();
+
+        let CircuitExtras::CircomV1 {
+            hashed_to_curve,
+            sha256_padded_bits,
+            sha256_preimage_bit_length,
+        } = witness.circuit_extras(circuit_id).unwrap();
+        assert_eq!(hashed_to_curve[0][..], limbs(&vector["hashedToCurve"]["x"]));
+        assert_eq!(hashed_to_curve[1][..], limbs(&vector["hashedToCurve"]["y"]));
+        let bits: String = sha256_padded_bits
+            .iter()
+            .map(|bit| char::from(b'0' + bit))
+            .collect();
+        assert_eq!(bits, field(vector, "sha256PaddedBits"));
+        assert_eq!(
+            Some(sha256_preimage_bit_length),
+            vector["sha256PreimageBitLength"].as_u64()
+        );
+
+        // the circuit verifies V1 signatures only
+        let sig =
+            PlumeSignature::sign_with_r(&pp, (&pk, &sk), &message, r, PlumeVersion::V2).unwrap();
+        let witness = witness::compute_witness(&pp, &pk, &message, &sig, PlumeVersion::V2).unwrap();
+        assert_eq!(
+            witness.circuit_extras(circuit_id),
+            Err(CircuitProfileError::Version {
+                profile: CircuitProfile::CircomV1,
+                version: PlumeVersion::V2
+            })
+        );
+        assert_eq!(
+            witness.circuit_extras("circom-v3"),
+            Err(CircuitProfileError::UnknownCircuit)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Runs the BIP-32 derivation against the vectors of `vectors/derivation.json`, shared with the other implementations; see
+//! `vectors/README.md` for the format.
+
+use plume_arkworks::derivation::{
+    derive_plume_key, DerivationError, DerivationPath, ExtendedSecretKey, HARDENED, PURPOSE,
+};
+use plume_arkworks::encoding;
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("../../vectors/derivation.json");
+
+fn field<'a>(vector: &'a Value, name: &str) -> &'a str {
+    vector[name]
+        .as_str()
+        .unwrap_or_else(|| panic!("`{name}` is missing or not a string"))
+}
+
+#[test]
+pub fn test_derivation_vectors() {
+    let file: Value = serde_json::from_str(VECTORS).unwrap();
+    assert_eq!(file["version"].as_u64(), Some(1));
+    let vectors = file["vectors"].as_array().unwrap();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        let path = field(vector, "path");
+        let seed = encoding::from_hex(field(vector, "seed")).unwrap();
+        let key = ExtendedSecretKey::from_seed(&seed)
+            .unwrap()
+            .derive_path(&path.parse().unwrap())
+            .unwrap();
+
+        assert_eq!(*key.to_xprv(), field(vector, "xprv"), "xprv of {path}");
+        assert_eq!(
+            encoding::to_hex(key.chain_code()),
+            field(vector, "chainCode"),
+            "chain code of {path}"
+        );
+        assert_eq!(
+            encoding::field_to_hex(key.secret_key()),
+            field(vector, "secretKey"),
+            "secret key of {path}"
+        );
+        assert_eq!(
+            encoding::point_to_hex(&key.public_key()).unwrap(),
+            field(vector, "publicKey"),
+            "public key of {path}"
+        );
+
+        // the `xprv` imports back to the same key, which derives the same children
+        let imported = ExtendedSecretKey::from_xprv(field(vector, "xprv")).unwrap();
+        assert_eq!(imported.secret_key(), key.secret_key());
+        assert_eq!(imported.depth(), key.depth());
+        assert_eq!(
+            *imported.derive_child(HARDENED).unwrap().to_xprv(),
+            *key.derive_child(HARDENED).unwrap().to_xprv()
+        );
+    }
+}
+
+#[test]
+pub fn test_plume_key() {
+    let seed = encoding::from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let (pk, sk) = derive_plume_key(&seed, 1).unwrap();
+    let key = ExtendedSecretKey::from_seed(&seed)
+        .unwrap()
+        .derive_path(&"m/7524'/1'".parse().unwrap())
+        .unwrap();
+    assert_eq!(*sk, *key.secret_key());
+    assert_eq!(pk, key.public_key());
+    assert_eq!(key.child_number(), 1 | HARDENED);
+
+    let path = DerivationPath::plume(1).unwrap();
+    assert_eq!(path.child_numbers(), [PURPOSE | HARDENED, 1 | HARDENED]);
+    assert_eq!(path.to_string(), "m/7524'/1'");
+    assert_eq!(
+        DerivationPath::plume(HARDENED),
+        Err(DerivationError::InvalidPath)
+    );
+    // each account has its own key
+    assert_ne!(derive_plume_key(&seed, 0).unwrap().0, pk);
+}
+
+#[test]
+pub fn test_derivation_errors() {
+    assert_eq!("m/7524h/0'".parse(), DerivationPath::plume(0));
+    assert_eq!("m".parse(), Ok(DerivationPath::from(vec![])));
+    for path in [
+        "",
+        "7524'",
+        "m/",
+        "m//0",
+        "m/+1",
+        "m/2147483648",
+        "m/0''",
+        "m/x",
+    ] {
+        assert_eq!(
+            path.parse::<DerivationPath>(),
+            Err(DerivationError::InvalidPath),
+            "{path}"
+        );
+    }
+
+    assert_eq!(
+        ExtendedSecretKey::from_seed(&[0; 15]).unwrap_err(),
+        DerivationError::InvalidSeedLength(15)
+    );
+    assert_eq!(
+        ExtendedSecretKey::from_seed(&[0; 65]).unwrap_err(),
+        DerivationError::InvalidSeedLength(65)
+    );
+
+    let xprv = ExtendedSecretKey::from_seed(&[0; 16]).unwrap().to_xprv();
+    // a changed character fails the checksum
+    let mut tampered = xprv.to_string();
+    tampered.replace_range(20..21, if &xprv[20..21] == "a" { "b" } else { "a" });
+    assert_eq!(
+        ExtendedSecretKey::from_xprv(&tampered).unwrap_err(),
+        DerivationError::InvalidXprv
+    );
+    // an extended public key isn't a secret key
+    let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    assert_eq!(
+        ExtendedSecretKey::from_xprv(xpub).unwrap_err(),
+        DerivationError::InvalidXprv
+    );
+}
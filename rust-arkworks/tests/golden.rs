@@ -0,0 +1,102 @@
+//! Pins the canonical encoding of [`plume_arkworks::formats`] to the files of `tests/golden`, written from the vectors of the repository.
+//!
+//! A failure here means the bytes the other implementations read have changed; bump the version of the format rather than the files.
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use plume_arkworks::formats::{self, FormatError};
+use plume_arkworks::{encoding, EncodingError, Parameters, PlumeSignature, PlumeVersion};
+use secp256k1::curves::{Affine, Secp256k1Parameters as P};
+use secp256k1::fields::Fr;
+
+const MESSAGE: &[u8] = b"An example app message string";
+const SECRET_KEY: &str = "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464";
+const R: &str = "93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808";
+
+fn read_golden(hex: &str) -> Vec<u8> {
+    encoding::from_hex(hex.trim()).unwrap()
+}
+
+#[test]
+pub fn test_golden_signatures() {
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let sk = encoding::field_from_hex(SECRET_KEY).unwrap();
+    let r = encoding::field_from_hex(R).unwrap();
+    let pk = pp.g_point.mul(sk).into_affine();
+
+    for (version, file) in [
+        (
+            PlumeVersion::V1,
+            include_str!("golden/secp256k1_signature_v1.hex"),
+        ),
+        (
+            PlumeVersion::V2,
+            include_str!("golden/secp256k1_signature_v2.hex"),
+        ),
+    ] {
+        let golden = read_golden(file);
+        let sig = PlumeSignature::sign_with_r(&pp, (&pk, &sk), MESSAGE, r, version).unwrap();
+        assert_eq!(sig.to_bytes().unwrap(), golden);
+        assert_eq!(golden.len(), formats::signature_byte_len::<P>());
+
+        let decoded = PlumeSignature::<P>::from_bytes(&golden).unwrap();
+        assert!(decoded.verify(&pp, &pk, MESSAGE, version).is_ok());
+        assert_eq!(decoded.to_bytes().unwrap(), golden);
+        assert_eq!(
+            encoding::from_hex(&decoded.to_hex().unwrap()).unwrap(),
+            golden
+        );
+
+        assert_eq!(
+            PlumeSignature::<P>::from_bytes(&golden[..golden.len() - 1]).err(),
+            Some(FormatError::Encoding(EncodingError::InvalidLength {
+                expected: golden.len(),
+                actual: golden.len() - 1
+            }))
+        );
+        // `c` of the order isn't canonical
+        let mut non_canonical = golden.clone();
+        non_canonical[5 + 3 * 33..5 + 3 * 33 + 32].copy_from_slice(
+            &encoding::from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+                .unwrap(),
+        );
+        assert_eq!(
+            PlumeSignature::<P>::from_bytes(&non_canonical).err(),
+            Some(FormatError::Encoding(EncodingError::NonCanonicalField))
+        );
+    }
+}
+
+#[test]
+pub fn test_golden_public_key() {
+    let golden = read_golden(include_str!("golden/secp256k1_public_key.hex"));
+    let sk: Fr = encoding::field_from_hex(SECRET_KEY).unwrap();
+    let pk = Affine::prime_subgroup_generator().mul(sk).into_affine();
+
+    assert_eq!(formats::public_key_to_bytes(&pk).unwrap(), golden);
+    assert_eq!(golden.len(), formats::public_key_byte_len::<P>());
+    assert_eq!(formats::public_key_from_bytes::<P>(&golden).unwrap(), pk);
+
+    let mut future = golden.clone();
+    future[4] = formats::PUBLIC_KEY_VERSION + 1;
+    assert_eq!(
+        formats::public_key_from_bytes::<P>(&future).err(),
+        Some(FormatError::UnsupportedPublicKey)
+    );
+    // a signature isn't a key
+    assert_eq!(
+        formats::public_key_from_bytes::<P>(&read_golden(include_str!(
+            "golden/secp256k1_signature_v1.hex"
+        )))
+        .err(),
+        Some(FormatError::Unknown)
+    );
+    assert_eq!(
+        formats::public_key_from_bytes::<P>(&golden[..golden.len() - 1]).err(),
+        Some(FormatError::Encoding(EncodingError::InvalidLength {
+            expected: golden.len(),
+            actual: golden.len() - 1
+        }))
+    );
+}
@@ -0,0 +1,101 @@
+//! Checks [`estimated_heap_usage`] against the allocations actually made, counted by a wrapper of the system allocator.
+//!
+//! The test binary is separate, so the counting allocator doesn't slow down the other tests.
+
+use ark_ec::AffineCurve;
+use plume_arkworks::memory::{estimated_heap_usage, Operation};
+use plume_arkworks::{v1, v2, Parameters, PlumeSignature, PlumeVersion};
+use rand::{rngs::StdRng, SeedableRng};
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts the bytes live on the current thread, and their peak, while tracking.
+struct CountingAllocator;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn record(delta: isize) {
+    // the thread locals are gone while a thread shuts down
+    let _ = TRACKING.try_with(|tracking| {
+        if tracking.get() {
+            let live = LIVE.with(|live| {
+                live.set(live.get() + delta);
+                live.get()
+            });
+            PEAK.with(|peak| peak.set(peak.get().max(live)));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size() as isize);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(-(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size as isize - layout.size() as isize);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The peak heap usage of `f` on top of what was live before.
+fn peak_heap_usage<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    LIVE.with(|live| live.set(0));
+    PEAK.with(|peak| peak.set(0));
+    TRACKING.with(|tracking| tracking.set(true));
+    let output = f();
+    TRACKING.with(|tracking| tracking.set(false));
+    (output, PEAK.with(Cell::get) as usize)
+}
+
+#[test]
+pub fn test_estimated_heap_usage() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+    let message = vec![0x42; 4096];
+
+    let (sig, peak) =
+        peak_heap_usage(|| v1::sign(&pp, &mut rng, (&keypair.0, &keypair.1), &message));
+    let sig = sig.unwrap();
+    assert!(
+        peak <= estimated_heap_usage::<Secp256k1Parameters>(Operation::Sign, PlumeVersion::V1),
+        "a V1 sign peaked at {peak} bytes"
+    );
+    let (valid, peak) = peak_heap_usage(|| sig.verify(&pp, &keypair.0, &message));
+    assert!(valid.is_ok());
+    assert!(
+        peak <= estimated_heap_usage::<Secp256k1Parameters>(Operation::Verify, PlumeVersion::V1),
+        "a V1 verify peaked at {peak} bytes"
+    );
+
+    let (sig, peak) =
+        peak_heap_usage(|| v2::sign(&pp, &mut rng, (&keypair.0, &keypair.1), &message));
+    let sig = sig.unwrap();
+    assert!(
+        peak <= estimated_heap_usage::<Secp256k1Parameters>(Operation::Sign, PlumeVersion::V2),
+        "a V2 sign peaked at {peak} bytes"
+    );
+    let (valid, peak) = peak_heap_usage(|| sig.verify(&pp, &keypair.0, &message));
+    assert!(valid.is_ok());
+    assert!(
+        peak <= estimated_heap_usage::<Secp256k1Parameters>(Operation::Verify, PlumeVersion::V2),
+        "a V2 verify peaked at {peak} bytes"
+    );
+}
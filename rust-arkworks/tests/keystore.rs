@@ -0,0 +1,160 @@
+//! Keystores of the Web3 Secret Storage vectors, and round trips; run with `--features keystore`.
+#![cfg(feature = "keystore")]
+
+use ark_ec::AffineCurve;
+use plume_arkworks::keystore::{self, Kdf, KeystoreError};
+use plume_arkworks::{encoding, recovery, Parameters, PlumeSignature, PlumeVersion};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use secp256k1::curves::Affine;
+
+const PASSWORD: &[u8] = b"testpassword";
+const SECRET_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+const ADDRESS: &str = "008aeeda4d805471df9b2a5b0f38a0c3bcba786b";
+
+/// The PBKDF2 vector of the Web3 Secret Storage definition.
+const PBKDF2_KEYSTORE: &str = r#"{
+  "crypto": {
+    "cipher": "aes-128-ctr",
+    "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+    "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+    "kdf": "pbkdf2",
+    "kdfparams": {
+      "c": 262144,
+      "dklen": 32,
+      "prf": "hmac-sha256",
+      "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+    },
+    "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+  },
+  "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+  "version": 3
+}"#;
+
+/// The key of the vectors under the light scrypt cost of geth, with its address.
+const SCRYPT_KEYSTORE: &str = r#"{
+  "address": "008aeeda4d805471df9b2a5b0f38a0c3bcba786b",
+  "crypto": {
+    "cipher": "aes-128-ctr",
+    "cipherparams": { "iv": "1c2c45f4b8b32f5e8a2b1e5d0c3b7a9f" },
+    "ciphertext": "600766d467498333f25fed805f36bf412df41d8c13ffb7763a0cc07550fe6a52",
+    "kdf": "scrypt",
+    "kdfparams": {
+      "dklen": 32,
+      "n": 4096,
+      "p": 6,
+      "r": 8,
+      "salt": "5a1b36bd9a0a1e2a4a38fa8bfc4b8a06e3af57ec6f2208d4d3d7b19fa4f1e5b3"
+    },
+    "mac": "688fd57237b5b062fd9990cf84285b1c9cf430e3d8c735ed27904866a66da67b"
+  },
+  "id": "a1b7e5d2-4c3f-4e8a-9b6d-2f1e0c9d8b7a",
+  "version": 3
+}"#;
+
+/// A cost low enough for the tests.
+const CHEAP: Kdf = Kdf::Scrypt {
+    log_n: 10,
+    r: 8,
+    p: 1,
+};
+
+#[test]
+pub fn test_keystore_vectors() {
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    for json in [PBKDF2_KEYSTORE, SCRYPT_KEYSTORE] {
+        let (pk, sk) = keystore::decrypt(json, PASSWORD).unwrap();
+        assert_eq!(encoding::field_to_hex(&*sk), SECRET_KEY);
+        assert_eq!(encoding::to_hex(&recovery::address(&pk).unwrap()), ADDRESS);
+
+        // the imported keypair signs
+        let sig = PlumeSignature::sign(
+            &pp,
+            &mut StdRng::seed_from_u64(0),
+            (&pk, &sk),
+            b"keystore",
+            PlumeVersion::V2,
+        )
+        .unwrap();
+        assert!(sig.verify(&pp, &pk, b"keystore", PlumeVersion::V2).is_ok());
+    }
+}
+
+#[test]
+pub fn test_keystore_round_trip() {
+    let mut rng = StdRng::seed_from_u64(0x504c_554d_45);
+    let sk = encoding::field_from_hex(SECRET_KEY).unwrap();
+    for kdf in [CHEAP, Kdf::Pbkdf2 { c: 1024 }] {
+        let json = keystore::encrypt(&mut rng, &sk, PASSWORD, kdf).unwrap();
+        let file: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(file["version"], 3);
+        assert_eq!(file["address"], ADDRESS);
+        assert_eq!(file["id"].as_str().unwrap().len(), 36);
+
+        let (_, decrypted) = keystore::decrypt(&json, PASSWORD).unwrap();
+        assert_eq!(*decrypted, sk);
+        assert_eq!(
+            keystore::decrypt(&json, b"wrongpassword").unwrap_err(),
+            KeystoreError::WrongPassword
+        );
+    }
+}
+
+#[test]
+pub fn test_keystore_errors() {
+    let mut rng = StdRng::seed_from_u64(0x504c_554d_45);
+    let sk = encoding::field_from_hex(SECRET_KEY).unwrap();
+    let json = keystore::encrypt(&mut rng, &sk, PASSWORD, CHEAP).unwrap();
+    let edit = |edit: &dyn Fn(&mut serde_json::Value)| {
+        let mut file: serde_json::Value = serde_json::from_str(&json).unwrap();
+        edit(&mut file);
+        keystore::decrypt(&file.to_string(), PASSWORD).unwrap_err()
+    };
+
+    assert_eq!(
+        edit(&|file| file["version"] = 4.into()),
+        KeystoreError::Malformed
+    );
+    assert_eq!(
+        edit(&|file| file["crypto"]["cipher"] = "aes-128-cbc".into()),
+        KeystoreError::UnsupportedCipher("aes-128-cbc".to_string())
+    );
+    assert_eq!(
+        edit(&|file| file["crypto"]["kdf"] = "argon2".into()),
+        KeystoreError::UnsupportedKdf("argon2".to_string())
+    );
+    assert_eq!(
+        edit(&|file| file["crypto"]["kdfparams"]["n"] = 1000.into()),
+        KeystoreError::InvalidKdfParams
+    );
+    // 128 ⋅ 8 ⋅ 2^21 is 2 GiB, over the bound
+    assert_eq!(
+        edit(&|file| file["crypto"]["kdfparams"]["n"] = (1u64 << 21).into()),
+        KeystoreError::InvalidKdfParams
+    );
+    assert_eq!(
+        edit(&|file| file["crypto"]["kdfparams"]["p"] = 17.into()),
+        KeystoreError::InvalidKdfParams
+    );
+    // `u32::MAX` iterations are rejected before they run
+    let mut pbkdf2: serde_json::Value = serde_json::from_str(PBKDF2_KEYSTORE).unwrap();
+    pbkdf2["crypto"]["kdfparams"]["c"] = u32::MAX.into();
+    assert_eq!(
+        keystore::decrypt(&pbkdf2.to_string(), PASSWORD).unwrap_err(),
+        KeystoreError::InvalidKdfParams
+    );
+    assert_eq!(
+        edit(&|file| file["crypto"]["ciphertext"] = "00".repeat(32).into()),
+        KeystoreError::WrongPassword
+    );
+    assert_eq!(
+        edit(&|file| file["address"] = "00".repeat(20).into()),
+        KeystoreError::AddressMismatch
+    );
+    assert_eq!(
+        keystore::decrypt("{}", PASSWORD).unwrap_err(),
+        KeystoreError::Malformed
+    );
+}
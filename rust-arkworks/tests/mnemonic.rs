@@ -0,0 +1,80 @@
+//! Nullifier keys from the BIP-39 vectors; run with `--features bip39`.
+#![cfg(feature = "bip39")]
+
+use plume_arkworks::derivation::{self, ExtendedSecretKey};
+use plume_arkworks::encoding;
+use plume_arkworks::mnemonic::{self, MnemonicError};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+#[test]
+pub fn test_mnemonic_vector() {
+    // the first vector of the BIP-39 reference implementation
+    let seed = mnemonic::seed_from_mnemonic(MNEMONIC, "TREZOR").unwrap();
+    assert_eq!(
+        encoding::to_hex(&*seed),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+    assert_eq!(
+        *ExtendedSecretKey::from_mnemonic(MNEMONIC, "TREZOR")
+            .unwrap()
+            .to_xprv(),
+        "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF"
+    );
+
+    let (pk, sk) = mnemonic::derive_plume_key(MNEMONIC, "TREZOR", 0).unwrap();
+    assert_eq!(
+        encoding::field_to_hex(&*sk),
+        "be15aa6002fc24011f2a0dc826216b266080f72e5fc6adafefe202a453309e19"
+    );
+    assert_eq!(
+        encoding::point_to_hex(&pk).unwrap(),
+        "02e374c7979655e0cb379a38ed401e2bd0dc3b88693d434486b6b35427b86bef9c"
+    );
+    // the key of the seed, as `derivation` derives it
+    let (seed_pk, seed_sk) = derivation::derive_plume_key(&*seed, 0).unwrap();
+    assert_eq!((pk, *sk), (seed_pk, *seed_sk));
+    // the passphrase is part of the seed
+    let (_, sk) = mnemonic::derive_plume_key(MNEMONIC, "", 0).unwrap();
+    assert_eq!(
+        encoding::field_to_hex(&*sk),
+        "9228d34bc40e24bfa27174a348b8e83b9491a5231b8ef0224f17ea81933363a6"
+    );
+}
+
+#[test]
+pub fn test_generate_mnemonic() {
+    let mut rng = StdRng::seed_from_u64(0x504c_554d_45);
+    for word_count in [12, 15, 18, 21, 24] {
+        let mnemonic = mnemonic::generate_mnemonic(&mut rng, word_count).unwrap();
+        assert_eq!(mnemonic.split(' ').count(), word_count);
+        assert!(mnemonic::derive_plume_key(&mnemonic, "", 0).is_ok());
+    }
+    assert_eq!(
+        mnemonic::generate_mnemonic(&mut rng, 13).unwrap_err(),
+        MnemonicError::InvalidWordCount(13)
+    );
+}
+
+#[test]
+pub fn test_invalid_mnemonic() {
+    // `about` is the checksum word; `abandon` in its place fails the checksum
+    let bad_checksum = MNEMONIC.replace("about", "abandon");
+    assert_eq!(
+        mnemonic::seed_from_mnemonic(&bad_checksum, "").unwrap_err(),
+        MnemonicError::InvalidMnemonic
+    );
+    let unknown_word = MNEMONIC.replace("about", "plume");
+    assert_eq!(
+        mnemonic::seed_from_mnemonic(&unknown_word, "").unwrap_err(),
+        MnemonicError::InvalidMnemonic
+    );
+    let short = MNEMONIC.replacen("abandon ", "", 1);
+    assert_eq!(
+        mnemonic::seed_from_mnemonic(&short, "").unwrap_err(),
+        MnemonicError::InvalidWordCount(11)
+    );
+}
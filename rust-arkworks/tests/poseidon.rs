@@ -0,0 +1,87 @@
+//! The Poseidon challenge against circomlib, and round trips; run with `--features poseidon`.
+#![cfg(feature = "poseidon")]
+
+use ark_ec::AffineCurve;
+use plume_arkworks::bn254::{self, Bn254Parameters};
+use plume_arkworks::poseidon::{self, PoseidonParameters};
+use plume_arkworks::{encoding, Parameters, PlumeSignature, PlumeVersion};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+
+#[test]
+pub fn test_poseidon_vectors() {
+    // circomlib's `Poseidon(2)` of `[1, 2]`
+    let parameters = PoseidonParameters::<bn254::Fr>::new();
+    assert_eq!(
+        encoding::field_to_hex(&parameters.hash(&[bn254::Fr::from(1u64), bn254::Fr::from(2u64)])),
+        "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+    );
+    assert_eq!(
+        encoding::field_to_hex(&parameters.round_constants()[0]),
+        "0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e"
+    );
+    assert_eq!(
+        encoding::field_to_hex(&parameters.mds()[0][0]),
+        "109b7f411ba0e4c9b2b70caf5c36a7b194be7c11ad24378bfedb68592ba8118b"
+    );
+
+    // the bytes of a Python implementation of the packing, over both fields
+    let data: Vec<u8> = (0..99).collect();
+    assert_eq!(
+        encoding::field_to_hex(&poseidon::hash_bytes::<secp256k1::fields::Fr>(&[])),
+        "e89b6dfc99ff320fd9e7b4c230e4e2f017212df18c2f769238abea86a36ef05a"
+    );
+    assert_eq!(
+        encoding::field_to_hex(&poseidon::hash_bytes::<secp256k1::fields::Fr>(&data)),
+        "74411087c923aa68bed40bdc8e1447f713b0454a994e0b9ee58e465dbaca61ba"
+    );
+    assert_eq!(
+        encoding::field_to_hex(&poseidon::hash_bytes::<bn254::Fr>(&data)),
+        "0ec281dfaafae1fa74fe719188d006f48e7d57bb7e8220ec166089fcf1af2d09"
+    );
+}
+
+#[test]
+pub fn test_poseidon_signatures() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let (pk, sk) = PlumeSignature::<Secp256k1Parameters>::keygen(&pp, &mut rng);
+    let message = b"Message";
+
+    for version in [PlumeVersion::V1, PlumeVersion::V2] {
+        let sig =
+            PlumeSignature::sign_poseidon(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+        assert!(sig.verify_poseidon(&pp, &pk, message, version).is_ok());
+        assert!(sig.verify_poseidon(&pp, &pk, b"Other", version).is_err());
+        // a Poseidon signature is none of the SHA-256 versions, and the other way around
+        assert!(sig.verify(&pp, &pk, message, version).is_err());
+        let sha256 = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+        assert!(sha256.verify_poseidon(&pp, &pk, message, version).is_err());
+        // the nullifier doesn't change
+        assert_eq!(sig.nullifier, sha256.nullifier);
+    }
+
+    // `c` of V2 is the Poseidon hash of `nul || g^r || z`, as a circuit computes it
+    let sig = PlumeSignature::sign_poseidon(&pp, &mut rng, (&pk, &sk), message, PlumeVersion::V2)
+        .unwrap();
+    let preimage = [&sig.nullifier, &sig.r_point, &sig.hashed_to_curve_r]
+        .iter()
+        .flat_map(|point| encoding::point_to_bytes(*point).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(sig.c, poseidon::hash_bytes(&preimage));
+
+    // over BN254 the challenge is native to the field of the circuits
+    let pp = Parameters {
+        g_point: bn254::G1Affine::prime_subgroup_generator(),
+    };
+    let (pk, sk) = PlumeSignature::<Bn254Parameters>::keygen(&pp, &mut rng);
+    let sig = PlumeSignature::sign_poseidon(&pp, &mut rng, (&pk, &sk), message, PlumeVersion::V2)
+        .unwrap();
+    assert!(sig
+        .verify_poseidon(&pp, &pk, message, PlumeVersion::V2)
+        .is_ok());
+    assert!(sig.verify(&pp, &pk, message, PlumeVersion::V2).is_err());
+}
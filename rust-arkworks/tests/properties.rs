@@ -0,0 +1,164 @@
+//! Properties of signing, verifying and the encodings, checked with `proptest` over random keys, nonces, messages and versions.
+//!
+//! The keys and nonces come from a `StdRng` seeded by the generated seed, so a failure shrinks to a seed and a message which reproduce it.
+
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::AffineCurve;
+use plume_arkworks::{
+    encoding, pasta, HashToCurveSuite, Parameters, PlumeSignature, PlumeVersion, PublicKey,
+};
+use proptest::prelude::*;
+use proptest::sample::Index;
+use rand::{rngs::StdRng, SeedableRng};
+use secp256k1::curves::Secp256k1Parameters;
+
+type P = Secp256k1Parameters;
+
+fn version() -> impl Strategy<Value = PlumeVersion> {
+    prop_oneof![
+        Just(PlumeVersion::V1),
+        Just(PlumeVersion::V2),
+        Just(PlumeVersion::V1_1),
+        Just(PlumeVersion::V2_1),
+    ]
+}
+
+fn message() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..256)
+}
+
+/// A byte to flip and what to flip it with, never zero.
+fn tamper() -> impl Strategy<Value = (Index, u8)> {
+    (any::<Index>(), 1..=u8::MAX)
+}
+
+fn parameters<Q: HashToCurveSuite>() -> Parameters<Q> {
+    Parameters {
+        g_point: GroupAffine::prime_subgroup_generator(),
+    }
+}
+
+/// A key drawn from `seed` and its signature of `message`.
+fn signed<Q: HashToCurveSuite>(
+    seed: u64,
+    message: &[u8],
+    version: PlumeVersion,
+) -> (PublicKey<Q>, PlumeSignature<Q>) {
+    let pp = parameters::<Q>();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (pk, sk) = PlumeSignature::keygen(&pp, &mut rng);
+    let sig = PlumeSignature::sign(&pp, &mut rng, (&pk, &sk), message, version).unwrap();
+    (pk, sig)
+}
+
+fn flip(mut bytes: Vec<u8>, (index, mask): (Index, u8)) -> Vec<u8> {
+    bytes[index.index(bytes.len())] ^= mask;
+    bytes
+}
+
+fn sign_and_verify<Q: HashToCurveSuite>(seed: u64, message: &[u8], version: PlumeVersion) {
+    let (pk, sig) = signed::<Q>(seed, message, version);
+    assert_eq!(
+        sig.verify(&parameters(), &pk, message, version)
+            .map_err(|e| e.to_string()),
+        Ok(())
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn sign_then_verify(seed in any::<u64>(), message in message(), version in version()) {
+        sign_and_verify::<P>(seed, &message, version);
+        sign_and_verify::<pasta::PallasParameters>(seed, &message, version);
+    }
+
+    #[test]
+    fn verify_rejects_other_versions(
+        seed in any::<u64>(),
+        message in message(),
+        (version, other) in (version(), version()).prop_filter("the same version", |(a, b)| a != b),
+    ) {
+        let (pk, sig) = signed::<P>(seed, &message, version);
+        prop_assert!(sig.verify(&parameters(), &pk, &message, other).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_tampered_message(
+        seed in any::<u64>(),
+        message in prop::collection::vec(any::<u8>(), 1..256),
+        version in version(),
+        tamper in tamper(),
+    ) {
+        let (pk, sig) = signed::<P>(seed, &message, version);
+        let tampered = flip(message, tamper);
+        prop_assert!(sig.verify(&parameters(), &pk, &tampered, version).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_tampered_signature(
+        seed in any::<u64>(),
+        message in message(),
+        version in version(),
+        tamper in tamper(),
+    ) {
+        let (pk, sig) = signed::<P>(seed, &message, version);
+        let tampered = flip(sig.to_bytes().unwrap(), tamper);
+        // a tampered encoding either fails to decode or to verify
+        if let Ok(tampered) = PlumeSignature::<P>::from_bytes(&tampered) {
+            prop_assert!(tampered.verify(&parameters(), &pk, &message, version).is_err());
+        }
+    }
+
+    #[test]
+    fn verify_rejects_tampered_public_key(
+        seed in any::<u64>(),
+        message in message(),
+        version in version(),
+        tamper in tamper(),
+    ) {
+        let (pk, sig) = signed::<P>(seed, &message, version);
+        let tampered = flip(encoding::point_to_bytes(&pk).unwrap(), tamper);
+        if let Ok(tampered) = encoding::point_from_bytes::<P>(&tampered) {
+            prop_assert!(sig.verify(&parameters(), &tampered, &message, version).is_err());
+        }
+    }
+
+    #[test]
+    fn encodings_round_trip(seed in any::<u64>(), message in message(), version in version()) {
+        let (pk, sig) = signed::<P>(seed, &message, version);
+
+        let bytes = sig.to_bytes().unwrap();
+        let decoded = PlumeSignature::<P>::from_bytes(&bytes).unwrap();
+        prop_assert_eq!(decoded.to_bytes().unwrap(), bytes.clone());
+        let decoded = PlumeSignature::<P>::from_hex(&sig.to_hex().unwrap()).unwrap();
+        prop_assert_eq!(decoded.to_bytes().unwrap(), bytes);
+        prop_assert!(decoded.verify(&parameters(), &pk, &message, version).is_ok());
+
+        let pk_bytes = encoding::point_to_bytes(&pk).unwrap();
+        prop_assert_eq!(encoding::point_from_bytes::<P>(&pk_bytes).unwrap(), pk);
+        let c_bytes = encoding::field_to_bytes(&sig.c);
+        prop_assert_eq!(encoding::field_from_bytes(&c_bytes), Ok(sig.c));
+    }
+}
+
+#[cfg(feature = "serde")]
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn serde_round_trip(seed in any::<u64>(), message in message(), version in version()) {
+        let (pk, sig) = signed::<P>(seed, &message, version);
+
+        let json = serde_json::to_string(&sig).unwrap();
+        let decoded: PlumeSignature<P> = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+        prop_assert_eq!(decoded.to_bytes().unwrap(), sig.to_bytes().unwrap());
+
+        let binary = bincode::serialize(&sig).unwrap();
+        let decoded: PlumeSignature<P> = bincode::deserialize(&binary).unwrap();
+        prop_assert_eq!(bincode::serialize(&decoded).unwrap(), binary);
+        prop_assert!(decoded.verify(&parameters(), &pk, &message, version).is_ok());
+    }
+}
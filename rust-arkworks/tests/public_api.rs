@@ -0,0 +1,146 @@
+//! Pins the signatures of the items of the prelude and of the extension points; a failure to compile here is a breaking change.
+
+use ark_ec::AffineCurve;
+use plume_arkworks::prelude::*;
+use rand::rngs::ThreadRng;
+use secp256k1::curves::{Affine, Secp256k1Parameters as P};
+
+#[test]
+pub fn test_prelude_signatures() {
+    let _: fn(&Parameters<P>, &mut ThreadRng) -> (PublicKey<P>, SecretKeyMaterial<P>) =
+        PlumeSignature::<P>::keygen;
+    let _: fn(&[u8; 32]) -> Result<SecretKeyMaterial<P>, EncodingError> =
+        <SecretKeyMaterial<P> as SecretKeyBytes>::from_bytes;
+    let _: fn(&[u8]) -> Result<PublicKey<P>, EncodingError> =
+        <PublicKey<P> as PublicKeySec1>::from_sec1_bytes;
+    let _: fn(
+        &Parameters<P>,
+        &mut ThreadRng,
+        (&PublicKey<P>, &SecretKeyMaterial<P>),
+        Message,
+    ) -> Result<PlumeSignatureV1<P>, HashToCurveError> = sign_v1::<P>;
+    let _: fn(
+        &Parameters<P>,
+        &mut ThreadRng,
+        (&PublicKey<P>, &SecretKeyMaterial<P>),
+        Message,
+    ) -> Result<PlumeSignatureV2<P>, HashToCurveError> = sign_v2::<P>;
+    let _: fn(
+        &PlumeSignatureV1<P>,
+        &Parameters<P>,
+        &PublicKey<P>,
+        Message,
+    ) -> Result<(), PlumeVerifyError> = PlumeSignatureV1::<P>::verify;
+    let _: fn(
+        &PlumeSignatureV2<P>,
+        &Parameters<P>,
+        &PublicKey<P>,
+        Message,
+    ) -> Result<(), PlumeVerifyError> = PlumeSignatureV2::<P>::verify;
+    let _: fn(PlumeVersion) -> bool = PlumeVersion::is_framed;
+
+    let _: fn(
+        &Parameters<P>,
+        &mut ThreadRng,
+        (&PublicKey<P>, &SecretKeyMaterial<P>),
+        Message,
+        PlumeVersion,
+    ) -> Result<PlumeSignature<P>, HashToCurveError> = PlumeSignature::<P>::sign;
+    let _: fn(
+        &Parameters<P>,
+        (&PublicKey<P>, &SecretKeyMaterial<P>),
+        Message,
+        <P as ark_ec::ModelParameters>::ScalarField,
+        PlumeVersion,
+    ) -> Result<PlumeSignature<P>, HashToCurveError> = PlumeSignature::<P>::sign_with_r;
+    let _: fn(
+        &PlumeSignature<P>,
+        &Parameters<P>,
+        &PublicKey<P>,
+        Message,
+        PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> = PlumeSignature::<P>::verify;
+    let _: fn(
+        &PlumeSignature<P>,
+        &Parameters<P>,
+        &PublicKey<P>,
+        Message,
+        PlumeVersion,
+    ) -> Result<(), PlumeVerifyError> = PlumeSignature::<P>::verify_non_zk;
+}
+
+#[test]
+pub fn test_prelude_fields() {
+    let pp = Parameters::<P> {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let mut rng = rand::thread_rng();
+    let keypair = PlumeSignature::keygen(&pp, &mut rng);
+
+    let PlumeSignatureV1 {
+        nullifier,
+        c: _,
+        s: _,
+        r_point: _,
+        hashed_to_curve_r: _,
+    } = sign_v1(&pp, &mut rng, (&keypair.0, &keypair.1), b"prelude").unwrap();
+    let _: Nullifier<P> = nullifier;
+    let PlumeSignatureV2 {
+        nullifier,
+        c: _,
+        s: _,
+    } = sign_v2(&pp, &mut rng, (&keypair.0, &keypair.1), b"prelude").unwrap();
+    let _: Nullifier<P> = nullifier;
+
+    for version in [
+        PlumeVersion::V1,
+        PlumeVersion::V2,
+        PlumeVersion::V1_1,
+        PlumeVersion::V2_1,
+    ] {
+        let sig =
+            PlumeSignature::sign(&pp, &mut rng, (&keypair.0, &keypair.1), b"prelude", version)
+                .unwrap();
+        assert!(sig.verify(&pp, &keypair.0, b"prelude", version).is_ok());
+        assert!(sig.verify(&pp, &keypair.0, b"other", version).is_err());
+    }
+}
+
+// the versions are pinned; the enum is non-exhaustive, so a match on it downstream has a `_` arm
+fn _versions(version: PlumeVersion) {
+    match version {
+        PlumeVersion::V1 | PlumeVersion::V2 | PlumeVersion::V1_1 | PlumeVersion::V2_1 => {}
+        _ => {}
+    }
+}
+
+#[test]
+pub fn test_extension_points() {
+    use plume_arkworks::hasher::Sha256;
+    use plume_arkworks::{ChallengeReduction, CurveDefault, GroupAffine};
+
+    let _: fn(&[u8], &PublicKey<P>) -> Result<GroupAffine<P>, HashToCurveError> =
+        plume_arkworks::hash_to_curve::hash_to_curve::<P>;
+    let _: fn(&[&[u8]], &[u8]) -> Result<GroupAffine<P>, HashToCurveError> =
+        <P as HashToCurveSuite>::map_to_curve;
+    let _: fn(&CurveDefault, &[u8]) -> <P as ark_ec::ModelParameters>::ScalarField =
+        <CurveDefault as ChallengeReduction>::reduce::<_, Sha256>;
+    let _: fn(
+        &Parameters<P>,
+        &mut ThreadRng,
+        (&PublicKey<P>, &SecretKeyMaterial<P>),
+        Message,
+        PlumeVersion,
+        &CurveDefault,
+    ) -> Result<PlumeSignature<P>, HashToCurveError> =
+        PlumeSignature::<P>::sign_with_reduction::<Sha256, CurveDefault>;
+    let _: fn(
+        &PlumeSignature<P>,
+        &Parameters<P>,
+        &PublicKey<P>,
+        Message,
+        PlumeVersion,
+        &CurveDefault,
+    ) -> Result<(), PlumeVerifyError> =
+        PlumeSignature::<P>::verify_non_zk_with_reduction::<Sha256, CurveDefault>;
+}
@@ -0,0 +1,197 @@
+//! Runs the backend against the shared JSON test vectors of `vectors/plume.json`, which the JavaScript and circom implementations are
+//! checked against as well; see `vectors/README.md` for the format.
+
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::PrimeField;
+use plume_arkworks::hash_to_curve::hash_to_curve;
+use plume_arkworks::{
+    babyjubjub, bn254, encoding, p256, pasta, HashToCurveSuite, Parameters, PlumeSignature,
+    PlumeVersion,
+};
+use secp256k1::curves::Secp256k1Parameters;
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("../../vectors/plume.json");
+
+/// The version of the format this loader reads.
+const FORMAT_VERSION: u64 = 1;
+
+/// A signature of a [`Vector`] under one version.
+struct Signature {
+    version: PlumeVersion,
+    c: String,
+    s: String,
+}
+
+/// A vector of the file, with its values still hex.
+struct Vector {
+    curve: String,
+    secret_key: String,
+    r: String,
+    message: Vec<u8>,
+    public_key: String,
+    hashed_to_curve: String,
+    nullifier: String,
+    r_point: String,
+    hashed_to_curve_r: String,
+    signatures: Vec<Signature>,
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Result<&'a str, String> {
+    value[name]
+        .as_str()
+        .ok_or_else(|| format!("`{name}` is missing or not a string"))
+}
+
+fn version(name: &str) -> Result<PlumeVersion, String> {
+    match name {
+        "v1" => Ok(PlumeVersion::V1),
+        "v2" => Ok(PlumeVersion::V2),
+        "v1.1" => Ok(PlumeVersion::V1_1),
+        "v2.1" => Ok(PlumeVersion::V2_1),
+        _ => Err(format!("unknown version `{name}`")),
+    }
+}
+
+/// Reads the vectors of `json`, failing on a format version it doesn't know or a vector without every field.
+fn load(json: &str) -> Result<Vec<Vector>, String> {
+    let file: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    if file["version"].as_u64() != Some(FORMAT_VERSION) {
+        return Err(format!("unsupported format version {}", file["version"]));
+    }
+    let vectors = file["vectors"].as_array().ok_or("`vectors` is missing")?;
+    vectors
+        .iter()
+        .map(|vector| {
+            let signatures = vector["signatures"]
+                .as_array()
+                .ok_or("`signatures` is missing")?
+                .iter()
+                .map(|signature| {
+                    Ok(Signature {
+                        version: version(field(signature, "version")?)?,
+                        c: field(signature, "c")?.to_owned(),
+                        s: field(signature, "s")?.to_owned(),
+                    })
+                })
+                .collect::<Result<_, String>>()?;
+            Ok(Vector {
+                curve: field(vector, "curve")?.to_owned(),
+                secret_key: field(vector, "secretKey")?.to_owned(),
+                r: field(vector, "r")?.to_owned(),
+                message: encoding::from_hex(field(vector, "message")?)
+                    .map_err(|e| format!("invalid `message`: {e:?}"))?,
+                public_key: field(vector, "publicKey")?.to_owned(),
+                hashed_to_curve: field(vector, "hashedToCurve")?.to_owned(),
+                nullifier: field(vector, "nullifier")?.to_owned(),
+                r_point: field(vector, "gPowR")?.to_owned(),
+                hashed_to_curve_r: field(vector, "hashMPKPowR")?.to_owned(),
+                signatures,
+            })
+        })
+        .collect()
+}
+
+fn check<P: HashToCurveSuite>(vector: &Vector)
+where
+    P::BaseField: PrimeField,
+{
+    let curve = &vector.curve;
+    let pp = Parameters {
+        g_point: GroupAffine::<P>::prime_subgroup_generator(),
+    };
+    let sk: P::ScalarField = encoding::field_from_hex(&vector.secret_key).unwrap();
+    let r: P::ScalarField = encoding::field_from_hex(&vector.r).unwrap();
+    let pk = pp.g_point.mul(sk).into_affine();
+    let hex = |point: &GroupAffine<P>| encoding::point_to_hex(point).unwrap();
+
+    assert_eq!(hex(&pk), vector.public_key, "public key on {curve}");
+    assert_eq!(
+        hex(&hash_to_curve::<P>(&vector.message, &pk).unwrap()),
+        vector.hashed_to_curve,
+        "hash to curve on {curve}"
+    );
+    for signature in &vector.signatures {
+        let version = signature.version;
+        let sig =
+            PlumeSignature::sign_with_r(&pp, (&pk, &sk), &vector.message, r, version).unwrap();
+        assert_eq!(
+            hex(&sig.nullifier),
+            vector.nullifier,
+            "nullifier on {curve}"
+        );
+        assert_eq!(hex(&sig.r_point), vector.r_point, "g^r on {curve}");
+        assert_eq!(
+            hex(&sig.hashed_to_curve_r),
+            vector.hashed_to_curve_r,
+            "z on {curve}"
+        );
+        assert_eq!(
+            encoding::field_to_hex(&sig.c),
+            signature.c,
+            "c of {version:?} on {curve}"
+        );
+        assert_eq!(
+            encoding::field_to_hex(&sig.s),
+            signature.s,
+            "s of {version:?} on {curve}"
+        );
+
+        // the signature of the file, rather than the one derived
+        let sig = PlumeSignature::<P> {
+            nullifier: encoding::point_from_hex(&vector.nullifier).unwrap(),
+            r_point: encoding::point_from_hex(&vector.r_point).unwrap(),
+            hashed_to_curve_r: encoding::point_from_hex(&vector.hashed_to_curve_r).unwrap(),
+            c: encoding::field_from_hex(&signature.c).unwrap(),
+            s: encoding::field_from_hex(&signature.s).unwrap(),
+        };
+        let pk = encoding::point_from_hex(&vector.public_key).unwrap();
+        assert!(
+            sig.verify(&pp, &pk, &vector.message, version).is_ok(),
+            "{version:?} on {curve}"
+        );
+    }
+}
+
+#[test]
+pub fn test_json_vectors() {
+    let vectors = load(VECTORS).unwrap();
+    for vector in &vectors {
+        match vector.curve.as_str() {
+            "secp256k1" => check::<Secp256k1Parameters>(vector),
+            "p256" => check::<p256::P256Parameters>(vector),
+            "pallas" => check::<pasta::PallasParameters>(vector),
+            "vesta" => check::<pasta::VestaParameters>(vector),
+            "bn254" => check::<bn254::Bn254Parameters>(vector),
+            "grumpkin" => check::<bn254::GrumpkinParameters>(vector),
+            "babyjubjub" => check::<babyjubjub::BabyJubJubParameters>(vector),
+            curve => panic!("no backend for `{curve}`"),
+        }
+    }
+    // every curve of the backend is covered, under both versions
+    for curve in plume_core::consts::CURVES {
+        let versions: Vec<_> = vectors
+            .iter()
+            .filter(|vector| vector.curve == curve.name)
+            .flat_map(|vector| vector.signatures.iter().map(|signature| signature.version))
+            .collect();
+        assert!(
+            versions.contains(&PlumeVersion::V1),
+            "no V1 vector for {}",
+            curve.name
+        );
+        assert!(
+            versions.contains(&PlumeVersion::V2),
+            "no V2 vector for {}",
+            curve.name
+        );
+    }
+}
+
+#[test]
+pub fn test_json_vectors_loader() {
+    assert!(load(&VECTORS.replacen("\"version\": 1", "\"version\": 2", 1)).is_err());
+    assert!(load(&VECTORS.replacen("\"v1\"", "\"v3\"", 1)).is_err());
+    assert!(load(&VECTORS.replacen("\"gPowR\"", "\"rPoint\"", 1)).is_err());
+}
@@ -0,0 +1,101 @@
+//! The costs of key generation, hashing to the curve, signing and verifying, by message size.
+//!
+//! `cargo bench --bench plume` runs them, with the randomness seeded; the groups have the names of the `plume` benchmark of
+//! `plume_arkworks`, so the two reports compare group by group.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use k256::elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::sha2::Sha256;
+use k256::Secp256k1;
+use plume_rustcrypto::{PlumeSignature, SecretKey, DST};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+const SEED: u64 = 0x504c_554d_45;
+
+/// The lengths of the messages, from a hash to a large payload.
+const MESSAGE_SIZES: [usize; 3] = [32, 256, 4096];
+
+type Sign = fn(&SecretKey, &[u8], &mut StdRng) -> PlumeSignature;
+
+fn message(rng: &mut StdRng, size: usize) -> Vec<u8> {
+    let mut message = vec![0; size];
+    rng.fill_bytes(&mut message);
+    message
+}
+
+fn bench_keygen(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    c.bench_function("keygen", |b| {
+        b.iter(|| SecretKey::random(&mut rng).public_key())
+    });
+}
+
+fn bench_hash_to_curve(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let pk = SecretKey::random(&mut rng).public_key();
+    let pk = pk.to_encoded_point(true);
+    let mut group = c.benchmark_group("hash_to_curve");
+    for size in MESSAGE_SIZES {
+        let message = message(&mut rng, size);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &message, |b, message| {
+            b.iter(|| {
+                Secp256k1::hash_from_bytes::<ExpandMsgXmd<Sha256>>(
+                    &[&[message.as_slice(), pk.as_bytes()].concat()],
+                    &[DST],
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_sign(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let sk = SecretKey::random(&mut rng);
+    for (name, sign) in [
+        ("sign_v1", PlumeSignature::sign_v1 as Sign),
+        ("sign_v2", PlumeSignature::sign_v2),
+    ] {
+        let mut group = c.benchmark_group(name);
+        for size in MESSAGE_SIZES {
+            let message = message(&mut rng, size);
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(size), &message, |b, message| {
+                b.iter(|| sign(&sk, message, &mut rng))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let sk = SecretKey::random(&mut rng);
+    for (name, sign) in [
+        ("verify_v1", PlumeSignature::sign_v1 as Sign),
+        ("verify_v2", PlumeSignature::sign_v2),
+    ] {
+        let mut group = c.benchmark_group(name);
+        for size in MESSAGE_SIZES {
+            let sig = sign(&sk, &message(&mut rng, size), &mut rng);
+            group.throughput(Throughput::Bytes(size as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(size), &sig, |b, sig| {
+                b.iter(|| assert!(sig.verify()))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_keygen,
+    bench_hash_to_curve,
+    bench_sign,
+    bench_verify
+);
+criterion_main!(benches);
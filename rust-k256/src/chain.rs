@@ -0,0 +1,99 @@
+//! Verification of a nullifier by a zero-knowledge proof, falling back to the signature.
+//!
+//! Integrators accept a nullifier either with a SNARK proof of a valid signature, which keeps the key private, or with the signature
+//! itself, which discloses the key. [`VerifierChain`] tries the proof first and the signature only if the application allows disclosing
+//! keys, and tells which one it accepted. The crate has no proof system; the proof is checked by the application's
+//! [`NullifierProofVerifier`].
+
+use super::prehash::{MessageMode, PlumeVerifier};
+use super::{AffinePoint, PlumeSignature, PublicKey};
+use std::fmt;
+
+/// Checks zero-knowledge proofs of nullifiers, e.g. of the PLUME circuits.
+pub trait NullifierProofVerifier {
+    /// Whether `proof` shows that `nullifier` is of a valid signature on `message`.
+    fn verify_proof(&self, message: &[u8], nullifier: &AffinePoint, proof: &[u8]) -> bool;
+}
+
+/// The way a nullifier was accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationPath {
+    /// By the zero-knowledge proof.
+    Proof,
+    /// By the signature, disclosing the key.
+    Signature,
+}
+
+/// The reason a nullifier isn't accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainError {
+    /// Neither a proof nor a signature was given.
+    NoEvidence,
+    /// The proof is invalid and there's no signature to fall back to.
+    InvalidProof,
+    /// A signature was given, but the chain doesn't allow disclosing the key.
+    DisclosureNotAllowed,
+    /// The signature is invalid or of another nullifier.
+    InvalidSignature,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NoEvidence => "neither a proof nor a signature of the nullifier is given",
+            Self::InvalidProof => "the proof of the nullifier is invalid",
+            Self::DisclosureNotAllowed => "verifying by the signature would disclose the key",
+            Self::InvalidSignature => "the signature of the nullifier is invalid",
+        })
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Tries the proof of a nullifier, then its signature.
+pub struct VerifierChain<V> {
+    /// Checks the proofs; `None` skips them.
+    pub proofs: Option<V>,
+    /// Whether the signature, and so the key, may be disclosed to verify the nullifier.
+    pub allow_disclosure: bool,
+    /// Verifies the signatures.
+    pub signatures: PlumeVerifier,
+}
+
+impl<V: NullifierProofVerifier> VerifierChain<V> {
+    /// Verifies `nullifier` for `message`, by `proof` or by the signature of a key made in a mode.
+    ///
+    /// `message` is what [`PlumeVerifier::verify`] takes for the mode, i.e. the digest in the pre-hashed one.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        nullifier: &AffinePoint,
+        proof: Option<&[u8]>,
+        signature: Option<(&PublicKey, &PlumeSignature, MessageMode)>,
+    ) -> Result<VerificationPath, ChainError> {
+        let proof_rejected = match (&self.proofs, proof) {
+            (Some(proofs), Some(proof)) => {
+                if proofs.verify_proof(message, nullifier, proof) {
+                    return Ok(VerificationPath::Proof);
+                }
+                true
+            }
+            _ => false,
+        };
+
+        match signature {
+            Some(_) if !self.allow_disclosure => Err(ChainError::DisclosureNotAllowed),
+            Some((pk, sig, mode)) => {
+                if sig.nullifier == *nullifier
+                    && self.signatures.verify(pk, message, mode, sig).is_ok()
+                {
+                    Ok(VerificationPath::Signature)
+                } else {
+                    Err(ChainError::InvalidSignature)
+                }
+            }
+            None if proof_rejected => Err(ChainError::InvalidProof),
+            None => Err(ChainError::NoEvidence),
+        }
+    }
+}
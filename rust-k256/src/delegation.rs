@@ -0,0 +1,151 @@
+//! Delegating nullifiers to session keys, as account abstraction (ERC-4337) wallets do.
+//!
+//! The owner key signs a [`Delegation`] over a session key, a scope, and an expiry, with PLUME or with ECDSA. A nullifier of the session key
+//! is then accepted by [`Delegation::verify_session_signature`] for the messages in the scope until the expiry.
+//!
+//! Note that the nullifiers are of the session key: a new session key gives new nullifiers for the same messages. Applications which
+//! need one nullifier per owner should bind the owner into the message, e.g. by the scope.
+
+use super::verified::VerifiedNullifier;
+use super::{CryptoRngCore, PlumeSignature, PublicKey, SecretKey};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use signature::{Signer, Verifier};
+use std::fmt;
+
+const DELEGATION_TAG: &[u8] = b"PLUME/delegation";
+
+/// The owner's signature authorizing a [`Delegation`].
+pub enum Authorization {
+    /// A PLUME signature; the owner's nullifier for the delegation comes along with it.
+    Plume(PlumeSignature),
+    /// An ECDSA signature over the SHA-256 of the delegation message.
+    Ecdsa(Signature),
+}
+
+/// The owner's authorization of `session_pk` to produce nullifiers for the messages starting with `scope` until `expiry`.
+pub struct Delegation {
+    /// The delegating key.
+    pub owner: PublicKey,
+    /// The delegated key.
+    pub session_pk: PublicKey,
+    /// The prefix of the messages the session key may sign.
+    pub scope: Vec<u8>,
+    /// The time the delegation ends, in the units of the `now` given to the verifier (e.g. seconds since the Unix epoch).
+    pub expiry: u64,
+    /// The owner's signature of [`Delegation::message`].
+    pub authorization: Authorization,
+}
+
+impl Delegation {
+    /// The message the owner signs: the tag `PLUME/delegation`, the compressed session key, the scope, and the expiry as a big-endian
+    /// `u64`; [framed](plume_core::transcript::framed), each field prefixed with its length as a big-endian `u64`.
+    pub fn message(session_pk: &PublicKey, scope: &[u8], expiry: u64) -> Vec<u8> {
+        plume_core::transcript::framed(&[
+            DELEGATION_TAG,
+            session_pk.to_encoded_point(true).as_bytes(),
+            scope,
+            &expiry.to_be_bytes(),
+        ])
+    }
+
+    /// Delegates to `session_pk` with a PLUME V2 signature of the owner.
+    pub fn sign_plume(
+        owner_sk: &SecretKey,
+        session_pk: PublicKey,
+        scope: &[u8],
+        expiry: u64,
+        rng: &mut impl CryptoRngCore,
+    ) -> Self {
+        let message = Self::message(&session_pk, scope, expiry);
+        Self {
+            owner: owner_sk.public_key(),
+            session_pk,
+            scope: scope.to_vec(),
+            expiry,
+            authorization: Authorization::Plume(PlumeSignature::sign_v2(owner_sk, &message, rng)),
+        }
+    }
+
+    /// Delegates to `session_pk` with an ECDSA signature of the owner.
+    pub fn sign_ecdsa(
+        owner_sk: &SecretKey,
+        session_pk: PublicKey,
+        scope: &[u8],
+        expiry: u64,
+    ) -> Self {
+        let message = Self::message(&session_pk, scope, expiry);
+        Self {
+            owner: owner_sk.public_key(),
+            session_pk,
+            scope: scope.to_vec(),
+            expiry,
+            authorization: Authorization::Ecdsa(SigningKey::from(owner_sk).sign(&message)),
+        }
+    }
+
+    /// Checks the owner's authorization and that the delegation hasn't expired at `now`.
+    pub fn verify(&self, now: u64) -> Result<(), DelegationError> {
+        if now >= self.expiry {
+            return Err(DelegationError::Expired);
+        }
+        let message = Self::message(&self.session_pk, &self.scope, self.expiry);
+        let authorized = match &self.authorization {
+            Authorization::Plume(sig) => self.owner.verify(&message, sig).is_ok(),
+            Authorization::Ecdsa(sig) => VerifyingKey::from(&self.owner)
+                .verify(&message, sig)
+                .is_ok(),
+        };
+        if authorized {
+            Ok(())
+        } else {
+            Err(DelegationError::InvalidAuthorization)
+        }
+    }
+
+    /// Accepts `sig` of the session key, yielding its nullifier, if the delegation is valid at `now` and the message is in its scope.
+    pub fn verify_session_signature(
+        &self,
+        sig: &PlumeSignature,
+        now: u64,
+    ) -> Result<VerifiedNullifier, DelegationError> {
+        self.verify(now)?;
+        if sig.pk != *self.session_pk.as_affine() {
+            return Err(DelegationError::WrongSessionKey);
+        }
+        if !sig.message.starts_with(&self.scope) {
+            return Err(DelegationError::OutOfScope);
+        }
+        sig.verified_nullifier()
+            .ok_or(DelegationError::InvalidSignature)
+    }
+}
+
+/// The reason a delegated signature isn't accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegationError {
+    /// The delegation has expired.
+    Expired,
+    /// The owner's signature of the delegation is invalid.
+    InvalidAuthorization,
+    /// The signature isn't made with the delegated key.
+    WrongSessionKey,
+    /// The message doesn't start with the delegated scope.
+    OutOfScope,
+    /// The session key's signature is invalid.
+    InvalidSignature,
+}
+
+impl fmt::Display for DelegationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Expired => "the delegation has expired",
+            Self::InvalidAuthorization => "the owner's authorization of the delegation is invalid",
+            Self::WrongSessionKey => "the signature isn't made with the delegated key",
+            Self::OutOfScope => "the message is out of the delegated scope",
+            Self::InvalidSignature => "the session key's signature is invalid",
+        })
+    }
+}
+
+impl std::error::Error for DelegationError {}
@@ -8,7 +8,8 @@
 // Find `arkworks-rs` crate as `plume_arkworks`.
 //
 //! # Examples
-//! If you want more control or to be more generic on traits `use` [`PlumeSigner`] from [`randomizedsigner`]
+//! If you want more control or to be more generic on traits `use` [`PlumeSigner`] from [`randomizedsigner`];
+//! [`PublicKey`] implements [`Verifier`] for the signatures.
 //! ```rust
 //! use plume_rustcrypto::{PlumeSignature, SecretKey};
 //! use rand_core::OsRng;
@@ -33,14 +34,15 @@ use k256::sha2::{digest::Output, Digest, Sha256}; // requires 'getrandom' featur
 use k256::ProjectivePoint;
 use k256::Scalar;
 use k256::U256;
-use signature::RandomizedSigner;
+use signature::{RandomizedSigner, Verifier};
 
 /// Exports types from the `k256` crate:
 ///
 /// - `NonZeroScalar`: A secret 256-bit scalar value.
 /// - `SecretKey`: A secret 256-bit scalar wrapped in a struct.  
+/// - `PublicKey`: A public key, which is a non-identity elliptic curve point.
 /// - `AffinePoint`: A public elliptic curve point.
-pub use k256::{AffinePoint, NonZeroScalar, SecretKey};
+pub use k256::{AffinePoint, NonZeroScalar, PublicKey, SecretKey};
 /// Re-exports the [`CryptoRngCore`] trait from the [`rand_core`] crate.
 /// This allows it to be used from the current module.
 pub use rand_core::CryptoRngCore;
@@ -56,18 +58,42 @@ use utils::*;
 /// Provides the [`RandomizedSigner`] trait implementation over [`PlumeSignature`].
 pub mod randomizedsigner;
 use randomizedsigner::PlumeSigner;
+/// Provides [`chain::VerifierChain`] trying a proof of a nullifier before its signature.
+pub mod chain;
+/// Provides [`delegation::Delegation`] of nullifiers to session keys.
+pub mod delegation;
+/// Provides [`negative_cache::RejectionCache`] shedding repeated invalid submissions.
+pub mod negative_cache;
+/// Provides [`policy::KeyPolicy`] restricting a [`PlumeSigner`].
+pub mod policy;
+/// Re-exports the stable public API.
+pub mod prelude;
+/// Provides [`prehash::PlumeVerifier`] bounding the work of verifying untrusted messages.
+pub mod prehash;
+/// Provides the [`tweak::Tweak`] trait for the keys.
+pub mod tweak;
+/// Provides [`validate::signature`] and the other group membership checks of the points.
+pub mod validate;
+/// Provides [`verified::VerifiedNullifier`], a nullifier which is known to be verified.
+pub mod verified;
 
 /// The domain separation tag used for hashing to the `secp256k1` curve
-pub const DST: &[u8] = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_"; // Hash to curve algorithm
+pub const DST: &[u8] = plume_core::consts::SECP256K1.dst; // Hash to curve algorithm
 
 /// Struct holding signature data for a PLUME signature.
 ///
 /// `v1specific` field differintiate whether V1 or V2 protocol will be used.
+///
+/// With `serde` the fields are written in the declaration order, under the ERC-7524 names: `message`, `publicKey`, `nullifier`, `c`, `s`,
+/// and `v1specific` holding `gPowR` and `hashMPKPowR`. Deserialization rejects unknown and duplicate fields, so a JSON
+/// signature has a single accepted form.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct PlumeSignature {
     /// The message that was signed.
     pub message: Vec<u8>,
     /// The public key used to verify the signature.
+    #[cfg_attr(feature = "serde", serde(rename = "publicKey"))]
     pub pk: AffinePoint,
     /// The nullifier.
     pub nullifier: AffinePoint,
@@ -81,10 +107,13 @@ pub struct PlumeSignature {
 /// Nested struct holding additional signature data used in variant 1 of the protocol.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct PlumeSignatureV1Fields {
     /// Part of the signature data, a curve point.  
+    #[cfg_attr(feature = "serde", serde(rename = "gPowR"))]
     pub r_point: AffinePoint,
     /// Part of the signature data, a curve point.
+    #[cfg_attr(feature = "serde", serde(rename = "hashMPKPowR"))]
     pub hashed_to_curve_r: AffinePoint,
 }
 impl PlumeSignature {
@@ -96,6 +125,10 @@ impl PlumeSignature {
         // hash[m, gsk]^[r + sk * c] / (hash[m, pk]^sk)^c = hash[m, pk]^r
         // c = hash2(g, g^sk, hash[m, g^sk], hash[m, pk]^sk, gr, hash[m, pk]^r)
 
+        if validate::signature(self).is_err() {
+            return false;
+        }
+
         let c_scalar = *self.c;
 
         let r_point = (ProjectivePoint::GENERATOR * *self.s) - (self.pk * (c_scalar));
@@ -156,12 +189,22 @@ impl PlumeSignature {
     }
 }
 
+impl Verifier<PlumeSignature> for PublicKey {
+    /// Checks that the signature is made by this key for `msg`, and [verifies](PlumeSignature::verify) it.
+    fn verify(&self, msg: &[u8], sig: &PlumeSignature) -> Result<(), signature::Error> {
+        if sig.message == msg && sig.pk == *self.as_affine() && sig.verify() {
+            Ok(())
+        } else {
+            Err(signature::Error::new())
+        }
+    }
+}
+
 fn c_sha256_vec_signal(values: Vec<&ProjectivePoint>) -> Output<Sha256> {
-    let preimage_vec = values
-        .into_iter()
-        .map(encode_pt)
-        .collect::<Vec<_>>()
-        .concat();
+    let preimage_vec = plume_core::transcript::join(
+        &values.into_iter().map(encode_pt).collect::<Vec<_>>(),
+        false,
+    );
     let mut sha256_hasher = Sha256::new();
     sha256_hasher.update(preimage_vec.as_slice());
     sha256_hasher.finalize()
@@ -0,0 +1,193 @@
+//! Shedding repeated invalid submissions.
+//!
+//! Under a spam attack a registry receives the same invalid submission for a nullifier over and over, and hashing to the curve for each
+//! of them is the expensive part. A [`RejectionCache`] remembers the rejected submissions for a while, so
+//! [`PlumeVerifier::verify_cached`] turns a repeat down without verifying it again.
+//!
+//! The entries are keyed by the nullifier together with the digest of the whole submission. Keying by the nullifier alone would let
+//! anyone block a nullifier by submitting garbage claiming it before the genuine signature arrives.
+//!
+//! Only rejections are cached: a valid submission is verified each time, as a registry acts on it once anyway. The cache holds at most
+//! `capacity` entries, dropping the oldest beyond that, and an entry expires `ttl` after the rejection. Times are in the units of the
+//! `now` given to it, as for [`Delegation`](crate::delegation::Delegation).
+
+use super::prehash::{MessageMode, PlumeVerifier};
+use super::{PlumeSignature, PublicKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::sha2::{Digest, Sha256};
+use signature::{Error, Verifier};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+const SUBMISSION_TAG: &[u8] = b"PLUME/submission";
+
+type Key = (Vec<u8>, [u8; 32]);
+
+/// The counts of a [`RejectionCache`], e.g. to export as metrics.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// The submissions turned down from the cache.
+    pub hits: u64,
+    /// The submissions which weren't in the cache and were verified.
+    pub misses: u64,
+    /// The entries dropped to stay within the capacity.
+    pub evictions: u64,
+}
+
+/// Recently rejected submissions, bounded in time and in size.
+#[derive(Debug, Clone)]
+pub struct RejectionCache {
+    ttl: u64,
+    capacity: usize,
+    /// The expiry of each entry.
+    expiries: HashMap<Key, u64>,
+    /// The entries in the order of insertion, with the expiry they were inserted with; a refreshed entry is in here more than once.
+    order: VecDeque<(Key, u64)>,
+    metrics: CacheMetrics,
+}
+
+impl RejectionCache {
+    /// A cache keeping a rejection for `ttl` and holding at most `capacity` of them.
+    pub fn new(ttl: u64, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            expiries: HashMap::new(),
+            order: VecDeque::new(),
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    /// The number of rejections held, expired ones included until they're purged.
+    pub fn len(&self) -> usize {
+        self.expiries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expiries.is_empty()
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+
+    /// Whether the submission was rejected and hasn't expired at `now`; counts a hit or a miss.
+    fn check(&mut self, key: &Key, now: u64) -> bool {
+        self.purge(now);
+        let hit = self.expiries.get(key).is_some_and(|expiry| now < *expiry);
+        if hit {
+            self.metrics.hits += 1;
+        } else {
+            self.metrics.misses += 1;
+        }
+        hit
+    }
+
+    fn insert(&mut self, key: Key, now: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let expiry = now.saturating_add(self.ttl);
+        self.expiries.insert(key.clone(), expiry);
+        self.order.push_back((key, expiry));
+        while self.expiries.len() > self.capacity {
+            match self.order.pop_front() {
+                Some((key, expiry)) => {
+                    if self.expiries.get(&key) == Some(&expiry) {
+                        self.expiries.remove(&key);
+                        self.metrics.evictions += 1;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Drops the entries expired at `now` from the front; the rest expire no earlier, as the `ttl` is the same for all.
+    fn purge(&mut self, now: u64) {
+        while let Some((key, expiry)) = self.order.front() {
+            if now < *expiry {
+                break;
+            }
+            if self.expiries.get(key) == Some(expiry) {
+                self.expiries.remove(key);
+            }
+            self.order.pop_front();
+        }
+    }
+}
+
+/// The source of the [`signature::Error`] of a submission turned down from a [`RejectionCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedRejection;
+
+impl fmt::Display for CachedRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the submission was rejected recently")
+    }
+}
+
+impl std::error::Error for CachedRejection {}
+
+/// The digest of everything the verification depends on, under the tag `PLUME/submission`.
+fn submission_digest(
+    pk: &PublicKey,
+    msg: &[u8],
+    mode: MessageMode,
+    sig: &PlumeSignature,
+) -> [u8; 32] {
+    let encode = |point: &k256::AffinePoint| point.to_encoded_point(true).as_bytes().to_vec();
+    let mut fields = vec![
+        encode(pk.as_affine()),
+        msg.to_vec(),
+        vec![matches!(mode, MessageMode::PreHashed) as u8],
+        sig.message.clone(),
+        encode(&sig.pk),
+        encode(&sig.nullifier),
+        sig.c.to_bytes().to_vec(),
+        sig.s.to_bytes().to_vec(),
+    ];
+    if let Some(v1) = &sig.v1specific {
+        fields.push(encode(&v1.r_point));
+        fields.push(encode(&v1.hashed_to_curve_r));
+    }
+    let fields = [vec![SUBMISSION_TAG.to_vec()], fields].concat();
+    Sha256::digest(plume_core::transcript::framed(
+        &fields.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+    ))
+    .into()
+}
+
+impl PlumeVerifier {
+    /// [`Self::verify`], turning down a submission `cache` holds a recent rejection of, and recording the rejection of a new one.
+    ///
+    /// A cached rejection is an error with [`CachedRejection`] as its source. A message over the limits, or a signature on another
+    /// message, is turned down before anything is hashed and isn't cached.
+    pub fn verify_cached(
+        &self,
+        cache: &mut RejectionCache,
+        now: u64,
+        pk: &PublicKey,
+        msg: &[u8],
+        mode: MessageMode,
+        sig: &PlumeSignature,
+    ) -> Result<(), Error> {
+        // the limits come before the digest, so an oversized submission is neither hashed nor cached
+        let signed = self.signed_message(msg, mode)?;
+        if sig.message != *signed {
+            return Err(Error::new());
+        }
+        let key = (
+            sig.nullifier.to_encoded_point(true).as_bytes().to_vec(),
+            submission_digest(pk, msg, mode, sig),
+        );
+        if cache.check(&key, now) {
+            return Err(Error::from_source(CachedRejection));
+        }
+        let result = pk.verify(&signed, sig);
+        if result.is_err() {
+            cache.insert(key, now);
+        }
+        result
+    }
+}
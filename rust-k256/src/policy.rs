@@ -0,0 +1,134 @@
+//! Restrictions on what a key may sign.
+//!
+//! A [`KeyPolicy`] attached to a [`PlumeSigner`](crate::randomizedsigner::PlumeSigner) is checked before anything is computed, so an
+//! out-of-policy request yields an error instead of a signature (and a nullifier). The error is a [`signature::Error`] with the
+//! [`PolicyViolation`] as its source.
+
+use std::fmt;
+
+/// The versions, scopes, and message formats a key is allowed to sign.
+///
+/// [`KeyPolicy::default`] allows everything; each method narrows the policy down.
+#[derive(Debug, Clone)]
+pub struct KeyPolicy {
+    /// Whether V1 signatures are allowed.
+    pub allow_v1: bool,
+    /// Whether V2 signatures are allowed.
+    pub allow_v2: bool,
+    /// The scopes a message must start with, one of them; any message is in scope when empty.
+    pub scopes: Vec<Vec<u8>>,
+    /// The longest message allowed.
+    pub max_message_len: Option<usize>,
+    /// A check of the message format, e.g. that it parses as the application's scope encoding.
+    pub message_format: Option<fn(&[u8]) -> bool>,
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        Self {
+            allow_v1: true,
+            allow_v2: true,
+            scopes: Vec::new(),
+            max_message_len: None,
+            message_format: None,
+        }
+    }
+}
+
+impl KeyPolicy {
+    /// Allows V1 signatures only.
+    pub fn v1_only(self) -> Self {
+        Self {
+            allow_v1: true,
+            allow_v2: false,
+            ..self
+        }
+    }
+
+    /// Allows V2 signatures only.
+    pub fn v2_only(self) -> Self {
+        Self {
+            allow_v1: false,
+            allow_v2: true,
+            ..self
+        }
+    }
+
+    /// Adds `scope` to the allowed ones; once there's any, a message has to start with one of them.
+    pub fn allow_scope(mut self, scope: &[u8]) -> Self {
+        self.scopes.push(scope.to_vec());
+        self
+    }
+
+    /// Limits the message length.
+    pub fn max_message_len(self, max: usize) -> Self {
+        Self {
+            max_message_len: Some(max),
+            ..self
+        }
+    }
+
+    /// Requires messages to pass `check`.
+    pub fn message_format(self, check: fn(&[u8]) -> bool) -> Self {
+        Self {
+            message_format: Some(check),
+            ..self
+        }
+    }
+
+    /// Checks signing `msg` with the variant chosen by `v1` against the policy.
+    pub fn check(&self, v1: bool, msg: &[u8]) -> Result<(), PolicyViolation> {
+        if (v1 && !self.allow_v1) || (!v1 && !self.allow_v2) {
+            return Err(PolicyViolation::Version { v1 });
+        }
+        if let Some(max) = self.max_message_len {
+            if msg.len() > max {
+                return Err(PolicyViolation::MessageTooLong {
+                    len: msg.len(),
+                    max,
+                });
+            }
+        }
+        if !self.scopes.is_empty() && !self.scopes.iter().any(|scope| msg.starts_with(scope)) {
+            return Err(PolicyViolation::Scope);
+        }
+        if let Some(check) = self.message_format {
+            if !check(msg) {
+                return Err(PolicyViolation::MessageFormat);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The reason a signing request is out of a [`KeyPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// The variant isn't allowed; `v1` tells which one was requested.
+    Version { v1: bool },
+    /// The message doesn't start with any of the allowed scopes.
+    Scope,
+    /// The message is longer than the policy allows.
+    MessageTooLong { len: usize, max: usize },
+    /// The message failed the format check.
+    MessageFormat,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Version { v1 } => write!(
+                f,
+                "the key isn't allowed to produce {} signatures",
+                if *v1 { "V1" } else { "V2" }
+            ),
+            Self::Scope => write!(f, "the message is out of the allowed scopes"),
+            Self::MessageTooLong { len, max } => {
+                write!(f, "the message is {len} bytes, over the limit of {max}")
+            }
+            Self::MessageFormat => write!(f, "the message doesn't have the allowed format"),
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
@@ -0,0 +1,95 @@
+//! Bounding the hashing work of verifying untrusted messages.
+//!
+//! Hashing to the curve is linear in the message, so a public verification endpoint can be made to hash arbitrarily long messages. In the
+//! [pre-hashed](MessageMode::PreHashed) mode the signed message is the tag `PLUME/prehash` followed by the SHA-256 of the application
+//! message, so the verifier works on 45 bytes whatever the message; the [raw](MessageMode::Raw) mode signs the message as is. The two
+//! modes give different nullifiers for the same message, so an application picks one.
+//!
+//! [`PlumeVerifier`] rejects raw messages over its length limit before hashing anything. A violation is a [`signature::Error`] with the
+//! [`PolicyViolation`] as its source, as for [`KeyPolicy`](crate::policy::KeyPolicy).
+
+use super::{PlumeSignature, PublicKey};
+use crate::policy::PolicyViolation;
+use k256::sha2::{Digest, Sha256};
+use signature::{Error, Verifier};
+use std::borrow::Cow;
+
+/// The prefix of pre-hashed messages, so they can't be confused with raw messages of the same bytes.
+pub const PREHASH_TAG: &[u8] = b"PLUME/prehash";
+
+/// The limit of [`PlumeVerifier::default`] on raw messages.
+pub const DEFAULT_MAX_RAW_LEN: usize = 64 * 1024;
+
+/// How the application message relates to the signed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageMode {
+    /// The application message is signed as is.
+    Raw,
+    /// The signed message is [`PREHASH_TAG`] followed by the SHA-256 of the application message.
+    PreHashed,
+}
+
+/// The SHA-256 of `msg`, the first stage of the pre-hashed mode.
+pub fn prehash(msg: &[u8]) -> [u8; 32] {
+    Sha256::digest(msg).into()
+}
+
+/// The message signed in the pre-hashed mode for the application message hashing to `digest`.
+pub fn prehashed_message(digest: &[u8; 32]) -> Vec<u8> {
+    [PREHASH_TAG, &digest[..]].concat()
+}
+
+/// A verifier limiting the work an untrusted message can cause.
+#[derive(Debug, Clone)]
+pub struct PlumeVerifier {
+    /// The longest raw message accepted for verification.
+    pub max_raw_len: usize,
+}
+
+impl Default for PlumeVerifier {
+    fn default() -> Self {
+        Self {
+            max_raw_len: DEFAULT_MAX_RAW_LEN,
+        }
+    }
+}
+
+impl PlumeVerifier {
+    /// Verifies `sig` by `pk` on `msg` signed in `mode`.
+    ///
+    /// In the raw mode `msg` is the message, checked against the limit first. In the pre-hashed mode it's the 32 bytes digest of
+    /// [`prehash`], so the application message doesn't even have to reach the verifier.
+    pub fn verify(
+        &self,
+        pk: &PublicKey,
+        msg: &[u8],
+        mode: MessageMode,
+        sig: &PlumeSignature,
+    ) -> Result<(), Error> {
+        pk.verify(&self.signed_message(msg, mode)?, sig)
+    }
+
+    /// The message signed for `msg` in `mode`, once `msg` is within the limits.
+    pub(crate) fn signed_message<'m>(
+        &self,
+        msg: &'m [u8],
+        mode: MessageMode,
+    ) -> Result<Cow<'m, [u8]>, Error> {
+        match mode {
+            MessageMode::Raw => {
+                if msg.len() > self.max_raw_len {
+                    return Err(Error::from_source(PolicyViolation::MessageTooLong {
+                        len: msg.len(),
+                        max: self.max_raw_len,
+                    }));
+                }
+                Ok(Cow::Borrowed(msg))
+            }
+            MessageMode::PreHashed => {
+                let digest = <&[u8; 32]>::try_from(msg)
+                    .map_err(|_| Error::from_source(PolicyViolation::MessageFormat))?;
+                Ok(Cow::Owned(prehashed_message(digest)))
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! The stable public API: `use plume_rustcrypto::prelude::*;`.
+//!
+//! What's exported here only changes with a breaking release. `tests/public_api.rs` pins the signatures of these items, so an accidental
+//! change fails the tests.
+
+pub use crate::delegation::{Authorization, Delegation, DelegationError};
+pub use crate::policy::{KeyPolicy, PolicyViolation};
+pub use crate::prehash::{MessageMode, PlumeVerifier};
+pub use crate::randomizedsigner::PlumeSigner;
+pub use crate::verified::VerifiedNullifier;
+pub use crate::{AffinePoint, NonZeroScalar, PlumeSignature, PlumeSignatureV1Fields};
+pub use crate::{PublicKey, SecretKey};
+pub use signature::{RandomizedSigner, Signer, Verifier};
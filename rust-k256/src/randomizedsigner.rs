@@ -1,6 +1,6 @@
 use super::{
-    CryptoRngCore, NonZeroScalar, PlumeSignature, PlumeSignatureV1Fields, ProjectivePoint,
-    SecretKey, DST,
+    policy::KeyPolicy, CryptoRngCore, NonZeroScalar, PlumeSignature, PlumeSignatureV1Fields,
+    ProjectivePoint, SecretKey, DST,
 };
 use k256::{
     elliptic_curve::{
@@ -12,13 +12,15 @@ use k256::{
     Secp256k1,
 };
 // Removed `pub` from this, since it's only interested to those who already imported `signature`
+#[cfg(feature = "getrandom")]
+use signature::Signer;
 use signature::{Error, RandomizedSigner};
 
 /// `PlumeSigner` is a `struct` that contains a reference to a secret key and a
 /// boolean defining output [`PlumeSignature`] variant.
 ///
 /// It implements the `RandomizedSigner` trait to generate signatures using the provided secret
-/// key. The struct is generic over the lifetime of the secret key reference so that the key can be borrowed immutably.
+/// key. With the `getrandom` feature it also implements `Signer`, drawing the nonce from [`rand_core::OsRng`]. The struct is generic over the lifetime of the secret key reference so that the key can be borrowed immutably.
 ///
 /// `serde` traits aren't added to this struct on purpose. It's a wrapper around [`SecretKey`] which provides variety of serialization formats (SEC1, bytes, ...).
 /// Also it uses just a reference to the secret key itself, so the choices for handling the key is kept open here.
@@ -31,24 +33,43 @@ pub struct PlumeSigner<'signing> {
     /// involve the key material, and distinguishing on it doesn't look possible
     // Since #lastoponsecret seems to me indistinguishible between variants here's `bool` is used instead of `subtle`
     pub v1: bool,
+    /// The restrictions checked before signing; see [`KeyPolicy`].
+    policy: Option<KeyPolicy>,
 }
 impl<'signing> PlumeSigner<'signing> {
     /// Creates a new `PlumeSigner` instance with the given secret key and signature
     /// variant.
     pub fn new(secret_key: &SecretKey, v1: bool) -> PlumeSigner {
-        PlumeSigner { secret_key, v1 }
+        PlumeSigner {
+            secret_key,
+            v1,
+            policy: None,
+        }
     }
-}
-impl<'signing> RandomizedSigner<PlumeSignature> for PlumeSigner<'signing> {
-    fn try_sign_with_rng(
+
+    /// Restricts the signer to `policy`. Signing out of it fails with an error which source is the [`PolicyViolation`](crate::policy::PolicyViolation).
+    pub fn with_policy(self, policy: KeyPolicy) -> Self {
+        Self {
+            policy: Some(policy),
+            ..self
+        }
+    }
+
+    /// Signs `msg` with the nonce `r_scalar` instead of one drawn from an RNG.
+    ///
+    /// # HAZMAT
+    /// `r_scalar` has to be uniformly random and never reused: two signatures with the same nonce reveal the secret key. It's for test
+    /// vectors and for checking this crate against `plume_arkworks`; use [`RandomizedSigner`] otherwise.
+    pub fn sign_with_r(
         &self,
-        rng: &mut impl CryptoRngCore,
+        r_scalar: &NonZeroScalar,
         msg: &[u8],
     ) -> Result<PlumeSignature, Error> {
-        // Pick a random r from Fp
-        let r_scalar = SecretKey::random(rng);
+        if let Some(policy) = &self.policy {
+            policy.check(self.v1, msg).map_err(Error::from_source)?;
+        }
 
-        let r_point = r_scalar.public_key();
+        let r_point = ProjectivePoint::GENERATOR * **r_scalar;
 
         let pk = self.secret_key.public_key();
         let pk_bytes = pk.to_encoded_point(true).to_bytes();
@@ -60,16 +81,13 @@ impl<'signing> RandomizedSigner<PlumeSignature> for PlumeSigner<'signing> {
         )
         .expect("something is drammatically wrong if the input hashed to the identity");
 
-        // it feels not that scary to store `r_scalar` as `NonZeroScalar` (compared to `self.secret_key`)
-        let r_scalar = r_scalar.to_nonzero_scalar();
-
         // Compute z = h^r
-        let hashed_to_curve_r = hashed_to_curve * r_scalar;
+        let hashed_to_curve_r = hashed_to_curve * *r_scalar;
 
         // Compute nul = h^sk
         let nullifier = hashed_to_curve * self.secret_key.to_nonzero_scalar();
 
-        // Compute c = sha512([g, pk, h, nul, g^r, z])
+        // Compute c = sha256([g, pk, h, nul, g^r, z])
         let mut hasher = Sha256::new();
         // shorthand for updating the hasher which repeats a lot below
         macro_rules! updhash {
@@ -91,7 +109,7 @@ impl<'signing> RandomizedSigner<PlumeSignature> for PlumeSigner<'signing> {
             .expect("it should be impossible to get the hash equal to zero");
 
         // Compute $s = r + sk ⋅ c$. #lastoponsecret
-        let s_scalar = NonZeroScalar::new(*r_scalar + *(c_scalar * self.secret_key.to_nonzero_scalar()))
+        let s_scalar = NonZeroScalar::new(**r_scalar + *(c_scalar * self.secret_key.to_nonzero_scalar()))
             .expect("something is terribly wrong if the nonce is equal to negated product of the secret and the hash");
 
         Ok(PlumeSignature {
@@ -102,7 +120,7 @@ impl<'signing> RandomizedSigner<PlumeSignature> for PlumeSigner<'signing> {
             s: s_scalar,
             v1specific: if self.v1 {
                 Some(PlumeSignatureV1Fields {
-                    r_point: r_point.into(),
+                    r_point: r_point.to_affine(),
                     hashed_to_curve_r: hashed_to_curve_r.to_point().to_affine(),
                 })
             } else {
@@ -111,3 +129,21 @@ impl<'signing> RandomizedSigner<PlumeSignature> for PlumeSigner<'signing> {
         })
     }
 }
+impl<'signing> RandomizedSigner<PlumeSignature> for PlumeSigner<'signing> {
+    fn try_sign_with_rng(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        msg: &[u8],
+    ) -> Result<PlumeSignature, Error> {
+        // Pick a random r from Fp
+        let r_scalar = SecretKey::random(rng);
+        // it feels not that scary to store `r_scalar` as `NonZeroScalar` (compared to `self.secret_key`)
+        self.sign_with_r(&r_scalar.to_nonzero_scalar(), msg)
+    }
+}
+#[cfg(feature = "getrandom")]
+impl<'signing> Signer<PlumeSignature> for PlumeSigner<'signing> {
+    fn try_sign(&self, msg: &[u8]) -> Result<PlumeSignature, Error> {
+        self.try_sign_with_rng(&mut rand_core::OsRng, msg)
+    }
+}
@@ -0,0 +1,45 @@
+//! Additive and multiplicative key tweaks, as in Taproot or the non-hardened derivations.
+//!
+//! A tweak of the secret key `sk` to `sk + t` (or `sk ⋅ t`) is matched by the tweak of the public key to `pk + g^t` (or `pk^t`), so either
+//! side can derive the tweaked key.
+//!
+//! # Nullifiers of tweaked keys
+//! The nullifier `hash[m, pk]^sk` hashes the public key, so a tweaked key has its own base and the nullifiers for the same message differ.
+//! Knowing the tweak doesn't help to link them either: relating `hash[m, pk']^(sk + t)` to `hash[m, pk]^sk` still takes `hash[m, pk']^sk`,
+//! which only the holder of `sk` can compute. Thus every tweak of a key is a separate nullifier space; a wallet which wants the same
+//! nullifier for the same message across derivations has to sign with one fixed key.
+
+use super::{NonZeroScalar, ProjectivePoint, PublicKey, Scalar, SecretKey};
+
+/// Tweaking a key by a scalar.
+///
+/// Both methods return `None` when the result would be the zero key (the identity for a public key).
+pub trait Tweak: Sized {
+    /// Adds `tweak` to the key: `sk + t` for a secret key, `pk + g^t` for a public key.
+    fn tweak_add(&self, tweak: &Scalar) -> Option<Self>;
+    /// Multiplies the key by `tweak`: `sk ⋅ t` for a secret key, `pk^t` for a public key.
+    fn tweak_mul(&self, tweak: &Scalar) -> Option<Self>;
+}
+
+impl Tweak for SecretKey {
+    fn tweak_add(&self, tweak: &Scalar) -> Option<Self> {
+        Option::from(NonZeroScalar::new(*self.to_nonzero_scalar() + tweak)).map(SecretKey::from)
+    }
+
+    fn tweak_mul(&self, tweak: &Scalar) -> Option<Self> {
+        Option::from(NonZeroScalar::new(*self.to_nonzero_scalar() * tweak)).map(SecretKey::from)
+    }
+}
+
+impl Tweak for PublicKey {
+    fn tweak_add(&self, tweak: &Scalar) -> Option<Self> {
+        PublicKey::from_affine(
+            (self.to_projective() + ProjectivePoint::GENERATOR * tweak).to_affine(),
+        )
+        .ok()
+    }
+
+    fn tweak_mul(&self, tweak: &Scalar) -> Option<Self> {
+        PublicKey::from_affine((self.to_projective() * tweak).to_affine()).ok()
+    }
+}
@@ -0,0 +1,67 @@
+//! The group membership checks of the points a signature or a key is made of.
+//!
+//! `secp256k1` is of prime order, so every point on the curve is in the subgroup, and an [`AffinePoint`] of `k256` is on the curve by
+//! construction; what's left is decoding strictly and rejecting the identity. Decoding, verification, and the import of keys all go through
+//! here, as in the `validate` module of the arkworks backend, so they can't disagree on what a valid point is.
+
+use super::{AffinePoint, PlumeSignature, PlumeSignatureV1Fields, PublicKey};
+use k256::elliptic_curve::sec1::FromEncodedPoint;
+use k256::EncodedPoint;
+use std::fmt;
+
+/// The reason a point is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointError {
+    /// The bytes aren't a SEC1 encoding of a point on the curve.
+    NotOnCurve,
+    /// The point is the identity.
+    Identity,
+}
+
+impl fmt::Display for PointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NotOnCurve => "the bytes aren't a point on the curve",
+            Self::Identity => "the point is the identity",
+        })
+    }
+}
+
+impl std::error::Error for PointError {}
+
+/// Checks `point` isn't the identity.
+pub fn point(point: &AffinePoint) -> Result<(), PointError> {
+    if *point == AffinePoint::IDENTITY {
+        return Err(PointError::Identity);
+    }
+    Ok(())
+}
+
+/// Decodes a point from its compressed or uncompressed SEC1 encoding, and [checks](point) it.
+pub fn point_from_sec1(bytes: &[u8]) -> Result<AffinePoint, PointError> {
+    let encoded = EncodedPoint::from_bytes(bytes).map_err(|_| PointError::NotOnCurve)?;
+    let decoded: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded).into();
+    let decoded = decoded.ok_or(PointError::NotOnCurve)?;
+    point(&decoded)?;
+    Ok(decoded)
+}
+
+/// Decodes a public key from its SEC1 encoding; see [`point_from_sec1`].
+pub fn public_key_from_sec1(bytes: &[u8]) -> Result<PublicKey, PointError> {
+    PublicKey::from_affine(point_from_sec1(bytes)?).map_err(|_| PointError::Identity)
+}
+
+/// Checks the points of `signature`: the key, the nullifier, and `g^r` and `z` of a V1 signature.
+pub fn signature(signature: &PlumeSignature) -> Result<(), PointError> {
+    point(&signature.pk)?;
+    point(&signature.nullifier)?;
+    if let Some(PlumeSignatureV1Fields {
+        r_point,
+        hashed_to_curve_r,
+    }) = &signature.v1specific
+    {
+        point(r_point)?;
+        point(hashed_to_curve_r)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! Nullifiers which are known to be verified.
+//!
+//! A [`VerifiedNullifier`] can only be obtained from a signature which verifies, so code taking one (e.g. an application's registry of
+//! spent nullifiers) can't be handed an unverified nullifier by mistake.
+
+use super::{AffinePoint, PlumeSignature, PublicKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use signature::Verifier;
+
+/// The nullifier of a verified signature, with the key and the message it was verified for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedNullifier {
+    nullifier: AffinePoint,
+    pk: AffinePoint,
+    message: Vec<u8>,
+}
+
+impl VerifiedNullifier {
+    /// Verifies `sig` by `pk` on `msg`, yielding its nullifier on success.
+    pub fn verify(
+        pk: &PublicKey,
+        msg: &[u8],
+        sig: &PlumeSignature,
+    ) -> Result<Self, signature::Error> {
+        pk.verify(msg, sig)?;
+        Ok(Self::new_unchecked(sig))
+    }
+
+    /// Only for signatures which were just verified.
+    pub(crate) fn new_unchecked(sig: &PlumeSignature) -> Self {
+        Self {
+            nullifier: sig.nullifier,
+            pk: sig.pk,
+            message: sig.message.clone(),
+        }
+    }
+
+    /// The nullifier.
+    pub fn nullifier(&self) -> &AffinePoint {
+        &self.nullifier
+    }
+
+    /// The compressed nullifier, e.g. as the key of a set of spent nullifiers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.nullifier.to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    /// The key of the signer.
+    pub fn public_key(&self) -> &AffinePoint {
+        &self.pk
+    }
+
+    /// The message signed.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+}
+
+impl PlumeSignature {
+    /// [Verifies](PlumeSignature::verify) the signature, yielding its nullifier on success.
+    pub fn verified_nullifier(&self) -> Option<VerifiedNullifier> {
+        self.verify()
+            .then(|| VerifiedNullifier::new_unchecked(self))
+    }
+}
@@ -0,0 +1,155 @@
+//! Signs the same `(sk, r, message)` with this crate and with `plume_arkworks`, and checks the two agree byte for byte.
+//!
+//! The nullifier, `g^r`, `z`, `c` and `s` have to be equal in their SEC1 and big-endian encodings, so the arkworks canonical encoding of
+//! either signature is the same bytes; each implementation then has to accept the other's signature. The tuples are the repository vector
+//! and ones drawn from a seeded RNG.
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use plume_arkworks::{encoding, Parameters, PlumeVersion};
+use plume_rustcrypto::randomizedsigner::PlumeSigner;
+use plume_rustcrypto::{
+    AffinePoint, NonZeroScalar, PlumeSignature, PlumeSignatureV1Fields, PublicKey, SecretKey,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use secp256k1::curves::{Affine, Secp256k1Parameters};
+use secp256k1::fields::Fr;
+
+type ArkworksSignature = plume_arkworks::PlumeSignature<Secp256k1Parameters>;
+
+const MESSAGE: &[u8] = b"An example app message string";
+const SK: [u8; 32] =
+    hex_literal::hex!("519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464");
+const R: [u8; 32] =
+    hex_literal::hex!("93b9323b629f251b8f3fc2dd11f4672c5544e8230d493eceea98a90bda789808");
+
+fn point(point: &AffinePoint) -> Vec<u8> {
+    point.to_encoded_point(true).as_bytes().to_vec()
+}
+
+fn k256_point(bytes: &[u8]) -> AffinePoint {
+    PublicKey::from_sec1_bytes(bytes).unwrap().into()
+}
+
+fn k256_scalar(bytes: &[u8]) -> NonZeroScalar {
+    NonZeroScalar::try_from(bytes).unwrap()
+}
+
+/// The arkworks canonical encoding of a signature with these points and scalars.
+fn canonical(
+    nullifier: &[u8],
+    r_point: &[u8],
+    hashed_to_curve_r: &[u8],
+    c: &[u8],
+    s: &[u8],
+) -> Vec<u8> {
+    [
+        &b"PLM\x01\x01"[..],
+        nullifier,
+        r_point,
+        hashed_to_curve_r,
+        c,
+        s,
+    ]
+    .concat()
+}
+
+fn check(sk: &[u8; 32], r: &[u8; 32], message: &[u8]) {
+    let secret_key = SecretKey::from_bytes(&(*sk).into()).unwrap();
+    let r_scalar = k256_scalar(r);
+    let pp = Parameters {
+        g_point: Affine::prime_subgroup_generator(),
+    };
+    let ark_sk: Fr = encoding::field_from_bytes(sk).unwrap();
+    let ark_pk = pp.g_point.mul(ark_sk).into_affine();
+    assert_eq!(
+        encoding::point_to_bytes(&ark_pk).unwrap(),
+        point(secret_key.public_key().as_affine())
+    );
+
+    let k256_v1 = PlumeSigner::new(&secret_key, true)
+        .sign_with_r(&r_scalar, message)
+        .unwrap();
+    let k256_v2 = PlumeSigner::new(&secret_key, false)
+        .sign_with_r(&r_scalar, message)
+        .unwrap();
+    // `g^r` and `z` only depend on `r`, the key and the message, so V2 takes those of V1
+    let v1specific = k256_v1.v1specific.as_ref().unwrap();
+    let (r_point, hashed_to_curve_r) = (
+        point(&v1specific.r_point),
+        point(&v1specific.hashed_to_curve_r),
+    );
+
+    for (version, k256_sig) in [(PlumeVersion::V1, &k256_v1), (PlumeVersion::V2, &k256_v2)] {
+        let ark_sig = ArkworksSignature::sign_with_r(
+            &pp,
+            (&ark_pk, &ark_sk),
+            message,
+            encoding::field_from_bytes(r).unwrap(),
+            version,
+        )
+        .unwrap();
+
+        assert_eq!(
+            encoding::point_to_bytes(&ark_sig.nullifier).unwrap(),
+            point(&k256_sig.nullifier),
+            "nullifier of {version:?}"
+        );
+        assert_eq!(
+            encoding::field_to_bytes(&ark_sig.c),
+            k256_sig.c.to_bytes().to_vec(),
+            "c of {version:?}"
+        );
+        assert_eq!(
+            encoding::field_to_bytes(&ark_sig.s),
+            k256_sig.s.to_bytes().to_vec(),
+            "s of {version:?}"
+        );
+        let k256_bytes = canonical(
+            &point(&k256_sig.nullifier),
+            &r_point,
+            &hashed_to_curve_r,
+            &k256_sig.c.to_bytes(),
+            &k256_sig.s.to_bytes(),
+        );
+        let ark_bytes = ark_sig.to_bytes().unwrap();
+        assert_eq!(ark_bytes, k256_bytes, "signature of {version:?}");
+
+        // each accepts the other's signature
+        assert!(ArkworksSignature::from_bytes(&k256_bytes)
+            .unwrap()
+            .verify(&pp, &ark_pk, message, version)
+            .is_ok());
+        let from_arkworks = PlumeSignature {
+            message: message.to_vec(),
+            pk: secret_key.public_key().into(),
+            nullifier: k256_point(&encoding::point_to_bytes(&ark_sig.nullifier).unwrap()),
+            c: k256_scalar(&encoding::field_to_bytes(&ark_sig.c)),
+            s: k256_scalar(&encoding::field_to_bytes(&ark_sig.s)),
+            v1specific: (version == PlumeVersion::V1).then(|| PlumeSignatureV1Fields {
+                r_point: k256_point(&encoding::point_to_bytes(&ark_sig.r_point).unwrap()),
+                hashed_to_curve_r: k256_point(
+                    &encoding::point_to_bytes(&ark_sig.hashed_to_curve_r).unwrap(),
+                ),
+            }),
+        };
+        assert!(from_arkworks.verify(), "arkworks signature of {version:?}");
+    }
+}
+
+#[test]
+pub fn test_differential_vector() {
+    check(&SK, &R, MESSAGE);
+}
+
+#[test]
+pub fn test_differential_random() {
+    let mut rng = StdRng::seed_from_u64(0x504c_554d_45);
+    for _ in 0..32 {
+        let (sk, r) = (SecretKey::random(&mut rng), SecretKey::random(&mut rng));
+        let mut message = vec![0; rng.gen_range(0..200)];
+        rng.fill(message.as_mut_slice());
+        check(&sk.to_bytes().into(), &r.to_bytes().into(), &message);
+    }
+}
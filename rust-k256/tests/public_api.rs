@@ -0,0 +1,63 @@
+//! Pins the signatures of the items of the prelude; a failure to compile here is a breaking change.
+#![cfg(feature = "getrandom")]
+
+use plume_rustcrypto::prelude::*;
+use rand_core::OsRng;
+
+#[test]
+pub fn test_prelude_signatures() {
+    let _: fn(&SecretKey, &[u8], &mut OsRng) -> PlumeSignature = PlumeSignature::sign_v1;
+    let _: fn(&SecretKey, &[u8], &mut OsRng) -> PlumeSignature = PlumeSignature::sign_v2;
+    let _: fn(&PlumeSignature) -> bool = PlumeSignature::verify;
+    let _: fn(&PlumeSignature) -> Option<VerifiedNullifier> = PlumeSignature::verified_nullifier;
+    let _: fn(&PublicKey, &[u8], &PlumeSignature) -> Result<VerifiedNullifier, signature::Error> =
+        VerifiedNullifier::verify;
+    let _: fn(&SecretKey, bool) -> PlumeSigner = PlumeSigner::new;
+    let _: fn(&PlumeSigner, &NonZeroScalar, &[u8]) -> Result<PlumeSignature, signature::Error> =
+        PlumeSigner::sign_with_r;
+    let _: fn(&KeyPolicy, bool, &[u8]) -> Result<(), PolicyViolation> = KeyPolicy::check;
+    let _: fn(
+        &PlumeVerifier,
+        &PublicKey,
+        &[u8],
+        MessageMode,
+        &PlumeSignature,
+    ) -> Result<(), signature::Error> = PlumeVerifier::verify;
+    let _: fn(&Delegation, u64) -> Result<(), DelegationError> = Delegation::verify;
+    let _: fn(&Delegation, &PlumeSignature, u64) -> Result<VerifiedNullifier, DelegationError> =
+        Delegation::verify_session_signature;
+}
+
+#[test]
+pub fn test_prelude_fields() {
+    let sk = SecretKey::random(&mut OsRng);
+    let signer = PlumeSigner::new(&sk, true);
+    let PlumeSignature {
+        message,
+        pk,
+        nullifier,
+        c,
+        s,
+        v1specific,
+    } = signer.sign_with_rng(&mut OsRng, b"prelude");
+    let _: (
+        Vec<u8>,
+        AffinePoint,
+        AffinePoint,
+        NonZeroScalar,
+        NonZeroScalar,
+    ) = (message, pk, nullifier, c, s);
+    let PlumeSignatureV1Fields {
+        r_point,
+        hashed_to_curve_r,
+    } = v1specific.unwrap();
+    let _: (AffinePoint, AffinePoint) = (r_point, hashed_to_curve_r);
+
+    assert!(sk
+        .public_key()
+        .verify(
+            b"prelude",
+            &PlumeSignature::sign_v2(&sk, b"prelude", &mut OsRng)
+        )
+        .is_ok());
+}
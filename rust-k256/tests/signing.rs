@@ -62,3 +62,437 @@ pub fn test_sign_v2() {
     assert_eq!(Scalar::from_repr(V2_C.into()).unwrap(), *sig.c);
     assert_eq!(Scalar::from_repr(V2_S.into()).unwrap(), *sig.s);
 }
+
+#[cfg(feature = "getrandom")]
+#[test]
+pub fn test_signer_and_verifier_traits() {
+    use plume_rustcrypto::randomizedsigner::PlumeSigner;
+    use signature::{Signer, Verifier};
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let pk = sk.public_key();
+
+    for v1 in [true, false] {
+        let sig: PlumeSignature = PlumeSigner::new(&sk, v1).sign(message);
+        assert!(pk.verify(message, &sig).is_ok());
+        assert!(pk.verify(b"another message", &sig).is_err());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn test_canonical_json() {
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+
+    let sig = PlumeSignature::sign_v1(&sk, message, &mut Mock {});
+    let json = serde_json::to_string(&sig).unwrap();
+    let keys = [
+        "message",
+        "publicKey",
+        "nullifier",
+        "\"c\"",
+        "\"s\"",
+        "v1specific",
+        "gPowR",
+        "hashMPKPowR",
+    ];
+    let positions: Vec<usize> = keys.iter().map(|key| json.find(key).unwrap()).collect();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let parsed: PlumeSignature = serde_json::from_str(&json).unwrap();
+    assert!(parsed.verify());
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+    let unknown = json.replacen('{', "{\"extra\":0,", 1);
+    assert!(serde_json::from_str::<PlumeSignature>(&unknown).is_err());
+    let duplicate = json.replacen(
+        '{',
+        &format!("{{\"s\":{},", serde_json::to_string(&sig.s).unwrap()),
+        1,
+    );
+    assert!(serde_json::from_str::<PlumeSignature>(&duplicate).is_err());
+}
+
+#[test]
+pub fn test_tweaks() {
+    use plume_rustcrypto::tweak::Tweak;
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let pk = sk.public_key();
+    let tweak = Scalar::from(42u64);
+
+    let added = sk.tweak_add(&tweak).unwrap();
+    assert_eq!(added.public_key(), pk.tweak_add(&tweak).unwrap());
+    let multiplied = sk.tweak_mul(&tweak).unwrap();
+    assert_eq!(multiplied.public_key(), pk.tweak_mul(&tweak).unwrap());
+
+    assert!(sk.tweak_mul(&Scalar::ZERO).is_none());
+    assert!(pk.tweak_mul(&Scalar::ZERO).is_none());
+    let negated = -*sk.to_nonzero_scalar();
+    assert!(sk.tweak_add(&negated).is_none());
+    assert!(pk.tweak_add(&negated).is_none());
+
+    // every tweak gives a separate nullifier for the same message
+    let nullifier = PlumeSignature::sign_v2(&sk, message, &mut Mock {}).nullifier;
+    let sig = PlumeSignature::sign_v2(&added, message, &mut Mock {});
+    assert!(sig.verify());
+    assert_ne!(sig.nullifier, nullifier);
+    assert_ne!(
+        PlumeSignature::sign_v2(&multiplied, message, &mut Mock {}).nullifier,
+        nullifier
+    );
+}
+
+#[test]
+pub fn test_key_policy() {
+    use plume_rustcrypto::policy::{KeyPolicy, PolicyViolation};
+    use plume_rustcrypto::randomizedsigner::PlumeSigner;
+    use std::error::Error;
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let violation = |signer: &PlumeSigner, msg: &[u8]| {
+        let err = signer.try_sign_with_rng(&mut Mock {}, msg).unwrap_err();
+        err.source()
+            .and_then(|source| source.downcast_ref::<PolicyViolation>())
+            .cloned()
+    };
+
+    let policy = KeyPolicy::default()
+        .v2_only()
+        .allow_scope(b"An example app")
+        .max_message_len(64);
+    let signer = PlumeSigner::new(&sk, false).with_policy(policy.clone());
+    assert!(signer
+        .try_sign_with_rng(&mut Mock {}, message)
+        .unwrap()
+        .verify());
+    assert_eq!(
+        violation(&signer, b"another app"),
+        Some(PolicyViolation::Scope)
+    );
+    assert_eq!(
+        violation(&signer, &[b"An example app".as_slice(), &[0; 64]].concat()),
+        Some(PolicyViolation::MessageTooLong { len: 78, max: 64 })
+    );
+
+    let signer = PlumeSigner::new(&sk, true).with_policy(policy);
+    assert_eq!(
+        violation(&signer, message),
+        Some(PolicyViolation::Version { v1: true })
+    );
+
+    let signer = PlumeSigner::new(&sk, false)
+        .with_policy(KeyPolicy::default().message_format(|msg| msg.is_ascii()));
+    assert!(signer.try_sign_with_rng(&mut Mock {}, message).is_ok());
+    assert_eq!(
+        violation(&signer, &[0xff]),
+        Some(PolicyViolation::MessageFormat)
+    );
+}
+
+#[test]
+pub fn test_delegation() {
+    use plume_rustcrypto::delegation::{Delegation, DelegationError};
+
+    let owner = SecretKey::from_bytes(&SK.into()).unwrap();
+    let session = SecretKey::from_slice(R).unwrap();
+    let scope = b"An example app";
+
+    // the signed format
+    assert_eq!(
+        Delegation::message(&session.public_key(), scope, 1000),
+        hex_literal::hex!(
+            "0000000000000010504c554d452f64656c65676174696f6e"
+            "0000000000000021039d8ca4350e7e2ad27abc6d2a281365818076662962a28429590e2dc736fe9804"
+            "000000000000000e416e206578616d706c6520617070"
+            "000000000000000800000000000003e8"
+        )
+    );
+
+    let sig = PlumeSignature::sign_v2(&session, message, &mut Mock {});
+    for delegation in [
+        Delegation::sign_plume(&owner, session.public_key(), scope, 1000, &mut Mock {}),
+        Delegation::sign_ecdsa(&owner, session.public_key(), scope, 1000),
+    ] {
+        assert_eq!(
+            delegation.verify_session_signature(&sig, 999),
+            Ok(sig.verified_nullifier().unwrap())
+        );
+        assert_eq!(
+            delegation.verify_session_signature(&sig, 1000),
+            Err(DelegationError::Expired)
+        );
+
+        let out_of_scope = PlumeSignature::sign_v2(&session, b"another app", &mut Mock {});
+        assert_eq!(
+            delegation.verify_session_signature(&out_of_scope, 999),
+            Err(DelegationError::OutOfScope)
+        );
+        let by_owner = PlumeSignature::sign_v2(&owner, message, &mut Mock {});
+        assert_eq!(
+            delegation.verify_session_signature(&by_owner, 999),
+            Err(DelegationError::WrongSessionKey)
+        );
+
+        // the authorization doesn't cover another expiry
+        let extended = Delegation {
+            expiry: 2000,
+            ..delegation
+        };
+        assert_eq!(
+            extended.verify_session_signature(&sig, 999),
+            Err(DelegationError::InvalidAuthorization)
+        );
+    }
+}
+
+#[test]
+pub fn test_verified_nullifier() {
+    use plume_rustcrypto::verified::VerifiedNullifier;
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let sig = PlumeSignature::sign_v2(&sk, message, &mut Mock {});
+
+    let verified = VerifiedNullifier::verify(&sk.public_key(), message, &sig).unwrap();
+    assert_eq!(verified.nullifier(), &sig.nullifier);
+    assert_eq!(verified.public_key(), &sig.pk);
+    assert_eq!(verified.message(), message);
+    assert_eq!(verified.to_bytes().len(), 33);
+    assert_eq!(sig.verified_nullifier(), Some(verified));
+
+    assert!(VerifiedNullifier::verify(&sk.public_key(), b"other", &sig).is_err());
+    let mut tampered = PlumeSignature::sign_v2(&sk, message, &mut Mock {});
+    tampered.nullifier = sk.public_key().into();
+    assert_eq!(tampered.verified_nullifier(), None);
+}
+
+#[test]
+pub fn test_prehash() {
+    use plume_rustcrypto::policy::PolicyViolation;
+    use plume_rustcrypto::prehash::{self, MessageMode, PlumeVerifier};
+    use std::error::Error as _;
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let pk = sk.public_key();
+    let verifier = PlumeVerifier { max_raw_len: 64 };
+    let long_message = vec![0x42; 1000];
+
+    let digest = prehash::prehash(&long_message);
+    let sig = PlumeSignature::sign_v2(&sk, &prehash::prehashed_message(&digest), &mut Mock {});
+    assert!(verifier
+        .verify(&pk, &digest, MessageMode::PreHashed, &sig)
+        .is_ok());
+    assert!(verifier
+        .verify(&pk, &long_message[..32], MessageMode::PreHashed, &sig)
+        .is_err());
+    let wrong_length = verifier
+        .verify(&pk, &long_message, MessageMode::PreHashed, &sig)
+        .unwrap_err();
+    assert_eq!(
+        wrong_length
+            .source()
+            .and_then(|source| source.downcast_ref::<PolicyViolation>()),
+        Some(&PolicyViolation::MessageFormat)
+    );
+
+    let raw = PlumeSignature::sign_v2(&sk, message, &mut Mock {});
+    assert!(verifier
+        .verify(&pk, message, MessageMode::Raw, &raw)
+        .is_ok());
+    let sig = PlumeSignature::sign_v2(&sk, &long_message, &mut Mock {});
+    let too_long = verifier
+        .verify(&pk, &long_message, MessageMode::Raw, &sig)
+        .unwrap_err();
+    assert_eq!(
+        too_long
+            .source()
+            .and_then(|source| source.downcast_ref::<PolicyViolation>()),
+        Some(&PolicyViolation::MessageTooLong { len: 1000, max: 64 })
+    );
+    assert!(PlumeVerifier::default()
+        .verify(&pk, &long_message, MessageMode::Raw, &sig)
+        .is_ok());
+}
+
+#[test]
+pub fn test_verifier_chain() {
+    use plume_rustcrypto::chain::{
+        ChainError, NullifierProofVerifier, VerificationPath, VerifierChain,
+    };
+    use plume_rustcrypto::prehash::{MessageMode, PlumeVerifier};
+    use plume_rustcrypto::AffinePoint;
+
+    struct AcceptValid;
+    impl NullifierProofVerifier for AcceptValid {
+        fn verify_proof(&self, _: &[u8], _: &AffinePoint, proof: &[u8]) -> bool {
+            proof == b"valid"
+        }
+    }
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let pk = sk.public_key();
+    let sig = PlumeSignature::sign_v2(&sk, message, &mut Mock {});
+    let disclosed = Some((&pk, &sig, MessageMode::Raw));
+    let mut chain = VerifierChain {
+        proofs: Some(AcceptValid),
+        allow_disclosure: true,
+        signatures: PlumeVerifier::default(),
+    };
+
+    assert_eq!(
+        chain.verify(message, &sig.nullifier, Some(&b"valid"[..]), disclosed),
+        Ok(VerificationPath::Proof)
+    );
+    assert_eq!(
+        chain.verify(message, &sig.nullifier, Some(&b"invalid"[..]), disclosed),
+        Ok(VerificationPath::Signature)
+    );
+    assert_eq!(
+        chain.verify(message, &sig.nullifier, Some(&b"invalid"[..]), None),
+        Err(ChainError::InvalidProof)
+    );
+    assert_eq!(
+        chain.verify(message, &sig.nullifier, None, None),
+        Err(ChainError::NoEvidence)
+    );
+    assert_eq!(
+        chain.verify(message, &pk.into(), None, disclosed),
+        Err(ChainError::InvalidSignature)
+    );
+
+    chain.allow_disclosure = false;
+    assert_eq!(
+        chain.verify(message, &sig.nullifier, None, disclosed),
+        Err(ChainError::DisclosureNotAllowed)
+    );
+    assert_eq!(
+        chain.verify(message, &sig.nullifier, Some(&b"valid"[..]), disclosed),
+        Ok(VerificationPath::Proof)
+    );
+}
+
+#[test]
+pub fn test_rejection_cache() {
+    use plume_rustcrypto::negative_cache::{CacheMetrics, CachedRejection, RejectionCache};
+    use plume_rustcrypto::policy::PolicyViolation;
+    use plume_rustcrypto::prehash::{MessageMode, PlumeVerifier};
+    use std::error::Error;
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let pk = sk.public_key();
+    let verifier = PlumeVerifier::default();
+    let mut cache = RejectionCache::new(10, 2);
+
+    let sig = PlumeSignature::sign_v2(&sk, message, &mut Mock {});
+    let mut forged = PlumeSignature::sign_v2(&sk, message, &mut Mock {});
+    forged.s = forged.c;
+
+    let rejection = verifier
+        .verify_cached(&mut cache, 0, &pk, message, MessageMode::Raw, &forged)
+        .unwrap_err();
+    assert!(rejection.source().is_none());
+    let rejection = verifier
+        .verify_cached(&mut cache, 5, &pk, message, MessageMode::Raw, &forged)
+        .unwrap_err();
+    assert!(rejection
+        .source()
+        .unwrap()
+        .downcast_ref::<CachedRejection>()
+        .is_some());
+    // the forgery doesn't block the genuine signature with the same nullifier
+    assert_eq!(forged.nullifier, sig.nullifier);
+    for now in [5, 6] {
+        assert!(verifier
+            .verify_cached(&mut cache, now, &pk, message, MessageMode::Raw, &sig)
+            .is_ok());
+    }
+    assert_eq!(cache.len(), 1);
+
+    // verified again once expired
+    let rejection = verifier
+        .verify_cached(&mut cache, 10, &pk, message, MessageMode::Raw, &forged)
+        .unwrap_err();
+    assert!(rejection.source().is_none());
+
+    // the oldest are dropped beyond the capacity
+    for (now, msg) in [(11, &b"a"[..]), (12, b"b")] {
+        assert!(verifier
+            .verify_cached(&mut cache, now, &pk, msg, MessageMode::Raw, &forged)
+            .is_err());
+    }
+    assert_eq!(cache.len(), 2);
+
+    // the limits are checked before the cache
+    let strict = PlumeVerifier { max_raw_len: 1 };
+    let rejection = strict
+        .verify_cached(&mut cache, 13, &pk, message, MessageMode::Raw, &forged)
+        .unwrap_err();
+    assert_eq!(
+        rejection
+            .source()
+            .and_then(|source| source.downcast_ref::<PolicyViolation>()),
+        Some(&PolicyViolation::MessageTooLong {
+            len: message.len(),
+            max: 1
+        })
+    );
+    assert_eq!(cache.len(), 2);
+    assert_eq!(
+        cache.metrics(),
+        CacheMetrics {
+            hits: 1,
+            misses: 6,
+            evictions: 1,
+        }
+    );
+}
+
+#[test]
+pub fn test_validate() {
+    use k256::{elliptic_curve::sec1::ToEncodedPoint, AffinePoint};
+    use plume_rustcrypto::validate::{self, PointError};
+
+    let sk = SecretKey::from_bytes(&SK.into()).unwrap();
+    let pk = sk.public_key();
+    let compressed = pk.as_affine().to_encoded_point(true);
+    let uncompressed = pk.as_affine().to_encoded_point(false);
+    assert_eq!(
+        validate::public_key_from_sec1(compressed.as_bytes()).unwrap(),
+        pk
+    );
+    assert_eq!(
+        validate::public_key_from_sec1(uncompressed.as_bytes()).unwrap(),
+        pk
+    );
+
+    let mut off_curve = uncompressed.as_bytes().to_vec();
+    off_curve[64] ^= 1;
+    // there's no point with `x = 0`, as `7` isn't a square
+    let mut x_of_0 = vec![2];
+    x_of_0.extend([0; 32]);
+    let mut x_of_p = vec![2];
+    x_of_p.extend(hex_literal::hex!(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+    ));
+    for (bytes, expected) in [
+        (&[0u8][..], PointError::Identity),
+        (&off_curve[..], PointError::NotOnCurve),
+        (&x_of_0[..], PointError::NotOnCurve),
+        (&x_of_p[..], PointError::NotOnCurve),
+        (&compressed.as_bytes()[..32], PointError::NotOnCurve),
+    ] {
+        assert_eq!(validate::point_from_sec1(bytes).unwrap_err(), expected);
+    }
+
+    for v1 in [true, false] {
+        let mut sig = if v1 {
+            PlumeSignature::sign_v1(&sk, message, &mut Mock {})
+        } else {
+            PlumeSignature::sign_v2(&sk, message, &mut Mock {})
+        };
+        assert!(validate::signature(&sig).is_ok());
+        sig.nullifier = AffinePoint::IDENTITY;
+        assert_eq!(validate::signature(&sig), Err(PointError::Identity));
+        assert!(!sig.verify());
+    }
+}
@@ -0,0 +1,357 @@
+//! Workspace automation, run as `cargo xtask <task>`.
+//!
+//! # Tasks
+//! - `npm [--verify-only] [--target <wasm-pack target>]` builds the `plume-sig` npm package from `javascript/` with `wasm-pack`, TypeScript
+//!   definitions included; they're generated by `wasm-bindgen` from the exported Rust types, so the package never drifts from the source.
+//!   `--verify-only` trims the signing code out, leaving a verifier. The package goes to `javascript/pkg` (`javascript/pkg-verify` for the
+//!   verifier).
+//! - `codegen [--out <dir>] [--check]` writes the constants of `plume_core::consts` (the generators, the suites and their tags, and
+//!   the encoding parameters) as the Solidity library `PlumeConstants.sol` and the TypeScript module `plumeConstants.ts`, into
+//!   `target/codegen` unless `--out` says otherwise. `--check` writes nothing and fails if the files there differ, for CI.
+//! - `vectors [--out <file>] [--check]` writes the vectors of `plume_core::conformance::VECTORS` as the shared JSON test vectors,
+//!   `vectors/plume.json` unless `--out` says otherwise, which the other implementations load and emit; see `vectors/README.md` for the
+//!   format. `--check` writes nothing and fails if the file differs.
+
+use plume_core::conformance::{self, Vector};
+use plume_core::consts::{self, CurveConstants};
+use plume_core::PlumeVersion;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+const USAGE: &str =
+    "usage: cargo xtask npm [--verify-only] [--target <bundler|nodejs|web|no-modules>]
+       cargo xtask codegen [--out <dir>] [--check]
+       cargo xtask vectors [--out <file>] [--check]";
+
+const GENERATED: &str =
+    "// Generated by `cargo xtask codegen` from `plume-core/src/consts.rs`; don't edit.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.split_first() {
+        Some((task, rest)) if task == "npm" => npm(rest),
+        Some((task, rest)) if task == "codegen" => codegen(rest),
+        Some((task, rest)) if task == "vectors" => vectors(rest),
+        _ => Err(USAGE.to_owned()),
+    };
+    if let Err(msg) = result {
+        eprintln!("{msg}");
+        process::exit(1);
+    }
+}
+
+fn npm(args: &[String]) -> Result<(), String> {
+    let mut verify_only = false;
+    let mut target = "bundler".to_owned();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verify-only" => verify_only = true,
+            "--target" => target = args.next().ok_or(USAGE)?.clone(),
+            _ => return Err(USAGE.to_owned()),
+        }
+    }
+
+    let crate_dir = workspace_root().join("javascript");
+    let out_dir = if verify_only { "pkg-verify" } else { "pkg" };
+    let mut command = Command::new("wasm-pack");
+    command.current_dir(&crate_dir).args([
+        "build",
+        "--release",
+        "--target",
+        &target,
+        "--out-dir",
+        out_dir,
+    ]);
+    if verify_only {
+        command.args(["--", "--no-default-features", "--features", "verify"]);
+    }
+
+    let status = command
+        .status()
+        .map_err(|e| format!("failed to run `wasm-pack` (is it installed?): {e}"))?;
+    if !status.success() {
+        return Err(format!("`wasm-pack` exited with {status}"));
+    }
+
+    let typings = crate_dir.join(out_dir).join("plume_sig.d.ts");
+    if !typings.exists() {
+        return Err(format!(
+            "no TypeScript definitions at {}",
+            typings.display()
+        ));
+    }
+    println!("npm package is in {}", crate_dir.join(out_dir).display());
+    Ok(())
+}
+
+fn codegen(args: &[String]) -> Result<(), String> {
+    let mut out_dir = workspace_root().join("target").join("codegen");
+    let mut check = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out_dir = PathBuf::from(args.next().ok_or(USAGE)?),
+            "--check" => check = true,
+            _ => return Err(USAGE.to_owned()),
+        }
+    }
+
+    let constants = constants()?;
+    let files = [
+        ("PlumeConstants.sol", solidity(&constants)),
+        ("plumeConstants.ts", typescript(&constants)),
+    ];
+    if check {
+        for (name, contents) in &files {
+            let path = out_dir.join(name);
+            if fs::read_to_string(&path).ok().as_deref() != Some(contents.as_str()) {
+                return Err(format!(
+                    "{} is out of date; run `cargo xtask codegen`",
+                    path.display()
+                ));
+            }
+        }
+        println!(
+            "generated constants in {} are up to date",
+            out_dir.display()
+        );
+        return Ok(());
+    }
+
+    fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("failed to create {}: {e}", out_dir.display()))?;
+    for (name, contents) in &files {
+        let path = out_dir.join(name);
+        fs::write(&path, contents)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    }
+    println!("generated constants are in {}", out_dir.display());
+    Ok(())
+}
+
+fn vectors(args: &[String]) -> Result<(), String> {
+    let mut path = workspace_root().join("vectors").join("plume.json");
+    let mut check = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => path = PathBuf::from(args.next().ok_or(USAGE)?),
+            "--check" => check = true,
+            _ => return Err(USAGE.to_owned()),
+        }
+    }
+
+    let contents = vectors_json(conformance::VECTORS);
+    if check {
+        if fs::read_to_string(&path).ok().as_deref() != Some(contents.as_str()) {
+            return Err(format!(
+                "{} is out of date; run `cargo xtask vectors`",
+                path.display()
+            ));
+        }
+        println!("test vectors in {} are up to date", path.display());
+        return Ok(());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    }
+    fs::write(&path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    println!("test vectors are in {}", path.display());
+    Ok(())
+}
+
+/// The JSON of `vectors`, a field a line so the file diffs well. Every value is hex or a plain name, so nothing needs escaping.
+fn vectors_json(vectors: &[Vector]) -> String {
+    let vectors: Vec<String> = vectors
+        .iter()
+        .map(|vector| {
+            let message: String = vector.message.iter().map(|b| format!("{b:02x}")).collect();
+            let mut out = "    {\n".to_owned();
+            for (name, value) in [
+                ("curve", vector.curve),
+                ("secretKey", vector.secret_key),
+                ("r", vector.r),
+                ("message", &message),
+                ("publicKey", vector.public_key),
+                ("hashedToCurve", vector.hashed_to_curve),
+                ("nullifier", vector.nullifier),
+                ("gPowR", vector.r_point),
+                ("hashMPKPowR", vector.hashed_to_curve_r),
+            ] {
+                out += &format!("      \"{name}\": \"{value}\",\n");
+            }
+            let signatures: Vec<String> = vector
+                .challenges
+                .iter()
+                .map(|challenge| {
+                    format!(
+                        "        {{ \"version\": \"{}\", \"c\": \"{}\", \"s\": \"{}\" }}",
+                        version_name(challenge.version),
+                        challenge.c,
+                        challenge.s
+                    )
+                })
+                .collect();
+            out += &format!(
+                "      \"signatures\": [\n{}\n      ]\n    }}",
+                signatures.join(",\n")
+            );
+            out
+        })
+        .collect();
+    format!(
+        "{{\n  \"version\": 1,\n  \"vectors\": [\n{}\n  ]\n}}\n",
+        vectors.join(",\n")
+    )
+}
+
+fn version_name(version: PlumeVersion) -> &'static str {
+    match version {
+        PlumeVersion::V1 => "v1",
+        PlumeVersion::V2 => "v2",
+        PlumeVersion::V1_1 => "v1.1",
+        PlumeVersion::V2_1 => "v2.1",
+        _ => "unknown",
+    }
+}
+
+/// A constant as both languages write it.
+enum Value {
+    Text(&'static str),
+    /// Bytes of printable ASCII, as the tags are.
+    Bytes(String),
+    /// The big-endian hex of a number of at most 256 bits.
+    Uint(&'static str),
+    Byte(u8),
+    Len(usize),
+}
+
+/// A comment and the named constants under it.
+type Section = (String, Vec<(String, Value)>);
+
+/// The constants to generate: a section for each curve, then the encoding parameters.
+fn constants() -> Result<Vec<Section>, String> {
+    let mut sections = Vec::new();
+    for curve in consts::CURVES {
+        sections.push((
+            format!("`{}`, hashed to with `{}`", curve.name, curve.suite),
+            curve_constants(curve)?,
+        ));
+    }
+    sections.push((
+        "The encodings: SEC1 compressed points and framed transcripts".to_owned(),
+        vec![
+            (
+                "POINT_TAG_EVEN".to_owned(),
+                Value::Byte(consts::POINT_TAG_EVEN),
+            ),
+            (
+                "POINT_TAG_ODD".to_owned(),
+                Value::Byte(consts::POINT_TAG_ODD),
+            ),
+            (
+                "FRAME_PREFIX_LENGTH".to_owned(),
+                Value::Len(consts::FRAME_PREFIX_LEN),
+            ),
+        ],
+    ));
+    Ok(sections)
+}
+
+fn curve_constants(curve: &CurveConstants) -> Result<Vec<(String, Value)>, String> {
+    let dst = [curve.dst, consts::DIGEST_DST_SUFFIX].concat();
+    let mut values = vec![
+        ("SUITE", Value::Text(curve.suite)),
+        ("DST", Value::Bytes(tag(curve.dst, curve)?)),
+        ("DIGEST_DST", Value::Bytes(tag(&dst, curve)?)),
+    ];
+    for (name, hex) in [
+        ("P", curve.field_modulus),
+        ("N", curve.order),
+        ("GX", curve.generator_x),
+        ("GY", curve.generator_y),
+    ] {
+        if hex.is_empty()
+            || hex.len() > 64
+            || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        {
+            return Err(format!(
+                "{name} of {} isn't the hex of a uint256",
+                curve.name
+            ));
+        }
+        values.push((name, Value::Uint(hex)));
+    }
+    values.push(("SCALAR_LENGTH", Value::Len(curve.scalar_len())));
+    values.push(("POINT_LENGTH", Value::Len(curve.compressed_point_len())));
+
+    let prefix = curve.name.to_uppercase();
+    Ok(values
+        .into_iter()
+        .map(|(name, value)| (format!("{prefix}_{name}"), value))
+        .collect())
+}
+
+/// `bytes` as text, which both languages can quote them as if they're printable ASCII.
+fn tag(bytes: &[u8], curve: &CurveConstants) -> Result<String, String> {
+    String::from_utf8(bytes.to_vec())
+        .ok()
+        .filter(|tag| {
+            tag.bytes()
+                .all(|b| b.is_ascii_graphic() && b != b'"' && b != b'\\')
+        })
+        .ok_or_else(|| format!("a tag of {} isn't printable ASCII", curve.name))
+}
+
+fn solidity(sections: &[Section]) -> String {
+    let mut out = format!(
+        "// SPDX-License-Identifier: MIT\n{GENERATED}\npragma solidity ^0.8.0;\n\n/// @title The constants of the PLUME protocol\nlibrary PlumeConstants {{\n"
+    );
+    for (i, (comment, values)) in sections.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out += &format!("    // {comment}\n");
+        for (name, value) in values {
+            let (ty, value) = match value {
+                Value::Text(text) => ("string", format!("\"{text}\"")),
+                Value::Bytes(bytes) => ("bytes", format!("\"{bytes}\"")),
+                Value::Uint(hex) => ("uint256", format!("0x{hex}")),
+                Value::Byte(byte) => ("uint8", format!("0x{byte:02x}")),
+                Value::Len(len) => ("uint256", len.to_string()),
+            };
+            out += &format!("    {ty} internal constant {name} = {value};\n");
+        }
+    }
+    out += "}\n";
+    out
+}
+
+fn typescript(sections: &[Section]) -> String {
+    let mut out = format!("{GENERATED}\n");
+    for (comment, values) in sections {
+        out += &format!("\n// {comment}\n");
+        for (name, value) in values {
+            let value = match value {
+                Value::Text(text) => format!("\"{text}\""),
+                Value::Bytes(bytes) => format!("new TextEncoder().encode(\"{bytes}\")"),
+                Value::Uint(hex) => format!("0x{hex}n"),
+                Value::Byte(byte) => format!("0x{byte:02x}"),
+                Value::Len(len) => len.to_string(),
+            };
+            out += &format!("export const {name} = {value};\n");
+        }
+    }
+    out
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}